@@ -6,9 +6,9 @@ use tokio::io::{self, AsyncReadExt};
 use zksync_block_reverter::{
     eth_client::{
         clients::{Client, PKSigningClient},
-        EthInterface,
+        BoundEthInterface, EthInterface,
     },
-    BlockReverter, BlockReverterEthConfig, NodeRole,
+    BlockReverter, BlockReverterEthConfig, EthSenderOperator, NodeRole,
 };
 use zksync_config::{
     configs::{
@@ -21,7 +21,7 @@ use zksync_core_leftovers::temp_config_store::decode_yaml_repr;
 use zksync_dal::{ConnectionPool, Core};
 use zksync_env_config::{object_store::SnapshotsObjectStoreConfig, FromEnv};
 use zksync_object_store::ObjectStoreFactory;
-use zksync_types::{Address, L1BatchNumber};
+use zksync_types::{Address, K256PrivateKey, L1BatchNumber};
 
 #[derive(Debug, Parser)]
 #[command(author = "Matter Labs", version, about = "Block revert utility", long_about = None)]
@@ -99,6 +99,18 @@ enum Command {
     /// Clears failed L1 transactions.
     #[command(name = "clear-failed-transactions")]
     ClearFailedL1Transactions,
+
+    /// Reconciles eth_sender state in Postgres with the state on L1, e.g. after an L1 reorg or stuck transactions.
+    /// eth_sender must be stopped while running this command and restarted afterwards.
+    #[command(name = "reconcile-eth-sender")]
+    ReconcileEthSender {
+        /// Only displays the reconciliation plan without applying it.
+        #[arg(long)]
+        dry_run: bool,
+        /// Displays the plan as a JSON object, so that it is machine-readable.
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[tokio::main]
@@ -354,6 +366,87 @@ async fn main() -> anyhow::Result<()> {
         Command::ClearFailedL1Transactions => {
             block_reverter.clear_failed_l1_transactions().await?;
         }
+        Command::ReconcileEthSender { dry_run, json } => {
+            let eth_client = Client::http(l1_secrets.l1_rpc_url.clone())
+                .context("Ethereum client")?
+                .build();
+            // Unlike the deprecated `private_key*()` getters in `SenderConfig`, wallets loaded from env
+            // include the previous operator key, which is necessary to reconcile its transactions.
+            let wallets = match wallets_config {
+                Some(wallets_config) => wallets_config,
+                None => Wallets::from_env().context("Wallets::from_env()")?,
+            };
+            let wallets = wallets
+                .eth_sender
+                .context("eth_sender wallets are not set")?;
+            let custom_operators = wallets
+                .blob_operator
+                .iter()
+                .chain(&wallets.previous_operator);
+            let custom_keys = custom_operators
+                .map(|wallet| (Some(wallet.address()), wallet.private_key().clone()));
+            let operator_keys: Vec<(Option<Address>, K256PrivateKey)> =
+                [(None, wallets.operator.private_key().clone())]
+                    .into_iter()
+                    .chain(custom_keys)
+                    .collect();
+
+            let operators: Vec<_> = operator_keys
+                .iter()
+                .map(|(from_addr, key)| EthSenderOperator {
+                    from_addr: *from_addr,
+                    address: key.address(),
+                })
+                .collect();
+            let plan = block_reverter
+                .eth_sender_reconciliation_plan(&eth_client, &config, &operators)
+                .await?;
+            if json {
+                println!("{}", serde_json::to_string(&plan)?);
+            } else if plan.is_empty() {
+                println!("eth_sender state is consistent with L1; nothing to reconcile");
+            } else {
+                println!("Reconciliation plan:");
+                for action in &plan.actions {
+                    println!("- {action}");
+                }
+            }
+            if dry_run || plan.is_empty() {
+                return Ok(());
+            }
+
+            println!("Make sure that eth_sender is stopped; restart it after the plan is applied");
+            println!("Apply the plan? Print y/n");
+            let mut input = [0u8];
+            io::stdin().read_exact(&mut input).await.unwrap();
+            if input[0] != b'y' && input[0] != b'Y' {
+                std::process::exit(0);
+            }
+
+            let l1_chain_id = eth_client
+                .fetch_chain_id()
+                .await
+                .context("cannot fetch Ethereum chain ID")?;
+            let signing_clients: Vec<_> = operator_keys
+                .into_iter()
+                .map(|(_, key)| {
+                    PKSigningClient::new_raw(
+                        key,
+                        contracts.diamond_proxy_addr,
+                        default_priority_fee_per_gas,
+                        l1_chain_id,
+                        Box::new(eth_client.clone()),
+                    )
+                })
+                .collect();
+            let signing_clients: Vec<&dyn BoundEthInterface> = signing_clients
+                .iter()
+                .map(|client| client as &dyn BoundEthInterface)
+                .collect();
+            block_reverter
+                .apply_eth_sender_reconciliation_plan(&plan, &config, &signing_clients)
+                .await?;
+        }
     }
     Ok(())
 }
@@ -11,6 +11,7 @@ use serde::Deserialize;
 use zksync_config::{
     configs::{
         api::{MaxResponseSize, MaxResponseSizeOverrides},
+        chain::{ValidationOpcode, ValidationSlotPattern},
         consensus::{ConsensusConfig, ConsensusSecrets},
         en_config::ENConfig,
         GeneralConfig, Secrets,
@@ -24,6 +25,7 @@ use zksync_core_leftovers::temp_config_store::{decode_yaml_repr, read_yaml_repr}
 use zksync_dal::{ConnectionPool, Core};
 use zksync_metadata_calculator::MetadataCalculatorRecoveryConfig;
 use zksync_node_api_server::{
+    execution_sandbox::CustomValidationRules,
     tx_sender::TxSenderConfig,
     web3::{state::InternalApiConfig, Namespace},
 };
@@ -276,6 +278,17 @@ pub(crate) struct OptionalENConfig {
     /// Tx nonce: how far ahead from the committed nonce can it be.
     #[serde(default = "OptionalENConfig::default_max_nonce_ahead")]
     pub max_nonce_ahead: u32,
+    /// Additional addresses that accounts may access during validation. Should match the main node configuration,
+    /// otherwise the node may reject transactions that would be accepted by the main node.
+    #[serde(default)]
+    pub validation_trusted_addresses: Vec<Address>,
+    /// Additional storage slots that accounts may read during validation, in the `<address>:<slot>` format
+    /// (`*` instead of the address matches all contracts). Should match the main node configuration.
+    #[serde(default)]
+    pub validation_trusted_slots: Vec<ValidationSlotPattern>,
+    /// Otherwise restricted opcodes that accounts may use during validation. Should match the main node configuration.
+    #[serde(default)]
+    pub validation_allowed_opcodes: Vec<ValidationOpcode>,
     /// Max number of VM instances to be concurrently spawned by the API server.
     /// This option can be tweaked down if the API server is running out of memory.
     #[serde(default = "OptionalENConfig::default_vm_concurrency_limit")]
@@ -444,6 +457,12 @@ pub(crate) struct OptionalENConfig {
     /// If set to 0, L1 batches will not be retained based on their timestamp. The default value is 7 days.
     #[serde(default = "OptionalENConfig::default_pruning_data_retention_sec")]
     pruning_data_retention_sec: u64,
+    /// Number of hard-pruned L1 batches for which the Merkle tree retains its versions (archive mode). If positive,
+    /// historical storage reads (`eth_getBalance`, `eth_getStorageAt`, `eth_getTransactionCount`, `eth_getProof`)
+    /// for pruned blocks are served from the tree; the results can be verified with Merkle proofs.
+    /// The default value is 0, i.e., the tree is pruned in sync with Postgres.
+    #[serde(default)]
+    pub pruning_archive_l1_batches: u64,
 }
 
 impl OptionalENConfig {
@@ -507,6 +526,21 @@ impl OptionalENConfig {
                 web3_json_rpc.max_nonce_ahead,
                 default_max_nonce_ahead
             ),
+            validation_trusted_addresses: general_config
+                .state_keeper_config
+                .as_ref()
+                .map(|a| a.validation_trusted_addresses.clone())
+                .unwrap_or_default(),
+            validation_trusted_slots: general_config
+                .state_keeper_config
+                .as_ref()
+                .map(|a| a.validation_trusted_slots.clone())
+                .unwrap_or_default(),
+            validation_allowed_opcodes: general_config
+                .state_keeper_config
+                .as_ref()
+                .map(|a| a.validation_allowed_opcodes.clone())
+                .unwrap_or_default(),
             vm_concurrency_limit: load_optional_config_or_default!(
                 general_config.api_config,
                 web3_json_rpc.vm_concurrency_limit,
@@ -643,6 +677,11 @@ impl OptionalENConfig {
                 data_retention_sec,
                 default_pruning_data_retention_sec
             ),
+            pruning_archive_l1_batches: load_optional_config_or_default!(
+                general_config.pruning,
+                archive_l1_batches,
+                default_pruning_archive_l1_batches
+            ),
             protective_reads_persistence_enabled: general_config
                 .db_config
                 .as_ref()
@@ -806,6 +845,10 @@ impl OptionalENConfig {
         3_600 * 24 * 7 // 7 days
     }
 
+    const fn default_pruning_archive_l1_batches() -> u64 {
+        0
+    }
+
     fn from_env() -> anyhow::Result<Self> {
         let mut result: OptionalENConfig = envy::prefixed("EN_")
             .from_env()
@@ -898,6 +941,11 @@ impl OptionalENConfig {
         Duration::from_secs(self.pruning_data_retention_sec)
     }
 
+    /// Checks whether the node retains Merkle tree versions for pruned L1 batches.
+    pub fn pruning_archive_mode(&self) -> bool {
+        self.pruning_enabled && self.pruning_archive_l1_batches > 0
+    }
+
     #[cfg(test)]
     fn mock() -> Self {
         // Set all values to their defaults
@@ -1370,6 +1418,7 @@ impl From<&ExternalNodeConfig> for InternalApiConfig {
             filters_disabled: config.optional.filters_disabled,
             dummy_verifier: config.remote.dummy_verifier,
             l1_batch_commit_data_generator_mode: config.remote.l1_batch_commit_data_generator_mode,
+            archive_mode: config.optional.pruning_archive_mode(),
         }
     }
 }
@@ -1392,6 +1441,13 @@ impl From<&ExternalNodeConfig> for TxSenderConfig {
             chain_id: config.required.l2_chain_id,
             // Does not matter for EN.
             whitelisted_tokens_for_aa: Default::default(),
+            custom_validation_rules: CustomValidationRules::new(
+                &config.optional.validation_trusted_addresses,
+                &config.optional.validation_trusted_slots,
+                &config.optional.validation_allowed_opcodes,
+            ),
+            // Enforced by the main node.
+            account_tx_limits: Default::default(),
         }
     }
 }
@@ -349,7 +349,9 @@ impl ExternalNodeBuilder {
 
         // Add tree pruning if needed.
         if self.config.optional.pruning_enabled {
-            layer = layer.with_pruning_config(self.config.optional.pruning_removal_delay());
+            layer = layer
+                .with_pruning_config(self.config.optional.pruning_removal_delay())
+                .with_archived_l1_batches(self.config.optional.pruning_archive_l1_batches);
         }
 
         self.node.add_layer(layer);
@@ -412,6 +414,7 @@ impl ExternalNodeBuilder {
             pruning_info_refresh_interval: Some(pruning_info_refresh_interval),
             websocket_requests_per_minute_limit: None, // To be set by WS server layer method if required.
             replication_lag_limit: None,               // TODO: Support replication lag limit
+            compute_unit_limiter: None,                // TODO: Support compute unit limits
         }
     }
 
@@ -1,28 +1,41 @@
 //! This module provides a "builder" for the main node,
 //! as well as an interface to run the node with the specified components.
 
+use std::sync::Arc;
+
 use anyhow::Context;
 use zksync_config::{
-    configs::{eth_sender::PubdataSendingMode, wallets::Wallets, GeneralConfig, Secrets},
+    configs::{
+        api::{PubSubNotificationsSource, Web3JsonRpcConfig},
+        eth_sender::PubdataSendingMode,
+        wallets::Wallets,
+        GeneralConfig, Secrets,
+    },
     ContractsConfig, GenesisConfig,
 };
 use zksync_core_leftovers::Component;
 use zksync_default_da_clients::{
+    blob_namespace::{config::BlobNamespaceConfig, wiring_layer::BlobNamespaceClientWiringLayer},
     no_da::wiring_layer::NoDAClientWiringLayer,
     object_store::{config::DAObjectStoreConfig, wiring_layer::ObjectStorageClientWiringLayer},
 };
+use zksync_eth_signer::RemoteSigner;
 use zksync_metadata_calculator::MetadataCalculatorConfig;
 use zksync_node_api_server::{
-    tx_sender::{ApiContracts, TxSenderConfig},
-    web3::{state::InternalApiConfig, Namespace},
+    execution_sandbox::CustomValidationRules,
+    tx_sender::{filter::TxLimitsFilter, ApiContracts, TxSenderConfig},
+    web3::{backend_jsonrpsee::ComputeUnitLimiter, state::InternalApiConfig, Namespace},
 };
 use zksync_node_framework::{
     implementations::layers::{
         base_token::{
+            aggregating_price_client::AggregatingPriceClientLayer,
             base_token_ratio_persister::BaseTokenRatioPersisterLayer,
             base_token_ratio_provider::BaseTokenRatioProviderLayer,
+            chainlink_client::ChainlinkClientLayer, cmc_client::CmcClientLayer,
             coingecko_client::CoingeckoClientLayer, forced_price_client::ForcedPriceClientLayer,
             no_op_external_price_api_client::NoOpExternalPriceApiClientLayer,
+            uniswap_twap_client::UniswapTwapClientLayer,
         },
         circuit_breaker_checker::CircuitBreakerCheckerLayer,
         commitment_generator::CommitmentGeneratorLayer,
@@ -47,19 +60,22 @@ use zksync_node_framework::{
         proof_data_handler::ProofDataHandlerLayer,
         query_eth_client::QueryEthClientLayer,
         sigint::SigintHandlerLayer,
+        signing_eth_client::SigningEthClientLayer,
         state_keeper::{
             main_batch_executor::MainBatchExecutorLayer, mempool_io::MempoolIOLayer,
             output_handler::OutputHandlerLayer, RocksdbStorageOptions, StateKeeperLayer,
         },
+        storage_notifications::StorageNotificationsLayer,
         tee_verifier_input_producer::TeeVerifierInputProducerLayer,
         vm_runner::{
             bwip::BasicWitnessInputProducerLayer, protective_reads::ProtectiveReadsWriterLayer,
+            trace_exporter::TraceExporterLayer,
         },
         web3_api::{
             caches::MempoolCacheLayer,
             server::{Web3ServerLayer, Web3ServerOptionalConfig},
             tree_api_client::TreeApiClientLayer,
-            tx_sender::{PostgresStorageCachesConfig, TxSenderLayer},
+            tx_sender::{PostgresStorageCachesConfig, TxFiltersConfig, TxSenderLayer},
             tx_sink::MasterPoolSinkLayer,
         },
     },
@@ -83,6 +99,8 @@ pub struct MainNodeBuilder {
     genesis_config: GenesisConfig,
     contracts_config: ContractsConfig,
     secrets: Secrets,
+    /// Compute unit limiter shared by the HTTP and WS servers.
+    compute_unit_limiter: Option<Arc<ComputeUnitLimiter>>,
 }
 
 impl MainNodeBuilder {
@@ -100,6 +118,7 @@ impl MainNodeBuilder {
             genesis_config,
             contracts_config,
             secrets,
+            compute_unit_limiter: None,
         }
     }
 
@@ -120,6 +139,29 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_storage_notifications_layer(mut self, components: &[Component]) -> anyhow::Result<Self> {
+        let Some(source) = self
+            .configs
+            .api_config
+            .as_ref()
+            .and_then(|config| config.web3_json_rpc.pubsub_notifications)
+        else {
+            return Ok(self);
+        };
+        let has_api =
+            components.contains(&Component::HttpApi) || components.contains(&Component::WsApi);
+        if source == PubSubNotificationsSource::InProcess
+            && has_api
+            && !components.contains(&Component::StateKeeper)
+        {
+            anyhow::bail!(
+                "In-process pub/sub notifications require the API server to run together with the state keeper"
+            );
+        }
+        self.node.add_layer(StorageNotificationsLayer::new(source));
+        Ok(self)
+    }
+
     fn add_prometheus_exporter_layer(mut self) -> anyhow::Result<Self> {
         let prom_config = try_load_config!(self.configs.prometheus_config);
         let prom_config = PrometheusExporterConfig::pull(prom_config.listener_port);
@@ -132,8 +174,40 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
-    fn add_pk_signing_client_layer(mut self) -> anyhow::Result<Self> {
+    fn add_signing_client_layer(mut self) -> anyhow::Result<Self> {
         let eth_config = try_load_config!(self.configs.eth);
+        if let Some(remote_signer_config) = eth_config.remote_signer.clone() {
+            let create_signer = |address| {
+                RemoteSigner::new(
+                    &remote_signer_config.url,
+                    address,
+                    remote_signer_config.request_timeout(),
+                )
+                .context("failed creating remote signer")
+            };
+            let operator_signer = create_signer(remote_signer_config.operator_address)?;
+            let blob_operator_signer = remote_signer_config
+                .blob_operator_address
+                .map(create_signer)
+                .transpose()?;
+            let previous_operator_signer = remote_signer_config
+                .previous_operator_address
+                .map(create_signer)
+                .transpose()?;
+            let mut layer = SigningEthClientLayer::new(
+                eth_config,
+                self.contracts_config.clone(),
+                self.genesis_config.l1_chain_id,
+                operator_signer,
+                blob_operator_signer,
+            );
+            if let Some(signer) = previous_operator_signer {
+                layer = layer.with_previous_operator_signer(signer);
+            }
+            self.node.add_layer(layer);
+            return Ok(self);
+        }
+
         let wallets = try_load_config!(self.wallets.eth_sender);
         self.node.add_layer(PKSigningEthClientLayer::new(
             eth_config,
@@ -231,8 +305,22 @@ impl MainNodeBuilder {
             try_load_config!(wallets.state_keeper),
         );
         let db_config = try_load_config!(self.configs.db_config);
-        let main_node_batch_executor_builder_layer =
+        let mut main_node_batch_executor_builder_layer =
             MainBatchExecutorLayer::new(sk_config.save_call_traces, OPTIONAL_BYTECODE_COMPRESSION);
+        // Custom validation rules are enforced by the state keeper in addition to the API server, so that
+        // transactions violating them are rejected regardless of how they got into the mempool. With the default
+        // rules, the state keeper doesn't trace transactions to avoid the overhead.
+        let custom_validation_rules = CustomValidationRules::from_config(&sk_config);
+        if !custom_validation_rules.is_empty() {
+            let whitelisted_tokens_for_aa = self
+                .configs
+                .api_config
+                .as_ref()
+                .map(|config| config.web3_json_rpc.whitelisted_tokens_for_aa.clone())
+                .unwrap_or_default();
+            main_node_batch_executor_builder_layer = main_node_batch_executor_builder_layer
+                .with_account_validation(custom_validation_rules, whitelisted_tokens_for_aa);
+        }
 
         let rocksdb_options = RocksdbStorageOptions {
             block_cache_capacity: db_config
@@ -276,27 +364,44 @@ impl MainNodeBuilder {
     fn add_tx_sender_layer(mut self) -> anyhow::Result<Self> {
         let sk_config = try_load_config!(self.configs.state_keeper_config);
         let rpc_config = try_load_config!(self.configs.api_config).web3_json_rpc;
+        let mempool_config = try_load_config!(self.configs.mempool_config);
         let postgres_storage_caches_config = PostgresStorageCachesConfig {
             factory_deps_cache_size: rpc_config.factory_deps_cache_size() as u64,
             initial_writes_cache_size: rpc_config.initial_writes_cache_size() as u64,
             latest_values_cache_size: rpc_config.latest_values_cache_size() as u64,
         };
 
+        let tx_sender_config = TxSenderConfig::new(
+            &sk_config,
+            &rpc_config,
+            try_load_config!(self.wallets.state_keeper)
+                .fee_account
+                .address(),
+            self.genesis_config.l2_chain_id,
+        )
+        .with_mempool_config(&mempool_config);
+
         // On main node we always use master pool sink.
-        self.node.add_layer(MasterPoolSinkLayer);
-        self.node.add_layer(TxSenderLayer::new(
-            TxSenderConfig::new(
-                &sk_config,
-                &rpc_config,
-                try_load_config!(self.wallets.state_keeper)
-                    .fee_account
-                    .address(),
-                self.genesis_config.l2_chain_id,
-            ),
-            postgres_storage_caches_config,
-            rpc_config.vm_concurrency_limit(),
-            ApiContracts::load_from_disk_blocking(), // TODO (BFT-138): Allow to dynamically reload API contracts
-        ));
+        self.node.add_layer(
+            MasterPoolSinkLayer::default().with_account_limits(tx_sender_config.account_tx_limits),
+        );
+        self.node.add_layer(
+            TxSenderLayer::new(
+                tx_sender_config,
+                postgres_storage_caches_config,
+                rpc_config.vm_concurrency_limit(),
+                ApiContracts::load_from_disk_blocking(), // TODO (BFT-138): Allow to dynamically reload API contracts
+            )
+            .with_tx_filters(TxFiltersConfig {
+                deny_list_path: rpc_config.tx_deny_list_path.clone().map(Into::into),
+                method_allowlist_path: rpc_config.tx_method_allowlist_path.clone().map(Into::into),
+                limits: TxLimitsFilter {
+                    max_calldata_size: rpc_config.max_tx_calldata_size,
+                    max_value: rpc_config.max_tx_value,
+                },
+                lists_reload_interval: rpc_config.tx_filter_lists_reload_interval(),
+            }),
+        );
         Ok(self)
     }
 
@@ -326,6 +431,7 @@ impl MainNodeBuilder {
             namespaces.push(Namespace::Debug)
         }
         namespaces.push(Namespace::Snapshots);
+        namespaces.push(Namespace::Txpool);
 
         let optional_config = Web3ServerOptionalConfig {
             namespaces: Some(namespaces),
@@ -333,6 +439,7 @@ impl MainNodeBuilder {
             subscriptions_limit: Some(rpc_config.subscriptions_limit()),
             batch_request_size_limit: Some(rpc_config.max_batch_request_size()),
             response_body_size_limit: Some(rpc_config.max_response_body_size()),
+            compute_unit_limiter: self.compute_unit_limiter(&rpc_config)?,
             ..Default::default()
         };
         self.node.add_layer(Web3ServerLayer::http(
@@ -344,24 +451,42 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    /// Returns the compute unit limiter shared by the HTTP and WS servers, so that clients cannot multiply
+    /// their quota by using both transports.
+    fn compute_unit_limiter(
+        &mut self,
+        rpc_config: &Web3JsonRpcConfig,
+    ) -> anyhow::Result<Option<Arc<ComputeUnitLimiter>>> {
+        let Some(limits) = rpc_config.compute_unit_limits() else {
+            return Ok(None);
+        };
+        if let Some(limiter) = &self.compute_unit_limiter {
+            return Ok(Some(limiter.clone()));
+        }
+        let api_keys = self
+            .secrets
+            .api
+            .as_ref()
+            .map_or(&[][..], |secrets| &secrets.api_keys);
+        let limiter = ComputeUnitLimiter::new(&limits, api_keys)
+            .context("invalid compute unit limits configuration")?;
+        let limiter = Arc::new(limiter);
+        self.compute_unit_limiter = Some(limiter.clone());
+        Ok(Some(limiter))
+    }
+
     fn add_ws_web3_api_layer(mut self) -> anyhow::Result<Self> {
         let rpc_config = try_load_config!(self.configs.api_config).web3_json_rpc;
         let state_keeper_config = try_load_config!(self.configs.state_keeper_config);
         let circuit_breaker_config = try_load_config!(self.configs.circuit_breaker_config);
         let with_debug_namespace = state_keeper_config.save_call_traces;
 
-        let mut namespaces = if let Some(namespaces) = &rpc_config.api_namespaces {
-            namespaces
-                .iter()
-                .map(|a| a.parse())
-                .collect::<Result<_, _>>()?
-        } else {
-            Namespace::DEFAULT.to_vec()
-        };
+        let mut namespaces = Namespace::DEFAULT.to_vec();
         if with_debug_namespace {
             namespaces.push(Namespace::Debug)
         }
         namespaces.push(Namespace::Snapshots);
+        namespaces.push(Namespace::Txpool);
 
         let optional_config = Web3ServerOptionalConfig {
             namespaces: Some(namespaces),
@@ -374,6 +499,7 @@ impl MainNodeBuilder {
             ),
             replication_lag_limit: circuit_breaker_config.replication_lag_limit(),
             with_extended_tracing: rpc_config.extended_api_tracing,
+            compute_unit_limiter: self.compute_unit_limiter(&rpc_config)?,
             ..Default::default()
         };
         self.node.add_layer(Web3ServerLayer::ws(
@@ -472,8 +598,14 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
-    fn add_no_da_client_layer(mut self) -> anyhow::Result<Self> {
-        self.node.add_layer(NoDAClientWiringLayer);
+    /// Adds the blob namespace DA client if it's configured, or falls back to the no-DA client otherwise.
+    fn add_da_client_layer(mut self) -> anyhow::Result<Self> {
+        if let Some(config) = BlobNamespaceConfig::from_env_opt()? {
+            self.node
+                .add_layer(BlobNamespaceClientWiringLayer::new(config));
+        } else {
+            self.node.add_layer(NoDAClientWiringLayer);
+        }
         Ok(self)
     }
 
@@ -527,6 +659,19 @@ impl MainNodeBuilder {
             ForcedPriceClientLayer::CLIENT_NAME => {
                 self.node.add_layer(ForcedPriceClientLayer::new(config));
             }
+            CmcClientLayer::CLIENT_NAME => {
+                self.node.add_layer(CmcClientLayer::new(config));
+            }
+            UniswapTwapClientLayer::CLIENT_NAME => {
+                self.node.add_layer(UniswapTwapClientLayer::new(config));
+            }
+            ChainlinkClientLayer::CLIENT_NAME => {
+                self.node.add_layer(ChainlinkClientLayer::new(config));
+            }
+            AggregatingPriceClientLayer::CLIENT_NAME => {
+                self.node
+                    .add_layer(AggregatingPriceClientLayer::new(config));
+            }
             _ => {
                 anyhow::bail!(
                     "Unknown external price API client source: {}",
@@ -549,6 +694,16 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_vm_runner_trace_exporter_layer(mut self) -> anyhow::Result<Self> {
+        let trace_exporter_config = try_load_config!(self.configs.trace_exporter_config);
+        self.node.add_layer(TraceExporterLayer::new(
+            trace_exporter_config,
+            self.genesis_config.l2_chain_id,
+        ));
+
+        Ok(self)
+    }
+
     fn add_base_token_ratio_persister_layer(mut self) -> anyhow::Result<Self> {
         let config = try_load_config!(self.configs.base_token_adjuster);
         let contracts_config = self.contracts_config.clone();
@@ -605,6 +760,8 @@ impl MainNodeBuilder {
             .add_query_eth_client_layer()?
             .add_sequencer_l1_gas_layer()?;
 
+        self = self.add_storage_notifications_layer(&components)?;
+
         // Add preconditions for all the components.
         self = self
             .add_l1_batch_commitment_mode_validation_layer()?
@@ -662,7 +819,7 @@ impl MainNodeBuilder {
                 }
                 Component::EthTxAggregator => {
                     self = self
-                        .add_pk_signing_client_layer()?
+                        .add_signing_client_layer()?
                         .add_eth_tx_aggregator_layer()?;
                 }
                 Component::EthTxManager => {
@@ -686,7 +843,7 @@ impl MainNodeBuilder {
                     self = self.add_commitment_generator_layer()?;
                 }
                 Component::DADispatcher => {
-                    self = self.add_no_da_client_layer()?.add_da_dispatcher_layer()?;
+                    self = self.add_da_client_layer()?.add_da_dispatcher_layer()?;
                 }
                 Component::VmRunnerProtectiveReads => {
                     self = self.add_vm_runner_protective_reads_layer()?;
@@ -699,6 +856,9 @@ impl MainNodeBuilder {
                 Component::VmRunnerBwip => {
                     self = self.add_vm_runner_bwip_layer()?;
                 }
+                Component::VmRunnerTraceExporter => {
+                    self = self.add_vm_runner_trace_exporter_layer()?;
+                }
             }
         }
         Ok(self.node.build()?)
@@ -182,6 +182,25 @@ pub enum WitnessJobStatus {
     Queued,
 }
 
+/// Kind of a prover artifact put into the object store. Determines the object store bucket of the artifact.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, strum::EnumString, strum::AsRefStr,
+)]
+pub enum ProverArtifactKind {
+    #[strum(serialize = "witness_inputs")]
+    WitnessInputs,
+    #[strum(serialize = "circuit")]
+    Circuit,
+    #[strum(serialize = "proof")]
+    Proof,
+    #[strum(serialize = "leaf_aggregation_inputs")]
+    LeafAggregationInputs,
+    #[strum(serialize = "node_aggregation_inputs")]
+    NodeAggregationInputs,
+    #[strum(serialize = "scheduler_inputs")]
+    SchedulerInputs,
+}
+
 #[derive(Debug)]
 pub struct WitnessJobInfo {
     pub block_number: L1BatchNumber,
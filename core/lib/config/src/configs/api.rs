@@ -9,7 +9,7 @@ use std::{
 
 use anyhow::Context as _;
 use serde::{de, Deserialize, Deserializer};
-use zksync_basic_types::{Address, H256};
+use zksync_basic_types::{Address, H256, U256};
 
 pub use crate::configs::PrometheusConfig;
 
@@ -123,6 +123,118 @@ impl<'de> Deserialize<'de> for MaxResponseSizeOverrides {
     }
 }
 
+/// Compute unit costs of specific RPC methods. Methods not mentioned in the costs have the cost of 1 unit.
+///
+/// Method names may end with `*` to match all methods with the specified prefix (e.g., `debug_trace*`). If several
+/// entries match a method, the exact match or the longest prefix takes precedence.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodComputeUnits(HashMap<String, NonZeroU32>);
+
+impl<S: Into<String>> FromIterator<(S, NonZeroU32)> for MethodComputeUnits {
+    fn from_iter<I: IntoIterator<Item = (S, NonZeroU32)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(method_name, units)| (method_name.into(), units))
+                .collect(),
+        )
+    }
+}
+
+impl FromStr for MethodComputeUnits {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut costs = HashMap::new();
+        for part in s.split(',') {
+            let (method_name, units) = part
+                .split_once('=')
+                .with_context(|| format!("Part `{part}` doesn't have form <method_name>=<int>"))?;
+            let method_name = method_name.trim();
+            let units = units.trim();
+            let units = units.parse().with_context(|| {
+                format!("`{units}` specified for method `{method_name}` is not a valid number of compute units")
+            })?;
+
+            if let Some(prev_units) = costs.insert(method_name.to_owned(), units) {
+                anyhow::bail!(
+                    "Compute units for `{method_name}` are redefined from {prev_units} to {units}"
+                );
+            }
+        }
+        Ok(Self(costs))
+    }
+}
+
+impl MethodComputeUnits {
+    pub fn empty() -> Self {
+        Self(HashMap::new())
+    }
+
+    /// Gets the cost of the specified method in compute units.
+    pub fn get(&self, method_name: &str) -> NonZeroU32 {
+        if let Some(&units) = self.0.get(method_name) {
+            return units;
+        }
+        let prefix_match = self.0.iter().filter_map(|(pattern, &units)| {
+            let prefix = pattern.strip_suffix('*')?;
+            method_name
+                .starts_with(prefix)
+                .then_some((prefix.len(), units))
+        });
+        prefix_match
+            .max_by_key(|(prefix_len, _)| *prefix_len)
+            .map_or(NonZeroU32::MIN, |(_, units)| units)
+    }
+
+    /// Iterates over all configured costs.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&str, NonZeroU32)> + '_ {
+        self.0
+            .iter()
+            .map(|(method_name, &units)| (method_name.as_str(), units))
+    }
+}
+
+impl<'de> Deserialize<'de> for MethodComputeUnits {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ParseVisitor;
+
+        impl<'v> de::Visitor<'v> for ParseVisitor {
+            type Value = MethodComputeUnits;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("comma-separated list of <method_name>=<units> tuples, such as: eth_getLogs=20,debug_trace*=100")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                value.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(ParseVisitor)
+    }
+}
+
+/// Limits on compute units spent by a single client of a JSON-RPC server.
+#[derive(Debug, Clone)]
+pub struct ComputeUnitLimits {
+    /// Number of compute units replenished for a client each second.
+    pub per_second: NonZeroU32,
+    /// Max number of compute units a client can spend at once.
+    pub burst: NonZeroU32,
+    /// Costs of RPC methods in compute units.
+    pub method_costs: MethodComputeUnits,
+    /// HTTP header with the client API key. If present in a request and recognized by the server,
+    /// the client is identified by the API key.
+    pub api_key_header: Option<String>,
+    /// HTTP header with the client IP address (e.g., `X-Forwarded-For` set by a load balancer). If the API key
+    /// is not provided, the client is identified by the last address in this header (i.e., the one appended by the proxy
+    /// closest to the server).
+    pub client_ip_header: Option<String>,
+}
+
 /// Response size limits for JSON-RPC servers.
 #[derive(Debug)]
 pub struct MaxResponseSize {
@@ -220,6 +332,48 @@ pub struct Web3JsonRpcConfig {
     /// (hundreds or thousands RPS).
     #[serde(default)]
     pub extended_api_tracing: bool,
+    /// Source of storage notifications used to drive pub/sub subscriptions. If not set, pub/sub notifiers
+    /// poll Postgres every `pubsub_polling_interval`.
+    #[serde(default)]
+    pub pubsub_notifications: Option<PubSubNotificationsSource>,
+    /// Path to a JSON file with addresses that may not be involved in submitted transactions (an array of addresses).
+    /// The file is reloaded if modified.
+    pub tx_deny_list_path: Option<String>,
+    /// Path to a JSON file with contract methods that submitted transactions are allowed to call (an object mapping
+    /// contract addresses to arrays of 4-byte method selectors; an empty array allows all methods of the contract).
+    /// The file is reloaded if modified.
+    pub tx_method_allowlist_path: Option<String>,
+    /// Max calldata size of submitted transactions in bytes. If not set, calldata size is only limited by `max_tx_size`.
+    pub max_tx_calldata_size: Option<usize>,
+    /// Max value (in wei) transferred by submitted transactions. If not set, the value is not limited.
+    pub max_tx_value: Option<U256>,
+    /// Interval between checks whether transaction filter lists were modified, in milliseconds.
+    /// Default is 10 seconds.
+    pub tx_filter_lists_reload_interval_ms: Option<u64>,
+    /// Number of compute units replenished for a single client each second. If not set, compute units are not limited.
+    pub compute_units_per_second_limit: Option<NonZeroU32>,
+    /// Max number of compute units a single client can spend at once. Default is `compute_units_per_second_limit`.
+    pub compute_units_burst_limit: Option<NonZeroU32>,
+    /// Costs of RPC methods in compute units. Methods not mentioned here have the cost of 1 unit.
+    #[serde(default = "MethodComputeUnits::empty")]
+    pub method_compute_units: MethodComputeUnits,
+    /// HTTP header with the client API key used to identify clients for compute unit limits. Only API keys
+    /// listed in the API secrets are recognized; requests with other keys are identified as if they had no key.
+    pub api_key_header: Option<String>,
+    /// HTTP header with the client IP address (e.g., `X-Forwarded-For`) used to identify clients without
+    /// an API key for compute unit limits. If neither header is present, the client shares limits with other
+    /// unidentified clients.
+    pub client_ip_header: Option<String>,
+}
+
+/// Source of storage notifications (e.g., about sealed L2 blocks) for pub/sub subscriptions.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+pub enum PubSubNotificationsSource {
+    /// Notifications are delivered via Postgres `LISTEN` / `NOTIFY`. Works regardless of the node deployment.
+    Postgres,
+    /// Notifications are broadcast in process. Only works if the API server runs in the same process
+    /// as the state keeper.
+    InProcess,
 }
 
 impl Web3JsonRpcConfig {
@@ -260,6 +414,17 @@ impl Web3JsonRpcConfig {
             whitelisted_tokens_for_aa: Default::default(),
             api_namespaces: None,
             extended_api_tracing: false,
+            pubsub_notifications: None,
+            tx_deny_list_path: None,
+            tx_method_allowlist_path: None,
+            max_tx_calldata_size: None,
+            max_tx_value: None,
+            tx_filter_lists_reload_interval_ms: None,
+            compute_units_per_second_limit: None,
+            compute_units_burst_limit: None,
+            method_compute_units: MethodComputeUnits::empty(),
+            api_key_header: None,
+            client_ip_header: None,
         }
     }
 
@@ -351,6 +516,21 @@ impl Web3JsonRpcConfig {
     pub fn mempool_cache_size(&self) -> usize {
         self.mempool_cache_size.unwrap_or(10_000)
     }
+
+    pub fn compute_unit_limits(&self) -> Option<ComputeUnitLimits> {
+        let per_second = self.compute_units_per_second_limit?;
+        Some(ComputeUnitLimits {
+            per_second,
+            burst: self.compute_units_burst_limit.unwrap_or(per_second),
+            method_costs: self.method_compute_units.clone(),
+            api_key_header: self.api_key_header.clone(),
+            client_ip_header: self.client_ip_header.clone(),
+        })
+    }
+
+    pub fn tx_filter_lists_reload_interval(&self) -> Duration {
+        Duration::from_millis(self.tx_filter_lists_reload_interval_ms.unwrap_or(10_000))
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -430,4 +610,21 @@ mod tests {
         assert_eq!(scaled.get("zks_getProof"), Some(32_000));
         assert_eq!(scaled.get("eth_blockNumber"), None);
     }
+
+    #[test]
+    fn working_with_method_compute_units() {
+        let costs: MethodComputeUnits = "eth_getLogs=20, debug_trace*=100,debug_traceCall = 50 "
+            .parse()
+            .unwrap();
+        assert_eq!(costs.iter().len(), 3);
+        assert_eq!(costs.get("eth_getLogs").get(), 20);
+        assert_eq!(costs.get("debug_traceCall").get(), 50);
+        assert_eq!(costs.get("debug_traceTransaction").get(), 100);
+        assert_eq!(costs.get("eth_blockNumber").get(), 1);
+
+        "eth_getLogs=0".parse::<MethodComputeUnits>().unwrap_err();
+        "eth_getLogs=1,eth_getLogs=2"
+            .parse::<MethodComputeUnits>()
+            .unwrap_err();
+    }
 }
@@ -1,8 +1,9 @@
-use std::{str::FromStr, time::Duration};
+use std::{fmt, str::FromStr, time::Duration};
 
+use anyhow::Context as _;
 use serde::Deserialize;
 use zksync_basic_types::{
-    commitment::L1BatchCommitmentMode, network::Network, Address, L2ChainId, H256,
+    commitment::L1BatchCommitmentMode, network::Network, Address, L2ChainId, H256, U256,
 };
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -47,6 +48,82 @@ impl Default for FeeModelVersion {
     }
 }
 
+/// Storage slot pattern trusted during account validation. Parsed from the `<address>:<slot>` format, where the address
+/// can be replaced with `*` to match all contracts, and the slot is a decimal or `0x`-prefixed hex number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(try_from = "String")]
+pub struct ValidationSlotPattern {
+    /// Contract address; `None` means that the pattern matches all contracts.
+    pub address: Option<Address>,
+    pub slot: U256,
+}
+
+impl FromStr for ValidationSlotPattern {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (address, slot) = s
+            .split_once(':')
+            .context("slot pattern must have `<address>:<slot>` format")?;
+        let (address, slot) = (address.trim(), slot.trim());
+        let address = match address {
+            "*" => None,
+            _ => Some(address.parse().context("invalid contract address")?),
+        };
+        let slot = match slot.strip_prefix("0x") {
+            Some(hex_slot) => U256::from_str_radix(hex_slot, 16)
+                .map_err(|err| anyhow::anyhow!("invalid hex slot: {err}"))?,
+            None => U256::from_dec_str(slot)
+                .map_err(|err| anyhow::anyhow!("invalid decimal slot: {err}"))?,
+        };
+        Ok(Self { address, slot })
+    }
+}
+
+impl TryFrom<String> for ValidationSlotPattern {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl fmt::Display for ValidationSlotPattern {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.address {
+            Some(address) => write!(formatter, "{address:?}:{:#x}", self.slot),
+            None => write!(formatter, "*:{:#x}", self.slot),
+        }
+    }
+}
+
+/// Opcode restricted during account validation by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationOpcode {
+    /// Context opcode exposing block-dependent data (e.g., the block number or the gas price).
+    Meta,
+}
+
+impl ValidationOpcode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Meta => "meta",
+        }
+    }
+}
+
+impl FromStr for ValidationOpcode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "meta" => Ok(Self::Meta),
+            _ => anyhow::bail!("unknown restricted validation opcode `{s}`; expected `meta`"),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq, Default)]
 pub struct StateKeeperConfig {
     /// The max number of slots for txs in a block before it should be sealed by the slots sealer.
@@ -118,6 +195,20 @@ pub struct StateKeeperConfig {
 
     /// Max number of computational gas that validation step is allowed to take.
     pub validation_computational_gas_limit: u32,
+    /// Additional contracts trusted during account validation, i.e. the validation logic can access any of their slots.
+    ///
+    /// This and the following custom validation rules are enforced by the API server when accepting transactions.
+    /// If any custom rules are set, they are also enforced by the main node state keeper when executing transactions.
+    /// Because of the latter, tightening the rules while an L1 batch is pending may make its re-execution on restart
+    /// reject previously accepted transactions.
+    #[serde(default)]
+    pub validation_trusted_addresses: Vec<Address>,
+    /// Additional storage slots trusted during account validation.
+    #[serde(default)]
+    pub validation_trusted_slots: Vec<ValidationSlotPattern>,
+    /// Opcodes that are restricted during account validation by default, but should be allowed.
+    #[serde(default)]
+    pub validation_allowed_opcodes: Vec<ValidationOpcode>,
     pub save_call_traces: bool,
 
     /// The maximal number of circuits that a batch can support.
@@ -176,6 +267,9 @@ impl StateKeeperConfig {
             minimal_l2_gas_price: 100000000,
             fee_model_version: FeeModelVersion::V2,
             validation_computational_gas_limit: 300000,
+            validation_trusted_addresses: vec![],
+            validation_trusted_slots: vec![],
+            validation_allowed_opcodes: vec![],
             save_call_traces: true,
             max_circuits_per_batch: 24100,
             protective_reads_persistence_enabled: true,
@@ -221,6 +315,18 @@ impl CircuitBreakerConfig {
     }
 }
 
+/// Policy used to order L2 transactions from different accounts in the mempool.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+pub enum MempoolOrderingPolicyKind {
+    /// Transactions are ordered by the received timestamp.
+    #[default]
+    Fifo,
+    /// Transactions with the highest max fee per gas are taken first.
+    HighestFeeFirst,
+    /// Accounts with the fewest recently executed transactions are given precedence.
+    FairShare,
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct MempoolConfig {
     pub sync_interval_ms: u64,
@@ -229,6 +335,14 @@ pub struct MempoolConfig {
     pub stuck_tx_timeout: u64,
     pub remove_stuck_txs: bool,
     pub delay_interval: u64,
+    /// Minimum fee bump (in percent) required to replace a pending L2 transaction with the same nonce.
+    /// If not set, pending transactions are replaced unconditionally.
+    pub replacement_min_fee_bump_percent: Option<u64>,
+    /// Maximum number of pending L2 transactions per initiator account. If not set, the number is not limited.
+    pub max_pending_txs_per_account: Option<usize>,
+    /// Policy used to order L2 transactions from different accounts.
+    #[serde(default)]
+    pub ordering_policy: MempoolOrderingPolicyKind,
 }
 
 impl MempoolConfig {
@@ -244,3 +358,29 @@ impl MempoolConfig {
         Duration::from_millis(self.delay_interval)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_validation_slot_patterns() {
+        let pattern: ValidationSlotPattern = "0x000000000000000000000000000000000000800a:0x10"
+            .parse()
+            .unwrap();
+        assert_eq!(pattern.address, Some(Address::from_low_u64_be(0x800a)));
+        assert_eq!(pattern.slot, U256::from(16));
+        let roundtrip: ValidationSlotPattern = pattern.to_string().parse().unwrap();
+        assert_eq!(roundtrip, pattern);
+
+        let pattern: ValidationSlotPattern = " * : 3 ".parse().unwrap();
+        assert_eq!(pattern.address, None);
+        assert_eq!(pattern.slot, U256::from(3));
+        assert_eq!(pattern.to_string(), "*:0x3");
+
+        "0x800a".parse::<ValidationSlotPattern>().unwrap_err();
+        "*:what".parse::<ValidationSlotPattern>().unwrap_err();
+        "meta".parse::<ValidationOpcode>().unwrap();
+        "call".parse::<ValidationOpcode>().unwrap_err();
+    }
+}
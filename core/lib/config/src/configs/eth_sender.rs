@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use anyhow::Context as _;
 use serde::Deserialize;
-use zksync_basic_types::H256;
+use zksync_basic_types::{url::SensitiveUrl, Address, H256};
 use zksync_crypto_primitives::K256PrivateKey;
 
 use crate::EthWatchConfig;
@@ -15,6 +15,8 @@ pub struct EthConfig {
     /// Options related to the `GasAdjuster` submodule.
     pub gas_adjuster: Option<GasAdjusterConfig>,
     pub watcher: Option<EthWatchConfig>,
+    /// Remote signer for L1 operator transactions. If set, operator private keys are not required.
+    pub remote_signer: Option<RemoteSignerConfig>,
 }
 
 impl EthConfig {
@@ -40,6 +42,9 @@ impl EthConfig {
                 l1_batch_min_age_before_execute_seconds: None,
                 max_acceptable_priority_fee_in_gwei: 100000000000,
                 pubdata_sending_mode: PubdataSendingMode::Calldata,
+                fee_spike_ratio: None,
+                cheap_blob_fee_ratio: None,
+                max_fee_postponement_seconds: None,
             }),
             gas_adjuster: Some(GasAdjusterConfig {
                 default_priority_fee_per_gas: 1000000000,
@@ -58,7 +63,9 @@ impl EthConfig {
             watcher: Some(EthWatchConfig {
                 confirmations_for_eth_event: None,
                 eth_node_poll_interval: 0,
+                event_subscriptions: vec![],
             }),
+            remote_signer: None,
         }
     }
 }
@@ -117,6 +124,18 @@ pub struct SenderConfig {
 
     /// The mode in which we send pubdata: Calldata, Blobs or Custom (DA layers, Object Store, etc.)
     pub pubdata_sending_mode: PubdataSendingMode,
+
+    /// If set, commit operations are postponed while L1 fees spike, i.e. while the latest L1 base fee
+    /// (or blob base fee if pubdata is sent in blobs) exceeds its median over recent L1 blocks by this factor.
+    pub fee_spike_ratio: Option<f64>,
+    /// If set and pubdata is sent in blobs, commit operations are postponed until `max_aggregated_blocks_to_commit`
+    /// L1 batches can be packed together unless blob space is cheap, i.e. unless the latest blob base fee
+    /// doesn't exceed its median over recent L1 blocks multiplied by this factor. While blob space is cheap,
+    /// commit operations are not postponed.
+    pub cheap_blob_fee_ratio: Option<f64>,
+    /// Maximum age of the oldest L1 batch in a commit operation in seconds, after which the operation
+    /// is no longer postponed based on L1 fees. If not specified, 1 hour is used.
+    pub max_fee_postponement_seconds: Option<u64>,
 }
 
 impl SenderConfig {
@@ -130,6 +149,14 @@ impl SenderConfig {
         Duration::from_secs(self.aggregate_tx_poll_period)
     }
 
+    const DEFAULT_MAX_FEE_POSTPONEMENT_SECONDS: u64 = 3_600;
+
+    /// Returns `self.max_fee_postponement_seconds` or the default value.
+    pub fn max_fee_postponement_seconds(&self) -> u64 {
+        self.max_fee_postponement_seconds
+            .unwrap_or(Self::DEFAULT_MAX_FEE_POSTPONEMENT_SECONDS)
+    }
+
     // Don't load private key, if it's not required.
     #[deprecated]
     pub fn private_key(&self) -> anyhow::Result<Option<K256PrivateKey>> {
@@ -153,6 +180,34 @@ impl SenderConfig {
     }
 }
 
+/// Configuration of a remote signer with a Web3Signer-compatible JSON-RPC API, which is used to sign
+/// L1 operator transactions instead of private keys.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct RemoteSignerConfig {
+    /// URL of the remote signer JSON-RPC API.
+    pub url: SensitiveUrl,
+    /// Address of the operator account managed by the remote signer.
+    pub operator_address: Address,
+    /// Address of the blob operator account managed by the remote signer. Required if pubdata is sent in blobs.
+    pub blob_operator_address: Option<Address>,
+    /// Address of the previous operator account managed by the remote signer. Should be set while the operator key
+    /// is being rotated, so that transactions sent by the previous operator can be resent if necessary.
+    pub previous_operator_address: Option<Address>,
+    /// Timeout for requests to the remote signer in milliseconds.
+    #[serde(default = "RemoteSignerConfig::default_request_timeout_ms")]
+    pub request_timeout_ms: u64,
+}
+
+impl RemoteSignerConfig {
+    pub const fn default_request_timeout_ms() -> u64 {
+        10_000
+    }
+
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_millis(self.request_timeout_ms)
+    }
+}
+
 #[derive(Debug, Deserialize, Copy, Clone, PartialEq, Default)]
 pub struct GasAdjusterConfig {
     /// Priority Fee to be used by GasAdjuster
@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
+use zksync_basic_types::Address;
 
 /// Configuration for the Ethereum watch crate.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
@@ -11,6 +12,10 @@ pub struct EthWatchConfig {
     /// How often we want to poll the Ethereum node.
     /// Value in milliseconds.
     pub eth_node_poll_interval: u64,
+    /// Additional L1 events to watch for and persist in the database, so that they can be consumed
+    /// by other components. Not supported in env-based configs.
+    #[serde(default)]
+    pub event_subscriptions: Vec<L1EventSubscriptionConfig>,
 }
 
 impl EthWatchConfig {
@@ -19,3 +24,21 @@ impl EthWatchConfig {
         Duration::from_millis(self.eth_node_poll_interval)
     }
 }
+
+/// Subscription to events emitted by an L1 contract.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct L1EventSubscriptionConfig {
+    /// Unique name of the subscription. Used as the key for the subscription progress and events in the database,
+    /// so it must not change between restarts.
+    pub name: String,
+    /// Address of the contract emitting events.
+    pub contract_address: Address,
+    /// Signatures of the watched events, e.g. `Transfer(address,address,uint256)`.
+    pub event_signatures: Vec<String>,
+    /// Amount of confirmations for the events to be processed.
+    /// If not specified, the same rule as for the priority operations is used.
+    pub confirmations: Option<u64>,
+    /// L1 block to start watching events from if the subscription has no progress saved.
+    /// If not specified, only events emitted after the subscription is first started are processed.
+    pub start_block: Option<u64>,
+}
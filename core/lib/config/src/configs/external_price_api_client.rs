@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use serde::Deserialize;
+use zksync_basic_types::Address;
 
 pub const DEFAULT_TIMEOUT_MS: u64 = 10_000;
 
@@ -14,9 +15,32 @@ pub struct ExternalPriceApiClientConfig {
     /// Forced conversion ratio. Only used with the ForcedPriceClient.
     pub forced_numerator: Option<u64>,
     pub forced_denominator: Option<u64>,
+    /// L1 address of the Uniswap v3 pool pairing the base token with WETH. Only used with the UniswapTwapPriceAPIClient.
+    pub uniswap_pool_address: Option<Address>,
+    /// Time window for the Uniswap time-weighted average price in seconds. Default is 30 minutes.
+    pub uniswap_twap_window_secs: Option<u32>,
+    /// L1 address of the Chainlink aggregator providing the base token price in ETH. Only used with
+    /// the ChainlinkPriceAPIClient.
+    pub chainlink_feed_address: Option<Address>,
+    /// Sources queried by the aggregating client. Each source is configured in the same way as a standalone client.
+    /// Only used with the AggregatingPriceAPIClient; not supported in env-based configs.
+    #[serde(default)]
+    pub aggregated_sources: Vec<ExternalPriceApiClientConfig>,
+    /// Minimum number of sources that must return valid quotes for the aggregated price to be produced.
+    /// Default is the majority of `aggregated_sources`.
+    pub min_sources: Option<usize>,
+    /// Quotes older than this age are discarded by the aggregating client. Default is 1 hour.
+    pub max_quote_age_ms: Option<u64>,
+    /// Quotes deviating from the median of all quotes by more than this percentage are discarded
+    /// by the aggregating client as outliers. Default is 10%.
+    pub max_deviation_percent: Option<f64>,
 }
 
 impl ExternalPriceApiClientConfig {
+    const DEFAULT_MAX_QUOTE_AGE: Duration = Duration::from_secs(3_600);
+    const DEFAULT_MAX_DEVIATION_PERCENT: f64 = 10.0;
+    const DEFAULT_UNISWAP_TWAP_WINDOW: Duration = Duration::from_secs(1_800);
+
     fn default_timeout() -> u64 {
         DEFAULT_TIMEOUT_MS
     }
@@ -24,4 +48,21 @@ impl ExternalPriceApiClientConfig {
     pub fn client_timeout(&self) -> Duration {
         Duration::from_millis(self.client_timeout_ms)
     }
+
+    pub fn max_quote_age(&self) -> Duration {
+        self.max_quote_age_ms
+            .map_or(Self::DEFAULT_MAX_QUOTE_AGE, Duration::from_millis)
+    }
+
+    pub fn uniswap_twap_window(&self) -> Duration {
+        self.uniswap_twap_window_secs
+            .map_or(Self::DEFAULT_UNISWAP_TWAP_WINDOW, |secs| {
+                Duration::from_secs(secs.into())
+            })
+    }
+
+    pub fn max_deviation_percent(&self) -> f64 {
+        self.max_deviation_percent
+            .unwrap_or(Self::DEFAULT_MAX_DEVIATION_PERCENT)
+    }
 }
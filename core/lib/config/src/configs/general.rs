@@ -8,7 +8,9 @@ use crate::{
         house_keeper::HouseKeeperConfig,
         pruning::PruningConfig,
         snapshot_recovery::SnapshotRecoveryConfig,
-        vm_runner::{BasicWitnessInputProducerConfig, ProtectiveReadsWriterConfig},
+        vm_runner::{
+            BasicWitnessInputProducerConfig, ProtectiveReadsWriterConfig, TraceExporterConfig,
+        },
         CommitmentGeneratorConfig, ExternalPriceApiClientConfig, FriProofCompressorConfig,
         FriProverConfig, FriProverGatewayConfig, FriWitnessGeneratorConfig,
         FriWitnessVectorGeneratorConfig, ObservabilityConfig, PrometheusConfig,
@@ -43,6 +45,7 @@ pub struct GeneralConfig {
     pub da_dispatcher_config: Option<DADispatcherConfig>,
     pub protective_reads_writer_config: Option<ProtectiveReadsWriterConfig>,
     pub basic_witness_input_producer_config: Option<BasicWitnessInputProducerConfig>,
+    pub trace_exporter_config: Option<TraceExporterConfig>,
     pub commitment_generator: Option<CommitmentGeneratorConfig>,
     pub snapshot_recovery: Option<SnapshotRecoveryConfig>,
     pub pruning: Option<PruningConfig>,
@@ -17,6 +17,15 @@ pub struct HouseKeeperConfig {
     pub prover_job_archiver_archive_after_secs: Option<u64>,
     pub fri_gpu_prover_archiver_archiving_interval_ms: Option<u64>,
     pub fri_gpu_prover_archiver_archive_after_secs: Option<u64>,
+    /// Interval between object store GC iterations. GC is disabled if not specified.
+    /// Artifacts are removed from the prover object store (`prover_object_store` in the prover config).
+    pub object_store_gc_interval_ms: Option<u64>,
+    /// Minimum time after proof generation for an L1 batch before its prover artifacts are removed
+    /// from the object store. GC is disabled if not specified.
+    pub object_store_gc_retention_period_secs: Option<u64>,
+    /// If set, object store GC only logs artifacts to be removed without removing them.
+    #[serde(default)]
+    pub object_store_gc_dry_run: bool,
 }
 
 impl HouseKeeperConfig {
@@ -29,4 +38,9 @@ impl HouseKeeperConfig {
         self.fri_gpu_prover_archiver_archiving_interval_ms
             .zip(self.fri_gpu_prover_archiver_archive_after_secs)
     }
+
+    pub fn object_store_gc_params(&self) -> Option<(u64, u64)> {
+        self.object_store_gc_interval_ms
+            .zip(self.object_store_gc_retention_period_secs)
+    }
 }
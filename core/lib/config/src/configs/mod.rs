@@ -22,11 +22,13 @@ pub use self::{
     observability::{ObservabilityConfig, OpentelemetryConfig},
     proof_data_handler::ProofDataHandlerConfig,
     pruning::PruningConfig,
-    secrets::{DatabaseSecrets, L1Secrets, Secrets},
+    secrets::{ApiSecrets, DatabaseSecrets, L1Secrets, Secrets},
     snapshot_recovery::SnapshotRecoveryConfig,
     snapshots_creator::SnapshotsCreatorConfig,
     utils::PrometheusConfig,
-    vm_runner::{BasicWitnessInputProducerConfig, ProtectiveReadsWriterConfig},
+    vm_runner::{
+        BasicWitnessInputProducerConfig, ProtectiveReadsWriterConfig, TraceExporterConfig,
+    },
 };
 
 pub mod api;
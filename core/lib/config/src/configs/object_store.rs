@@ -1,4 +1,7 @@
-use serde::Deserialize;
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use anyhow::Context as _;
+use serde::{de, Deserialize, Deserializer};
 
 /// Configuration for the object store
 #[derive(Debug, Clone, PartialEq, Deserialize)]
@@ -16,6 +19,15 @@ pub struct ObjectStoreConfig {
     /// **Important.** Mirroring logic assumes that objects in the underlying store are immutable. If this is not the case,
     /// the mirrored objects may become stale.
     pub local_mirror_path: Option<String>,
+    /// Codecs used to compress objects before putting them into the store, keyed by the bucket name.
+    /// Objects in buckets without a codec are stored uncompressed.
+    ///
+    /// **Important.** Objects are only decompressed in buckets with a configured codec (any codec can be read
+    /// regardless of which one is configured). Thus, when enabling compression for a bucket, all components reading
+    /// from the bucket must be upgraded and configured first, before any writer starts compressing objects.
+    /// Likewise, compression must not be disabled for a bucket that may contain compressed objects.
+    #[serde(default)]
+    pub compression: BucketCompressionCodecs,
 }
 
 impl ObjectStoreConfig {
@@ -24,6 +36,107 @@ impl ObjectStoreConfig {
     }
 }
 
+/// Codec used to compress objects in the store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum ObjectCompressionCodec {
+    Gzip,
+    Zstd,
+}
+
+impl FromStr for ObjectCompressionCodec {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Gzip" => Ok(Self::Gzip),
+            "Zstd" => Ok(Self::Zstd),
+            _ => anyhow::bail!("unknown compression codec `{s}`; expected `Gzip` or `Zstd`"),
+        }
+    }
+}
+
+/// Compression codecs for specific object store buckets.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BucketCompressionCodecs(HashMap<String, ObjectCompressionCodec>);
+
+impl<S: Into<String>> FromIterator<(S, ObjectCompressionCodec)> for BucketCompressionCodecs {
+    fn from_iter<I: IntoIterator<Item = (S, ObjectCompressionCodec)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(bucket, codec)| (bucket.into(), codec))
+                .collect(),
+        )
+    }
+}
+
+impl FromStr for BucketCompressionCodecs {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut codecs = HashMap::new();
+        for part in s.split(',').filter(|part| !part.trim().is_empty()) {
+            let (bucket, codec) = part
+                .split_once('=')
+                .with_context(|| format!("Part `{part}` doesn't have form <bucket>=<codec>"))?;
+            let bucket = bucket.trim();
+            let codec = codec.trim().parse().with_context(|| {
+                format!("`{codec}` specified for bucket `{bucket}` is not a valid codec")
+            })?;
+            if let Some(prev_codec) = codecs.insert(bucket.to_owned(), codec) {
+                anyhow::bail!(
+                    "Codec for bucket `{bucket}` is redefined from {prev_codec:?} to {codec:?}"
+                );
+            }
+        }
+        Ok(Self(codecs))
+    }
+}
+
+impl BucketCompressionCodecs {
+    pub fn empty() -> Self {
+        Self(HashMap::new())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Gets the codec for the specified bucket, or `None` if objects in the bucket are not compressed.
+    pub fn get(&self, bucket: &str) -> Option<ObjectCompressionCodec> {
+        self.0.get(bucket).copied()
+    }
+
+    /// Iterates over all bucket codecs.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&str, ObjectCompressionCodec)> + '_ {
+        self.0
+            .iter()
+            .map(|(bucket, &codec)| (bucket.as_str(), codec))
+    }
+}
+
+impl<'de> Deserialize<'de> for BucketCompressionCodecs {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ParseVisitor;
+
+        impl<'v> de::Visitor<'v> for ParseVisitor {
+            type Value = BucketCompressionCodecs;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("comma-separated list of <bucket>=<codec> tuples, such as: prover_jobs_fri=Zstd,witness_inputs=Gzip")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                value.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(ParseVisitor)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 #[serde(tag = "mode")]
 pub enum ObjectStoreMode {
@@ -40,4 +153,32 @@ pub enum ObjectStoreMode {
     FileBacked {
         file_backed_base_path: String,
     },
+    /// Store based on the S3 API (AWS S3 or a compatible service, e.g. MinIO).
+    S3 {
+        /// Name of the bucket.
+        bucket: String,
+        /// Region of the bucket, e.g. `us-east-1`. Used for request signing.
+        region: String,
+        /// Custom endpoint URL, e.g. `http://localhost:9000` for MinIO. If not specified,
+        /// the AWS endpoint for `region` is used.
+        endpoint: Option<String>,
+        /// Addressing style for bucket URLs. If not specified, virtual-host addressing is used.
+        #[serde(default)]
+        addressing_style: S3AddressingStyle,
+        /// Path to a credentials file in the AWS shared credentials format (e.g., mounted from a secret).
+        /// If not specified, credentials are read from `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY`
+        /// and (optionally) `AWS_SESSION_TOKEN` env variables.
+        s3_credential_file_path: Option<String>,
+    },
+}
+
+/// Addressing style for S3 bucket URLs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum S3AddressingStyle {
+    /// Bucket is a part of the hostname: `https://{bucket}.s3.{region}.amazonaws.com/{key}`.
+    #[default]
+    VirtualHost,
+    /// Bucket is a part of the path: `https://s3.{region}.amazonaws.com/{bucket}/{key}`. Required by most
+    /// self-hosted S3-compatible services.
+    Path,
 }
@@ -16,4 +16,8 @@ pub struct PruningConfig {
     /// the retention period greater than that implicitly imposed by other criteria (e.g., 7 or 30 days).
     /// If set to 0, L1 batches will not be retained based on their timestamp. The default value is 1 hour.
     pub data_retention_sec: Option<u64>,
+    /// Number of hard-pruned L1 batches for which the Merkle tree retains its versions (archive mode). If set
+    /// to a positive value, historical storage reads for pruned blocks are served from the tree.
+    /// The default value is 0, i.e., the tree is pruned in sync with Postgres.
+    pub archive_l1_batches: Option<u64>,
 }
@@ -1,4 +1,5 @@
 use anyhow::Context;
+use secrecy::{ExposeSecret as _, Secret};
 use zksync_basic_types::url::SensitiveUrl;
 
 use crate::configs::consensus::ConsensusSecrets;
@@ -15,11 +16,30 @@ pub struct L1Secrets {
     pub l1_rpc_url: SensitiveUrl,
 }
 
+/// API key of a JSON-RPC client.
+#[derive(Debug, Clone)]
+pub struct ApiKey(pub Secret<String>);
+
+impl PartialEq for ApiKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.expose_secret().eq(other.0.expose_secret())
+    }
+}
+
+/// Secrets for the JSON-RPC API servers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiSecrets {
+    /// API keys recognized when identifying clients for compute unit limits. Clients providing a key
+    /// not in this list are identified as if they didn't provide a key.
+    pub api_keys: Vec<ApiKey>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Secrets {
     pub consensus: Option<ConsensusSecrets>,
     pub database: Option<DatabaseSecrets>,
     pub l1: Option<L1Secrets>,
+    pub api: Option<ApiSecrets>,
 }
 
 impl DatabaseSecrets {
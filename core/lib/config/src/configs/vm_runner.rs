@@ -34,3 +34,20 @@ impl BasicWitnessInputProducerConfig {
         "./db/basic_witness_input_producer".to_owned()
     }
 }
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Default)]
+pub struct TraceExporterConfig {
+    /// Path to the RocksDB data directory that serves state cache.
+    #[serde(default = "TraceExporterConfig::default_db_path")]
+    pub db_path: String,
+    /// How many max batches should be processed at the same time.
+    pub window_size: u32,
+    /// All batches before this one (inclusive) are always considered to be processed.
+    pub first_processed_batch: L1BatchNumber,
+}
+
+impl TraceExporterConfig {
+    fn default_db_path() -> String {
+        "./db/trace_exporter".to_owned()
+    }
+}
@@ -62,6 +62,9 @@ impl Wallet {
 pub struct EthSender {
     pub operator: Wallet,
     pub blob_operator: Option<Wallet>,
+    /// Previous main operator, set while the main operator key is being rotated. Transactions already
+    /// sent by this operator are monitored and resent using this key until all of them are confirmed.
+    pub previous_operator: Option<Wallet>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -83,6 +86,7 @@ impl Wallets {
                 blob_operator: Some(
                     Wallet::from_private_key_bytes(H256::repeat_byte(0x2), None).unwrap(),
                 ),
+                previous_operator: None,
             }),
             state_keeper: Some(StateKeeper {
                 fee_account: AddressWallet::from_address(H160::repeat_byte(0x3)),
@@ -6,7 +6,7 @@ use zksync_basic_types::{
     commitment::L1BatchCommitmentMode,
     network::Network,
     protocol_version::{ProtocolSemanticVersion, ProtocolVersionId, VersionPatch},
-    L1BatchNumber, L1ChainId, L2ChainId,
+    L1BatchNumber, L1ChainId, L2ChainId, U256,
 };
 use zksync_consensus_utils::EncodeDist;
 use zksync_crypto_primitives::K256PrivateKey;
@@ -43,6 +43,17 @@ impl Distribution<configs::chain::FeeModelVersion> for EncodeDist {
     }
 }
 
+impl Distribution<configs::chain::MempoolOrderingPolicyKind> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::chain::MempoolOrderingPolicyKind {
+        type T = configs::chain::MempoolOrderingPolicyKind;
+        match rng.gen_range(0..3) {
+            0 => T::Fifo,
+            1 => T::HighestFeeFirst,
+            _ => T::FairShare,
+        }
+    }
+}
+
 impl Distribution<configs::ApiConfig> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::ApiConfig {
         configs::ApiConfig {
@@ -101,6 +112,32 @@ impl Distribution<configs::api::Web3JsonRpcConfig> for EncodeDist {
             api_namespaces: self
                 .sample_opt(|| self.sample_range(rng).map(|_| self.sample(rng)).collect()),
             extended_api_tracing: self.sample(rng),
+            pubsub_notifications: self.sample(rng),
+            tx_deny_list_path: self.sample(rng),
+            tx_method_allowlist_path: self.sample(rng),
+            max_tx_calldata_size: self.sample(rng),
+            max_tx_value: self.sample_opt(|| U256([rng.gen(), rng.gen(), 0, 0])),
+            tx_filter_lists_reload_interval_ms: self.sample(rng),
+            compute_units_per_second_limit: self.sample(rng),
+            compute_units_burst_limit: self.sample(rng),
+            method_compute_units: [
+                ("eth_getLogs", self.sample(rng)),
+                ("debug_trace*", self.sample(rng)),
+            ]
+            .into_iter()
+            .collect(),
+            api_key_header: self.sample(rng),
+            client_ip_header: self.sample(rng),
+        }
+    }
+}
+
+impl Distribution<configs::api::PubSubNotificationsSource> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::api::PubSubNotificationsSource {
+        type T = configs::api::PubSubNotificationsSource;
+        match rng.gen_range(0..2) {
+            0 => T::Postgres,
+            _ => T::InProcess,
         }
     }
 }
@@ -177,6 +214,9 @@ impl Distribution<configs::chain::StateKeeperConfig> for EncodeDist {
             max_pubdata_per_batch: self.sample(rng),
             fee_model_version: self.sample(rng),
             validation_computational_gas_limit: self.sample(rng),
+            validation_trusted_addresses: self.sample_range(rng).map(|_| rng.gen()).collect(),
+            validation_trusted_slots: self.sample_range(rng).map(|_| self.sample(rng)).collect(),
+            validation_allowed_opcodes: self.sample_range(rng).map(|_| self.sample(rng)).collect(),
             save_call_traces: self.sample(rng),
             max_circuits_per_batch: self.sample(rng),
             protective_reads_persistence_enabled: self.sample(rng),
@@ -189,6 +229,21 @@ impl Distribution<configs::chain::StateKeeperConfig> for EncodeDist {
     }
 }
 
+impl Distribution<configs::chain::ValidationSlotPattern> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::chain::ValidationSlotPattern {
+        configs::chain::ValidationSlotPattern {
+            address: self.sample_opt(|| rng.gen()),
+            slot: U256::from(rng.gen::<u64>()),
+        }
+    }
+}
+
+impl Distribution<configs::chain::ValidationOpcode> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, _rng: &mut R) -> configs::chain::ValidationOpcode {
+        configs::chain::ValidationOpcode::Meta
+    }
+}
+
 impl Distribution<configs::chain::OperationsManagerConfig> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::chain::OperationsManagerConfig {
         configs::chain::OperationsManagerConfig {
@@ -217,6 +272,9 @@ impl Distribution<configs::chain::MempoolConfig> for EncodeDist {
             stuck_tx_timeout: self.sample(rng),
             remove_stuck_txs: self.sample(rng),
             delay_interval: self.sample(rng),
+            replacement_min_fee_bump_percent: self.sample(rng),
+            max_pending_txs_per_account: self.sample_opt(|| rng.gen()),
+            ordering_policy: self.sample(rng),
         }
     }
 }
@@ -324,6 +382,19 @@ impl Distribution<configs::EthConfig> for EncodeDist {
             sender: self.sample(rng),
             gas_adjuster: self.sample(rng),
             watcher: self.sample(rng),
+            remote_signer: self.sample(rng),
+        }
+    }
+}
+
+impl Distribution<configs::eth_sender::RemoteSignerConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::eth_sender::RemoteSignerConfig {
+        configs::eth_sender::RemoteSignerConfig {
+            url: format!("localhost:{}", rng.gen::<u16>()).parse().unwrap(),
+            operator_address: rng.gen(),
+            blob_operator_address: self.sample_opt(|| rng.gen()),
+            previous_operator_address: self.sample_opt(|| rng.gen()),
+            request_timeout_ms: self.sample(rng),
         }
     }
 }
@@ -380,6 +451,9 @@ impl Distribution<configs::eth_sender::SenderConfig> for EncodeDist {
             l1_batch_min_age_before_execute_seconds: self.sample(rng),
             max_acceptable_priority_fee_in_gwei: self.sample(rng),
             pubdata_sending_mode: PubdataSendingMode::Calldata,
+            fee_spike_ratio: self.sample(rng),
+            cheap_blob_fee_ratio: self.sample(rng),
+            max_fee_postponement_seconds: self.sample(rng),
         }
     }
 }
@@ -408,6 +482,22 @@ impl Distribution<configs::EthWatchConfig> for EncodeDist {
         configs::EthWatchConfig {
             confirmations_for_eth_event: self.sample(rng),
             eth_node_poll_interval: self.sample(rng),
+            event_subscriptions: self.sample_collect(rng),
+        }
+    }
+}
+
+impl Distribution<configs::eth_watch::L1EventSubscriptionConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> configs::eth_watch::L1EventSubscriptionConfig {
+        configs::eth_watch::L1EventSubscriptionConfig {
+            name: self.sample(rng),
+            contract_address: rng.gen(),
+            event_signatures: self.sample_collect(rng),
+            confirmations: self.sample(rng),
+            start_block: self.sample(rng),
         }
     }
 }
@@ -608,6 +698,9 @@ impl Distribution<configs::house_keeper::HouseKeeperConfig> for EncodeDist {
             prover_job_archiver_archive_after_secs: self.sample(rng),
             fri_gpu_prover_archiver_archiving_interval_ms: self.sample(rng),
             fri_gpu_prover_archiver_archive_after_secs: self.sample(rng),
+            object_store_gc_interval_ms: self.sample(rng),
+            object_store_gc_retention_period_secs: self.sample(rng),
+            object_store_gc_dry_run: self.sample(rng),
         }
     }
 }
@@ -615,10 +708,17 @@ impl Distribution<configs::house_keeper::HouseKeeperConfig> for EncodeDist {
 impl Distribution<configs::object_store::ObjectStoreMode> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::object_store::ObjectStoreMode {
         type T = configs::object_store::ObjectStoreMode;
-        match rng.gen_range(0..4) {
+        match rng.gen_range(0..5) {
             0 => T::GCS {
                 bucket_base_url: self.sample(rng),
             },
+            3 => T::S3 {
+                bucket: self.sample(rng),
+                region: self.sample(rng),
+                endpoint: self.sample(rng),
+                addressing_style: self.sample(rng),
+                s3_credential_file_path: self.sample(rng),
+            },
             1 => T::GCSWithCredentialFile {
                 bucket_base_url: self.sample(rng),
                 gcs_credential_file_path: self.sample(rng),
@@ -633,12 +733,39 @@ impl Distribution<configs::object_store::ObjectStoreMode> for EncodeDist {
     }
 }
 
+impl Distribution<configs::object_store::S3AddressingStyle> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::object_store::S3AddressingStyle {
+        type T = configs::object_store::S3AddressingStyle;
+        match rng.gen_range(0..2) {
+            0 => T::VirtualHost,
+            _ => T::Path,
+        }
+    }
+}
+
+impl Distribution<configs::object_store::ObjectCompressionCodec> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> configs::object_store::ObjectCompressionCodec {
+        type T = configs::object_store::ObjectCompressionCodec;
+        match rng.gen_range(0..2) {
+            0 => T::Gzip,
+            _ => T::Zstd,
+        }
+    }
+}
+
 impl Distribution<configs::ObjectStoreConfig> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::ObjectStoreConfig {
         configs::ObjectStoreConfig {
             mode: self.sample(rng),
             max_retries: self.sample(rng),
             local_mirror_path: self.sample(rng),
+            compression: self
+                .sample_range(rng)
+                .map(|_| (Distribution::<String>::sample(self, rng), self.sample(rng)))
+                .collect(),
         }
     }
 }
@@ -824,6 +951,18 @@ impl Distribution<configs::secrets::DatabaseSecrets> for EncodeDist {
     }
 }
 
+impl Distribution<configs::secrets::ApiSecrets> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::secrets::ApiSecrets {
+        use configs::secrets::{ApiKey, ApiSecrets};
+        ApiSecrets {
+            api_keys: self
+                .sample_range(rng)
+                .map(|_| ApiKey(String::into(self.sample(rng))))
+                .collect(),
+        }
+    }
+}
+
 impl Distribution<configs::secrets::Secrets> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::secrets::Secrets {
         use configs::secrets::Secrets;
@@ -831,6 +970,7 @@ impl Distribution<configs::secrets::Secrets> for EncodeDist {
             consensus: self.sample_opt(|| self.sample(rng)),
             database: self.sample_opt(|| self.sample(rng)),
             l1: self.sample_opt(|| self.sample(rng)),
+            api: self.sample_opt(|| self.sample(rng)),
         }
     }
 }
@@ -860,6 +1000,7 @@ impl Distribution<configs::wallets::EthSender> for EncodeDist {
         configs::wallets::EthSender {
             operator: self.sample(rng),
             blob_operator: self.sample_opt(|| self.sample(rng)),
+            previous_operator: self.sample_opt(|| self.sample(rng)),
         }
     }
 }
@@ -924,6 +1065,16 @@ impl Distribution<configs::vm_runner::BasicWitnessInputProducerConfig> for Encod
     }
 }
 
+impl Distribution<configs::vm_runner::TraceExporterConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::vm_runner::TraceExporterConfig {
+        configs::vm_runner::TraceExporterConfig {
+            db_path: self.sample(rng),
+            window_size: self.sample(rng),
+            first_processed_batch: L1BatchNumber(rng.gen()),
+        }
+    }
+}
+
 impl Distribution<configs::CommitmentGeneratorConfig> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::CommitmentGeneratorConfig {
         configs::CommitmentGeneratorConfig {
@@ -980,6 +1131,7 @@ impl Distribution<configs::pruning::PruningConfig> for EncodeDist {
             chunk_size: self.sample(rng),
             removal_delay_sec: self.sample_opt(|| rng.gen()),
             data_retention_sec: self.sample(rng),
+            archive_l1_batches: self.sample(rng),
         }
     }
 }
@@ -1001,13 +1153,32 @@ impl Distribution<configs::external_price_api_client::ExternalPriceApiClientConf
         &self,
         rng: &mut R,
     ) -> configs::external_price_api_client::ExternalPriceApiClientConfig {
+        // Aggregated sources are sampled without nested sources to keep the config finite.
+        let sample_source =
+            |rng: &mut R| configs::external_price_api_client::ExternalPriceApiClientConfig {
+                source: self.sample(rng),
+                base_url: self.sample(rng),
+                api_key: self.sample(rng),
+                client_timeout_ms: self.sample(rng),
+                forced_numerator: self.sample(rng),
+                forced_denominator: self.sample(rng),
+                uniswap_pool_address: self.sample_opt(|| rng.gen()),
+                uniswap_twap_window_secs: self.sample(rng),
+                chainlink_feed_address: self.sample_opt(|| rng.gen()),
+                aggregated_sources: vec![],
+                min_sources: None,
+                max_quote_age_ms: None,
+                max_deviation_percent: None,
+            };
+
         configs::external_price_api_client::ExternalPriceApiClientConfig {
-            source: self.sample(rng),
-            base_url: self.sample(rng),
-            api_key: self.sample(rng),
-            client_timeout_ms: self.sample(rng),
-            forced_numerator: self.sample(rng),
-            forced_denominator: self.sample(rng),
+            aggregated_sources: (0..rng.gen_range(0..3))
+                .map(|_| sample_source(rng))
+                .collect(),
+            min_sources: self.sample(rng),
+            max_quote_age_ms: self.sample(rng),
+            max_deviation_percent: self.sample(rng),
+            ..sample_source(rng)
         }
     }
 }
@@ -1038,6 +1209,7 @@ impl Distribution<configs::GeneralConfig> for EncodeDist {
             da_dispatcher_config: self.sample(rng),
             protective_reads_writer_config: self.sample(rng),
             basic_witness_input_producer_config: self.sample(rng),
+            trace_exporter_config: self.sample(rng),
             commitment_generator: self.sample(rng),
             snapshot_recovery: self.sample(rng),
             pruning: self.sample(rng),
@@ -0,0 +1,3 @@
+-- IDs of blobs for each pubdata chunk if the L1 batch pubdata was split into several blobs;
+-- in this case, `blob_id` contains the ID of the first chunk.
+ALTER TABLE data_availability ADD COLUMN IF NOT EXISTS chunk_blob_ids TEXT[];
@@ -0,0 +1,2 @@
+-- Time when object store blobs with witness inputs for the L1 batch were garbage-collected.
+ALTER TABLE proof_generation_details ADD COLUMN IF NOT EXISTS blobs_removed_at TIMESTAMP;
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS vm_runner_trace_exporter
+(
+    l1_batch_number       BIGINT    NOT NULL PRIMARY KEY,
+    created_at            TIMESTAMP NOT NULL,
+    updated_at            TIMESTAMP NOT NULL,
+    processing_started_at TIMESTAMP,
+    time_taken            TIME
+);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS eth_sender_operator_rotations;
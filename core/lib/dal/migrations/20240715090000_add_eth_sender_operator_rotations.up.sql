@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS eth_sender_operator_rotations
+(
+    previous_operator_addr BYTEA     NOT NULL PRIMARY KEY,
+    new_operator_addr      BYTEA     NOT NULL,
+    created_at             TIMESTAMP NOT NULL,
+    completed_at           TIMESTAMP
+);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS eth_watch_subscription_events;
+DROP TABLE IF EXISTS eth_watch_subscription_checkpoints;
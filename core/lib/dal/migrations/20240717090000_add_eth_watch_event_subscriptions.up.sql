@@ -0,0 +1,25 @@
+CREATE TABLE IF NOT EXISTS eth_watch_subscription_checkpoints
+(
+    subscription_name            TEXT      NOT NULL PRIMARY KEY,
+    last_processed_l1_block      BIGINT    NOT NULL,
+    last_processed_l1_block_hash BYTEA     NOT NULL,
+    created_at                   TIMESTAMP NOT NULL,
+    updated_at                   TIMESTAMP NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS eth_watch_subscription_events
+(
+    subscription_name TEXT      NOT NULL,
+    l1_block_number   BIGINT    NOT NULL,
+    log_index         INT       NOT NULL,
+    l1_block_hash     BYTEA     NOT NULL,
+    tx_hash           BYTEA     NOT NULL,
+    address           BYTEA     NOT NULL,
+    topic1            BYTEA     NOT NULL,
+    topic2            BYTEA     NOT NULL,
+    topic3            BYTEA     NOT NULL,
+    topic4            BYTEA     NOT NULL,
+    value             BYTEA     NOT NULL,
+    created_at        TIMESTAMP NOT NULL,
+    PRIMARY KEY (subscription_name, l1_block_number, log_index)
+);
@@ -0,0 +1,8 @@
+DELETE FROM eth_watch_subscription_checkpoints checkpoints
+WHERE EXISTS (
+    SELECT 1 FROM eth_watch_subscription_checkpoints newer
+    WHERE newer.subscription_name = checkpoints.subscription_name
+        AND newer.last_processed_l1_block > checkpoints.last_processed_l1_block
+);
+ALTER TABLE eth_watch_subscription_checkpoints DROP CONSTRAINT eth_watch_subscription_checkpoints_pkey;
+ALTER TABLE eth_watch_subscription_checkpoints ADD PRIMARY KEY (subscription_name);
@@ -0,0 +1,3 @@
+-- Recent checkpoints are kept so that the fork point can be found after an L1 reorg.
+ALTER TABLE eth_watch_subscription_checkpoints DROP CONSTRAINT eth_watch_subscription_checkpoints_pkey;
+ALTER TABLE eth_watch_subscription_checkpoints ADD PRIMARY KEY (subscription_name, last_processed_l1_block);
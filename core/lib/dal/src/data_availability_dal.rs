@@ -25,21 +25,50 @@ impl DataAvailabilityDal<'_, '_> {
         blob_id: &str,
         sent_at: chrono::NaiveDateTime,
     ) -> DalResult<()> {
+        self.insert_l1_batch_da_chunks(number, &[blob_id.to_owned()], sent_at)
+            .await
+    }
+
+    /// Inserts blob IDs for the given L1 batch, the pubdata of which was dispatched in one or more chunks.
+    /// If blob IDs are already present, verifies that they match the ones provided in the function arguments
+    /// (preventing the same L1 batch from being stored twice).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `blob_ids` is empty.
+    pub async fn insert_l1_batch_da_chunks(
+        &mut self,
+        number: L1BatchNumber,
+        blob_ids: &[String],
+        sent_at: chrono::NaiveDateTime,
+    ) -> DalResult<()> {
+        assert!(!blob_ids.is_empty(), "no blob IDs provided");
+        let blob_id = blob_ids[0].as_str();
+        let chunk_blob_ids = (blob_ids.len() > 1).then_some(blob_ids);
+
         let update_result = sqlx::query!(
             r#"
             INSERT INTO
-                data_availability (l1_batch_number, blob_id, sent_at, created_at, updated_at)
+                data_availability (
+                    l1_batch_number,
+                    blob_id,
+                    chunk_blob_ids,
+                    sent_at,
+                    created_at,
+                    updated_at
+                )
             VALUES
-                ($1, $2, $3, NOW(), NOW())
+                ($1, $2, $3, $4, NOW(), NOW())
             ON CONFLICT DO NOTHING
             "#,
             i64::from(number.0),
             blob_id,
+            chunk_blob_ids,
             sent_at,
         )
         .instrument("insert_l1_batch_da")
         .with_arg("number", &number)
-        .with_arg("blob_id", &blob_id)
+        .with_arg("blob_ids", &blob_ids)
         .report_latency()
         .execute(self.storage)
         .await?;
@@ -56,7 +85,8 @@ impl DataAvailabilityDal<'_, '_> {
             let query = sqlx::query!(
                 r#"
                 SELECT
-                    blob_id
+                    blob_id,
+                    chunk_blob_ids
                 FROM
                     data_availability
                 WHERE
@@ -65,17 +95,16 @@ impl DataAvailabilityDal<'_, '_> {
                 i64::from(number.0),
             );
 
-            let matched: String = instrumentation
+            let row = instrumentation
                 .clone()
                 .with(query)
                 .report_latency()
                 .fetch_one(self.storage)
-                .await?
-                .blob_id;
+                .await?;
 
-            if matched != *blob_id.to_string() {
+            if row.blob_id != blob_id || row.chunk_blob_ids.as_deref() != chunk_blob_ids {
                 let err = instrumentation.constraint_error(anyhow::anyhow!(
-                    "Error storing DA blob id. DA blob_id {blob_id} for L1 batch #{number} does not match the expected value"
+                    "Error storing DA blob id. DA blob_ids {blob_ids:?} for L1 batch #{number} does not match the expected value"
                 ));
                 return Err(err);
             }
@@ -157,6 +186,7 @@ impl DataAvailabilityDal<'_, '_> {
             SELECT
                 l1_batch_number,
                 blob_id,
+                chunk_blob_ids,
                 inclusion_data,
                 sent_at
             FROM
@@ -5,13 +5,14 @@ use sqlx::types::chrono::{DateTime, Utc};
 use zksync_db_connection::{connection::Connection, interpolate_query, match_query_as};
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
-    eth_sender::{EthTx, EthTxBlobSidecar, TxHistory, TxHistoryToSend},
+    eth_sender::{EthTx, EthTxBlobSidecar, OperatorRotation, TxHistory, TxHistoryToSend},
     Address, L1BatchNumber, H256, U256,
 };
 
 use crate::{
     models::storage_eth_tx::{
-        L1BatchEthSenderStats, StorageEthTx, StorageTxHistory, StorageTxHistoryToSend,
+        L1BatchEthSenderStats, StorageEthTx, StorageOperatorRotation, StorageTxHistory,
+        StorageTxHistoryToSend,
     },
     Core,
 };
@@ -56,6 +57,88 @@ impl EthSenderDal<'_, '_> {
         Ok(txs.into_iter().map(|tx| tx.into()).collect())
     }
 
+    /// Returns all transactions of the specified operator that are not confirmed yet, including ones
+    /// without any sent attempts, ordered by ID.
+    pub async fn get_unconfirmed_txs(
+        &mut self,
+        operator_address: Option<Address>,
+    ) -> sqlx::Result<Vec<EthTx>> {
+        let txs = sqlx::query_as!(
+            StorageEthTx,
+            r#"
+            SELECT
+                *
+            FROM
+                eth_txs
+            WHERE
+                from_addr IS NOT DISTINCT FROM $1 -- can't just use equality as NULL != NULL
+                AND confirmed_eth_tx_history_id IS NULL
+            ORDER BY
+                id
+            "#,
+            operator_address.as_ref().map(|h160| h160.as_bytes()),
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+        Ok(txs.into_iter().map(|tx| tx.into()).collect())
+    }
+
+    /// Returns confirmed transactions of the specified type that include L1 batches after `last_l1_batch`,
+    /// ordered by ID.
+    pub async fn get_confirmed_txs_after_l1_batch(
+        &mut self,
+        tx_type: AggregatedActionType,
+        last_l1_batch: L1BatchNumber,
+    ) -> sqlx::Result<Vec<EthTx>> {
+        let txs = sqlx::query_as!(
+            StorageEthTx,
+            r#"
+            SELECT
+                *
+            FROM
+                eth_txs
+            WHERE
+                tx_type = $1
+                AND confirmed_eth_tx_history_id IS NOT NULL
+                AND id IN (
+                    (
+                        SELECT
+                            eth_commit_tx_id
+                        FROM
+                            l1_batches
+                        WHERE
+                            number > $2
+                    )
+                    UNION
+                    (
+                        SELECT
+                            eth_prove_tx_id
+                        FROM
+                            l1_batches
+                        WHERE
+                            number > $2
+                    )
+                    UNION
+                    (
+                        SELECT
+                            eth_execute_tx_id
+                        FROM
+                            l1_batches
+                        WHERE
+                            number > $2
+                    )
+                )
+            ORDER BY
+                id
+            "#,
+            tx_type.to_string(),
+            i64::from(last_l1_batch.0)
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+        Ok(txs.into_iter().map(|tx| tx.into()).collect())
+    }
+
     pub async fn get_eth_l1_batches(&mut self) -> sqlx::Result<L1BatchEthSenderStats> {
         struct EthTxRow {
             number: i64,
@@ -590,6 +673,119 @@ impl EthSenderDal<'_, '_> {
         Ok(nonce.map(|n| n + 1))
     }
 
+    /// Starts rotation of the main operator key from `previous_operator` to `new_operator`. All transactions
+    /// of the main operator (i.e., ones with unset `from_addr`) are attributed to `previous_operator`, so that
+    /// the new operator starts its own nonce sequence. Returns `false` if rotation of `previous_operator`
+    /// was already started before; in this case, no transactions are changed.
+    pub async fn start_operator_rotation(
+        &mut self,
+        previous_operator: Address,
+        new_operator: Address,
+    ) -> sqlx::Result<bool> {
+        let row = sqlx::query!(
+            r#"
+            WITH
+                inserted AS (
+                    INSERT INTO
+                        eth_sender_operator_rotations (previous_operator_addr, new_operator_addr, created_at)
+                    VALUES
+                        ($1, $2, NOW())
+                    ON CONFLICT (previous_operator_addr) DO NOTHING
+                    RETURNING
+                        previous_operator_addr
+                ),
+                updated AS (
+                    UPDATE eth_txs
+                    SET
+                        from_addr = $1,
+                        updated_at = NOW()
+                    WHERE
+                        from_addr IS NULL
+                        AND EXISTS (
+                            SELECT
+                                1
+                            FROM
+                                inserted
+                        )
+                )
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                inserted
+            "#,
+            previous_operator.as_bytes(),
+            new_operator.as_bytes()
+        )
+        .fetch_one(self.storage.conn())
+        .await?;
+        Ok(row.count > 0)
+    }
+
+    pub async fn get_operator_rotation(
+        &mut self,
+        previous_operator: Address,
+    ) -> sqlx::Result<Option<OperatorRotation>> {
+        let rotation = sqlx::query_as!(
+            StorageOperatorRotation,
+            r#"
+            SELECT
+                *
+            FROM
+                eth_sender_operator_rotations
+            WHERE
+                previous_operator_addr = $1
+            "#,
+            previous_operator.as_bytes()
+        )
+        .fetch_optional(self.storage.conn())
+        .await?;
+        Ok(rotation.map(Into::into))
+    }
+
+    pub async fn complete_operator_rotation(
+        &mut self,
+        previous_operator: Address,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE eth_sender_operator_rotations
+            SET
+                completed_at = NOW()
+            WHERE
+                previous_operator_addr = $1
+                AND completed_at IS NULL
+            "#,
+            previous_operator.as_bytes()
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the number of transactions sent from the specified custom operator address
+    /// that are not confirmed yet (including ones not sent to L1 at all).
+    pub async fn get_unconfirmed_txs_count(
+        &mut self,
+        operator_address: Address,
+    ) -> sqlx::Result<usize> {
+        let count = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                eth_txs
+            WHERE
+                from_addr = $1
+                AND confirmed_eth_tx_history_id IS NULL
+            "#,
+            operator_address.as_bytes()
+        )
+        .fetch_one(self.storage.conn())
+        .await?
+        .count;
+        Ok(count as usize)
+    }
+
     pub async fn mark_failed_transaction(&mut self, eth_tx_id: u32) -> sqlx::Result<()> {
         sqlx::query!(
             r#"
@@ -684,4 +880,20 @@ impl EthSenderDal<'_, '_> {
 
         Ok(())
     }
+
+    /// Deletes a single transaction together with its history. L1 batches referencing the transaction
+    /// are unlinked from it, so that the corresponding aggregated operation is created anew.
+    pub async fn delete_eth_tx(&mut self, eth_tx_id: u32) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM eth_txs
+            WHERE
+                id = $1
+            "#,
+            eth_tx_id as i32
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
 }
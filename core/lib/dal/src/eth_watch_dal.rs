@@ -0,0 +1,513 @@
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::{
+    eth_watch::{L1Event, L1EventCheckpoint},
+    Address, L1BlockNumber, H256,
+};
+
+use crate::Core;
+
+/// DAL for L1 events collected by the configurable `eth_watch` event subscriptions.
+#[derive(Debug)]
+pub struct EthWatchDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl EthWatchDal<'_, '_> {
+    /// Returns the latest checkpoint for the specified subscription.
+    pub async fn get_subscription_checkpoint(
+        &mut self,
+        subscription_name: &str,
+    ) -> DalResult<Option<L1EventCheckpoint>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                last_processed_l1_block,
+                last_processed_l1_block_hash
+            FROM
+                eth_watch_subscription_checkpoints
+            WHERE
+                subscription_name = $1
+            ORDER BY
+                last_processed_l1_block DESC
+            LIMIT
+                1
+            "#,
+            subscription_name
+        )
+        .instrument("get_subscription_checkpoint")
+        .with_arg("subscription_name", &subscription_name)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| L1EventCheckpoint {
+            l1_block_number: L1BlockNumber(row.last_processed_l1_block as u32),
+            l1_block_hash: H256::from_slice(&row.last_processed_l1_block_hash),
+        }))
+    }
+
+    /// Returns up to `limit` checkpoints for the specified subscription preceding `before_l1_block`, latest first.
+    pub async fn get_subscription_checkpoints(
+        &mut self,
+        subscription_name: &str,
+        before_l1_block: L1BlockNumber,
+        limit: usize,
+    ) -> DalResult<Vec<L1EventCheckpoint>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                last_processed_l1_block,
+                last_processed_l1_block_hash
+            FROM
+                eth_watch_subscription_checkpoints
+            WHERE
+                subscription_name = $1
+                AND last_processed_l1_block < $2
+            ORDER BY
+                last_processed_l1_block DESC
+            LIMIT
+                $3
+            "#,
+            subscription_name,
+            i64::from(before_l1_block.0),
+            limit as i64
+        )
+        .instrument("get_subscription_checkpoints")
+        .with_arg("subscription_name", &subscription_name)
+        .with_arg("before_l1_block", &before_l1_block)
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| L1EventCheckpoint {
+                l1_block_number: L1BlockNumber(row.last_processed_l1_block as u32),
+                l1_block_hash: H256::from_slice(&row.last_processed_l1_block_hash),
+            })
+            .collect())
+    }
+
+    /// Adds a checkpoint for the specified subscription. Previous checkpoints are retained
+    /// until they are pruned with [`Self::prune_subscription_checkpoints()`].
+    pub async fn set_subscription_checkpoint(
+        &mut self,
+        subscription_name: &str,
+        checkpoint: L1EventCheckpoint,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                eth_watch_subscription_checkpoints (
+                    subscription_name,
+                    last_processed_l1_block,
+                    last_processed_l1_block_hash,
+                    created_at,
+                    updated_at
+                )
+            VALUES
+                ($1, $2, $3, NOW(), NOW())
+            ON CONFLICT (subscription_name, last_processed_l1_block) DO
+            UPDATE
+            SET
+                last_processed_l1_block_hash = excluded.last_processed_l1_block_hash,
+                updated_at = NOW()
+            "#,
+            subscription_name,
+            i64::from(checkpoint.l1_block_number.0),
+            checkpoint.l1_block_hash.as_bytes()
+        )
+        .instrument("set_subscription_checkpoint")
+        .with_arg("subscription_name", &subscription_name)
+        .with_arg("checkpoint", &checkpoint)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Removes all but `retained_count` latest checkpoints for the specified subscription.
+    pub async fn prune_subscription_checkpoints(
+        &mut self,
+        subscription_name: &str,
+        retained_count: usize,
+    ) -> DalResult<u64> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM eth_watch_subscription_checkpoints
+            WHERE
+                subscription_name = $1
+                AND last_processed_l1_block < (
+                    SELECT
+                        MIN(last_processed_l1_block)
+                    FROM
+                        (
+                            SELECT
+                                last_processed_l1_block
+                            FROM
+                                eth_watch_subscription_checkpoints
+                            WHERE
+                                subscription_name = $1
+                            ORDER BY
+                                last_processed_l1_block DESC
+                            LIMIT
+                                $2
+                        ) AS retained
+                )
+            "#,
+            subscription_name,
+            retained_count as i64
+        )
+        .instrument("prune_subscription_checkpoints")
+        .with_arg("subscription_name", &subscription_name)
+        .with_arg("retained_count", &retained_count)
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Removes checkpoints for the specified subscription after `last_retained_l1_block`.
+    /// Used to roll back the subscription after an L1 reorg.
+    pub async fn delete_subscription_checkpoints_after(
+        &mut self,
+        subscription_name: &str,
+        last_retained_l1_block: L1BlockNumber,
+    ) -> DalResult<u64> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM eth_watch_subscription_checkpoints
+            WHERE
+                subscription_name = $1
+                AND last_processed_l1_block > $2
+            "#,
+            subscription_name,
+            i64::from(last_retained_l1_block.0)
+        )
+        .instrument("delete_subscription_checkpoints_after")
+        .with_arg("subscription_name", &subscription_name)
+        .with_arg("last_retained_l1_block", &last_retained_l1_block)
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Inserts events for the specified subscription. Events that are already present are skipped.
+    pub async fn insert_subscription_events(
+        &mut self,
+        subscription_name: &str,
+        events: &[L1Event],
+    ) -> DalResult<()> {
+        let mut block_numbers = Vec::with_capacity(events.len());
+        let mut log_indices = Vec::with_capacity(events.len());
+        let mut block_hashes = Vec::with_capacity(events.len());
+        let mut tx_hashes = Vec::with_capacity(events.len());
+        let mut addresses = Vec::with_capacity(events.len());
+        let mut topics: [Vec<&[u8]>; 4] = Default::default();
+        let mut values = Vec::with_capacity(events.len());
+        for event in events {
+            block_numbers.push(i64::from(event.l1_block_number.0));
+            log_indices.push(event.log_index as i32);
+            block_hashes.push(event.l1_block_hash.as_bytes());
+            tx_hashes.push(event.tx_hash.as_bytes());
+            addresses.push(event.address.as_bytes());
+            for (i, topics) in topics.iter_mut().enumerate() {
+                let topic = event.topics.get(i).map_or(&[] as &[u8], H256::as_bytes);
+                topics.push(topic);
+            }
+            values.push(event.data.as_slice());
+        }
+        let [topic1, topic2, topic3, topic4] = topics;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                eth_watch_subscription_events (
+                    subscription_name,
+                    l1_block_number,
+                    log_index,
+                    l1_block_hash,
+                    tx_hash,
+                    address,
+                    topic1,
+                    topic2,
+                    topic3,
+                    topic4,
+                    value,
+                    created_at
+                )
+            SELECT
+                $1,
+                u.l1_block_number,
+                u.log_index,
+                u.l1_block_hash,
+                u.tx_hash,
+                u.address,
+                u.topic1,
+                u.topic2,
+                u.topic3,
+                u.topic4,
+                u.value,
+                NOW()
+            FROM
+                UNNEST(
+                    $2::BIGINT[],
+                    $3::INT[],
+                    $4::BYTEA[],
+                    $5::BYTEA[],
+                    $6::BYTEA[],
+                    $7::BYTEA[],
+                    $8::BYTEA[],
+                    $9::BYTEA[],
+                    $10::BYTEA[],
+                    $11::BYTEA[]
+                ) AS u (
+                    l1_block_number,
+                    log_index,
+                    l1_block_hash,
+                    tx_hash,
+                    address,
+                    topic1,
+                    topic2,
+                    topic3,
+                    topic4,
+                    value
+                )
+            ON CONFLICT (subscription_name, l1_block_number, log_index) DO NOTHING
+            "#,
+            subscription_name,
+            &block_numbers,
+            &log_indices,
+            &block_hashes as &[&[u8]],
+            &tx_hashes as &[&[u8]],
+            &addresses as &[&[u8]],
+            &topic1 as &[&[u8]],
+            &topic2 as &[&[u8]],
+            &topic3 as &[&[u8]],
+            &topic4 as &[&[u8]],
+            &values as &[&[u8]]
+        )
+        .instrument("insert_subscription_events")
+        .with_arg("subscription_name", &subscription_name)
+        .with_arg("events.len", &events.len())
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Removes events for the specified subscription emitted in L1 blocks after `last_retained_l1_block`.
+    /// Used to roll back the subscription after an L1 reorg.
+    pub async fn delete_subscription_events_after(
+        &mut self,
+        subscription_name: &str,
+        last_retained_l1_block: L1BlockNumber,
+    ) -> DalResult<u64> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM eth_watch_subscription_events
+            WHERE
+                subscription_name = $1
+                AND l1_block_number > $2
+            "#,
+            subscription_name,
+            i64::from(last_retained_l1_block.0)
+        )
+        .instrument("delete_subscription_events_after")
+        .with_arg("subscription_name", &subscription_name)
+        .with_arg("last_retained_l1_block", &last_retained_l1_block)
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Returns up to `limit` events for the specified subscription starting from (and including) `from_l1_block`,
+    /// ordered by their position on L1.
+    pub async fn get_subscription_events(
+        &mut self,
+        subscription_name: &str,
+        from_l1_block: L1BlockNumber,
+        limit: usize,
+    ) -> DalResult<Vec<L1Event>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_block_number,
+                log_index,
+                l1_block_hash,
+                tx_hash,
+                address,
+                topic1,
+                topic2,
+                topic3,
+                topic4,
+                value
+            FROM
+                eth_watch_subscription_events
+            WHERE
+                subscription_name = $1
+                AND l1_block_number >= $2
+            ORDER BY
+                l1_block_number,
+                log_index
+            LIMIT
+                $3
+            "#,
+            subscription_name,
+            i64::from(from_l1_block.0),
+            limit as i64
+        )
+        .instrument("get_subscription_events")
+        .with_arg("subscription_name", &subscription_name)
+        .with_arg("from_l1_block", &from_l1_block)
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| L1Event {
+                l1_block_number: L1BlockNumber(row.l1_block_number as u32),
+                l1_block_hash: H256::from_slice(&row.l1_block_hash),
+                tx_hash: H256::from_slice(&row.tx_hash),
+                log_index: row.log_index as u32,
+                address: Address::from_slice(&row.address),
+                topics: [row.topic1, row.topic2, row.topic3, row.topic4]
+                    .into_iter()
+                    .filter(|topic| !topic.is_empty())
+                    .map(|topic| H256::from_slice(&topic))
+                    .collect(),
+                data: row.value,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    fn mock_event(l1_block_number: u32, log_index: u32) -> L1Event {
+        L1Event {
+            l1_block_number: L1BlockNumber(l1_block_number),
+            l1_block_hash: H256::repeat_byte(l1_block_number as u8),
+            tx_hash: H256::random(),
+            log_index,
+            address: Address::repeat_byte(1),
+            topics: vec![H256::repeat_byte(0xff), H256::random()],
+            data: vec![1, 2, 3],
+        }
+    }
+
+    #[tokio::test]
+    async fn storing_subscription_events() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.eth_watch_dal();
+        assert_eq!(dal.get_subscription_checkpoint("test").await.unwrap(), None);
+
+        let events = [mock_event(1, 0), mock_event(1, 3), mock_event(5, 0)];
+        dal.insert_subscription_events("test", &events)
+            .await
+            .unwrap();
+        // Re-inserting events should be a no-op.
+        dal.insert_subscription_events("test", &events[1..])
+            .await
+            .unwrap();
+        dal.insert_subscription_events("other", &[mock_event(2, 0)])
+            .await
+            .unwrap();
+        let checkpoint = L1EventCheckpoint {
+            l1_block_number: L1BlockNumber(5),
+            l1_block_hash: H256::repeat_byte(5),
+        };
+        dal.set_subscription_checkpoint("test", checkpoint)
+            .await
+            .unwrap();
+        assert_eq!(
+            dal.get_subscription_checkpoint("test").await.unwrap(),
+            Some(checkpoint)
+        );
+
+        let stored_events = dal
+            .get_subscription_events("test", L1BlockNumber(0), 10)
+            .await
+            .unwrap();
+        assert_eq!(stored_events, events);
+        let stored_events = dal
+            .get_subscription_events("test", L1BlockNumber(2), 10)
+            .await
+            .unwrap();
+        assert_eq!(stored_events, events[2..]);
+
+        let deleted_count = dal
+            .delete_subscription_events_after("test", L1BlockNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(deleted_count, 1);
+        let stored_events = dal
+            .get_subscription_events("test", L1BlockNumber(0), 10)
+            .await
+            .unwrap();
+        assert_eq!(stored_events, events[..2]);
+        let other_events = dal
+            .get_subscription_events("other", L1BlockNumber(0), 10)
+            .await
+            .unwrap();
+        assert_eq!(other_events.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn storing_subscription_checkpoints() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.eth_watch_dal();
+
+        let checkpoints: Vec<_> = (1..=5)
+            .map(|number| L1EventCheckpoint {
+                l1_block_number: L1BlockNumber(number * 10),
+                l1_block_hash: H256::repeat_byte(number as u8),
+            })
+            .collect();
+        for &checkpoint in &checkpoints {
+            dal.set_subscription_checkpoint("test", checkpoint)
+                .await
+                .unwrap();
+        }
+        dal.set_subscription_checkpoint("other", checkpoints[0])
+            .await
+            .unwrap();
+        assert_eq!(
+            dal.get_subscription_checkpoint("test").await.unwrap(),
+            Some(checkpoints[4])
+        );
+
+        let stored_checkpoints = dal
+            .get_subscription_checkpoints("test", L1BlockNumber(40), 2)
+            .await
+            .unwrap();
+        assert_eq!(stored_checkpoints, [checkpoints[2], checkpoints[1]]);
+
+        let pruned_count = dal.prune_subscription_checkpoints("test", 3).await.unwrap();
+        assert_eq!(pruned_count, 2);
+        let stored_checkpoints = dal
+            .get_subscription_checkpoints("test", L1BlockNumber(100), 10)
+            .await
+            .unwrap();
+        assert_eq!(
+            stored_checkpoints,
+            [checkpoints[4], checkpoints[3], checkpoints[2]]
+        );
+
+        let deleted_count = dal
+            .delete_subscription_checkpoints_after("test", L1BlockNumber(35))
+            .await
+            .unwrap();
+        assert_eq!(deleted_count, 2);
+        assert_eq!(
+            dal.get_subscription_checkpoint("test").await.unwrap(),
+            Some(checkpoints[2])
+        );
+        assert_eq!(
+            dal.get_subscription_checkpoint("other").await.unwrap(),
+            Some(checkpoints[0])
+        );
+    }
+}
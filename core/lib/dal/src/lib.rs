@@ -15,8 +15,9 @@ use crate::{
     base_token_dal::BaseTokenDal, blocks_dal::BlocksDal, blocks_web3_dal::BlocksWeb3Dal,
     consensus_dal::ConsensusDal, contract_verification_dal::ContractVerificationDal,
     data_availability_dal::DataAvailabilityDal, eth_sender_dal::EthSenderDal,
-    events_dal::EventsDal, events_web3_dal::EventsWeb3Dal, factory_deps_dal::FactoryDepsDal,
-    proof_generation_dal::ProofGenerationDal, protocol_versions_dal::ProtocolVersionsDal,
+    eth_watch_dal::EthWatchDal, events_dal::EventsDal, events_web3_dal::EventsWeb3Dal,
+    factory_deps_dal::FactoryDepsDal, proof_generation_dal::ProofGenerationDal,
+    protocol_versions_dal::ProtocolVersionsDal,
     protocol_versions_web3_dal::ProtocolVersionsWeb3Dal, pruning_dal::PruningDal,
     snapshot_recovery_dal::SnapshotRecoveryDal, snapshots_creator_dal::SnapshotsCreatorDal,
     snapshots_dal::SnapshotsDal, storage_logs_dal::StorageLogsDal,
@@ -35,12 +36,14 @@ pub mod consensus_dal;
 pub mod contract_verification_dal;
 mod data_availability_dal;
 pub mod eth_sender_dal;
+pub mod eth_watch_dal;
 pub mod events_dal;
 pub mod events_web3_dal;
 pub mod factory_deps_dal;
 pub mod helpers;
 pub mod metrics;
 mod models;
+pub mod notifications;
 pub mod proof_generation_dal;
 pub mod protocol_versions_dal;
 pub mod protocol_versions_web3_dal;
@@ -89,6 +92,8 @@ where
 
     fn eth_sender_dal(&mut self) -> EthSenderDal<'_, 'a>;
 
+    fn eth_watch_dal(&mut self) -> EthWatchDal<'_, 'a>;
+
     fn events_dal(&mut self) -> EventsDal<'_, 'a>;
 
     fn events_web3_dal(&mut self) -> EventsWeb3Dal<'_, 'a>;
@@ -171,6 +176,10 @@ impl<'a> CoreDal<'a> for Connection<'a, Core> {
         EthSenderDal { storage: self }
     }
 
+    fn eth_watch_dal(&mut self) -> EthWatchDal<'_, 'a> {
+        EthWatchDal { storage: self }
+    }
+
     fn events_dal(&mut self) -> EventsDal<'_, 'a> {
         EventsDal { storage: self }
     }
@@ -6,6 +6,7 @@ use zksync_types::{pubdata_da::DataAvailabilityBlob, L1BatchNumber};
 pub(crate) struct StorageDABlob {
     pub l1_batch_number: i64,
     pub blob_id: String,
+    pub chunk_blob_ids: Option<Vec<String>>,
     pub inclusion_data: Option<Vec<u8>>,
     pub sent_at: NaiveDateTime,
 }
@@ -15,6 +16,7 @@ impl From<StorageDABlob> for DataAvailabilityBlob {
         DataAvailabilityBlob {
             l1_batch_number: L1BatchNumber(blob.l1_batch_number as u32),
             blob_id: blob.blob_id,
+            chunk_blob_ids: blob.chunk_blob_ids.unwrap_or_default(),
             inclusion_data: blob.inclusion_data,
             sent_at: blob.sent_at.and_utc(),
         }
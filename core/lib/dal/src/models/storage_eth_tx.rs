@@ -3,7 +3,7 @@ use std::str::FromStr;
 use sqlx::types::chrono::NaiveDateTime;
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
-    eth_sender::{EthTx, TxHistory, TxHistoryToSend},
+    eth_sender::{EthTx, OperatorRotation, TxHistory, TxHistoryToSend},
     Address, L1BatchNumber, Nonce, H256,
 };
 
@@ -31,6 +31,14 @@ pub struct StorageEthTx {
     pub blob_sidecar: Option<Vec<u8>>,
 }
 
+#[derive(Debug, Clone)]
+pub struct StorageOperatorRotation {
+    pub previous_operator_addr: Vec<u8>,
+    pub new_operator_addr: Vec<u8>,
+    pub created_at: NaiveDateTime,
+    pub completed_at: Option<NaiveDateTime>,
+}
+
 #[derive(Debug, Default)]
 pub struct L1BatchEthSenderStats {
     pub saved: Vec<(AggregatedActionType, L1BatchNumber)>,
@@ -87,6 +95,19 @@ impl From<StorageEthTx> for EthTx {
     }
 }
 
+impl From<StorageOperatorRotation> for OperatorRotation {
+    fn from(rotation: StorageOperatorRotation) -> Self {
+        Self {
+            previous_operator: Address::from_slice(&rotation.previous_operator_addr),
+            new_operator: Address::from_slice(&rotation.new_operator_addr),
+            started_at_timestamp: rotation.created_at.and_utc().timestamp() as u64,
+            completed_at_timestamp: rotation
+                .completed_at
+                .map(|time| time.and_utc().timestamp() as u64),
+        }
+    }
+}
+
 impl From<StorageTxHistory> for TxHistory {
     fn from(history: StorageTxHistory) -> TxHistory {
         TxHistory {
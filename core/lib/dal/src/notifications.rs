@@ -0,0 +1,231 @@
+//! Notifications about changes in the node storage, such as sealed L2 blocks or new transactions in the mempool.
+//!
+//! Notifications are best-effort: consumers should treat a notification as a hint that storage *may* contain
+//! new data and re-query it, rather than rely on notifications being delivered exactly once.
+
+use std::{fmt, str::FromStr};
+
+use anyhow::Context as _;
+use sqlx::postgres::PgListener;
+use tokio::sync::broadcast;
+use zksync_db_connection::instrument::InstrumentExt;
+use zksync_types::{L2BlockNumber, H256};
+
+use crate::{ConnectionPool, Core};
+
+/// Postgres channel for [`StorageNotification::L2BlockSealed`] notifications.
+pub const L2_BLOCK_SEALED_CHANNEL: &str = "zksync_l2_block_sealed";
+/// Postgres channel for [`StorageNotification::L2TxInserted`] notifications.
+pub const L2_TX_INSERTED_CHANNEL: &str = "zksync_l2_tx_inserted";
+
+/// Notification about a change in the node storage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageNotification {
+    /// L2 block with the specified number was sealed (i.e., its header and all its data are persisted).
+    L2BlockSealed(L2BlockNumber),
+    /// L2 transaction with the specified hash was inserted into the mempool.
+    L2TxInserted(H256),
+}
+
+impl StorageNotification {
+    fn channel(&self) -> &'static str {
+        match self {
+            Self::L2BlockSealed(_) => L2_BLOCK_SEALED_CHANNEL,
+            Self::L2TxInserted(_) => L2_TX_INSERTED_CHANNEL,
+        }
+    }
+
+    fn payload(&self) -> String {
+        match self {
+            Self::L2BlockSealed(number) => number.0.to_string(),
+            Self::L2TxInserted(hash) => format!("{hash:?}"),
+        }
+    }
+
+    fn parse(channel: &str, payload: &str) -> anyhow::Result<Self> {
+        Ok(match channel {
+            L2_BLOCK_SEALED_CHANNEL => {
+                Self::L2BlockSealed(L2BlockNumber(payload.parse().context("L2 block number")?))
+            }
+            L2_TX_INSERTED_CHANNEL => {
+                Self::L2TxInserted(H256::from_str(payload).context("transaction hash")?)
+            }
+            _ => anyhow::bail!("unexpected notification channel: {channel}"),
+        })
+    }
+}
+
+/// Channel for [`StorageNotification`]s.
+#[derive(Clone)]
+pub enum StorageNotifications {
+    /// Notifications are sent using Postgres `NOTIFY` and can be received by any process connected
+    /// to the same database. The pool must be connected to the main (i.e., not replica) database instance.
+    Postgres(ConnectionPool<Core>),
+    /// Notifications are broadcast in process. Only works if all senders and receivers run in the same process.
+    InProcess(broadcast::Sender<StorageNotification>),
+}
+
+impl fmt::Debug for StorageNotifications {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Postgres(pool) => formatter.debug_tuple("Postgres").field(pool).finish(),
+            Self::InProcess(sender) => formatter
+                .debug_struct("InProcess")
+                .field("receiver_count", &sender.receiver_count())
+                .finish(),
+        }
+    }
+}
+
+impl StorageNotifications {
+    /// Creates an in-process notifications channel with the specified capacity.
+    pub fn in_process(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self::InProcess(sender)
+    }
+
+    /// Sends a notification to all current subscribers. For Postgres notifications, this should be called
+    /// after the corresponding changes are committed.
+    pub async fn send(&self, notification: StorageNotification) -> anyhow::Result<()> {
+        match self {
+            Self::Postgres(pool) => {
+                let mut connection = pool.connection_tagged("notifications").await?;
+                sqlx::query("SELECT pg_notify($1, $2)")
+                    .bind(notification.channel())
+                    .bind(notification.payload())
+                    .instrument("send_storage_notification")
+                    .with_arg("notification", &notification)
+                    .execute(&mut connection)
+                    .await?;
+            }
+            Self::InProcess(sender) => {
+                // Errors only if there are no receivers, which is fine.
+                sender.send(notification).ok();
+            }
+        }
+        Ok(())
+    }
+
+    /// Subscribes to notifications sent after this call.
+    pub async fn subscribe(&self) -> anyhow::Result<StorageNotificationsReceiver> {
+        Ok(match self {
+            Self::Postgres(pool) => {
+                let mut listener = PgListener::connect(pool.database_url().expose_str())
+                    .await
+                    .context("failed connecting Postgres listener")?;
+                listener
+                    .listen_all([L2_BLOCK_SEALED_CHANNEL, L2_TX_INSERTED_CHANNEL])
+                    .await
+                    .context("failed subscribing to Postgres notifications")?;
+                StorageNotificationsReceiver::Postgres(listener)
+            }
+            Self::InProcess(sender) => StorageNotificationsReceiver::InProcess(sender.subscribe()),
+        })
+    }
+}
+
+/// Receiver part of [`StorageNotifications`].
+pub enum StorageNotificationsReceiver {
+    Postgres(PgListener),
+    InProcess(broadcast::Receiver<StorageNotification>),
+}
+
+impl fmt::Debug for StorageNotificationsReceiver {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            Self::Postgres(_) => "Postgres",
+            Self::InProcess(_) => "InProcess",
+        };
+        formatter
+            .debug_struct("StorageNotificationsReceiver")
+            .field("kind", &kind)
+            .finish()
+    }
+}
+
+impl StorageNotificationsReceiver {
+    /// Waits for the next notification. Returns `Ok(None)` if some notifications may have been lost
+    /// (e.g., because the receiver lagged behind or the Postgres connection was re-established); in this case,
+    /// the consumer should re-check storage. Returns an error if the channel is no longer usable.
+    pub async fn recv(&mut self) -> anyhow::Result<Option<StorageNotification>> {
+        match self {
+            Self::Postgres(listener) => {
+                let Some(notification) = listener.try_recv().await? else {
+                    // The connection was lost and will be re-established on the next call.
+                    return Ok(None);
+                };
+                StorageNotification::parse(notification.channel(), notification.payload()).map(Some)
+            }
+            Self::InProcess(receiver) => match receiver.recv().await {
+                Ok(notification) => Ok(Some(notification)),
+                Err(broadcast::error::RecvError::Lagged(_)) => Ok(None),
+                Err(broadcast::error::RecvError::Closed) => {
+                    anyhow::bail!("in-process notifications channel is closed")
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_notifications() {
+        let notifications = [
+            StorageNotification::L2BlockSealed(L2BlockNumber(42)),
+            StorageNotification::L2TxInserted(H256::repeat_byte(0x23)),
+        ];
+        for notification in notifications {
+            let parsed =
+                StorageNotification::parse(notification.channel(), &notification.payload())
+                    .unwrap();
+            assert_eq!(parsed, notification);
+        }
+
+        StorageNotification::parse("unknown", "42").unwrap_err();
+        StorageNotification::parse(L2_BLOCK_SEALED_CHANNEL, "0x01").unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn in_process_notifications() {
+        let notifications = StorageNotifications::in_process(1);
+        let mut receiver = notifications.subscribe().await.unwrap();
+        let notification = StorageNotification::L2BlockSealed(L2BlockNumber(1));
+        notifications.send(notification).await.unwrap();
+        assert_eq!(receiver.recv().await.unwrap(), Some(notification));
+
+        // Overflow the channel.
+        for number in 2..4 {
+            let notification = StorageNotification::L2BlockSealed(L2BlockNumber(number));
+            notifications.send(notification).await.unwrap();
+        }
+        assert_eq!(receiver.recv().await.unwrap(), None);
+        assert_eq!(
+            receiver.recv().await.unwrap(),
+            Some(StorageNotification::L2BlockSealed(L2BlockNumber(3)))
+        );
+
+        drop(notifications);
+        receiver.recv().await.unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn postgres_notifications() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let notifications = StorageNotifications::Postgres(pool);
+        let mut receiver = notifications.subscribe().await.unwrap();
+
+        let sent_notifications = [
+            StorageNotification::L2TxInserted(H256::repeat_byte(1)),
+            StorageNotification::L2BlockSealed(L2BlockNumber(1)),
+        ];
+        for notification in sent_notifications {
+            notifications.send(notification).await.unwrap();
+        }
+        for notification in sent_notifications {
+            assert_eq!(receiver.recv().await.unwrap(), Some(notification));
+        }
+    }
+}
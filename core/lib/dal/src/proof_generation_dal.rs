@@ -304,6 +304,80 @@ impl ProofGenerationDal<'_, '_> {
 
         Ok(result)
     }
+
+    /// Returns L1 batches with generated proofs for which object store blobs can be garbage-collected, together
+    /// with URLs of their blobs in the witness inputs bucket. Only batches with numbers greater than `after`
+    /// that were proven more than `retention_period` ago and weren't garbage-collected yet are returned,
+    /// in the ascending order.
+    pub async fn get_l1_batches_for_blob_gc(
+        &mut self,
+        retention_period: Duration,
+        after: L1BatchNumber,
+        limit: usize,
+    ) -> DalResult<Vec<(L1BatchNumber, Vec<String>)>> {
+        let retention_period = pg_interval_from_duration(retention_period);
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number,
+                proof_gen_data_blob_url,
+                vm_run_data_blob_url
+            FROM
+                proof_generation_details
+            WHERE
+                status = 'generated'
+                AND blobs_removed_at IS NULL
+                AND updated_at < NOW() - $1::INTERVAL
+                AND l1_batch_number > $2
+            ORDER BY
+                l1_batch_number ASC
+            LIMIT
+                $3
+            "#,
+            &retention_period,
+            i64::from(after.0),
+            limit as i64
+        )
+        .instrument("get_l1_batches_for_blob_gc")
+        .with_arg("retention_period", &retention_period)
+        .with_arg("after", &after)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let urls = [row.proof_gen_data_blob_url, row.vm_run_data_blob_url];
+                let urls = urls.into_iter().flatten().collect();
+                (L1BatchNumber(row.l1_batch_number as u32), urls)
+            })
+            .collect())
+    }
+
+    pub async fn mark_blobs_as_removed(
+        &mut self,
+        l1_batch_numbers: &[L1BatchNumber],
+    ) -> DalResult<()> {
+        let l1_batch_numbers: Vec<_> = l1_batch_numbers
+            .iter()
+            .map(|number| i64::from(number.0))
+            .collect();
+        sqlx::query!(
+            r#"
+            UPDATE proof_generation_details
+            SET
+                blobs_removed_at = NOW()
+            WHERE
+                l1_batch_number = ANY ($1)
+            "#,
+            &l1_batch_numbers
+        )
+        .instrument("mark_blobs_as_removed")
+        .with_arg("l1_batch_numbers.len", &l1_batch_numbers.len())
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -424,5 +498,42 @@ mod tests {
             .await
             .unwrap();
         assert_eq!(unpicked_l1_batch, None);
+
+        // Check blob GC for the proven batch.
+        let gc_batches = conn
+            .proof_generation_dal()
+            .get_l1_batches_for_blob_gc(Duration::from_secs(3_600), L1BatchNumber(0), 10)
+            .await
+            .unwrap();
+        assert!(gc_batches.is_empty(), "{gc_batches:?}");
+        let gc_batches = conn
+            .proof_generation_dal()
+            .get_l1_batches_for_blob_gc(Duration::ZERO, L1BatchNumber(0), 10)
+            .await
+            .unwrap();
+        assert_eq!(
+            gc_batches,
+            [(
+                L1BatchNumber(1),
+                vec!["data".to_owned(), "vm_run".to_owned()]
+            )]
+        );
+        let gc_batches = conn
+            .proof_generation_dal()
+            .get_l1_batches_for_blob_gc(Duration::ZERO, L1BatchNumber(1), 10)
+            .await
+            .unwrap();
+        assert!(gc_batches.is_empty(), "{gc_batches:?}");
+
+        conn.proof_generation_dal()
+            .mark_blobs_as_removed(&[L1BatchNumber(1)])
+            .await
+            .unwrap();
+        let gc_batches = conn
+            .proof_generation_dal()
+            .get_l1_batches_for_blob_gc(Duration::ZERO, L1BatchNumber(0), 10)
+            .await
+            .unwrap();
+        assert!(gc_batches.is_empty(), "{gc_batches:?}");
     }
 }
@@ -258,6 +258,35 @@ impl TransactionsDal<'_, '_> {
         Ok(())
     }
 
+    /// Acquires an exclusive lock on pending L2 transactions of the specified initiator until the end
+    /// of the current DB transaction. Used to serialize concurrent insertions for the same account,
+    /// so that checks on pending transactions and the following insertion are atomic.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the connection is not in a transaction (the lock would be released immediately in this case).
+    pub async fn lock_pending_l2_txs(&mut self, initiator_address: Address) -> DalResult<()> {
+        assert!(
+            self.storage.in_transaction(),
+            "locking pending L2 transactions outside a DB transaction is meaningless"
+        );
+        // Advisory lock keys are 64-bit, so we use a prefix of the address; collisions only lead
+        // to unnecessary serialization.
+        let lock_key = i64::from_be_bytes(initiator_address.0[..8].try_into().unwrap());
+        sqlx::query!(
+            r#"
+            SELECT
+                PG_ADVISORY_XACT_LOCK($1)
+            "#,
+            lock_key
+        )
+        .instrument("lock_pending_l2_txs")
+        .with_arg("initiator_address", &initiator_address)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
     pub async fn insert_transaction_l2(
         &mut self,
         tx: &L2Tx,
@@ -2173,6 +2202,8 @@ impl TransactionsDal<'_, '_> {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
     use zksync_types::ProtocolVersion;
 
     use super::*;
@@ -2258,4 +2289,50 @@ mod tests {
             .unwrap();
         assert_eq!(tx_from_db[0].hash, tx_hash);
     }
+
+    #[tokio::test]
+    async fn locking_pending_l2_txs() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let initiator = Address::repeat_byte(1);
+        let mut conn = connection_pool.connection().await.unwrap();
+        let mut transaction = conn.start_transaction().await.unwrap();
+        transaction
+            .transactions_dal()
+            .lock_pending_l2_txs(initiator)
+            .await
+            .unwrap();
+
+        // Locking another initiator must not block.
+        let mut other_conn = connection_pool.connection().await.unwrap();
+        let mut other_transaction = other_conn.start_transaction().await.unwrap();
+        other_transaction
+            .transactions_dal()
+            .lock_pending_l2_txs(Address::repeat_byte(2))
+            .await
+            .unwrap();
+        other_transaction.rollback().await.unwrap();
+
+        let lock_acquired = AtomicBool::new(false);
+        let acquire_lock = async {
+            let mut other_transaction = other_conn.start_transaction().await.unwrap();
+            other_transaction
+                .transactions_dal()
+                .lock_pending_l2_txs(initiator)
+                .await
+                .unwrap();
+            lock_acquired.store(true, Ordering::SeqCst);
+            other_transaction.commit().await.unwrap();
+        };
+        let release_lock = async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            assert!(!lock_acquired.load(Ordering::SeqCst));
+            transaction.commit().await.unwrap();
+        };
+        tokio::time::timeout(Duration::from_secs(10), async {
+            tokio::join!(acquire_lock, release_lock)
+        })
+        .await
+        .expect("lock was not released");
+        assert!(lock_acquired.load(Ordering::SeqCst));
+    }
 }
@@ -9,9 +9,10 @@ use zksync_db_connection::{
     interpolate_query, match_query_as,
 };
 use zksync_types::{
-    api, api::TransactionReceipt, event::DEPLOY_EVENT_SIGNATURE, Address, L2BlockNumber, L2ChainId,
-    Transaction, CONTRACT_DEPLOYER_ADDRESS, H256, U256,
+    api, api::TransactionReceipt, event::DEPLOY_EVENT_SIGNATURE, fee::Fee, Address, L2BlockNumber,
+    L2ChainId, Nonce, Transaction, CONTRACT_DEPLOYER_ADDRESS, H256, U256,
 };
+use zksync_utils::bigdecimal_to_u256;
 
 use crate::{
     models::storage_transaction::{
@@ -412,6 +413,166 @@ impl TransactionsWeb3Dal<'_, '_> {
         Ok(U256::from(pending_nonce))
     }
 
+    /// Returns the hash and fee of a pending L2 transaction (i.e., one that is neither included into an L2 block
+    /// nor rejected) with the specified initiator and nonce.
+    pub async fn get_pending_l2_tx_fee(
+        &mut self,
+        initiator_address: Address,
+        nonce: Nonce,
+    ) -> DalResult<Option<(H256, Fee)>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                hash,
+                gas_limit AS "gas_limit!",
+                max_fee_per_gas AS "max_fee_per_gas!",
+                max_priority_fee_per_gas AS "max_priority_fee_per_gas!",
+                gas_per_pubdata_limit AS "gas_per_pubdata_limit!"
+            FROM
+                transactions
+            WHERE
+                initiator_address = $1
+                AND nonce = $2
+                AND is_priority = FALSE
+                AND miniblock_number IS NULL
+                AND error IS NULL
+            "#,
+            initiator_address.as_bytes(),
+            i64::from(nonce.0)
+        )
+        .instrument("get_pending_l2_tx_fee")
+        .with_arg("initiator_address", &initiator_address)
+        .with_arg("nonce", &nonce)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| {
+            let fee = Fee {
+                gas_limit: bigdecimal_to_u256(row.gas_limit),
+                max_fee_per_gas: bigdecimal_to_u256(row.max_fee_per_gas),
+                max_priority_fee_per_gas: bigdecimal_to_u256(row.max_priority_fee_per_gas),
+                gas_per_pubdata_limit: bigdecimal_to_u256(row.gas_per_pubdata_limit),
+            };
+            (H256::from_slice(&row.hash), fee)
+        }))
+    }
+
+    /// Returns the number of pending L2 transactions (i.e., ones that are neither included into an L2 block
+    /// nor rejected) for the specified initiator.
+    pub async fn get_pending_l2_txs_count(
+        &mut self,
+        initiator_address: Address,
+    ) -> DalResult<usize> {
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                transactions
+            WHERE
+                initiator_address = $1
+                AND is_priority = FALSE
+                AND miniblock_number IS NULL
+                AND error IS NULL
+            "#,
+            initiator_address.as_bytes()
+        )
+        .instrument("get_pending_l2_txs_count")
+        .with_arg("initiator_address", &initiator_address)
+        .fetch_one(self.storage)
+        .await?;
+
+        Ok(count as usize)
+    }
+
+    /// Returns pending L2 transactions (i.e., ones that are neither included into an L2 block nor rejected) ordered
+    /// by initiator address and nonce. If `initiator_address` is specified, only transactions from this account
+    /// are returned.
+    pub async fn get_pending_l2_transactions(
+        &mut self,
+        initiator_address: Option<Address>,
+        limit: usize,
+        chain_id: L2ChainId,
+    ) -> DalResult<Vec<api::Transaction>> {
+        let rows = sqlx::query_as!(
+            StorageApiTransaction,
+            r#"
+            SELECT
+                transactions.hash AS tx_hash,
+                transactions.index_in_block AS index_in_block,
+                NULL::BIGINT AS block_number,
+                transactions.nonce AS nonce,
+                transactions.signature AS signature,
+                transactions.initiator_address AS initiator_address,
+                transactions.tx_format AS tx_format,
+                transactions.value AS value,
+                transactions.gas_limit AS gas_limit,
+                transactions.max_fee_per_gas AS max_fee_per_gas,
+                transactions.max_priority_fee_per_gas AS max_priority_fee_per_gas,
+                transactions.effective_gas_price AS effective_gas_price,
+                transactions.l1_batch_number AS l1_batch_number,
+                transactions.l1_batch_tx_index AS l1_batch_tx_index,
+                transactions.data -> 'contractAddress' AS "execute_contract_address!",
+                transactions.data -> 'calldata' AS "calldata!",
+                NULL::BYTEA AS block_hash
+            FROM
+                transactions
+            WHERE
+                is_priority = FALSE
+                AND miniblock_number IS NULL
+                AND error IS NULL
+                AND (
+                    $1::BYTEA IS NULL
+                    OR initiator_address = $1
+                )
+            ORDER BY
+                initiator_address,
+                nonce
+            LIMIT
+                $2
+            "#,
+            initiator_address.as_ref().map(Address::as_bytes),
+            limit as i64
+        )
+        .instrument("get_pending_l2_transactions")
+        .with_arg("initiator_address", &initiator_address)
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| row.into_api(chain_id)).collect())
+    }
+
+    /// Returns initiator addresses and nonces of all pending L2 transactions (i.e., ones that are neither included
+    /// into an L2 block nor rejected). Unlike [`Self::get_pending_l2_transactions()`], the output is not limited,
+    /// so it can be used to compute exact statistics for pending transactions.
+    pub async fn get_pending_l2_tx_nonces(&mut self) -> DalResult<Vec<(Address, Nonce)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                initiator_address,
+                nonce AS "nonce!"
+            FROM
+                transactions
+            WHERE
+                is_priority = FALSE
+                AND miniblock_number IS NULL
+                AND error IS NULL
+            "#
+        )
+        .instrument("get_pending_l2_tx_nonces")
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let address = Address::from_slice(&row.initiator_address);
+                (address, Nonce(row.nonce as u32))
+            })
+            .collect())
+    }
+
     /// Returns the server transactions (not API ones) from a L2 block range.
     pub async fn get_raw_l2_blocks_transactions(
         &mut self,
@@ -480,7 +641,7 @@ mod tests {
     use std::collections::HashMap;
 
     use zksync_types::{
-        fee::TransactionExecutionMetrics, l2::L2Tx, Nonce, ProtocolVersion, ProtocolVersionId,
+        fee::TransactionExecutionMetrics, l2::L2Tx, ProtocolVersion, ProtocolVersionId,
     };
 
     use super::*;
@@ -728,6 +889,106 @@ mod tests {
         assert_eq!(next_nonce, 2.into());
     }
 
+    #[tokio::test]
+    async fn getting_pending_l2_txs() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+
+        let initiator = Address::repeat_byte(1);
+        let mut tx_by_nonce = HashMap::new();
+        for nonce in [0, 1, 2] {
+            let mut tx = mock_l2_transaction();
+            tx.common_data.nonce = Nonce(nonce);
+            tx.common_data.initiator_address = initiator;
+            tx_by_nonce.insert(nonce, tx.clone());
+            conn.transactions_dal()
+                .insert_transaction_l2(&tx, TransactionExecutionMetrics::default())
+                .await
+                .unwrap();
+        }
+
+        let count = conn
+            .transactions_web3_dal()
+            .get_pending_l2_txs_count(initiator)
+            .await
+            .unwrap();
+        assert_eq!(count, 3);
+        let (hash, fee) = conn
+            .transactions_web3_dal()
+            .get_pending_l2_tx_fee(initiator, Nonce(1))
+            .await
+            .unwrap()
+            .expect("no pending transaction");
+        assert_eq!(hash, tx_by_nonce[&1].hash());
+        assert_eq!(fee, tx_by_nonce[&1].common_data.fee);
+
+        conn.transactions_dal()
+            .mark_tx_as_rejected(tx_by_nonce[&1].hash(), "oops")
+            .await
+            .unwrap();
+        let count = conn
+            .transactions_web3_dal()
+            .get_pending_l2_txs_count(initiator)
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+        let pending_tx = conn
+            .transactions_web3_dal()
+            .get_pending_l2_tx_fee(initiator, Nonce(1))
+            .await
+            .unwrap();
+        assert_eq!(pending_tx, None);
+
+        let count = conn
+            .transactions_web3_dal()
+            .get_pending_l2_txs_count(Address::repeat_byte(2))
+            .await
+            .unwrap();
+        assert_eq!(count, 0);
+
+        let pending_txs = conn
+            .transactions_web3_dal()
+            .get_pending_l2_transactions(Some(initiator), 100, L2ChainId::default())
+            .await
+            .unwrap();
+        let pending_hashes: Vec<_> = pending_txs.iter().map(|tx| tx.hash).collect();
+        assert_eq!(
+            pending_hashes,
+            [tx_by_nonce[&0].hash(), tx_by_nonce[&2].hash()]
+        );
+        assert!(pending_txs.iter().all(|tx| tx.block_number.is_none()));
+
+        let pending_txs = conn
+            .transactions_web3_dal()
+            .get_pending_l2_transactions(None, 1, L2ChainId::default())
+            .await
+            .unwrap();
+        assert_eq!(pending_txs.len(), 1);
+        assert_eq!(pending_txs[0].hash, tx_by_nonce[&0].hash());
+
+        let pending_txs = conn
+            .transactions_web3_dal()
+            .get_pending_l2_transactions(Some(Address::repeat_byte(2)), 100, L2ChainId::default())
+            .await
+            .unwrap();
+        assert!(pending_txs.is_empty());
+
+        let mut pending_nonces = conn
+            .transactions_web3_dal()
+            .get_pending_l2_tx_nonces()
+            .await
+            .unwrap();
+        pending_nonces.sort_unstable();
+        assert_eq!(
+            pending_nonces,
+            [(initiator, Nonce(0)), (initiator, Nonce(2))]
+        );
+    }
+
     #[tokio::test]
     async fn getting_next_nonce_by_initiator_account_after_snapshot_recovery() {
         // Emulate snapshot recovery: no transactions with past nonces are present in the storage
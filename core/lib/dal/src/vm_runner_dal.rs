@@ -252,4 +252,202 @@ impl VmRunnerDal<'_, '_> {
         }
         Ok(())
     }
+
+    pub async fn get_trace_exporter_latest_processed_batch(
+        &mut self,
+    ) -> DalResult<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MAX(l1_batch_number) AS "last_processed_l1_batch"
+            FROM
+                vm_runner_trace_exporter
+            WHERE
+                time_taken IS NOT NULL
+            "#
+        )
+        .instrument("get_trace_exporter_latest_processed_batch")
+        .report_latency()
+        .fetch_one(self.storage)
+        .await?;
+        Ok(row.last_processed_l1_batch.map(|n| L1BatchNumber(n as u32)))
+    }
+
+    pub async fn get_trace_exporter_last_ready_batch(
+        &mut self,
+        default_batch: L1BatchNumber,
+        window_size: u32,
+    ) -> DalResult<L1BatchNumber> {
+        let row = sqlx::query!(
+            r#"
+            WITH
+                available_batches AS (
+                    SELECT
+                        MAX(number) AS "last_batch"
+                    FROM
+                        l1_batches
+                ),
+                processed_batches AS (
+                    SELECT
+                        COALESCE(MAX(l1_batch_number), $1) + $2 AS "last_ready_batch"
+                    FROM
+                        vm_runner_trace_exporter
+                    WHERE
+                        time_taken IS NOT NULL
+                )
+            SELECT
+                LEAST(last_batch, last_ready_batch) AS "last_ready_batch!"
+            FROM
+                available_batches
+                FULL JOIN processed_batches ON TRUE
+            "#,
+            default_batch.0 as i32,
+            window_size as i32
+        )
+        .instrument("get_trace_exporter_last_ready_batch")
+        .report_latency()
+        .fetch_one(self.storage)
+        .await?;
+        Ok(L1BatchNumber(row.last_ready_batch as u32))
+    }
+
+    pub async fn mark_trace_exporter_batch_as_processing(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                vm_runner_trace_exporter (l1_batch_number, created_at, updated_at, processing_started_at)
+            VALUES
+                ($1, NOW(), NOW(), NOW())
+            ON CONFLICT (l1_batch_number) DO
+            UPDATE
+            SET
+                updated_at = NOW(),
+                processing_started_at = NOW()
+            "#,
+            i64::from(l1_batch_number.0),
+        )
+        .instrument("mark_trace_exporter_batch_as_processing")
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn mark_trace_exporter_batch_as_completed(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        let update_result = sqlx::query!(
+            r#"
+            UPDATE vm_runner_trace_exporter
+            SET
+                time_taken = NOW() - processing_started_at
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(l1_batch_number.0),
+        )
+        .instrument("mark_trace_exporter_batch_as_completed")
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        if update_result.rows_affected() == 0 {
+            anyhow::bail!(
+                "Trying to mark an L1 batch as completed while it is not being processed"
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::ProtocolVersion;
+
+    use super::*;
+    use crate::{tests::create_l1_batch_header, ConnectionPool, CoreDal};
+
+    #[tokio::test]
+    async fn trace_exporter_progress() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        for number in 0..=3 {
+            conn.blocks_dal()
+                .insert_mock_l1_batch(&create_l1_batch_header(number))
+                .await
+                .unwrap();
+        }
+
+        let mut dal = conn.vm_runner_dal();
+        assert_eq!(
+            dal.get_trace_exporter_latest_processed_batch()
+                .await
+                .unwrap(),
+            None
+        );
+        let last_ready_batch = dal
+            .get_trace_exporter_last_ready_batch(L1BatchNumber(0), 2)
+            .await
+            .unwrap();
+        assert_eq!(last_ready_batch, L1BatchNumber(2));
+
+        // Batches being processed don't count as processed.
+        dal.mark_trace_exporter_batch_as_processing(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(
+            dal.get_trace_exporter_latest_processed_batch()
+                .await
+                .unwrap(),
+            None
+        );
+        dal.mark_trace_exporter_batch_as_completed(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(
+            dal.get_trace_exporter_latest_processed_batch()
+                .await
+                .unwrap(),
+            Some(L1BatchNumber(1))
+        );
+        let last_ready_batch = dal
+            .get_trace_exporter_last_ready_batch(L1BatchNumber(0), 2)
+            .await
+            .unwrap();
+        assert_eq!(last_ready_batch, L1BatchNumber(3));
+
+        // A batch cannot be completed without being marked as processing first.
+        dal.mark_trace_exporter_batch_as_completed(L1BatchNumber(2))
+            .await
+            .unwrap_err();
+
+        // Processing may be restarted, e.g. after a restart of the exporter.
+        for _ in 0..2 {
+            dal.mark_trace_exporter_batch_as_processing(L1BatchNumber(2))
+                .await
+                .unwrap();
+        }
+        dal.mark_trace_exporter_batch_as_completed(L1BatchNumber(2))
+            .await
+            .unwrap();
+        assert_eq!(
+            dal.get_trace_exporter_latest_processed_batch()
+                .await
+                .unwrap(),
+            Some(L1BatchNumber(2))
+        );
+        // The last ready batch is capped by the last batch in the storage.
+        let last_ready_batch = dal
+            .get_trace_exporter_last_ready_batch(L1BatchNumber(0), 2)
+            .await
+            .unwrap();
+        assert_eq!(last_ready_batch, L1BatchNumber(3));
+    }
 }
@@ -148,7 +148,10 @@ enum ConnectionInner<'a> {
 }
 
 /// Marker trait for restricting using all possible types as a storage marker.
-pub trait DbMarker: 'static + Send + Sync + Clone {}
+pub trait DbMarker: 'static + Send + Sync + Clone {
+    /// Name of the env variable containing the URL of the template database for tests.
+    const TEST_DATABASE_URL_VAR: &'static str = "TEST_DATABASE_URL";
+}
 
 /// Storage processor is the main storage interaction point.
 /// It holds down the connection (either direct or pooled) to the database
@@ -151,10 +151,21 @@ impl TestTemplate {
 
     /// Obtains the test database URL from the environment variable.
     pub fn empty() -> anyhow::Result<Self> {
-        let db_url = env::var("TEST_DATABASE_URL").context(
-            "TEST_DATABASE_URL must be set. Normally, this is done by the 'zk' tool. \
-            Make sure that you are running the tests with 'zk test rust' command or equivalent.",
-        )?;
+        Self::from_env_var("TEST_DATABASE_URL")
+    }
+
+    /// Obtains the test database URL for the specified database from the environment variable.
+    pub fn for_db<DB: DbMarker>() -> anyhow::Result<Self> {
+        Self::from_env_var(DB::TEST_DATABASE_URL_VAR)
+    }
+
+    fn from_env_var(var_name: &str) -> anyhow::Result<Self> {
+        let db_url = env::var(var_name).with_context(|| {
+            format!(
+                "{var_name} must be set. Normally, this is done by the 'zk' tool. \
+                 Make sure that you are running the tests with 'zk test rust' command or equivalent."
+            )
+        })?;
         Ok(Self(db_url.parse()?))
     }
 
@@ -295,7 +306,7 @@ impl<DB: DbMarker> ConnectionPool<DB> {
     /// behavior of components that rely on singleton / constrained pools in production.
     pub async fn constrained_test_pool(connections: u32) -> ConnectionPool<DB> {
         assert!(connections > 0, "Number of connections must be positive");
-        let mut builder = TestTemplate::empty()
+        let mut builder = TestTemplate::for_db::<DB>()
             .expect("failed creating test template")
             .create_db(connections)
             .await
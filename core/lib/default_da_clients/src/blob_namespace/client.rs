@@ -0,0 +1,227 @@
+use std::time::Duration;
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use jsonrpsee::{
+    core::ClientError,
+    http_client::{HeaderMap, HeaderValue, HttpClient, HttpClientBuilder},
+};
+use zksync_da_client::{
+    types::{DAError, DispatchResponse, InclusionData},
+    DataAvailabilityClient,
+};
+use zksync_types::{
+    ethabi::{self, Token},
+    web3::Bytes,
+    H256, U256,
+};
+
+use crate::blob_namespace::{
+    config::BlobNamespaceConfig,
+    rpc::{Blob, BlobNamespaceRpcClient, BlobProof},
+};
+
+/// An implementation of the `DataAvailabilityClient` trait for DA nodes supporting namespaced blobs
+/// via JSON-RPC (e.g., Celestia or Avail-style light nodes).
+///
+/// Pubdata larger than the node blob size limit is split into several blobs, which are submitted
+/// in a single request. The blob ID encodes the DA block height and commitments to all submitted blobs.
+#[derive(Clone, Debug)]
+pub struct BlobNamespaceDAClient {
+    client: HttpClient,
+    namespace: Bytes,
+    max_blob_size: usize,
+}
+
+impl BlobNamespaceDAClient {
+    pub fn new(config: BlobNamespaceConfig) -> anyhow::Result<Self> {
+        let namespace = hex::decode(config.namespace.trim_start_matches("0x"))
+            .context("namespace is not a valid hex string")?;
+        anyhow::ensure!(!namespace.is_empty(), "namespace must not be empty");
+        anyhow::ensure!(config.max_blob_size > 0, "max_blob_size must be positive");
+
+        let mut headers = HeaderMap::new();
+        if let Some(token) = &config.auth_token {
+            let value = HeaderValue::from_str(&format!("Bearer {token}"))
+                .context("auth token is not a valid header value")?;
+            headers.insert("Authorization", value);
+        }
+        let client = HttpClientBuilder::default()
+            .request_timeout(Duration::from_millis(config.request_timeout_ms))
+            .set_headers(headers)
+            .build(&config.api_node_url)
+            .context("failed creating JSON-RPC client")?;
+
+        Ok(Self {
+            client,
+            namespace: namespace.into(),
+            max_blob_size: config.max_blob_size,
+        })
+    }
+
+    fn split_into_blobs(&self, data: Vec<u8>) -> Vec<Blob> {
+        if data.len() <= self.max_blob_size {
+            return vec![Blob {
+                namespace: self.namespace.clone(),
+                data: data.into(),
+            }];
+        }
+        data.chunks(self.max_blob_size)
+            .map(|chunk| Blob {
+                namespace: self.namespace.clone(),
+                data: chunk.to_vec().into(),
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl DataAvailabilityClient for BlobNamespaceDAClient {
+    async fn dispatch_blob(
+        &self,
+        batch_number: u32,
+        data: Vec<u8>,
+    ) -> Result<DispatchResponse, DAError> {
+        let blobs = self.split_into_blobs(data);
+        let blob_count = blobs.len();
+        let response = self
+            .client
+            .submit(blobs)
+            .await
+            .map_err(|err| to_da_error(err, "failed submitting blobs"))?;
+
+        if response.commitments.len() != blob_count {
+            return Err(DAError {
+                error: anyhow::anyhow!(
+                    "DA node returned {} commitments for {blob_count} blobs submitted for batch #{batch_number}",
+                    response.commitments.len()
+                ),
+                is_transient: false,
+            });
+        }
+        tracing::debug!(
+            "Submitted {blob_count} blob(s) for batch #{batch_number} at DA height {}",
+            response.height
+        );
+
+        Ok(DispatchResponse {
+            blob_id: BlobId {
+                height: response.height,
+                commitments: response.commitments,
+            }
+            .to_string(),
+        })
+    }
+
+    async fn get_inclusion_data(&self, blob_id: &str) -> Result<Option<InclusionData>, DAError> {
+        let blob_id = BlobId::parse(blob_id).map_err(|error| DAError {
+            error,
+            is_transient: false,
+        })?;
+
+        let mut proofs = Vec::with_capacity(blob_id.commitments.len());
+        for &commitment in &blob_id.commitments {
+            let proof = self
+                .client
+                .get_proof(blob_id.height, self.namespace.clone(), commitment)
+                .await
+                .map_err(|err| to_da_error(err, "failed getting blob proof"))?;
+            let Some(proof) = proof else {
+                return Ok(None);
+            };
+            proofs.push((commitment, proof));
+        }
+
+        Ok(Some(InclusionData {
+            data: encode_inclusion_data(blob_id.height, proofs),
+        }))
+    }
+
+    fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
+        Box::new(self.clone())
+    }
+
+    fn blob_size_limit(&self) -> Option<usize> {
+        Some(self.max_blob_size)
+    }
+}
+
+/// Identifier of the blobs submitted for a single batch. Serialized as `{height}:{commitment},{commitment},...`
+/// with hex-encoded commitments.
+#[derive(Debug, PartialEq)]
+struct BlobId {
+    height: u64,
+    commitments: Vec<H256>,
+}
+
+impl BlobId {
+    fn parse(s: &str) -> anyhow::Result<Self> {
+        let (height, commitments) = s
+            .split_once(':')
+            .with_context(|| format!("malformed blob ID: {s}"))?;
+        let height = height
+            .parse()
+            .with_context(|| format!("malformed height in blob ID: {s}"))?;
+        let commitments = commitments
+            .split(',')
+            .map(|commitment| {
+                let bytes = hex::decode(commitment)?;
+                anyhow::ensure!(bytes.len() == 32, "unexpected commitment length");
+                Ok(H256::from_slice(&bytes))
+            })
+            .collect::<anyhow::Result<_>>()
+            .with_context(|| format!("malformed commitments in blob ID: {s}"))?;
+        Ok(Self {
+            height,
+            commitments,
+        })
+    }
+}
+
+impl std::fmt::Display for BlobId {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(formatter, "{}:", self.height)?;
+        for (i, commitment) in self.commitments.iter().enumerate() {
+            let separator = if i == 0 { "" } else { "," };
+            write!(formatter, "{separator}{}", hex::encode(commitment))?;
+        }
+        Ok(())
+    }
+}
+
+/// ABI-encodes inclusion proofs as `(uint256 height, (bytes32 commitment, bytes32 dataRoot, uint256 index, bytes32[] proof)[])`,
+/// so that they can be decoded in Solidity.
+fn encode_inclusion_data(height: u64, proofs: Vec<(H256, BlobProof)>) -> Vec<u8> {
+    let proofs = proofs
+        .into_iter()
+        .map(|(commitment, proof)| {
+            Token::Tuple(vec![
+                Token::FixedBytes(commitment.as_bytes().to_vec()),
+                Token::FixedBytes(proof.data_root.as_bytes().to_vec()),
+                Token::Uint(proof.index.into()),
+                Token::Array(
+                    proof
+                        .proof
+                        .into_iter()
+                        .map(|hash| Token::FixedBytes(hash.as_bytes().to_vec()))
+                        .collect(),
+                ),
+            ])
+        })
+        .collect();
+    ethabi::encode(&[Token::Tuple(vec![
+        Token::Uint(U256::from(height)),
+        Token::Array(proofs),
+    ])])
+}
+
+fn to_da_error(err: ClientError, context: &'static str) -> DAError {
+    let is_transient = matches!(
+        err,
+        ClientError::Transport(_) | ClientError::RequestTimeout | ClientError::RestartNeeded(_)
+    );
+    DAError {
+        error: anyhow::Error::from(err).context(context),
+        is_transient,
+    }
+}
@@ -0,0 +1,46 @@
+use serde::Deserialize;
+use zksync_env_config::envy_load;
+
+/// Configuration of the client for DA nodes supporting namespaced blobs.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BlobNamespaceConfig {
+    /// URL of the DA node JSON-RPC API.
+    pub api_node_url: String,
+    /// Hex-encoded namespace the blobs are submitted to.
+    pub namespace: String,
+    /// Bearer token used to authenticate requests to the DA node, if required.
+    #[serde(default)]
+    pub auth_token: Option<String>,
+    /// Maximum size of a single blob accepted by the DA node. Larger payloads are split into several blobs.
+    #[serde(default = "BlobNamespaceConfig::default_max_blob_size")]
+    pub max_blob_size: usize,
+    /// Timeout for requests to the DA node.
+    #[serde(default = "BlobNamespaceConfig::default_request_timeout_ms")]
+    pub request_timeout_ms: u64,
+}
+
+impl BlobNamespaceConfig {
+    const ENV_PREFIX: &'static str = "DA_CLIENT_BLOB_NAMESPACE_";
+
+    const fn default_max_blob_size() -> usize {
+        1 << 20 // 1 MiB
+    }
+
+    const fn default_request_timeout_ms() -> u64 {
+        30_000
+    }
+
+    pub fn from_env() -> anyhow::Result<Self> {
+        envy_load("blob_namespace", Self::ENV_PREFIX)
+    }
+
+    /// Loads the config from env if any of its variables is set; otherwise, returns `None`.
+    pub fn from_env_opt() -> anyhow::Result<Option<Self>> {
+        let is_configured = std::env::vars().any(|(key, _)| key.starts_with(Self::ENV_PREFIX));
+        if is_configured {
+            Self::from_env().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
@@ -0,0 +1,169 @@
+//! In-process mock of a DA node supporting namespaced blobs. Can be used to test DA dispatching
+//! end to end without a real network.
+
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use jsonrpsee::{
+    core::RpcResult,
+    server::{ServerBuilder, ServerHandle},
+    types::{error::INVALID_PARAMS_CODE, ErrorObjectOwned},
+};
+use zksync_types::{
+    web3::{keccak256, Bytes},
+    H256,
+};
+
+use crate::blob_namespace::rpc::{Blob, BlobNamespaceRpcServer, BlobProof, SubmitResponse};
+
+#[derive(Debug)]
+struct StoredBlob {
+    namespace: Bytes,
+    data: Vec<u8>,
+    commitment: H256,
+}
+
+#[derive(Debug, Default)]
+struct MockNodeState {
+    /// Blobs included at each DA height. Each submission creates a new height.
+    blocks: Vec<Vec<StoredBlob>>,
+    max_blob_size: Option<usize>,
+    withhold_proofs: bool,
+}
+
+/// Mock DA node storing blobs in memory.
+#[derive(Debug, Clone, Default)]
+pub struct MockBlobNamespaceNode {
+    state: Arc<Mutex<MockNodeState>>,
+}
+
+impl MockBlobNamespaceNode {
+    /// Makes the node reject blobs larger than the specified size.
+    pub fn with_max_blob_size(self, max_blob_size: usize) -> Self {
+        self.state.lock().unwrap().max_blob_size = Some(max_blob_size);
+        self
+    }
+
+    /// Sets whether the node should withhold inclusion proofs, emulating blobs that are not included yet.
+    pub fn set_withhold_proofs(&self, withhold: bool) {
+        self.state.lock().unwrap().withhold_proofs = withhold;
+    }
+
+    /// Returns data of all blobs in the specified namespace in the order of submission.
+    pub fn blobs(&self, namespace: &[u8]) -> Vec<Vec<u8>> {
+        let state = self.state.lock().unwrap();
+        state
+            .blocks
+            .iter()
+            .flatten()
+            .filter(|blob| blob.namespace.0 == namespace)
+            .map(|blob| blob.data.clone())
+            .collect()
+    }
+
+    /// Starts serving the JSON-RPC API on a random localhost port. The server is stopped once the returned handle
+    /// is dropped.
+    pub async fn spawn(&self) -> anyhow::Result<(SocketAddr, ServerHandle)> {
+        let server = ServerBuilder::default()
+            .http_only()
+            .build((Ipv4Addr::LOCALHOST, 0))
+            .await?;
+        let local_addr = server.local_addr()?;
+        let handle = server.start(self.clone().into_rpc());
+        Ok((local_addr, handle))
+    }
+}
+
+#[async_trait]
+impl BlobNamespaceRpcServer for MockBlobNamespaceNode {
+    async fn submit(&self, blobs: Vec<Blob>) -> RpcResult<SubmitResponse> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(max_blob_size) = state.max_blob_size {
+            if let Some(blob) = blobs.iter().find(|blob| blob.data.0.len() > max_blob_size) {
+                let message = format!(
+                    "blob size {} exceeds the limit {max_blob_size}",
+                    blob.data.0.len()
+                );
+                return Err(ErrorObjectOwned::owned(
+                    INVALID_PARAMS_CODE,
+                    message,
+                    None::<()>,
+                ));
+            }
+        }
+
+        let blobs: Vec<_> = blobs
+            .into_iter()
+            .map(|blob| StoredBlob {
+                commitment: blob_commitment(&blob.namespace.0, &blob.data.0),
+                namespace: blob.namespace,
+                data: blob.data.0,
+            })
+            .collect();
+        let commitments = blobs.iter().map(|blob| blob.commitment).collect();
+        state.blocks.push(blobs);
+        Ok(SubmitResponse {
+            height: state.blocks.len() as u64,
+            commitments,
+        })
+    }
+
+    async fn get_proof(
+        &self,
+        height: u64,
+        namespace: Bytes,
+        commitment: H256,
+    ) -> RpcResult<Option<BlobProof>> {
+        let state = self.state.lock().unwrap();
+        if state.withhold_proofs {
+            return Ok(None);
+        }
+        // Heights are 1-based.
+        let Some(block) = height
+            .checked_sub(1)
+            .and_then(|idx| state.blocks.get(idx as usize))
+        else {
+            return Ok(None);
+        };
+        let Some(index) = block
+            .iter()
+            .position(|blob| blob.namespace == namespace && blob.commitment == commitment)
+        else {
+            return Ok(None);
+        };
+
+        let leaves: Vec<_> = block.iter().map(|blob| blob.commitment).collect();
+        let (data_root, proof) = merkle_root_and_path(&leaves, index);
+        Ok(Some(BlobProof {
+            data_root,
+            index: index as u64,
+            proof,
+        }))
+    }
+}
+
+fn blob_commitment(namespace: &[u8], data: &[u8]) -> H256 {
+    H256(keccak256(&[namespace, data].concat()))
+}
+
+fn merkle_root_and_path(leaves: &[H256], mut index: usize) -> (H256, Vec<H256>) {
+    let mut level = leaves.to_vec();
+    level.resize(leaves.len().next_power_of_two(), H256::zero());
+    let mut path = vec![];
+    while level.len() > 1 {
+        path.push(level[index ^ 1]);
+        level = level
+            .chunks(2)
+            .map(|pair| {
+                H256(keccak256(
+                    &[pair[0].as_bytes(), pair[1].as_bytes()].concat(),
+                ))
+            })
+            .collect();
+        index /= 2;
+    }
+    (level[0], path)
+}
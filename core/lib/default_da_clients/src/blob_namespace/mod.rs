@@ -0,0 +1,8 @@
+pub mod client;
+pub mod config;
+#[cfg(any(test, feature = "testonly"))]
+pub mod mock;
+mod rpc;
+#[cfg(test)]
+mod tests;
+pub mod wiring_layer;
@@ -0,0 +1,49 @@
+//! JSON-RPC API of a DA node supporting namespaced blobs (similar to the Celestia node blob API).
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
+use zksync_types::{web3::Bytes, H256};
+
+/// Blob submitted to the DA node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Blob {
+    pub namespace: Bytes,
+    pub data: Bytes,
+}
+
+/// Response to a blob submission.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitResponse {
+    /// Height of the DA block that included the submitted blobs.
+    pub height: u64,
+    /// Commitments to the submitted blobs, in the submission order.
+    pub commitments: Vec<H256>,
+}
+
+/// Proof of a blob inclusion into a DA block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobProof {
+    /// Root of the Merkle tree built over commitments of all blobs at the DA block.
+    pub data_root: H256,
+    /// Index of the blob commitment in the Merkle tree.
+    pub index: u64,
+    /// Merkle path from the blob commitment to `data_root`, starting from the leaf level.
+    pub proof: Vec<H256>,
+}
+
+#[rpc(client, server, namespace = "blob")]
+pub trait BlobNamespaceRpc {
+    #[method(name = "submit")]
+    async fn submit(&self, blobs: Vec<Blob>) -> RpcResult<SubmitResponse>;
+
+    /// Returns `None` if the blob is not (yet) included.
+    #[method(name = "getProof")]
+    async fn get_proof(
+        &self,
+        height: u64,
+        namespace: Bytes,
+        commitment: H256,
+    ) -> RpcResult<Option<BlobProof>>;
+}
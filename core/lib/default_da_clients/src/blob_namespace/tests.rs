@@ -0,0 +1,202 @@
+use std::{net::SocketAddr, time::Duration};
+
+use tokio::sync::watch;
+use zksync_config::DADispatcherConfig;
+use zksync_da_client::DataAvailabilityClient;
+use zksync_da_dispatcher::DataAvailabilityDispatcher;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_node_test_utils::create_l1_batch;
+use zksync_types::{
+    ethabi::{self, ParamType, Token},
+    web3::keccak256,
+    ProtocolVersion, H256,
+};
+
+use super::{
+    client::BlobNamespaceDAClient, config::BlobNamespaceConfig, mock::MockBlobNamespaceNode,
+};
+
+const NAMESPACE: &[u8] = b"zksync";
+
+fn client_config(url: String, max_blob_size: usize) -> BlobNamespaceConfig {
+    BlobNamespaceConfig {
+        api_node_url: url,
+        namespace: hex::encode(NAMESPACE),
+        auth_token: Some("test".to_owned()),
+        max_blob_size,
+        request_timeout_ms: 5_000,
+    }
+}
+
+fn create_client(addr: SocketAddr, max_blob_size: usize) -> BlobNamespaceDAClient {
+    BlobNamespaceDAClient::new(client_config(format!("http://{addr}/"), max_blob_size)).unwrap()
+}
+
+/// Decodes inclusion data and checks that all Merkle proofs are valid. Returns the number of proven blobs.
+fn verify_inclusion_data(data: &[u8]) -> usize {
+    let proof_type = ParamType::Tuple(vec![
+        ParamType::FixedBytes(32),
+        ParamType::FixedBytes(32),
+        ParamType::Uint(256),
+        ParamType::Array(Box::new(ParamType::FixedBytes(32))),
+    ]);
+    let param = ParamType::Tuple(vec![
+        ParamType::Uint(256),
+        ParamType::Array(Box::new(proof_type)),
+    ]);
+    let mut tokens = ethabi::decode(&[param], data).unwrap();
+    let Token::Tuple(mut fields) = tokens.pop().unwrap() else {
+        unreachable!();
+    };
+    let proofs = fields.pop().unwrap().into_array().unwrap();
+    for proof in &proofs {
+        let fields = proof.clone().into_tuple().unwrap();
+        let commitment = H256::from_slice(&fields[0].clone().into_fixed_bytes().unwrap());
+        let data_root = H256::from_slice(&fields[1].clone().into_fixed_bytes().unwrap());
+        let mut index = fields[2].clone().into_uint().unwrap().as_u64();
+        let path = fields[3].clone().into_array().unwrap();
+
+        let mut hash = commitment;
+        for sibling in path {
+            let sibling = sibling.into_fixed_bytes().unwrap();
+            let pair = if index % 2 == 0 {
+                [hash.as_bytes(), &sibling].concat()
+            } else {
+                [&sibling, hash.as_bytes()].concat()
+            };
+            hash = H256(keccak256(&pair));
+            index /= 2;
+        }
+        assert_eq!(hash, data_root);
+    }
+    proofs.len()
+}
+
+#[tokio::test]
+async fn dispatching_blob_and_getting_inclusion_data() {
+    let node = MockBlobNamespaceNode::default();
+    let (addr, _server_handle) = node.spawn().await.unwrap();
+    let client = create_client(addr, 100);
+    assert_eq!(client.blob_size_limit(), Some(100));
+
+    let response = client.dispatch_blob(1, vec![1; 50]).await.unwrap();
+    assert_eq!(node.blobs(NAMESPACE), [vec![1; 50]]);
+
+    node.set_withhold_proofs(true);
+    let inclusion_data = client.get_inclusion_data(&response.blob_id).await.unwrap();
+    assert!(inclusion_data.is_none());
+
+    node.set_withhold_proofs(false);
+    let inclusion_data = client
+        .get_inclusion_data(&response.blob_id)
+        .await
+        .unwrap()
+        .expect("no inclusion data");
+    assert_eq!(verify_inclusion_data(&inclusion_data.data), 1);
+}
+
+#[tokio::test]
+async fn dispatching_large_blob_in_chunks() {
+    let node = MockBlobNamespaceNode::default().with_max_blob_size(10);
+    let (addr, _server_handle) = node.spawn().await.unwrap();
+    let client = create_client(addr, 10);
+
+    let data: Vec<u8> = (0..25).collect();
+    let response = client.dispatch_blob(1, data.clone()).await.unwrap();
+    let blobs = node.blobs(NAMESPACE);
+    assert_eq!(blobs.len(), 3);
+    assert_eq!(blobs.concat(), data);
+
+    let inclusion_data = client
+        .get_inclusion_data(&response.blob_id)
+        .await
+        .unwrap()
+        .expect("no inclusion data");
+    assert_eq!(verify_inclusion_data(&inclusion_data.data), 3);
+}
+
+#[tokio::test]
+async fn oversized_blob_is_rejected_by_node() {
+    let node = MockBlobNamespaceNode::default().with_max_blob_size(10);
+    let (addr, _server_handle) = node.spawn().await.unwrap();
+    let client = create_client(addr, 20);
+
+    let err = client.dispatch_blob(1, vec![0; 15]).await.unwrap_err();
+    assert!(!err.is_transient(), "{err}");
+    assert!(node.blobs(NAMESPACE).is_empty());
+}
+
+#[tokio::test]
+async fn unavailable_node_produces_transient_error() {
+    let node = MockBlobNamespaceNode::default();
+    let (addr, server_handle) = node.spawn().await.unwrap();
+    server_handle.stop().unwrap();
+    server_handle.stopped().await;
+
+    let client = create_client(addr, 10);
+    let err = client.dispatch_blob(1, vec![0; 5]).await.unwrap_err();
+    assert!(err.is_transient(), "{err}");
+}
+
+#[tokio::test]
+async fn invalid_blob_id_is_rejected() {
+    let client =
+        BlobNamespaceDAClient::new(client_config("http://127.0.0.1:1/".to_owned(), 10)).unwrap();
+    for blob_id in ["", "1", "x:00", "1:zz", "1:0011"] {
+        let err = client.get_inclusion_data(blob_id).await.unwrap_err();
+        assert!(!err.is_transient(), "{err}");
+    }
+}
+
+#[tokio::test]
+async fn dispatching_pubdata_to_mock_node_end_to_end() {
+    let node = MockBlobNamespaceNode::default().with_max_blob_size(10);
+    let (addr, _server_handle) = node.spawn().await.unwrap();
+    let client = create_client(addr, 10);
+
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    conn.protocol_versions_dal()
+        .save_protocol_version_with_tx(&ProtocolVersion::default())
+        .await
+        .unwrap();
+    let pubdata: Vec<u8> = (0..25).collect();
+    for (number, pubdata) in [(1, pubdata.clone()), (2, vec![1; 5])] {
+        let mut header = create_l1_batch(number);
+        header.pubdata_input = Some(pubdata);
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&header)
+            .await
+            .unwrap();
+    }
+
+    let config = DADispatcherConfig {
+        polling_interval_ms: Some(10),
+        ..DADispatcherConfig::for_tests()
+    };
+    let dispatcher = DataAvailabilityDispatcher::new(pool.clone(), config, Box::new(client));
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let dispatcher_task = tokio::spawn(dispatcher.run(stop_receiver));
+
+    tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let dal = &mut conn.data_availability_dal();
+            let ready_batches = dal.get_ready_for_da_dispatch_l1_batches(10).await.unwrap();
+            let awaiting_blob = dal.get_first_da_blob_awaiting_inclusion().await.unwrap();
+            if ready_batches.is_empty() && awaiting_blob.is_none() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("timed out waiting for pubdata to be dispatched and included");
+
+    stop_sender.send_replace(true);
+    dispatcher_task.await.unwrap().unwrap();
+
+    let blobs = node.blobs(NAMESPACE);
+    assert_eq!(blobs.len(), 4);
+    assert_eq!(blobs[..3].concat(), pubdata);
+    assert_eq!(blobs[3], [1; 5]);
+}
@@ -0,0 +1,43 @@
+use zksync_da_client::DataAvailabilityClient;
+use zksync_node_framework::{
+    implementations::resources::da_client::DAClientResource,
+    wiring_layer::{WiringError, WiringLayer},
+    IntoContext,
+};
+
+use crate::blob_namespace::{client::BlobNamespaceDAClient, config::BlobNamespaceConfig};
+
+#[derive(Debug)]
+pub struct BlobNamespaceClientWiringLayer {
+    config: BlobNamespaceConfig,
+}
+
+impl BlobNamespaceClientWiringLayer {
+    pub fn new(config: BlobNamespaceConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[derive(Debug, IntoContext)]
+pub struct Output {
+    pub client: DAClientResource,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for BlobNamespaceClientWiringLayer {
+    type Input = ();
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "blob_namespace_da_layer"
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        let client: Box<dyn DataAvailabilityClient> =
+            Box::new(BlobNamespaceDAClient::new(self.config)?);
+
+        Ok(Output {
+            client: DAClientResource(client),
+        })
+    }
+}
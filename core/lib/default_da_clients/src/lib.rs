@@ -1,2 +1,3 @@
+pub mod blob_namespace;
 pub mod no_da;
 pub mod object_store;
@@ -3,6 +3,7 @@ use zksync_config::configs::{
     api::{
         ContractVerificationApiConfig, HealthCheckConfig, MerkleTreeApiConfig, Web3JsonRpcConfig,
     },
+    secrets::{ApiKey, ApiSecrets},
     ApiConfig, PrometheusConfig,
 };
 
@@ -25,6 +26,21 @@ impl FromEnv for Web3JsonRpcConfig {
     }
 }
 
+impl FromEnv for ApiSecrets {
+    fn from_env() -> anyhow::Result<Self> {
+        let api_keys =
+            std::env::var("API_WEB3_JSON_RPC_API_KEYS").context("API_WEB3_JSON_RPC_API_KEYS")?;
+        Ok(Self {
+            api_keys: api_keys
+                .split(',')
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .map(|key| ApiKey(key.to_owned().into()))
+                .collect(),
+        })
+    }
+}
+
 impl FromEnv for HealthCheckConfig {
     fn from_env() -> anyhow::Result<Self> {
         envy_load("healthcheck", "API_HEALTHCHECK_")
@@ -48,6 +64,9 @@ impl FromEnv for MerkleTreeApiConfig {
 mod tests {
     use std::num::{NonZeroU32, NonZeroUsize};
 
+    use zksync_basic_types::U256;
+    use zksync_config::configs::api::PubSubNotificationsSource;
+
     use super::*;
     use crate::test_utils::{addr, hash, EnvMutex};
 
@@ -100,6 +119,22 @@ mod tests {
                 ],
                 api_namespaces: Some(vec!["debug".to_string()]),
                 extended_api_tracing: true,
+                pubsub_notifications: Some(PubSubNotificationsSource::InProcess),
+                tx_deny_list_path: Some("/etc/zksync/deny_list.json".into()),
+                tx_method_allowlist_path: None,
+                max_tx_calldata_size: Some(65536),
+                max_tx_value: Some(U256::exp10(21)),
+                tx_filter_lists_reload_interval_ms: Some(5000),
+                compute_units_per_second_limit: Some(NonZeroU32::new(1000).unwrap()),
+                compute_units_burst_limit: None,
+                method_compute_units: [
+                    ("eth_getLogs", NonZeroU32::new(20).unwrap()),
+                    ("debug_trace*", NonZeroU32::new(100).unwrap()),
+                ]
+                .into_iter()
+                .collect(),
+                api_key_header: Some("x-api-key".into()),
+                client_ip_header: Some("x-forwarded-for".into()),
             },
             prometheus: PrometheusConfig {
                 listener_port: 3312,
@@ -133,6 +168,15 @@ mod tests {
             API_WEB3_JSON_RPC_REQUEST_TIMEOUT=10
             API_WEB3_JSON_RPC_API_NAMESPACES=debug
             API_WEB3_JSON_RPC_EXTENDED_API_TRACING=true
+            API_WEB3_JSON_RPC_PUBSUB_NOTIFICATIONS="InProcess"
+            API_WEB3_JSON_RPC_TX_DENY_LIST_PATH="/etc/zksync/deny_list.json"
+            API_WEB3_JSON_RPC_MAX_TX_CALLDATA_SIZE=65536
+            API_WEB3_JSON_RPC_MAX_TX_VALUE="0x3635c9adc5dea00000"
+            API_WEB3_JSON_RPC_TX_FILTER_LISTS_RELOAD_INTERVAL_MS=5000
+            API_WEB3_JSON_RPC_COMPUTE_UNITS_PER_SECOND_LIMIT=1000
+            API_WEB3_JSON_RPC_METHOD_COMPUTE_UNITS="eth_getLogs=20,debug_trace*=100"
+            API_WEB3_JSON_RPC_API_KEY_HEADER="x-api-key"
+            API_WEB3_JSON_RPC_CLIENT_IP_HEADER="x-forwarded-for"
             API_WEB3_JSON_RPC_ACCOUNT_PKS="0x0000000000000000000000000000000000000000000000000000000000000001,0x0000000000000000000000000000000000000000000000000000000000000002"
             API_WEB3_JSON_RPC_WHITELISTED_TOKENS_FOR_AA="0x0000000000000000000000000000000000000001,0x0000000000000000000000000000000000000002"
             API_WEB3_JSON_RPC_ESTIMATE_GAS_SCALE_FACTOR=1.0
@@ -164,4 +208,17 @@ mod tests {
         let actual = ApiConfig::from_env().unwrap();
         assert_eq!(actual, expected_config());
     }
+
+    #[test]
+    fn api_secrets_from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            API_WEB3_JSON_RPC_API_KEYS="alice, bob,"
+        "#;
+        lock.set_env(config);
+
+        let actual = ApiSecrets::from_env().unwrap();
+        let expected_keys = ["alice", "bob"].map(|key| ApiKey(key.to_owned().into()));
+        assert_eq!(actual.api_keys, expected_keys);
+    }
 }
@@ -37,7 +37,9 @@ impl FromEnv for MempoolConfig {
 #[cfg(test)]
 mod tests {
     use zksync_basic_types::{commitment::L1BatchCommitmentMode, L2ChainId};
-    use zksync_config::configs::chain::FeeModelVersion;
+    use zksync_config::configs::chain::{
+        FeeModelVersion, MempoolOrderingPolicyKind, ValidationOpcode,
+    };
 
     use super::*;
     use crate::test_utils::{addr, hash, EnvMutex};
@@ -95,6 +97,14 @@ mod tests {
             max_pubdata_per_batch: 100_000,
             fee_model_version: FeeModelVersion::V2,
             validation_computational_gas_limit: 10_000_000,
+            validation_trusted_addresses: vec![addr("a000000000000000000000000000000000000001")],
+            validation_trusted_slots: vec![
+                "0xb000000000000000000000000000000000000002:0x10"
+                    .parse()
+                    .unwrap(),
+                "*:3".parse().unwrap(),
+            ],
+            validation_allowed_opcodes: vec![ValidationOpcode::Meta],
             save_call_traces: false,
             bootloader_hash: Some(hash(
                 "0x010007ede999d096c84553fb514d3d6ca76fbf39789dda76bfeda9f3ae06236e",
@@ -133,6 +143,9 @@ mod tests {
             CHAIN_STATE_KEEPER_MAX_PUBDATA_PER_BATCH="100000"
             CHAIN_STATE_KEEPER_FEE_MODEL_VERSION="V2"
             CHAIN_STATE_KEEPER_VALIDATION_COMPUTATIONAL_GAS_LIMIT="10000000"
+            CHAIN_STATE_KEEPER_VALIDATION_TRUSTED_ADDRESSES="0xa000000000000000000000000000000000000001"
+            CHAIN_STATE_KEEPER_VALIDATION_TRUSTED_SLOTS="0xb000000000000000000000000000000000000002:0x10,*:3"
+            CHAIN_STATE_KEEPER_VALIDATION_ALLOWED_OPCODES="meta"
             CHAIN_STATE_KEEPER_SAVE_CALL_TRACES="false"
             CHAIN_STATE_KEEPER_BOOTLOADER_HASH=0x010007ede999d096c84553fb514d3d6ca76fbf39789dda76bfeda9f3ae06236e
             CHAIN_STATE_KEEPER_DEFAULT_AA_HASH=0x0100055b041eb28aff6e3a6e0f37c31fd053fc9ef142683b05e5f0aee6934066
@@ -169,6 +182,9 @@ mod tests {
             stuck_tx_timeout: 10,
             remove_stuck_txs: true,
             delay_interval: 100,
+            replacement_min_fee_bump_percent: Some(10),
+            max_pending_txs_per_account: Some(64),
+            ordering_policy: MempoolOrderingPolicyKind::FairShare,
         }
     }
 
@@ -182,6 +198,9 @@ mod tests {
             CHAIN_MEMPOOL_REMOVE_STUCK_TXS="true"
             CHAIN_MEMPOOL_DELAY_INTERVAL="100"
             CHAIN_MEMPOOL_CAPACITY="1000000"
+            CHAIN_MEMPOOL_REPLACEMENT_MIN_FEE_BUMP_PERCENT="10"
+            CHAIN_MEMPOOL_MAX_PENDING_TXS_PER_ACCOUNT="64"
+            CHAIN_MEMPOOL_ORDERING_POLICY="FairShare"
         "#;
         lock.set_env(config);
 
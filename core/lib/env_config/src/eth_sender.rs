@@ -1,6 +1,9 @@
 use anyhow::Context as _;
 use zksync_config::{
-    configs::{eth_sender::SenderConfig, L1Secrets},
+    configs::{
+        eth_sender::{RemoteSignerConfig, SenderConfig},
+        L1Secrets,
+    },
     EthConfig, EthWatchConfig, GasAdjusterConfig,
 };
 
@@ -12,6 +15,7 @@ impl FromEnv for EthConfig {
             sender: SenderConfig::from_env().ok(),
             gas_adjuster: GasAdjusterConfig::from_env().ok(),
             watcher: EthWatchConfig::from_env().ok(),
+            remote_signer: RemoteSignerConfig::from_env().ok(),
         })
     }
 }
@@ -33,6 +37,12 @@ impl FromEnv for SenderConfig {
     }
 }
 
+impl FromEnv for RemoteSignerConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("eth_sender.remote_signer", "ETH_SENDER_REMOTE_SIGNER_")
+    }
+}
+
 impl FromEnv for GasAdjusterConfig {
     fn from_env() -> anyhow::Result<Self> {
         envy_load("eth_sender.gas_adjuster", "ETH_SENDER_GAS_ADJUSTER_")
@@ -44,7 +54,7 @@ mod tests {
     use zksync_config::configs::eth_sender::{ProofSendingMode, PubdataSendingMode};
 
     use super::*;
-    use crate::test_utils::{hash, EnvMutex};
+    use crate::test_utils::{addr, hash, EnvMutex};
 
     static MUTEX: EnvMutex = EnvMutex::new();
 
@@ -70,6 +80,9 @@ mod tests {
                     l1_batch_min_age_before_execute_seconds: Some(1000),
                     max_acceptable_priority_fee_in_gwei: 100_000_000_000,
                     pubdata_sending_mode: PubdataSendingMode::Calldata,
+                    fee_spike_ratio: Some(1.5),
+                    cheap_blob_fee_ratio: None,
+                    max_fee_postponement_seconds: Some(1_800),
                 }),
                 gas_adjuster: Some(GasAdjusterConfig {
                     default_priority_fee_per_gas: 20000000000,
@@ -88,6 +101,14 @@ mod tests {
                 watcher: Some(EthWatchConfig {
                     confirmations_for_eth_event: Some(0),
                     eth_node_poll_interval: 300,
+                    event_subscriptions: vec![],
+                }),
+                remote_signer: Some(RemoteSignerConfig {
+                    url: "http://127.0.0.1:9000".parse().unwrap(),
+                    operator_address: addr("de03a0b5963f75f1c8485b355ff6d30f3093bde7"),
+                    blob_operator_address: None,
+                    previous_operator_address: None,
+                    request_timeout_ms: 5_000,
                 }),
             },
             L1Secrets {
@@ -131,7 +152,12 @@ mod tests {
             ETH_SENDER_SENDER_L1_BATCH_MIN_AGE_BEFORE_EXECUTE_SECONDS="1000"
             ETH_SENDER_SENDER_MAX_ACCEPTABLE_PRIORITY_FEE_IN_GWEI="100000000000"
             ETH_SENDER_SENDER_PUBDATA_SENDING_MODE="Calldata"
+            ETH_SENDER_SENDER_FEE_SPIKE_RATIO="1.5"
+            ETH_SENDER_SENDER_MAX_FEE_POSTPONEMENT_SECONDS="1800"
             ETH_CLIENT_WEB3_URL="http://127.0.0.1:8545"
+            ETH_SENDER_REMOTE_SIGNER_URL="http://127.0.0.1:9000"
+            ETH_SENDER_REMOTE_SIGNER_OPERATOR_ADDRESS="0xde03a0b5963f75f1c8485b355ff6d30f3093bde7"
+            ETH_SENDER_REMOTE_SIGNER_REQUEST_TIMEOUT_MS="5000"
 
         "#;
         lock.set_env(config);
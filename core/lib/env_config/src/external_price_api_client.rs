@@ -27,6 +27,17 @@ mod tests {
             client_timeout_ms: DEFAULT_TIMEOUT_MS,
             forced_numerator: Some(100),
             forced_denominator: Some(1),
+            uniswap_pool_address: Some(
+                "0x88e6a0c2ddd26feeb64f039a2c41296fcb3f5640"
+                    .parse()
+                    .unwrap(),
+            ),
+            uniswap_twap_window_secs: Some(600),
+            chainlink_feed_address: None,
+            aggregated_sources: vec![],
+            min_sources: Some(2),
+            max_quote_age_ms: Some(60_000),
+            max_deviation_percent: Some(5.0),
         }
     }
 
@@ -39,6 +50,11 @@ mod tests {
             EXTERNAL_PRICE_API_CLIENT_API_KEY=qwerty12345
             EXTERNAL_PRICE_API_CLIENT_FORCED_NUMERATOR=100
             EXTERNAL_PRICE_API_CLIENT_FORCED_DENOMINATOR=1
+            EXTERNAL_PRICE_API_CLIENT_UNISWAP_POOL_ADDRESS=0x88e6a0c2ddd26feeb64f039a2c41296fcb3f5640
+            EXTERNAL_PRICE_API_CLIENT_UNISWAP_TWAP_WINDOW_SECS=600
+            EXTERNAL_PRICE_API_CLIENT_MIN_SOURCES=2
+            EXTERNAL_PRICE_API_CLIENT_MAX_QUOTE_AGE_MS=60000
+            EXTERNAL_PRICE_API_CLIENT_MAX_DEVIATION_PERCENT=5
         "#;
         lock.set_env(config);
 
@@ -20,7 +20,7 @@ mod tests {
     use zksync_config::{
         configs::{
             fri_prover::{CloudType, SetupLoadMode},
-            object_store::ObjectStoreMode,
+            object_store::{BucketCompressionCodecs, ObjectStoreMode},
         },
         ObjectStoreConfig,
     };
@@ -50,6 +50,7 @@ mod tests {
                 },
                 max_retries: 5,
                 local_mirror_path: None,
+                compression: BucketCompressionCodecs::empty(),
             }),
             public_object_store: Some(ObjectStoreConfig {
                 mode: ObjectStoreMode::GCSWithCredentialFile {
@@ -58,6 +59,7 @@ mod tests {
                 },
                 max_retries: 5,
                 local_mirror_path: None,
+                compression: BucketCompressionCodecs::empty(),
             }),
             availability_check_interval_in_secs: Some(1_800),
             cloud_type: CloudType::GCP,
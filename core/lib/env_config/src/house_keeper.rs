@@ -33,6 +33,10 @@ mod tests {
             fri_gpu_prover_archiver_archiving_interval_ms: Some(86_400_000),
             // 48 hours
             fri_gpu_prover_archiver_archive_after_secs: Some(172_800),
+            object_store_gc_interval_ms: Some(600_000),
+            // 7 days
+            object_store_gc_retention_period_secs: Some(604_800),
+            object_store_gc_dry_run: true,
         }
     }
 
@@ -56,6 +60,9 @@ mod tests {
             HOUSE_KEEPER_PROVER_JOB_ARCHIVER_ARCHIVE_AFTER_SECS="172800"
             HOUSE_KEEPER_FRI_GPU_PROVER_ARCHIVER_ARCHIVING_INTERVAL_MS="86400000"
             HOUSE_KEEPER_FRI_GPU_PROVER_ARCHIVER_ARCHIVE_AFTER_SECS="172800"
+            HOUSE_KEEPER_OBJECT_STORE_GC_INTERVAL_MS="600000"
+            HOUSE_KEEPER_OBJECT_STORE_GC_RETENTION_PERIOD_SECS="604800"
+            HOUSE_KEEPER_OBJECT_STORE_GC_DRY_RUN="true"
         "#;
         lock.set_env(config);
 
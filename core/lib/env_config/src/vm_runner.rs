@@ -1,4 +1,6 @@
-use zksync_config::configs::{BasicWitnessInputProducerConfig, ProtectiveReadsWriterConfig};
+use zksync_config::configs::{
+    BasicWitnessInputProducerConfig, ProtectiveReadsWriterConfig, TraceExporterConfig,
+};
 
 use crate::{envy_load, FromEnv};
 
@@ -13,3 +15,9 @@ impl FromEnv for BasicWitnessInputProducerConfig {
         envy_load("vm_runner.bwip", "VM_RUNNER_BWIP_")
     }
 }
+
+impl FromEnv for TraceExporterConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("vm_runner.trace_exporter", "VM_RUNNER_TRACE_EXPORTER_")
+    }
+}
@@ -16,6 +16,10 @@ impl FromEnv for Wallets {
             .ok()
             .map(|pk| pk.parse::<H256>().context("Malformed pk"))
             .transpose()?;
+        let previous_operator = std::env::var("ETH_SENDER_SENDER_PREVIOUS_OPERATOR_PRIVATE_KEY")
+            .ok()
+            .map(|pk| pk.parse::<H256>().context("Malformed pk"))
+            .transpose()?;
 
         let eth_sender = if let Some(operator) = operator {
             let operator = Wallet::from_private_key_bytes(operator, None)?;
@@ -24,9 +28,15 @@ impl FromEnv for Wallets {
             } else {
                 None
             };
+            let previous_operator = if let Some(previous_operator) = previous_operator {
+                Some(Wallet::from_private_key_bytes(previous_operator, None)?)
+            } else {
+                None
+            };
             Some(EthSender {
                 operator,
                 blob_operator,
+                previous_operator,
             })
         } else {
             None
@@ -19,6 +19,7 @@ use crate::{
 
 #[derive(Debug, Clone)]
 struct MockTx {
+    sender: Address,
     recipient: Address,
     input: Vec<u8>,
     hash: H256,
@@ -30,10 +31,12 @@ struct MockTx {
 impl From<Vec<u8>> for MockTx {
     fn from(tx: Vec<u8>) -> Self {
         let len = tx.len();
+        let sender = Address::from_slice(&tx[len - 20..]);
+        let len = len - 20;
         let recipient = Address::from_slice(&tx[len - 116..len - 96]);
         let max_fee_per_gas = U256::from(&tx[len - 96..len - 64]);
         let max_priority_fee_per_gas = U256::from(&tx[len - 64..len - 32]);
-        let nonce = U256::from(&tx[len - 32..]).as_u64();
+        let nonce = U256::from(&tx[len - 32..len]).as_u64();
         let hash = {
             let mut buffer = [0_u8; 32];
             buffer.copy_from_slice(&tx[..32]);
@@ -41,6 +44,7 @@ impl From<Vec<u8>> for MockTx {
         };
 
         Self {
+            sender,
             recipient,
             input: tx[32..len - 116].to_vec(),
             nonce,
@@ -54,6 +58,7 @@ impl From<Vec<u8>> for MockTx {
 impl From<MockTx> for web3::Transaction {
     fn from(tx: MockTx) -> Self {
         Self {
+            from: Some(tx.sender),
             to: Some(tx.recipient),
             input: tx.input.into(),
             hash: tx.hash,
@@ -71,15 +76,21 @@ struct MockExecutedTx {
     success: bool,
 }
 
+/// Nonces of a single account in the mocked network.
+#[derive(Debug, Default)]
+struct MockAccountNonces {
+    current_nonce: u64,
+    pending_nonce: u64,
+    nonces: BTreeMap<u64, u64>,
+}
+
 /// Mutable part of [`MockEthereum`] that needs to be synchronized via an `RwLock`.
 #[derive(Debug, Default)]
 struct MockEthereumInner {
     block_number: u64,
     executed_txs: HashMap<H256, MockExecutedTx>,
     sent_txs: HashMap<H256, MockTx>,
-    current_nonce: u64,
-    pending_nonce: u64,
-    nonces: BTreeMap<u64, u64>,
+    accounts: HashMap<Address, MockAccountNonces>,
 }
 
 impl MockEthereumInner {
@@ -92,18 +103,20 @@ impl MockEthereumInner {
     ) {
         let block_number = self.block_number;
         self.block_number += confirmations;
-        let nonce = self.current_nonce;
-        self.current_nonce += 1;
-        let tx_nonce = self.sent_txs[&tx_hash].nonce;
+        let tx = &self.sent_txs[&tx_hash];
+        let tx_nonce = tx.nonce;
+        let account = self.accounts.entry(tx.sender).or_default();
+        let nonce = account.current_nonce;
+        account.current_nonce += 1;
 
         if non_ordering_confirmations {
             if tx_nonce >= nonce {
-                self.current_nonce = tx_nonce;
+                account.current_nonce = tx_nonce;
             }
         } else {
             assert_eq!(tx_nonce, nonce, "nonce mismatch");
         }
-        self.nonces.insert(block_number, nonce + 1);
+        account.nonces.insert(block_number, nonce + 1);
 
         let status = MockExecutedTx {
             success,
@@ -119,18 +132,18 @@ impl MockEthereumInner {
     }
 
     fn get_transaction_count(&self, address: Address, block: web3::BlockNumber) -> U256 {
-        if address != MockEthereum::SENDER_ACCOUNT {
-            unimplemented!("Getting nonce for custom account is not supported");
-        }
+        let Some(account) = self.accounts.get(&address) else {
+            return U256::zero();
+        };
 
         match block {
             web3::BlockNumber::Number(block_number) => {
-                let mut nonce_range = self.nonces.range(..=block_number.as_u64());
+                let mut nonce_range = account.nonces.range(..=block_number.as_u64());
                 let (_, &nonce) = nonce_range.next_back().unwrap_or((&0, &0));
                 nonce.into()
             }
-            web3::BlockNumber::Pending => self.pending_nonce.into(),
-            web3::BlockNumber::Latest => self.current_nonce.into(),
+            web3::BlockNumber::Pending => account.pending_nonce.into(),
+            web3::BlockNumber::Latest => account.current_nonce.into(),
             _ => unimplemented!(
                 "`nonce_at_for_account()` called with unsupported block number: {block:?}"
             ),
@@ -140,8 +153,9 @@ impl MockEthereumInner {
     fn send_raw_transaction(&mut self, tx: web3::Bytes) -> Result<H256, ClientError> {
         let mock_tx = MockTx::from(tx.0);
         let mock_tx_hash = mock_tx.hash;
+        let account = self.accounts.entry(mock_tx.sender).or_default();
 
-        if mock_tx.nonce < self.current_nonce {
+        if mock_tx.nonce < account.current_nonce {
             let err = ErrorObject::owned(
                 101,
                 "transaction with the same nonce already processed",
@@ -150,8 +164,8 @@ impl MockEthereumInner {
             return Err(ClientError::Call(err));
         }
 
-        if mock_tx.nonce == self.pending_nonce {
-            self.pending_nonce += 1;
+        if mock_tx.nonce == account.pending_nonce {
+            account.pending_nonce += 1;
         }
         self.sent_txs.insert(mock_tx_hash, mock_tx);
         Ok(mock_tx_hash)
@@ -403,6 +417,7 @@ impl MockEthereumBuilder {
     /// Builds a mock Ethereum client.
     pub fn build(self) -> MockEthereum {
         MockEthereum {
+            sender_account: MockEthereum::SENDER_ACCOUNT,
             max_fee_per_gas: self.max_fee_per_gas,
             max_priority_fee_per_gas: self.max_priority_fee_per_gas,
             non_ordering_confirmations: self.non_ordering_confirmations,
@@ -415,6 +430,7 @@ impl MockEthereumBuilder {
 /// Mock Ethereum client.
 #[derive(Debug, Clone)]
 pub struct MockEthereum {
+    sender_account: Address,
     max_fee_per_gas: U256,
     max_priority_fee_per_gas: U256,
     non_ordering_confirmations: bool,
@@ -447,6 +463,14 @@ impl MockEthereum {
         H256::from_low_u64_ne(result)
     }
 
+    /// Returns a client for the same mocked network that signs transactions on behalf of the specified account.
+    pub fn with_sender_account(&self, sender_account: Address) -> Self {
+        Self {
+            sender_account,
+            ..self.clone()
+        }
+    }
+
     /// Returns the number of transactions sent via this client.
     pub fn sent_tx_count(&self) -> usize {
         self.inner.read().unwrap().sent_txs.len()
@@ -471,6 +495,7 @@ impl MockEthereum {
         raw_tx.extend_from_slice(&ethabi::encode(&max_fee_per_gas.into_tokens()));
         raw_tx.extend_from_slice(&ethabi::encode(&max_priority_fee_per_gas.into_tokens()));
         raw_tx.extend_from_slice(&ethabi::encode(&nonce.into_tokens()));
+        raw_tx.extend_from_slice(self.sender_account.as_bytes());
         let hash = Self::fake_sha256(&raw_tx); // Okay for test purposes.
 
         // Concatenate `raw_tx` plus hash for test purposes
@@ -545,7 +570,7 @@ impl BoundEthInterface for MockEthereum {
     }
 
     fn sender_account(&self) -> Address {
-        Self::SENDER_ACCOUNT
+        self.sender_account
     }
 
     async fn sign_prepared_tx_for_addr(
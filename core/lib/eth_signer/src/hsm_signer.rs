@@ -0,0 +1,222 @@
+use std::{
+    fmt,
+    sync::{Arc, OnceLock},
+};
+
+use async_trait::async_trait;
+use zksync_types::{
+    web3::{keccak256, Signature},
+    Address, EIP712TypedStructure, Eip712Domain, PackedEthSignature, H256, H512, U256,
+};
+
+use crate::{
+    raw_ethereum_tx::{Transaction, TransactionParameters},
+    EthereumSigner, SignerError,
+};
+
+/// Order of the secp256k1 curve group.
+const SECP256K1_ORDER: U256 = U256([
+    0xbfd2_5e8c_d036_4141,
+    0xbaae_dce6_af48_a03b,
+    0xffff_ffff_ffff_fffe,
+    0xffff_ffff_ffff_ffff,
+]);
+
+/// Interface of a hardware security module (HSM) holding secp256k1 keys, modeled after PKCS#11.
+///
+/// Implementations are expected to open sessions, log in, etc. as necessary; keys are referenced by their labels
+/// (the `CKA_LABEL` attribute in PKCS#11 terms). Keys never leave the HSM; only digests are passed to it for signing.
+#[async_trait]
+pub trait Hsm: 'static + Send + Sync + fmt::Debug {
+    /// Returns the uncompressed public key for the key with the specified label, without the leading `0x04` byte.
+    async fn public_key(&self, key_label: &str) -> Result<H512, SignerError>;
+
+    /// Signs a 32-byte digest using raw ECDSA (the `CKM_ECDSA` mechanism in PKCS#11 terms) with the key
+    /// with the specified label. Returns the `(r, s)` signature components.
+    async fn sign_digest(&self, key_label: &str, digest: H256)
+        -> Result<(H256, H256), SignerError>;
+}
+
+/// Ethereum signer backed by a key stored in an [`Hsm`].
+///
+/// Since HSMs generally don't produce recoverable signatures, the recovery ID is determined by recovering
+/// the signer address for both possible values and comparing it with the address of the key.
+///
+/// There is no config for HSM signing, and the node builders don't create this signer: the embedder must provide
+/// an [`Hsm`] implementation for its HSM vendor (e.g., on top of a PKCS#11 library) and pass the resulting signers
+/// to `SigningEthClientLayer` in place of `RemoteSigner`s.
+#[derive(Debug, Clone)]
+pub struct HsmSigner {
+    hsm: Arc<dyn Hsm>,
+    key_label: Arc<str>,
+    address: Arc<OnceLock<Address>>,
+}
+
+impl HsmSigner {
+    pub fn new(hsm: Arc<dyn Hsm>, key_label: &str) -> Self {
+        Self {
+            hsm,
+            key_label: key_label.into(),
+            address: Arc::default(),
+        }
+    }
+
+    async fn address(&self) -> Result<Address, SignerError> {
+        if let Some(&address) = self.address.get() {
+            return Ok(address);
+        }
+        let public_key = self.hsm.public_key(&self.key_label).await?;
+        let address = Address::from_slice(&keccak256(public_key.as_bytes())[12..]);
+        Ok(*self.address.get_or_init(|| address))
+    }
+
+    /// Signs `digest` in the HSM and returns the signature with the recovery ID as `v` and normalized `s` value.
+    async fn sign_digest(&self, digest: H256) -> Result<PackedEthSignature, SignerError> {
+        let address = self.address().await?;
+        let (r, s) = self.hsm.sign_digest(&self.key_label, digest).await?;
+        // Ethereum only accepts signatures with low `s` values (see EIP-2), which HSMs don't necessarily produce.
+        // Negating `s` doesn't invalidate the signature, but flips the recovery ID, so it's determined afterwards.
+        let s = U256::from_big_endian(s.as_bytes());
+        let s = if s > SECP256K1_ORDER / 2 {
+            SECP256K1_ORDER - s
+        } else {
+            s
+        };
+        let mut s_bytes = H256::zero();
+        s.to_big_endian(s_bytes.as_bytes_mut());
+
+        for recovery_id in [0, 1] {
+            let signature = PackedEthSignature::from_rsv(&r, &s_bytes, recovery_id);
+            if signature.signature_recover_signer(&digest).ok() == Some(address) {
+                return Ok(signature);
+            }
+        }
+        Err(SignerError::SigningFailed(format!(
+            "HSM signature with key `{}` does not correspond to address {address:?}",
+            self.key_label
+        )))
+    }
+}
+
+#[async_trait]
+impl EthereumSigner for HsmSigner {
+    async fn sign_typed_data<S: EIP712TypedStructure + Sync>(
+        &self,
+        domain: &Eip712Domain,
+        typed_struct: &S,
+    ) -> Result<PackedEthSignature, SignerError> {
+        let digest = PackedEthSignature::typed_data_to_signed_bytes(domain, typed_struct);
+        self.sign_digest(digest).await
+    }
+
+    async fn sign_transaction(
+        &self,
+        raw_tx: TransactionParameters,
+    ) -> Result<Vec<u8>, SignerError> {
+        let chain_id = raw_tx.chain_id;
+        let tx = Transaction::from(raw_tx);
+        let signature = self.sign_digest(tx.signing_hash(chain_id)).await?;
+        let signature = Signature {
+            v: tx.signature_v(signature.v(), chain_id),
+            r: H256::from_slice(signature.r()),
+            s: H256::from_slice(signature.s()),
+        };
+        Ok(tx.into_signed(chain_id, signature).raw_transaction.0)
+    }
+
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        self.address().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use zksync_types::K256PrivateKey;
+
+    use super::*;
+    use crate::{
+        testonly::{test_key, transactions},
+        PrivateKeySigner,
+    };
+
+    /// In-process HSM mock. Produces signatures with high `s` values to check normalization.
+    #[derive(Debug)]
+    struct MockHsm {
+        keys: HashMap<String, K256PrivateKey>,
+    }
+
+    #[async_trait]
+    impl Hsm for MockHsm {
+        async fn public_key(&self, key_label: &str) -> Result<H512, SignerError> {
+            let key = self
+                .keys
+                .get(key_label)
+                .ok_or_else(|| SignerError::SigningFailed(format!("unknown key `{key_label}`")))?;
+            Ok(key.public())
+        }
+
+        async fn sign_digest(
+            &self,
+            key_label: &str,
+            digest: H256,
+        ) -> Result<(H256, H256), SignerError> {
+            let key = self
+                .keys
+                .get(key_label)
+                .ok_or_else(|| SignerError::SigningFailed(format!("unknown key `{key_label}`")))?;
+            let signature = key.sign_web3_message(&digest);
+            let high_s = SECP256K1_ORDER - U256::from_big_endian(signature.s.as_bytes());
+            let mut s = H256::zero();
+            high_s.to_big_endian(s.as_bytes_mut());
+            Ok((signature.r, s))
+        }
+    }
+
+    fn hsm_signer(key_label: &str) -> HsmSigner {
+        let hsm = MockHsm {
+            keys: HashMap::from([("operator".to_owned(), test_key())]),
+        };
+        HsmSigner::new(Arc::new(hsm), key_label)
+    }
+
+    #[tokio::test]
+    async fn signing_transactions() {
+        let signer = hsm_signer("operator");
+        let pk_signer = PrivateKeySigner::new(test_key());
+        assert_eq!(
+            signer.get_address().await.unwrap(),
+            pk_signer.get_address().await.unwrap()
+        );
+
+        for tx in transactions() {
+            let raw_tx = signer.sign_transaction(tx.clone()).await.unwrap();
+            let expected_raw_tx = pk_signer.sign_transaction(tx.clone()).await.unwrap();
+            assert_eq!(raw_tx, expected_raw_tx, "{tx:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn signing_typed_data() {
+        let signer = hsm_signer("operator");
+        let domain = Eip712Domain::new(270.into());
+        let signature = signer.sign_typed_data(&domain, &domain).await.unwrap();
+
+        let expected_signature = PrivateKeySigner::new(test_key())
+            .sign_typed_data(&domain, &domain)
+            .await
+            .unwrap();
+        assert_eq!(signature, expected_signature);
+    }
+
+    #[tokio::test]
+    async fn signing_with_unknown_key() {
+        let signer = hsm_signer("unknown");
+        let err = signer.sign_transaction(transactions()[0].clone()).await;
+        assert!(
+            matches!(&err, Err(SignerError::SigningFailed(msg)) if msg.contains("unknown key")),
+            "{err:?}"
+        );
+    }
+}
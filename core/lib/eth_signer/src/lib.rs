@@ -1,10 +1,19 @@
 use async_trait::async_trait;
 use zksync_types::{Address, EIP712TypedStructure, Eip712Domain, PackedEthSignature};
 
-pub use crate::{pk_signer::PrivateKeySigner, raw_ethereum_tx::TransactionParameters};
+pub use crate::{
+    hsm_signer::{Hsm, HsmSigner},
+    pk_signer::PrivateKeySigner,
+    raw_ethereum_tx::TransactionParameters,
+    remote_signer::RemoteSigner,
+};
 
+mod hsm_signer;
 mod pk_signer;
 mod raw_ethereum_tx;
+mod remote_signer;
+#[cfg(test)]
+mod testonly;
 
 #[derive(Debug, thiserror::Error)]
 #[non_exhaustive]
@@ -240,22 +240,44 @@ impl Transaction {
         }
     }
 
-    /// Sign and return a raw signed transaction.
-    pub fn sign(self, private_key: &K256PrivateKey, chain_id: u64) -> SignedTransaction {
-        let adjust_v_value = matches!(
+    /// Returns `true` if the signature `v` value for this transaction must include the chain ID (per EIP-155).
+    fn uses_eip155_v_value(&self) -> bool {
+        matches!(
             self.transaction_type.map(|t| t.as_u64()),
             Some(LEGACY_TX_ID) | None
-        );
+        )
+    }
 
+    /// Returns the hash of the transaction that should be signed.
+    pub fn signing_hash(&self, chain_id: u64) -> H256 {
         let encoded = self.encode(chain_id, None);
-        let message_hash = H256(keccak256(encoded.as_ref()));
+        H256(keccak256(encoded.as_ref()))
+    }
 
-        let signature = if adjust_v_value {
-            private_key.sign_web3(&message_hash, Some(chain_id))
+    /// Converts a recovery ID (0 or 1) of a signature over [`Self::signing_hash()`] into the `v` value
+    /// expected for this transaction type.
+    pub fn signature_v(&self, recovery_id: u8, chain_id: u64) -> u64 {
+        if self.uses_eip155_v_value() {
+            u64::from(recovery_id) + 35 + chain_id * 2
         } else {
-            private_key.sign_web3_message(&message_hash)
+            recovery_id.into()
+        }
+    }
+
+    /// Returns the recovery ID (0 or 1) encoded in the signature `v` value for this transaction type.
+    pub fn signature_recovery_id(&self, v: u64, chain_id: u64) -> Option<u8> {
+        let recovery_id = if self.uses_eip155_v_value() {
+            v.checked_sub(35 + chain_id * 2)?
+        } else {
+            v
         };
+        (recovery_id <= 1).then_some(recovery_id as u8)
+    }
 
+    /// Encodes the transaction together with a signature over [`Self::signing_hash()`]. The signature `v` value
+    /// must be adjusted according to the transaction type; see [`Self::signature_v()`].
+    pub fn into_signed(self, chain_id: u64, signature: Signature) -> SignedTransaction {
+        let message_hash = self.signing_hash(chain_id);
         let signed = self.encode(chain_id, Some(&signature));
         let transaction_hash = keccak256(signed.as_ref()).into();
 
@@ -268,4 +290,35 @@ impl Transaction {
             transaction_hash,
         }
     }
+
+    /// Sign and return a raw signed transaction.
+    pub fn sign(self, private_key: &K256PrivateKey, chain_id: u64) -> SignedTransaction {
+        let message_hash = self.signing_hash(chain_id);
+        let signature = if self.uses_eip155_v_value() {
+            private_key.sign_web3(&message_hash, Some(chain_id))
+        } else {
+            private_key.sign_web3_message(&message_hash)
+        };
+        self.into_signed(chain_id, signature)
+    }
+}
+
+impl From<TransactionParameters> for Transaction {
+    fn from(raw_tx: TransactionParameters) -> Self {
+        Self {
+            to: raw_tx.to,
+            nonce: raw_tx.nonce,
+            gas: raw_tx.gas,
+            // According to the code in web3 <https://docs.rs/web3/latest/src/web3/api/accounts.rs.html#86>
+            // We should use `max_fee_per_gas` as `gas_price` if we use EIP1559
+            gas_price: raw_tx.max_fee_per_gas,
+            value: raw_tx.value,
+            data: raw_tx.data,
+            transaction_type: raw_tx.transaction_type,
+            access_list: raw_tx.access_list.unwrap_or_default(),
+            max_priority_fee_per_gas: raw_tx.max_priority_fee_per_gas,
+            max_fee_per_blob_gas: raw_tx.max_fee_per_blob_gas,
+            blob_versioned_hashes: raw_tx.blob_versioned_hashes,
+        }
+    }
 }
@@ -0,0 +1,312 @@
+use std::time::Duration;
+
+use jsonrpsee::{
+    core::client::ClientT,
+    http_client::{HttpClient, HttpClientBuilder},
+    rpc_params,
+};
+use serde::{Deserialize, Serialize};
+use zksync_types::{
+    url::SensitiveUrl,
+    web3::{AccessList, Bytes, Signature},
+    Address, EIP712TypedStructure, Eip712Domain, PackedEthSignature, H256, U256, U64,
+};
+
+use crate::{
+    raw_ethereum_tx::{Transaction, TransactionParameters},
+    EthereumSigner, SignerError,
+};
+
+/// Ethereum signer delegating signing to a remote service with a [Web3Signer]-compatible JSON-RPC API
+/// (i.e., supporting the `eth_signTransaction` method).
+///
+/// Transactions returned by the remote signer are checked to match the requested transaction and to be signed
+/// by the expected account.
+///
+/// The signer doesn't support signing typed data, since `eth_signTypedData` requires a JSON representation
+/// of the signed struct which isn't available for an arbitrary [`EIP712TypedStructure`].
+///
+/// [Web3Signer]: https://docs.web3signer.consensys.io/
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    client: HttpClient,
+    address: Address,
+}
+
+impl RemoteSigner {
+    /// Creates a signer for the account with the specified `address` managed by the remote signer at `url`.
+    pub fn new(
+        url: &SensitiveUrl,
+        address: Address,
+        request_timeout: Duration,
+    ) -> Result<Self, SignerError> {
+        let client = HttpClientBuilder::default()
+            .request_timeout(request_timeout)
+            .build(url.expose_str())
+            .map_err(|err| {
+                SignerError::SigningFailed(format!("failed creating remote signer client: {err}"))
+            })?;
+        Ok(Self { client, address })
+    }
+}
+
+#[async_trait::async_trait]
+impl EthereumSigner for RemoteSigner {
+    async fn sign_typed_data<S: EIP712TypedStructure + Sync>(
+        &self,
+        _domain: &Eip712Domain,
+        _typed_struct: &S,
+    ) -> Result<PackedEthSignature, SignerError> {
+        Err(SignerError::SigningFailed(
+            "signing typed data is not supported by the remote signer".to_owned(),
+        ))
+    }
+
+    async fn sign_transaction(
+        &self,
+        raw_tx: TransactionParameters,
+    ) -> Result<Vec<u8>, SignerError> {
+        let request = SignTransactionRequest::new(self.address, raw_tx.clone());
+        let signed_tx: Bytes = self
+            .client
+            .request("eth_signTransaction", rpc_params![request])
+            .await
+            .map_err(|err| {
+                SignerError::SigningFailed(format!("remote signer returned error: {err}"))
+            })?;
+        verify_signed_transaction(self.address, raw_tx, &signed_tx.0)?;
+        Ok(signed_tx.0)
+    }
+
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        Ok(self.address)
+    }
+}
+
+/// Transaction object passed to the `eth_signTransaction` method.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SignTransactionRequest {
+    pub from: Address,
+    pub to: Option<Address>,
+    pub nonce: U256,
+    pub gas: U256,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_price: Option<U256>,
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    pub value: U256,
+    pub data: Bytes,
+    pub chain_id: U64,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub transaction_type: Option<U64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_list: Option<AccessList>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_blob_gas: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_versioned_hashes: Option<Vec<H256>>,
+}
+
+impl SignTransactionRequest {
+    fn new(from: Address, raw_tx: TransactionParameters) -> Self {
+        let is_legacy = matches!(
+            raw_tx.transaction_type.map(|ty| ty.as_u64()),
+            None | Some(0)
+        );
+        Self {
+            from,
+            to: raw_tx.to,
+            nonce: raw_tx.nonce,
+            gas: raw_tx.gas,
+            // Consistently with `PrivateKeySigner`, `max_fee_per_gas` is used as the gas price for legacy transactions.
+            gas_price: is_legacy.then_some(raw_tx.max_fee_per_gas),
+            max_fee_per_gas: raw_tx.max_fee_per_gas,
+            max_priority_fee_per_gas: raw_tx.max_priority_fee_per_gas,
+            value: raw_tx.value,
+            data: raw_tx.data.into(),
+            chain_id: raw_tx.chain_id.into(),
+            transaction_type: raw_tx.transaction_type,
+            access_list: raw_tx.access_list,
+            max_fee_per_blob_gas: raw_tx.max_fee_per_blob_gas,
+            blob_versioned_hashes: raw_tx.blob_versioned_hashes,
+        }
+    }
+}
+
+/// Checks that `signed_tx` is `raw_tx` signed by `address`.
+fn verify_signed_transaction(
+    address: Address,
+    raw_tx: TransactionParameters,
+    signed_tx: &[u8],
+) -> Result<(), SignerError> {
+    let chain_id = raw_tx.chain_id;
+    let tx = Transaction::from(raw_tx);
+    let signature = extract_signature(signed_tx).ok_or_else(|| {
+        SignerError::SigningFailed("remote signer returned malformed transaction".to_owned())
+    })?;
+
+    let recovery_id = tx
+        .signature_recovery_id(signature.v, chain_id)
+        .ok_or_else(|| {
+            SignerError::SigningFailed(format!(
+                "remote signer returned signature with unexpected v: {}",
+                signature.v
+            ))
+        })?;
+    let signing_hash = tx.signing_hash(chain_id);
+    let packed_signature = PackedEthSignature::from_rsv(&signature.r, &signature.s, recovery_id);
+    let signer = packed_signature
+        .signature_recover_signer(&signing_hash)
+        .map_err(|err| SignerError::SigningFailed(format!("invalid remote signature: {err}")))?;
+    if signer != address {
+        return Err(SignerError::SigningFailed(format!(
+            "remote signer signed transaction by {signer:?} instead of {address:?}"
+        )));
+    }
+
+    let expected_tx = tx.into_signed(chain_id, signature).raw_transaction;
+    if expected_tx.0 != signed_tx {
+        return Err(SignerError::SigningFailed(
+            "remote signer returned transaction differing from the requested one".to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+/// Extracts a signature from an RLP-encoded signed legacy or EIP-2718 typed transaction.
+fn extract_signature(signed_tx: &[u8]) -> Option<Signature> {
+    let first_byte = *signed_tx.first()?;
+    // Legacy transactions are RLP lists; typed transactions start with a type byte (see EIP-2718).
+    let payload = if first_byte >= 0xc0 {
+        signed_tx
+    } else {
+        &signed_tx[1..]
+    };
+    let rlp = rlp::Rlp::new(payload);
+    let item_count = rlp.item_count().ok()?;
+    if item_count < 3 {
+        return None;
+    }
+    let v: u64 = rlp.val_at(item_count - 3).ok()?;
+    let r: U256 = rlp.val_at(item_count - 2).ok()?;
+    let s: U256 = rlp.val_at(item_count - 1).ok()?;
+
+    let mut signature = Signature {
+        v,
+        r: H256::zero(),
+        s: H256::zero(),
+    };
+    r.to_big_endian(signature.r.as_bytes_mut());
+    s.to_big_endian(signature.s.as_bytes_mut());
+    Some(signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use jsonrpsee::{
+        server::{RpcModule, Server, ServerHandle},
+        types::ErrorObjectOwned,
+    };
+
+    use super::*;
+    use crate::{
+        testonly::{test_key, transactions},
+        PrivateKeySigner,
+    };
+
+    /// Behavior of the mock remote signer.
+    #[derive(Debug, Clone, Copy)]
+    enum MockBehavior {
+        Correct,
+        ChangeNonce,
+    }
+
+    /// Starts an in-process mock signer signing transactions with [`test_key()`].
+    async fn start_mock_signer(behavior: MockBehavior) -> (SocketAddr, ServerHandle) {
+        let mut module = RpcModule::new(PrivateKeySigner::new(test_key()));
+        module
+            .register_async_method("eth_signTransaction", move |params, signer, _| async move {
+                let request: SignTransactionRequest = params.one()?;
+                let address = signer.get_address().await.unwrap();
+                if request.from != address {
+                    return Err(ErrorObjectOwned::owned(
+                        -32000,
+                        format!("unknown account {:?}", request.from),
+                        None::<()>,
+                    ));
+                }
+
+                let mut raw_tx = TransactionParameters {
+                    nonce: request.nonce,
+                    to: request.to,
+                    gas: request.gas,
+                    gas_price: request.gas_price,
+                    value: request.value,
+                    data: request.data.0,
+                    chain_id: request.chain_id.as_u64(),
+                    transaction_type: request.transaction_type,
+                    access_list: request.access_list,
+                    max_fee_per_gas: request.max_fee_per_gas,
+                    max_priority_fee_per_gas: request.max_priority_fee_per_gas,
+                    max_fee_per_blob_gas: request.max_fee_per_blob_gas,
+                    blob_versioned_hashes: request.blob_versioned_hashes,
+                };
+                if matches!(behavior, MockBehavior::ChangeNonce) {
+                    raw_tx.nonce += U256::one();
+                }
+                let signed_tx = signer.sign_transaction(raw_tx).await.unwrap();
+                Ok::<_, ErrorObjectOwned>(Bytes(signed_tx))
+            })
+            .unwrap();
+
+        let server = Server::builder().build("127.0.0.1:0").await.unwrap();
+        let local_addr = server.local_addr().unwrap();
+        (local_addr, server.start(module))
+    }
+
+    fn remote_signer(local_addr: SocketAddr, address: Address) -> RemoteSigner {
+        let url = format!("http://{local_addr}").parse().unwrap();
+        RemoteSigner::new(&url, address, Duration::from_secs(5)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn signing_transactions() {
+        let (local_addr, _server) = start_mock_signer(MockBehavior::Correct).await;
+        let signer = remote_signer(local_addr, test_key().address());
+        let pk_signer = PrivateKeySigner::new(test_key());
+
+        for tx in transactions() {
+            let raw_tx = signer.sign_transaction(tx.clone()).await.unwrap();
+            let expected_raw_tx = pk_signer.sign_transaction(tx.clone()).await.unwrap();
+            assert_eq!(raw_tx, expected_raw_tx, "{tx:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn signing_transaction_for_unknown_account() {
+        let (local_addr, _server) = start_mock_signer(MockBehavior::Correct).await;
+        let signer = remote_signer(local_addr, Address::repeat_byte(1));
+
+        let err = signer
+            .sign_transaction(transactions()[0].clone())
+            .await
+            .unwrap_err();
+        let SignerError::SigningFailed(msg) = err;
+        assert!(msg.contains("unknown account"), "{msg}");
+    }
+
+    #[tokio::test]
+    async fn rejecting_modified_transaction() {
+        let (local_addr, _server) = start_mock_signer(MockBehavior::ChangeNonce).await;
+        let signer = remote_signer(local_addr, test_key().address());
+
+        for tx in transactions() {
+            let err = signer.sign_transaction(tx).await.unwrap_err();
+            let SignerError::SigningFailed(msg) = err;
+            assert!(msg.contains("remote signer"), "{msg}");
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! Test utilities shared by signer tests.
+
+use zksync_types::{K256PrivateKey, H160, H256, U256, U64};
+
+use crate::TransactionParameters;
+
+pub(crate) fn test_key() -> K256PrivateKey {
+    K256PrivateKey::from_bytes(H256::repeat_byte(5)).unwrap()
+}
+
+/// Returns transactions of all supported types (legacy, EIP-2930, EIP-1559 and EIP-4844).
+pub(crate) fn transactions() -> Vec<TransactionParameters> {
+    let base_tx = TransactionParameters {
+        nonce: U256::from(1u32),
+        to: Some(H160::repeat_byte(1)),
+        gas: 100_000.into(),
+        gas_price: None,
+        max_fee_per_gas: U256::from(2u32),
+        max_priority_fee_per_gas: U256::from(1u32),
+        value: Default::default(),
+        data: vec![1, 2, 3],
+        chain_id: 270,
+        transaction_type: None,
+        access_list: None,
+        blob_versioned_hashes: None,
+        max_fee_per_blob_gas: None,
+    };
+    [None, Some(1_u32), Some(2), Some(3)]
+        .into_iter()
+        .map(|tx_type| {
+            let mut tx = base_tx.clone();
+            tx.transaction_type = tx_type.map(U64::from);
+            if tx_type == Some(3) {
+                tx.max_fee_per_blob_gas = Some(10.into());
+                tx.blob_versioned_hashes = Some(vec![H256::repeat_byte(0x01)]);
+            }
+            tx
+        })
+        .collect()
+}
@@ -0,0 +1,348 @@
+use std::{fmt, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::future;
+use zksync_config::configs::ExternalPriceApiClientConfig;
+use zksync_types::{base_token_ratio::BaseTokenAPIRatio, Address};
+
+use crate::{
+    metrics::{QuoteOutcome, METRICS},
+    utils::get_fraction,
+    PriceAPIClient,
+};
+
+/// Price API client querying multiple sources concurrently and returning the median of their quotes.
+///
+/// Quotes that are stale or deviate from the median of all quotes by more than the configured threshold are discarded.
+/// If fewer than the configured minimum number of sources return valid quotes, fetching the ratio fails.
+pub struct AggregatingPriceAPIClient {
+    sources: Vec<(String, Arc<dyn PriceAPIClient>)>,
+    source_timeout: Duration,
+    min_sources: usize,
+    max_quote_age: Duration,
+    max_deviation_percent: f64,
+}
+
+impl fmt::Debug for AggregatingPriceAPIClient {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let source_names: Vec<_> = self.sources.iter().map(|(name, _)| name).collect();
+        formatter
+            .debug_struct("AggregatingPriceAPIClient")
+            .field("sources", &source_names)
+            .field("source_timeout", &self.source_timeout)
+            .field("min_sources", &self.min_sources)
+            .field("max_quote_age", &self.max_quote_age)
+            .field("max_deviation_percent", &self.max_deviation_percent)
+            .finish()
+    }
+}
+
+impl AggregatingPriceAPIClient {
+    /// Creates a client aggregating quotes from the provided named sources. Names are used in logs and metrics,
+    /// so they should be unique.
+    pub fn new(
+        config: &ExternalPriceApiClientConfig,
+        sources: Vec<(String, Arc<dyn PriceAPIClient>)>,
+    ) -> anyhow::Result<Self> {
+        // By default, require the majority of sources to agree on the price.
+        let min_sources = config.min_sources.unwrap_or(sources.len() / 2 + 1);
+        anyhow::ensure!(
+            min_sources > 0 && min_sources <= sources.len(),
+            "min_sources ({min_sources}) must be positive and not exceed the number of sources ({})",
+            sources.len()
+        );
+        let max_deviation_percent = config.max_deviation_percent();
+        anyhow::ensure!(
+            max_deviation_percent >= 0.0,
+            "max_deviation_percent ({max_deviation_percent}) must be non-negative"
+        );
+
+        Ok(Self {
+            sources,
+            source_timeout: config.client_timeout(),
+            min_sources,
+            max_quote_age: config.max_quote_age(),
+            max_deviation_percent,
+        })
+    }
+
+    async fn fetch_quote(
+        &self,
+        client: &dyn PriceAPIClient,
+        token_address: Address,
+    ) -> anyhow::Result<BaseTokenAPIRatio> {
+        tokio::time::timeout(self.source_timeout, client.fetch_ratio(token_address))
+            .await
+            .map_err(|_| anyhow::anyhow!("timed out after {:?}", self.source_timeout))?
+    }
+
+    fn is_stale(&self, ratio: &BaseTokenAPIRatio, now: DateTime<Utc>) -> bool {
+        // Timestamps in the future are not considered stale.
+        (now - ratio.ratio_timestamp)
+            .to_std()
+            .map_or(false, |age| age > self.max_quote_age)
+    }
+
+    /// Discards outliers among fresh quotes and returns the median of the remaining ones.
+    fn aggregate(&self, mut quotes: Vec<Quote<'_>>) -> anyhow::Result<BaseTokenAPIRatio> {
+        self.ensure_enough_quotes(quotes.len(), "valid")?;
+
+        let median = median_price(&mut quotes);
+        quotes.retain(|quote| {
+            let deviation_percent = (quote.price - median).abs() / median * 100.0;
+            METRICS.deviation_percent[&quote.source.to_owned()].set(deviation_percent);
+            if deviation_percent > self.max_deviation_percent {
+                tracing::warn!(
+                    "Discarding quote {} from source `{}` deviating from median {median} by {deviation_percent:.2}%",
+                    quote.price,
+                    quote.source
+                );
+                METRICS.quotes[&(quote.source.to_owned(), QuoteOutcome::Outlier)].inc();
+                false
+            } else {
+                METRICS.quotes[&(quote.source.to_owned(), QuoteOutcome::Accepted)].inc();
+                true
+            }
+        });
+        self.ensure_enough_quotes(quotes.len(), "non-outlier")?;
+
+        // `quotes` remain sorted after discarding outliers.
+        let mid = quotes.len() / 2;
+        let (numerator, denominator) = if quotes.len() % 2 == 1 {
+            (quotes[mid].ratio.numerator, quotes[mid].ratio.denominator)
+        } else {
+            get_fraction((quotes[mid - 1].price + quotes[mid].price) / 2.0)
+        };
+        let ratio_timestamp = quotes
+            .iter()
+            .map(|quote| quote.ratio.ratio_timestamp)
+            .min()
+            .unwrap(); // `quotes` is non-empty as checked above
+        Ok(BaseTokenAPIRatio {
+            numerator,
+            denominator,
+            ratio_timestamp,
+        })
+    }
+
+    fn ensure_enough_quotes(&self, count: usize, kind: &str) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            count >= self.min_sources,
+            "only {count} of {} price sources returned {kind} quotes; at least {} are required",
+            self.sources.len(),
+            self.min_sources
+        );
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PriceAPIClient for AggregatingPriceAPIClient {
+    async fn fetch_ratio(&self, token_address: Address) -> anyhow::Result<BaseTokenAPIRatio> {
+        let fetches = self.sources.iter().map(|(name, client)| async move {
+            (name, self.fetch_quote(client.as_ref(), token_address).await)
+        });
+        let results = future::join_all(fetches).await;
+
+        let now = Utc::now();
+        let mut quotes = Vec::with_capacity(results.len());
+        for (source, result) in results {
+            match result {
+                Ok(ratio) if self.is_stale(&ratio, now) => {
+                    tracing::warn!(
+                        "Discarding stale quote from source `{source}` with timestamp {}",
+                        ratio.ratio_timestamp
+                    );
+                    METRICS.quotes[&(source.clone(), QuoteOutcome::Stale)].inc();
+                }
+                Ok(ratio) => quotes.push(Quote::new(source, ratio)),
+                Err(err) => {
+                    tracing::warn!("Failed fetching quote from source `{source}`: {err:#}");
+                    METRICS.quotes[&(source.clone(), QuoteOutcome::Error)].inc();
+                }
+            }
+        }
+        self.aggregate(quotes)
+    }
+}
+
+#[derive(Debug)]
+struct Quote<'a> {
+    source: &'a str,
+    ratio: BaseTokenAPIRatio,
+    price: f64,
+}
+
+impl<'a> Quote<'a> {
+    fn new(source: &'a str, ratio: BaseTokenAPIRatio) -> Self {
+        let price = ratio.numerator.get() as f64 / ratio.denominator.get() as f64;
+        Self {
+            source,
+            ratio,
+            price,
+        }
+    }
+}
+
+/// Sorts `quotes` by price and returns the median price. `quotes` must be non-empty.
+fn median_price(quotes: &mut [Quote<'_>]) -> f64 {
+    quotes.sort_unstable_by(|a, b| a.price.total_cmp(&b.price));
+    let mid = quotes.len() / 2;
+    if quotes.len() % 2 == 1 {
+        quotes[mid].price
+    } else {
+        (quotes[mid - 1].price + quotes[mid].price) / 2.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use super::*;
+
+    #[derive(Debug)]
+    enum MockClient {
+        Ratio(u64, u64, DateTime<Utc>),
+        Error,
+        Hanging,
+    }
+
+    impl MockClient {
+        fn ratio(numerator: u64, denominator: u64) -> Arc<dyn PriceAPIClient> {
+            Arc::new(Self::Ratio(numerator, denominator, Utc::now()))
+        }
+    }
+
+    #[async_trait]
+    impl PriceAPIClient for MockClient {
+        async fn fetch_ratio(&self, _token_address: Address) -> anyhow::Result<BaseTokenAPIRatio> {
+            match self {
+                Self::Ratio(numerator, denominator, ratio_timestamp) => Ok(BaseTokenAPIRatio {
+                    numerator: NonZeroU64::new(*numerator).unwrap(),
+                    denominator: NonZeroU64::new(*denominator).unwrap(),
+                    ratio_timestamp: *ratio_timestamp,
+                }),
+                Self::Error => anyhow::bail!("mock error"),
+                Self::Hanging => future::pending().await,
+            }
+        }
+    }
+
+    fn config(min_sources: Option<usize>) -> ExternalPriceApiClientConfig {
+        ExternalPriceApiClientConfig {
+            source: "aggregated".to_owned(),
+            base_url: None,
+            api_key: None,
+            client_timeout_ms: 100,
+            forced_numerator: None,
+            forced_denominator: None,
+            uniswap_pool_address: None,
+            uniswap_twap_window_secs: None,
+            chainlink_feed_address: None,
+            aggregated_sources: vec![],
+            min_sources,
+            max_quote_age_ms: Some(60_000),
+            max_deviation_percent: Some(10.0),
+        }
+    }
+
+    fn named(sources: Vec<Arc<dyn PriceAPIClient>>) -> Vec<(String, Arc<dyn PriceAPIClient>)> {
+        sources
+            .into_iter()
+            .enumerate()
+            .map(|(i, client)| (format!("mock{i}"), client))
+            .collect()
+    }
+
+    async fn fetch_ratio(
+        min_sources: Option<usize>,
+        sources: Vec<Arc<dyn PriceAPIClient>>,
+    ) -> anyhow::Result<(u64, u64)> {
+        let client = AggregatingPriceAPIClient::new(&config(min_sources), named(sources))?;
+        let ratio = client.fetch_ratio(Address::zero()).await?;
+        Ok((ratio.numerator.get(), ratio.denominator.get()))
+    }
+
+    #[tokio::test]
+    async fn aggregating_quotes() {
+        let ratio = fetch_ratio(
+            None,
+            vec![
+                MockClient::ratio(105, 100),
+                MockClient::ratio(1, 1),
+                MockClient::ratio(98, 100),
+            ],
+        )
+        .await
+        .unwrap();
+        assert_eq!(ratio, (1, 1));
+
+        let ratio = fetch_ratio(
+            Some(2),
+            vec![MockClient::ratio(1, 1), MockClient::ratio(11, 10)],
+        )
+        .await
+        .unwrap();
+        assert_eq!(ratio, (21, 20));
+    }
+
+    #[tokio::test]
+    async fn discarding_outliers() {
+        let ratio = fetch_ratio(
+            Some(2),
+            vec![
+                MockClient::ratio(1, 1),
+                MockClient::ratio(1_000, 1),
+                MockClient::ratio(101, 100),
+                MockClient::ratio(99, 100),
+            ],
+        )
+        .await
+        .unwrap();
+        assert_eq!(ratio, (1, 1));
+
+        let err = fetch_ratio(
+            Some(2),
+            vec![MockClient::ratio(1, 1), MockClient::ratio(2, 1)],
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("non-outlier"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn discarding_failed_and_stale_quotes() {
+        let stale_timestamp = Utc::now() - chrono::Duration::hours(1);
+        let sources = vec![
+            MockClient::ratio(3, 2),
+            Arc::new(MockClient::Error) as Arc<dyn PriceAPIClient>,
+            Arc::new(MockClient::Hanging),
+            Arc::new(MockClient::Ratio(1, 1, stale_timestamp)),
+        ];
+        let ratio = fetch_ratio(Some(1), sources).await.unwrap();
+        assert_eq!(ratio, (3, 2));
+
+        let sources = vec![
+            MockClient::ratio(3, 2),
+            Arc::new(MockClient::Error) as Arc<dyn PriceAPIClient>,
+            Arc::new(MockClient::Hanging),
+            Arc::new(MockClient::Ratio(1, 1, stale_timestamp)),
+        ];
+        let err = fetch_ratio(None, sources).await.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("only 1 of 4 price sources returned valid quotes"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn invalid_config() {
+        let sources = named(vec![MockClient::ratio(1, 1)]);
+        AggregatingPriceAPIClient::new(&config(Some(2)), sources.clone()).unwrap_err();
+        AggregatingPriceAPIClient::new(&config(Some(0)), sources).unwrap_err();
+        AggregatingPriceAPIClient::new(&config(None), vec![]).unwrap_err();
+    }
+}
@@ -0,0 +1,220 @@
+use std::num::NonZeroU64;
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use chrono::DateTime;
+use zksync_config::configs::ExternalPriceApiClientConfig;
+use zksync_eth_client::clients::{DynClient, L1};
+use zksync_types::{base_token_ratio::BaseTokenAPIRatio, ethabi, Address, U256};
+
+use crate::{
+    utils::{call_l1_function, get_fraction, u256_to_f64},
+    PriceAPIClient,
+};
+
+/// Subset of the Chainlink `AggregatorV3Interface` ABI used by the client.
+const AGGREGATOR_ABI: &str = r#"[
+  {
+    "type": "function",
+    "name": "decimals",
+    "inputs": [],
+    "outputs": [{ "name": "", "type": "uint8" }],
+    "stateMutability": "view"
+  },
+  {
+    "type": "function",
+    "name": "latestRoundData",
+    "inputs": [],
+    "outputs": [
+      { "name": "roundId", "type": "uint80" },
+      { "name": "answer", "type": "int256" },
+      { "name": "startedAt", "type": "uint256" },
+      { "name": "updatedAt", "type": "uint256" },
+      { "name": "answeredInRound", "type": "uint80" }
+    ],
+    "stateMutability": "view"
+  }
+]"#;
+
+/// Price client reading the base token price from a Chainlink aggregator on L1. The aggregator must provide
+/// the price of the base token denominated in ETH (e.g., a `TOKEN / ETH` feed).
+///
+/// The returned ratio is timestamped with the time the latest round was updated, so that stale feeds can be detected.
+#[derive(Debug)]
+pub struct ChainlinkPriceAPIClient {
+    client: Box<DynClient<L1>>,
+    feed_address: Address,
+    aggregator_abi: ethabi::Contract,
+}
+
+impl ChainlinkPriceAPIClient {
+    pub fn new(
+        config: ExternalPriceApiClientConfig,
+        client: Box<DynClient<L1>>,
+    ) -> anyhow::Result<Self> {
+        let feed_address = config
+            .chainlink_feed_address
+            .context("Chainlink price client started with no feed address")?;
+        Ok(Self {
+            client: client.for_component("chainlink_price_client"),
+            feed_address,
+            aggregator_abi: ethabi::Contract::load(AGGREGATOR_ABI.as_bytes())
+                .expect("invalid Chainlink aggregator ABI"),
+        })
+    }
+
+    async fn call(&self, function_name: &str) -> anyhow::Result<Vec<ethabi::Token>> {
+        let function = self.aggregator_abi.function(function_name)?;
+        call_l1_function(self.client.as_ref(), self.feed_address, function, &[]).await
+    }
+}
+
+#[async_trait]
+impl PriceAPIClient for ChainlinkPriceAPIClient {
+    async fn fetch_ratio(&self, _token_address: Address) -> anyhow::Result<BaseTokenAPIRatio> {
+        let decimals = match self.call("decimals").await?.as_slice() {
+            [ethabi::Token::Uint(decimals)] => decimals.as_u32(),
+            tokens => anyhow::bail!("unexpected `decimals` output: {tokens:?}"),
+        };
+        let (answer, updated_at) = match self.call("latestRoundData").await?.as_slice() {
+            [_, ethabi::Token::Int(answer), _, ethabi::Token::Uint(updated_at), _] => {
+                (*answer, *updated_at)
+            }
+            tokens => anyhow::bail!("unexpected `latestRoundData` output: {tokens:?}"),
+        };
+        // `answer` is a two's complement signed integer.
+        anyhow::ensure!(
+            !answer.is_zero() && !answer.bit(255),
+            "Chainlink feed {:?} returned non-positive answer",
+            self.feed_address
+        );
+        let ratio_timestamp = i64::try_from(updated_at)
+            .ok()
+            .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0))
+            .with_context(|| format!("invalid round update timestamp: {updated_at}"))?;
+
+        // Use the exact ratio if it fits into `u64`s, and an approximation otherwise.
+        let exact_ratio = U256::from(10)
+            .checked_pow(decimals.into())
+            .filter(|_| answer <= U256::from(u64::MAX))
+            .filter(|denominator| *denominator <= U256::from(u64::MAX));
+        let (numerator, denominator) = if let Some(denominator) = exact_ratio {
+            (
+                NonZeroU64::new(answer.as_u64()).unwrap(), // `answer` is checked to be positive above
+                NonZeroU64::new(denominator.as_u64()).unwrap(),
+            )
+        } else {
+            get_fraction(u256_to_f64(answer) / 10.0_f64.powi(decimals as i32))
+        };
+
+        Ok(BaseTokenAPIRatio {
+            numerator,
+            denominator,
+            ratio_timestamp,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use zksync_types::web3;
+    use zksync_web3_decl::client::MockClient;
+
+    use super::*;
+
+    const FEED_ADDRESS: Address = Address::repeat_byte(1);
+
+    fn mock_l1_client(answer: U256, updated_at: u64) -> Box<DynClient<L1>> {
+        let abi = ethabi::Contract::load(AGGREGATOR_ABI.as_bytes()).unwrap();
+        let decimals_selector = abi.function("decimals").unwrap().short_signature();
+        let round_data_selector = abi.function("latestRoundData").unwrap().short_signature();
+
+        let client = MockClient::builder(L1::default())
+            .method(
+                "eth_call",
+                move |req: web3::CallRequest, _block: web3::BlockId| {
+                    assert_eq!(req.to, Some(FEED_ADDRESS));
+                    let selector = &req.data.as_ref().unwrap().0[..4];
+                    let output = if selector == decimals_selector {
+                        vec![ethabi::Token::Uint(18.into())]
+                    } else if selector == round_data_selector {
+                        vec![
+                            ethabi::Token::Uint(1.into()),
+                            ethabi::Token::Int(answer),
+                            ethabi::Token::Uint(updated_at.into()),
+                            ethabi::Token::Uint(updated_at.into()),
+                            ethabi::Token::Uint(1.into()),
+                        ]
+                    } else {
+                        panic!("unexpected call: {req:?}");
+                    };
+                    Ok(web3::Bytes(ethabi::encode(&output)))
+                },
+            )
+            .build();
+        Box::new(client)
+    }
+
+    fn config() -> ExternalPriceApiClientConfig {
+        ExternalPriceApiClientConfig {
+            source: "chainlink".to_owned(),
+            base_url: None,
+            api_key: None,
+            client_timeout_ms: 1_000,
+            forced_numerator: None,
+            forced_denominator: None,
+            uniswap_pool_address: None,
+            uniswap_twap_window_secs: None,
+            chainlink_feed_address: Some(FEED_ADDRESS),
+            aggregated_sources: vec![],
+            min_sources: None,
+            max_quote_age_ms: None,
+            max_deviation_percent: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn fetching_ratio() {
+        let updated_at = Utc::now().timestamp() as u64 - 60;
+        // 0.0005 ETH with 18 decimals
+        let answer = U256::from(500_000_000_000_000_u64);
+        let client =
+            ChainlinkPriceAPIClient::new(config(), mock_l1_client(answer, updated_at)).unwrap();
+
+        let ratio = client.fetch_ratio(Address::zero()).await.unwrap();
+        assert_eq!(ratio.numerator.get(), 500_000_000_000_000);
+        assert_eq!(ratio.denominator.get(), 1_000_000_000_000_000_000);
+        assert_eq!(ratio.ratio_timestamp.timestamp() as u64, updated_at);
+    }
+
+    #[tokio::test]
+    async fn fetching_ratio_with_large_answer() {
+        // 100 ETH with 18 decimals
+        let answer = U256::exp10(20);
+        let client =
+            ChainlinkPriceAPIClient::new(config(), mock_l1_client(answer, 1_700_000_000)).unwrap();
+
+        let ratio = client.fetch_ratio(Address::zero()).await.unwrap();
+        assert_eq!(ratio.numerator.get(), 100);
+        assert_eq!(ratio.denominator.get(), 1);
+    }
+
+    #[tokio::test]
+    async fn rejecting_negative_answer() {
+        let answer = U256::MAX; // -1 in two's complement
+        let client =
+            ChainlinkPriceAPIClient::new(config(), mock_l1_client(answer, 1_700_000_000)).unwrap();
+
+        let err = client.fetch_ratio(Address::zero()).await.unwrap_err();
+        assert!(err.to_string().contains("non-positive"), "{err}");
+    }
+
+    #[test]
+    fn creating_client_without_feed_address() {
+        let mut config = config();
+        config.chainlink_feed_address = None;
+        let client = Box::new(MockClient::builder(L1::default()).build());
+        ChainlinkPriceAPIClient::new(config, client).unwrap_err();
+    }
+}
@@ -0,0 +1,276 @@
+use std::{collections::HashMap, sync::RwLock};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{de::DeserializeOwned, Deserialize};
+use url::Url;
+use zksync_config::configs::ExternalPriceApiClientConfig;
+use zksync_types::{base_token_ratio::BaseTokenAPIRatio, Address};
+
+use crate::{address_to_string, utils::get_fraction, PriceAPIClient};
+
+const DEFAULT_CMC_API_URL: &str = "https://pro-api.coinmarketcap.com";
+const CMC_AUTH_HEADER: &str = "x-cmc_pro_api_key";
+const ETH_SYMBOL: &str = "ETH";
+
+/// Price client using the CoinMarketCap REST API. CoinMarketCap identifies tokens by internal IDs, which are resolved
+/// from token addresses on the first request and cached afterwards.
+#[derive(Debug)]
+pub struct CmcPriceAPIClient {
+    base_url: Url,
+    client: reqwest::Client,
+    token_ids: RwLock<HashMap<Address, u64>>,
+}
+
+impl CmcPriceAPIClient {
+    pub fn new(config: ExternalPriceApiClientConfig) -> anyhow::Result<Self> {
+        let mut headers = reqwest::header::HeaderMap::new();
+        if let Some(api_key) = &config.api_key {
+            let api_key = reqwest::header::HeaderValue::from_str(api_key)
+                .context("invalid CoinMarketCap API key")?;
+            headers.insert(CMC_AUTH_HEADER, api_key);
+        }
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .timeout(config.client_timeout())
+            .build()
+            .context("failed building HTTP client")?;
+
+        let base_url = config.base_url.as_deref().unwrap_or(DEFAULT_CMC_API_URL);
+        Ok(Self {
+            base_url: Url::parse(base_url).context("failed parsing CoinMarketCap URL")?,
+            client,
+            token_ids: RwLock::default(),
+        })
+    }
+
+    async fn get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> anyhow::Result<T> {
+        let url = self.base_url.join(path).expect("failed to join URL path");
+        let response = self.client.get(url).query(query).send().await?;
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "Http error while calling CoinMarketCap API. Status: {}, path: {path}, msg: {}",
+                response.status(),
+                response.text().await.unwrap_or_default()
+            );
+        }
+        let response = response.json::<CmcResponse<T>>().await?;
+        Ok(response.data)
+    }
+
+    async fn get_token_id(&self, address: Address) -> anyhow::Result<u64> {
+        if let Some(&id) = self.token_ids.read().unwrap().get(&address) {
+            return Ok(id);
+        }
+
+        let address_str = address_to_string(&address);
+        let tokens: HashMap<String, CmcTokenInfo> = self
+            .get("/v2/cryptocurrency/info", &[("address", &address_str)])
+            .await?;
+        let mut tokens = tokens.into_values();
+        let (Some(token), None) = (tokens.next(), tokens.next()) else {
+            anyhow::bail!("CoinMarketCap returned no or ambiguous info for token {address_str}");
+        };
+        self.token_ids.write().unwrap().insert(address, token.id);
+        Ok(token.id)
+    }
+
+    async fn get_token_price(&self, token_id: u64) -> anyhow::Result<CmcQuote> {
+        let token_id_str = token_id.to_string();
+        let mut quotes: HashMap<String, CmcTokenQuotes> = self
+            .get(
+                "/v2/cryptocurrency/quotes/latest",
+                &[("id", &token_id_str), ("convert", ETH_SYMBOL)],
+            )
+            .await?;
+        quotes
+            .remove(&token_id_str)
+            .and_then(|mut token| token.quote.remove(ETH_SYMBOL))
+            .with_context(|| format!("Price not found for CoinMarketCap token {token_id}"))
+    }
+}
+
+#[async_trait]
+impl PriceAPIClient for CmcPriceAPIClient {
+    async fn fetch_ratio(&self, token_address: Address) -> anyhow::Result<BaseTokenAPIRatio> {
+        let token_id = self.get_token_id(token_address).await?;
+        let quote = self.get_token_price(token_id).await?;
+        anyhow::ensure!(
+            quote.price.is_normal() && quote.price > 0.0,
+            "CoinMarketCap returned invalid price for token {token_id}: {}",
+            quote.price
+        );
+        let (numerator, denominator) = get_fraction(quote.price);
+
+        Ok(BaseTokenAPIRatio {
+            numerator,
+            denominator,
+            ratio_timestamp: quote.last_updated,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CmcResponse<T> {
+    data: T,
+}
+
+#[derive(Debug, Deserialize)]
+struct CmcTokenInfo {
+    id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct CmcTokenQuotes {
+    quote: HashMap<String, CmcQuote>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CmcQuote {
+    price: f64,
+    last_updated: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::SocketAddr,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    use axum::{
+        extract::{Query, State},
+        http::{HeaderMap, StatusCode},
+        routing::get,
+        Json, Router,
+    };
+    use serde_json::json;
+
+    use super::*;
+
+    const TOKEN_ADDRESS: Address = Address::repeat_byte(0xab);
+    const TOKEN_ID: u64 = 4_242;
+    const API_KEY: &str = "test-key";
+
+    #[derive(Debug, Default)]
+    struct MockState {
+        info_requests: AtomicUsize,
+        quote_requests: AtomicUsize,
+    }
+
+    async fn info_handler(
+        State(state): State<Arc<MockState>>,
+        headers: HeaderMap,
+        Query(query): Query<HashMap<String, String>>,
+    ) -> Result<Json<serde_json::Value>, StatusCode> {
+        if headers.get(CMC_AUTH_HEADER).map(|value| value.as_bytes()) != Some(API_KEY.as_bytes()) {
+            return Err(StatusCode::UNAUTHORIZED);
+        }
+        state.info_requests.fetch_add(1, Ordering::SeqCst);
+        let data = if query["address"] == address_to_string(&TOKEN_ADDRESS) {
+            json!({ TOKEN_ID.to_string(): { "id": TOKEN_ID, "symbol": "TST" } })
+        } else {
+            json!({})
+        };
+        Ok(Json(json!({ "status": { "error_code": 0 }, "data": data })))
+    }
+
+    async fn quotes_handler(
+        State(state): State<Arc<MockState>>,
+        Query(query): Query<HashMap<String, String>>,
+    ) -> Result<Json<serde_json::Value>, StatusCode> {
+        state.quote_requests.fetch_add(1, Ordering::SeqCst);
+        if query["id"] != TOKEN_ID.to_string() || query["convert"] != ETH_SYMBOL {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        Ok(Json(json!({
+            "status": { "error_code": 0 },
+            "data": {
+                TOKEN_ID.to_string(): {
+                    "id": TOKEN_ID,
+                    "quote": {
+                        "ETH": { "price": 0.25, "last_updated": "2024-06-01T12:00:00.000Z" }
+                    }
+                }
+            }
+        })))
+    }
+
+    async fn spawn_mock_server(state: Arc<MockState>) -> SocketAddr {
+        let app = Router::new()
+            .route("/v2/cryptocurrency/info", get(info_handler))
+            .route("/v2/cryptocurrency/quotes/latest", get(quotes_handler))
+            .with_state(state);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        local_addr
+    }
+
+    fn config(local_addr: SocketAddr, api_key: &str) -> ExternalPriceApiClientConfig {
+        ExternalPriceApiClientConfig {
+            source: "coinmarketcap".to_owned(),
+            base_url: Some(format!("http://{local_addr}")),
+            api_key: Some(api_key.to_owned()),
+            client_timeout_ms: 5_000,
+            forced_numerator: None,
+            forced_denominator: None,
+            uniswap_pool_address: None,
+            uniswap_twap_window_secs: None,
+            chainlink_feed_address: None,
+            aggregated_sources: vec![],
+            min_sources: None,
+            max_quote_age_ms: None,
+            max_deviation_percent: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn fetching_ratio() {
+        let state = Arc::<MockState>::default();
+        let local_addr = spawn_mock_server(state.clone()).await;
+        let client = CmcPriceAPIClient::new(config(local_addr, API_KEY)).unwrap();
+
+        for _ in 0..2 {
+            let ratio = client.fetch_ratio(TOKEN_ADDRESS).await.unwrap();
+            assert_eq!((ratio.numerator.get(), ratio.denominator.get()), (1, 4));
+            let expected_timestamp: DateTime<Utc> = "2024-06-01T12:00:00Z".parse().unwrap();
+            assert_eq!(ratio.ratio_timestamp, expected_timestamp);
+        }
+        // Token ID should be cached.
+        assert_eq!(state.info_requests.load(Ordering::SeqCst), 1);
+        assert_eq!(state.quote_requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn fetching_ratio_for_unknown_token() {
+        let state = Arc::<MockState>::default();
+        let local_addr = spawn_mock_server(state.clone()).await;
+        let client = CmcPriceAPIClient::new(config(local_addr, API_KEY)).unwrap();
+
+        let err = client
+            .fetch_ratio(Address::repeat_byte(1))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("no or ambiguous info"), "{err}");
+        assert_eq!(state.quote_requests.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn fetching_ratio_with_invalid_api_key() {
+        let state = Arc::<MockState>::default();
+        let local_addr = spawn_mock_server(state).await;
+        let client = CmcPriceAPIClient::new(config(local_addr, "wrong-key")).unwrap();
+
+        let err = client.fetch_ratio(TOKEN_ADDRESS).await.unwrap_err();
+        assert!(err.to_string().contains("401"), "{err}");
+    }
+}
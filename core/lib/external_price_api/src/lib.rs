@@ -1,5 +1,10 @@
+pub mod aggregating_client;
+pub mod chainlink;
+pub mod cmc_api;
 pub mod coingecko_api;
 pub mod forced_price_client;
+mod metrics;
+pub mod uniswap_twap;
 mod utils;
 
 use std::fmt;
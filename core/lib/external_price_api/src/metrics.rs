@@ -0,0 +1,30 @@
+//! Metrics for price API clients.
+
+use vise::{Counter, EncodeLabelValue, Gauge, LabeledFamily, Metrics};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+pub(crate) enum QuoteOutcome {
+    /// Source returned an error or timed out.
+    Error,
+    /// Quote was older than the configured max age.
+    Stale,
+    /// Quote deviated from the median by more than the configured threshold.
+    Outlier,
+    /// Quote was used to compute the aggregated price.
+    Accepted,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "external_price_api")]
+pub(crate) struct ExternalPriceApiMetrics {
+    /// Number of quotes requested from each source by the aggregating client, grouped by their outcome.
+    #[metrics(labels = ["source", "outcome"])]
+    pub quotes: LabeledFamily<(String, QuoteOutcome), Counter, 2>,
+    /// Relative deviation of the latest quote returned by each source from the median of all quotes, in percent.
+    #[metrics(labels = ["source"])]
+    pub deviation_percent: LabeledFamily<String, Gauge<f64>>,
+}
+
+#[vise::register]
+pub(crate) static METRICS: vise::Global<ExternalPriceApiMetrics> = vise::Global::new();
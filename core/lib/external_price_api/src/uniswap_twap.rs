@@ -0,0 +1,306 @@
+use anyhow::Context as _;
+use async_trait::async_trait;
+use chrono::Utc;
+use zksync_config::configs::ExternalPriceApiClientConfig;
+use zksync_eth_client::clients::{DynClient, L1};
+use zksync_types::{base_token_ratio::BaseTokenAPIRatio, ethabi, Address, U256};
+
+use crate::{
+    utils::{call_l1_function, get_fraction},
+    PriceAPIClient,
+};
+
+/// Subset of the Uniswap v3 pool and ERC-20 ABIs used by the client.
+const POOL_ABI: &str = r#"[
+  {
+    "type": "function",
+    "name": "token0",
+    "inputs": [],
+    "outputs": [{ "name": "", "type": "address" }],
+    "stateMutability": "view"
+  },
+  {
+    "type": "function",
+    "name": "token1",
+    "inputs": [],
+    "outputs": [{ "name": "", "type": "address" }],
+    "stateMutability": "view"
+  },
+  {
+    "type": "function",
+    "name": "observe",
+    "inputs": [{ "name": "secondsAgos", "type": "uint32[]" }],
+    "outputs": [
+      { "name": "tickCumulatives", "type": "int56[]" },
+      { "name": "secondsPerLiquidityCumulativeX128s", "type": "uint160[]" }
+    ],
+    "stateMutability": "view"
+  },
+  {
+    "type": "function",
+    "name": "decimals",
+    "inputs": [],
+    "outputs": [{ "name": "", "type": "uint8" }],
+    "stateMutability": "view"
+  }
+]"#;
+
+/// Price client computing the time-weighted average price (TWAP) of the base token from a Uniswap v3 pool on L1.
+/// The pool must pair the base token with WETH (or another token pegged to ETH).
+#[derive(Debug)]
+pub struct UniswapTwapPriceAPIClient {
+    client: Box<DynClient<L1>>,
+    pool_address: Address,
+    twap_window_secs: u32,
+    abi: ethabi::Contract,
+}
+
+impl UniswapTwapPriceAPIClient {
+    pub fn new(
+        config: ExternalPriceApiClientConfig,
+        client: Box<DynClient<L1>>,
+    ) -> anyhow::Result<Self> {
+        let pool_address = config
+            .uniswap_pool_address
+            .context("Uniswap TWAP price client started with no pool address")?;
+        let twap_window_secs = u32::try_from(config.uniswap_twap_window().as_secs())
+            .context("Uniswap TWAP window is too large")?;
+        anyhow::ensure!(twap_window_secs > 0, "Uniswap TWAP window must be positive");
+
+        Ok(Self {
+            client: client.for_component("uniswap_twap_price_client"),
+            pool_address,
+            twap_window_secs,
+            abi: ethabi::Contract::load(POOL_ABI.as_bytes()).expect("invalid Uniswap pool ABI"),
+        })
+    }
+
+    async fn call(
+        &self,
+        contract_address: Address,
+        function_name: &str,
+        params: &[ethabi::Token],
+    ) -> anyhow::Result<Vec<ethabi::Token>> {
+        let function = self.abi.function(function_name)?;
+        call_l1_function(self.client.as_ref(), contract_address, function, params).await
+    }
+
+    async fn pool_token(&self, function_name: &str) -> anyhow::Result<Address> {
+        match self
+            .call(self.pool_address, function_name, &[])
+            .await?
+            .as_slice()
+        {
+            [ethabi::Token::Address(address)] => Ok(*address),
+            tokens => anyhow::bail!("unexpected `{function_name}` output: {tokens:?}"),
+        }
+    }
+
+    async fn token_decimals(&self, token_address: Address) -> anyhow::Result<i32> {
+        match self.call(token_address, "decimals", &[]).await?.as_slice() {
+            [ethabi::Token::Uint(decimals)] if *decimals <= U256::from(u8::MAX) => {
+                Ok(decimals.as_u32() as i32)
+            }
+            tokens => {
+                anyhow::bail!("unexpected `decimals` output for {token_address:?}: {tokens:?}")
+            }
+        }
+    }
+
+    /// Returns the average tick of the pool over the TWAP window.
+    async fn average_tick(&self) -> anyhow::Result<f64> {
+        let seconds_agos = ethabi::Token::Array(vec![
+            ethabi::Token::Uint(self.twap_window_secs.into()),
+            ethabi::Token::Uint(0.into()),
+        ]);
+        let tokens = self
+            .call(self.pool_address, "observe", &[seconds_agos])
+            .await?;
+        let tick_cumulatives = match tokens.as_slice() {
+            [ethabi::Token::Array(tick_cumulatives), _] => tick_cumulatives,
+            _ => anyhow::bail!("unexpected `observe` output: {tokens:?}"),
+        };
+        let [ethabi::Token::Int(start), ethabi::Token::Int(end)] = tick_cumulatives.as_slice()
+        else {
+            anyhow::bail!("unexpected tick cumulatives: {tick_cumulatives:?}");
+        };
+        let tick_delta = int56_to_i64(*end) - int56_to_i64(*start);
+        Ok(tick_delta as f64 / f64::from(self.twap_window_secs))
+    }
+}
+
+/// Converts a sign-extended two's complement `int56` value to `i64`.
+fn int56_to_i64(value: U256) -> i64 {
+    value.low_u64() as i64
+}
+
+#[async_trait]
+impl PriceAPIClient for UniswapTwapPriceAPIClient {
+    async fn fetch_ratio(&self, token_address: Address) -> anyhow::Result<BaseTokenAPIRatio> {
+        let token0 = self.pool_token("token0").await?;
+        let token1 = self.pool_token("token1").await?;
+        let (base_is_token0, quote_token) = if token0 == token_address {
+            (true, token1)
+        } else if token1 == token_address {
+            (false, token0)
+        } else {
+            anyhow::bail!(
+                "Uniswap pool {:?} doesn't contain token {token_address:?}",
+                self.pool_address
+            );
+        };
+        let base_decimals = self.token_decimals(token_address).await?;
+        let quote_decimals = self.token_decimals(quote_token).await?;
+
+        // The pool price is the amount of `token1` per `token0` in the smallest units.
+        let pool_price = 1.0001_f64.powf(self.average_tick().await?);
+        let price_in_smallest_units = if base_is_token0 {
+            pool_price
+        } else {
+            pool_price.recip()
+        };
+        let price = price_in_smallest_units * 10.0_f64.powi(base_decimals - quote_decimals);
+        anyhow::ensure!(
+            price.is_normal(),
+            "Uniswap TWAP for token {token_address:?} is not representable: {price}"
+        );
+        let (numerator, denominator) = get_fraction(price);
+
+        Ok(BaseTokenAPIRatio {
+            numerator,
+            denominator,
+            ratio_timestamp: Utc::now(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::web3;
+    use zksync_web3_decl::client::MockClient;
+
+    use super::*;
+
+    const POOL_ADDRESS: Address = Address::repeat_byte(1);
+    const BASE_TOKEN: Address = Address::repeat_byte(2);
+    const WETH: Address = Address::repeat_byte(3);
+    const TWAP_WINDOW_SECS: u32 = 600;
+
+    fn int_token(value: i64) -> ethabi::Token {
+        let value = if value < 0 {
+            !U256::from(value.unsigned_abs()) + 1
+        } else {
+            U256::from(value)
+        };
+        ethabi::Token::Int(value)
+    }
+
+    fn mock_l1_client(
+        tokens: (Address, Address),
+        base_decimals: u8,
+        tick_cumulatives: (i64, i64),
+    ) -> Box<DynClient<L1>> {
+        let abi = ethabi::Contract::load(POOL_ABI.as_bytes()).unwrap();
+        let client = MockClient::builder(L1::default())
+            .method(
+                "eth_call",
+                move |req: web3::CallRequest, _block: web3::BlockId| {
+                    let data = &req.data.as_ref().unwrap().0;
+                    let (function, params) = abi
+                        .functions()
+                        .find_map(|function| {
+                            let params = data.strip_prefix(&function.short_signature())?;
+                            Some((function, params))
+                        })
+                        .unwrap_or_else(|| panic!("unexpected call: {req:?}"));
+
+                    let to = req.to.unwrap();
+                    let output = match function.name.as_str() {
+                        "token0" if to == POOL_ADDRESS => vec![ethabi::Token::Address(tokens.0)],
+                        "token1" if to == POOL_ADDRESS => vec![ethabi::Token::Address(tokens.1)],
+                        "decimals" if to == BASE_TOKEN => {
+                            vec![ethabi::Token::Uint(base_decimals.into())]
+                        }
+                        "decimals" if to == WETH => vec![ethabi::Token::Uint(18.into())],
+                        "observe" if to == POOL_ADDRESS => {
+                            let params = function.decode_input(params).unwrap();
+                            let expected_seconds_agos = ethabi::Token::Array(vec![
+                                ethabi::Token::Uint(TWAP_WINDOW_SECS.into()),
+                                ethabi::Token::Uint(0.into()),
+                            ]);
+                            assert_eq!(params, [expected_seconds_agos]);
+                            vec![
+                                ethabi::Token::Array(vec![
+                                    int_token(tick_cumulatives.0),
+                                    int_token(tick_cumulatives.1),
+                                ]),
+                                ethabi::Token::Array(vec![
+                                    ethabi::Token::Uint(0.into()),
+                                    ethabi::Token::Uint(0.into()),
+                                ]),
+                            ]
+                        }
+                        _ => panic!("unexpected call: {req:?}"),
+                    };
+                    Ok(web3::Bytes(ethabi::encode(&output)))
+                },
+            )
+            .build();
+        Box::new(client)
+    }
+
+    fn config() -> ExternalPriceApiClientConfig {
+        ExternalPriceApiClientConfig {
+            source: "uniswap_twap".to_owned(),
+            base_url: None,
+            api_key: None,
+            client_timeout_ms: 1_000,
+            forced_numerator: None,
+            forced_denominator: None,
+            uniswap_pool_address: Some(POOL_ADDRESS),
+            uniswap_twap_window_secs: Some(TWAP_WINDOW_SECS),
+            chainlink_feed_address: None,
+            aggregated_sources: vec![],
+            min_sources: None,
+            max_quote_age_ms: None,
+            max_deviation_percent: None,
+        }
+    }
+
+    fn ratio_as_f64(ratio: &BaseTokenAPIRatio) -> f64 {
+        ratio.numerator.get() as f64 / ratio.denominator.get() as f64
+    }
+
+    #[tokio::test]
+    async fn fetching_ratio_for_token0() {
+        // Average tick of -69_082 corresponds to the pool price ~0.001.
+        let tick_cumulatives = (1_000_000, 1_000_000 - 69_082 * i64::from(TWAP_WINDOW_SECS));
+        let l1_client = mock_l1_client((BASE_TOKEN, WETH), 18, tick_cumulatives);
+        let client = UniswapTwapPriceAPIClient::new(config(), l1_client).unwrap();
+
+        let ratio = client.fetch_ratio(BASE_TOKEN).await.unwrap();
+        let price = ratio_as_f64(&ratio);
+        assert!((price - 0.001).abs() < 1e-6, "{price}");
+    }
+
+    #[tokio::test]
+    async fn fetching_ratio_for_token1_with_different_decimals() {
+        // Average tick of -207_243 corresponds to the pool price ~1e-9 (i.e., 0.001 ETH per 1 token with 6 decimals).
+        let tick_cumulatives = (-5_000, -5_000 - 207_243 * i64::from(TWAP_WINDOW_SECS));
+        let l1_client = mock_l1_client((WETH, BASE_TOKEN), 6, tick_cumulatives);
+        let client = UniswapTwapPriceAPIClient::new(config(), l1_client).unwrap();
+
+        let ratio = client.fetch_ratio(BASE_TOKEN).await.unwrap();
+        let price = ratio_as_f64(&ratio);
+        assert!((price - 0.001).abs() < 1e-6, "{price}");
+    }
+
+    #[tokio::test]
+    async fn fetching_ratio_for_unknown_token() {
+        let l1_client = mock_l1_client((WETH, Address::repeat_byte(0xff)), 18, (0, 0));
+        let client = UniswapTwapPriceAPIClient::new(config(), l1_client).unwrap();
+
+        let err = client.fetch_ratio(BASE_TOKEN).await.unwrap_err();
+        assert!(err.to_string().contains("doesn't contain token"), "{err}");
+    }
+}
@@ -1,6 +1,12 @@
 use std::num::NonZeroU64;
 
+use anyhow::Context as _;
 use fraction::Fraction;
+use zksync_eth_client::{
+    clients::{DynClient, L1},
+    EthInterface,
+};
+use zksync_types::{ethabi, web3, Address, U256};
 
 /// Using the base token price and eth price, calculate the fraction of the base token to eth.
 pub fn get_fraction(ratio_f64: f64) -> (NonZeroU64, NonZeroU64) {
@@ -13,3 +19,36 @@ pub fn get_fraction(ratio_f64: f64) -> (NonZeroU64, NonZeroU64) {
 
     (numerator, denominator)
 }
+
+/// Calls a view function of an L1 contract at the latest block and decodes its outputs.
+pub(crate) async fn call_l1_function(
+    client: &DynClient<L1>,
+    contract_address: Address,
+    function: &ethabi::Function,
+    params: &[ethabi::Token],
+) -> anyhow::Result<Vec<ethabi::Token>> {
+    let input = function
+        .encode_input(params)
+        .with_context(|| format!("failed encoding input for `{}`", function.signature()))?;
+    let request = web3::CallRequest {
+        to: Some(contract_address),
+        data: Some(web3::Bytes(input)),
+        ..web3::CallRequest::default()
+    };
+    let output = client
+        .call_contract_function(request, None)
+        .await
+        .with_context(|| format!("failed calling `{}` on {contract_address:?}", function.name))?;
+    function
+        .decode_output(&output.0)
+        .with_context(|| format!("failed decoding output of `{}`", function.signature()))
+}
+
+/// Converts a value to `f64`, possibly losing precision.
+pub(crate) fn u256_to_f64(value: U256) -> f64 {
+    value
+        .0
+        .iter()
+        .rev()
+        .fold(0.0, |acc, &limb| acc * 2.0_f64.powi(64) + limb as f64)
+}
@@ -1,9 +1,11 @@
 mod mempool_store;
+mod ordering;
 #[cfg(test)]
 mod tests;
 mod types;
 
 pub use crate::{
     mempool_store::{MempoolInfo, MempoolStats, MempoolStore},
-    types::L2TxFilter,
+    ordering::{FairShareOrdering, FifoOrdering, HighestFeeFirstOrdering, MempoolOrderingPolicy},
+    types::{AccountTxLimits, L2TxFilter, L2TxRejection},
 };
@@ -4,7 +4,10 @@ use zksync_types::{
     l1::L1Tx, l2::L2Tx, Address, ExecuteTransactionCommon, Nonce, PriorityOpId, Transaction,
 };
 
-use crate::types::{AccountTransactions, L2TxFilter, MempoolScore};
+use crate::{
+    ordering::{FifoOrdering, MempoolOrderingPolicy},
+    types::{AccountTransactions, L2TxFilter, MempoolScore},
+};
 
 #[derive(Debug)]
 pub struct MempoolInfo {
@@ -33,6 +36,7 @@ pub struct MempoolStore {
     /// Number of L2 transactions in the mempool.
     size: u64,
     capacity: u64,
+    ordering_policy: Box<dyn MempoolOrderingPolicy>,
 }
 
 impl MempoolStore {
@@ -45,9 +49,17 @@ impl MempoolStore {
             stashed_accounts: vec![],
             size: 0,
             capacity,
+            ordering_policy: Box::new(FifoOrdering),
         }
     }
 
+    /// Sets the policy used to order L2 transactions from different accounts. By default, [`FifoOrdering`] is used.
+    #[must_use]
+    pub fn with_ordering_policy(mut self, policy: Box<dyn MempoolOrderingPolicy>) -> Self {
+        self.ordering_policy = policy;
+        self
+    }
+
     /// Inserts batch of new transactions to mempool
     /// `initial_nonces` provides current committed nonce information to mempool
     /// variable is used only if account is not present in mempool yet and we have to bootstrap it
@@ -103,12 +115,14 @@ impl MempoolStore {
         let account = transaction.initiator_account();
 
         let metadata = match self.l2_transactions_per_account.entry(account) {
-            hash_map::Entry::Occupied(mut txs) => txs.get_mut().insert(transaction),
+            hash_map::Entry::Occupied(mut txs) => txs
+                .get_mut()
+                .insert(transaction, self.ordering_policy.as_ref()),
             hash_map::Entry::Vacant(entry) => {
                 let account_nonce = initial_nonces.get(&account).cloned().unwrap_or(Nonce(0));
                 entry
                     .insert(AccountTransactions::new(account_nonce))
-                    .insert(transaction)
+                    .insert(transaction, self.ordering_policy.as_ref())
             }
         };
         if let Some(score) = metadata.previous_score {
@@ -163,11 +177,13 @@ impl MempoolStore {
             self.stashed_accounts.push(stashed_pointer.account);
         }
         // insert pointer to the next transaction if it exists
+        self.ordering_policy
+            .on_transaction_taken(tx_pointer.account);
         let (transaction, score) = self
             .l2_transactions_per_account
             .get_mut(&tx_pointer.account)
             .expect("mempool: dangling pointer in priority queue")
-            .next();
+            .next(self.ordering_policy.as_ref());
 
         if let Some(score) = score {
             self.l2_priority_queue.insert(score);
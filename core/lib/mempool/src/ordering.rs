@@ -0,0 +1,88 @@
+//! Ordering policies for L2 transactions in the mempool.
+
+use std::{collections::HashMap, fmt};
+
+use zksync_types::{l2::L2Tx, Address, U256};
+
+/// Policy determining the order in which L2 transactions from different accounts are taken from the mempool.
+///
+/// The policy only orders transactions *across* accounts; transactions of a single account are always
+/// taken in the nonce order. The policy is queried when the next transaction of an account becomes executable,
+/// so priorities assigned by a stateful policy may become stale for accounts waiting in the mempool.
+pub trait MempoolOrderingPolicy: fmt::Debug + Send + 'static {
+    /// Returns priority of the transaction. Transactions with greater priority are taken first;
+    /// transactions with equal priority are ordered by the received timestamp.
+    fn priority(&self, transaction: &L2Tx) -> U256;
+
+    /// Notifies the policy that a transaction initiated by `account` was taken from the mempool.
+    fn on_transaction_taken(&mut self, _account: Address) {}
+}
+
+/// Orders transactions by the received timestamp only (first in, first out).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FifoOrdering;
+
+impl MempoolOrderingPolicy for FifoOrdering {
+    fn priority(&self, _transaction: &L2Tx) -> U256 {
+        U256::zero()
+    }
+}
+
+/// Takes transactions with the highest `max_fee_per_gas` first.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HighestFeeFirstOrdering;
+
+impl MempoolOrderingPolicy for HighestFeeFirstOrdering {
+    fn priority(&self, transaction: &L2Tx) -> U256 {
+        transaction.common_data.fee.max_fee_per_gas
+    }
+}
+
+/// Gives precedence to accounts that had the fewest transactions taken from the mempool recently,
+/// so that a single account cannot monopolize block space.
+///
+/// Transactions are counted in windows of a fixed size; counters are reset once the window is full.
+#[derive(Debug)]
+pub struct FairShareOrdering {
+    window_size: usize,
+    taken_in_window: usize,
+    taken_per_account: HashMap<Address, u64>,
+}
+
+impl Default for FairShareOrdering {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_WINDOW_SIZE)
+    }
+}
+
+impl FairShareOrdering {
+    /// Default number of transactions in a window.
+    pub const DEFAULT_WINDOW_SIZE: usize = 1_000;
+
+    /// Creates a policy with the specified number of transactions in a window.
+    pub fn new(window_size: usize) -> Self {
+        assert!(window_size > 0, "window size must be positive");
+        Self {
+            window_size,
+            taken_in_window: 0,
+            taken_per_account: HashMap::new(),
+        }
+    }
+}
+
+impl MempoolOrderingPolicy for FairShareOrdering {
+    fn priority(&self, transaction: &L2Tx) -> U256 {
+        let account = transaction.initiator_account();
+        let taken = self.taken_per_account.get(&account).copied().unwrap_or(0);
+        U256::MAX - taken
+    }
+
+    fn on_transaction_taken(&mut self, account: Address) {
+        *self.taken_per_account.entry(account).or_default() += 1;
+        self.taken_in_window += 1;
+        if self.taken_in_window >= self.window_size {
+            self.taken_in_window = 0;
+            self.taken_per_account.clear();
+        }
+    }
+}
@@ -12,7 +12,11 @@ use zksync_types::{
     H256, U256,
 };
 
-use crate::{mempool_store::MempoolStore, types::L2TxFilter};
+use crate::{
+    mempool_store::MempoolStore,
+    ordering::{FairShareOrdering, HighestFeeFirstOrdering},
+    types::{AccountTxLimits, L2TxFilter, L2TxRejection},
+};
 
 #[test]
 fn basic_flow() {
@@ -200,6 +204,144 @@ fn replace_tx() {
     assert!(mempool.next_transaction(&L2TxFilter::default()).is_none());
 }
 
+#[test]
+fn replacement_checks() {
+    let limits = AccountTxLimits {
+        min_replacement_fee_bump_percent: Some(10),
+        max_pending_txs_per_account: None,
+    };
+    let fee = |max_fee_per_gas: u64, gas_per_pubdata_limit: u64| Fee {
+        gas_limit: U256::zero(),
+        max_fee_per_gas: max_fee_per_gas.into(),
+        max_priority_fee_per_gas: U256::zero(),
+        gas_per_pubdata_limit: gas_per_pubdata_limit.into(),
+    };
+
+    limits
+        .check_replacement(&fee(100, 50), &fee(110, 50))
+        .unwrap();
+    limits
+        .check_replacement(&fee(100, 50), &fee(100, 55))
+        .unwrap();
+    limits
+        .check_replacement(&fee(100, 50), &fee(150, 100))
+        .unwrap();
+    let err = limits
+        .check_replacement(&fee(100, 50), &fee(109, 54))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        L2TxRejection::ReplacementUnderpriced {
+            required_max_fee_per_gas: 110.into(),
+            required_gas_per_pubdata_limit: 55.into(),
+        }
+    );
+    limits
+        .check_replacement(&fee(100, 50), &fee(1_000, 49))
+        .unwrap_err();
+
+    // Without a configured bump, any replacement is allowed.
+    AccountTxLimits::default()
+        .check_replacement(&fee(100, 50), &fee(1, 1))
+        .unwrap();
+}
+
+#[test]
+fn pending_count_checks() {
+    let limits = AccountTxLimits {
+        min_replacement_fee_bump_percent: None,
+        max_pending_txs_per_account: Some(2),
+    };
+    limits.check_pending_count(0).unwrap();
+    limits.check_pending_count(1).unwrap();
+    let err = limits.check_pending_count(2).unwrap_err();
+    assert_eq!(err, L2TxRejection::TooManyPendingTransactions { limit: 2 });
+    AccountTxLimits::default()
+        .check_pending_count(usize::MAX)
+        .unwrap();
+}
+
+#[test]
+fn highest_fee_first_ordering() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100)
+        .with_ordering_policy(Box::new(HighestFeeFirstOrdering));
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let account2 = Address::random();
+    let now = unix_timestamp_ms();
+    let transactions = vec![
+        with_timestamp(gen_l2_tx_with_fee(account0, Nonce(0), 100, 1), now),
+        with_timestamp(gen_l2_tx_with_fee(account0, Nonce(1), 300, 1), now),
+        with_timestamp(gen_l2_tx_with_fee(account1, Nonce(0), 200, 1), now + 10),
+        with_timestamp(gen_l2_tx_with_fee(account2, Nonce(0), 100, 1), now + 20),
+    ];
+    mempool.insert(transactions, HashMap::new());
+
+    let mut taken = vec![];
+    while let Some(tx) = mempool.next_transaction(&L2TxFilter::default()) {
+        taken.push(view(Some(tx)));
+    }
+    // The second transaction of `account0` cannot be taken before the first one.
+    assert_eq!(
+        taken,
+        [(account1, 0), (account0, 0), (account0, 1), (account2, 0)]
+    );
+}
+
+#[test]
+fn fair_share_ordering() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100)
+        .with_ordering_policy(Box::new(FairShareOrdering::default()));
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let now = unix_timestamp_ms();
+    let transactions = vec![
+        gen_l2_tx_with_timestamp(account0, Nonce(0), now),
+        gen_l2_tx_with_timestamp(account0, Nonce(1), now + 1),
+        gen_l2_tx_with_timestamp(account0, Nonce(2), now + 2),
+        gen_l2_tx_with_timestamp(account1, Nonce(0), now + 10),
+        gen_l2_tx_with_timestamp(account1, Nonce(1), now + 11),
+    ];
+    mempool.insert(transactions, HashMap::new());
+
+    let mut taken = vec![];
+    while let Some(tx) = mempool.next_transaction(&L2TxFilter::default()) {
+        taken.push(view(Some(tx)));
+    }
+    assert_eq!(
+        taken,
+        [
+            (account0, 0),
+            (account1, 0),
+            (account0, 1),
+            (account1, 1),
+            (account0, 2)
+        ]
+    );
+}
+
+#[test]
+fn fair_share_window() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100)
+        .with_ordering_policy(Box::new(FairShareOrdering::new(1)));
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let now = unix_timestamp_ms();
+    let transactions = vec![
+        gen_l2_tx_with_timestamp(account0, Nonce(0), now),
+        gen_l2_tx_with_timestamp(account0, Nonce(1), now + 1),
+        gen_l2_tx_with_timestamp(account1, Nonce(0), now + 10),
+    ];
+    mempool.insert(transactions, HashMap::new());
+
+    // Since counters are reset after each transaction, the policy is equivalent to FIFO.
+    let mut taken = vec![];
+    while let Some(tx) = mempool.next_transaction(&L2TxFilter::default()) {
+        taken.push(view(Some(tx)));
+    }
+    assert_eq!(taken, [(account0, 0), (account0, 1), (account1, 0)]);
+}
+
 #[test]
 fn two_ready_txs() {
     let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
@@ -384,6 +526,38 @@ fn gen_l2_tx_with_timestamp(address: Address, nonce: Nonce, received_at_ms: u64)
     txn.into()
 }
 
+fn gen_l2_tx_with_fee(
+    address: Address,
+    nonce: Nonce,
+    max_fee_per_gas: u64,
+    gas_per_pubdata_limit: u64,
+) -> Transaction {
+    let fee = Fee {
+        gas_limit: U256::zero(),
+        max_fee_per_gas: max_fee_per_gas.into(),
+        max_priority_fee_per_gas: U256::zero(),
+        gas_per_pubdata_limit: gas_per_pubdata_limit.into(),
+    };
+    let mut txn = L2Tx::new(
+        Address::default(),
+        Vec::new(),
+        nonce,
+        fee,
+        address,
+        U256::zero(),
+        vec![],
+        Default::default(),
+    );
+    // Make transaction hashes distinct, similarly to real transactions.
+    txn.set_input(vec![], H256::random());
+    txn.into()
+}
+
+fn with_timestamp(mut transaction: Transaction, received_at_ms: u64) -> Transaction {
+    transaction.received_timestamp_ms = received_at_ms;
+    transaction
+}
+
 fn gen_l1_tx(priority_id: PriorityOpId) -> Transaction {
     let execute = Execute {
         contract_address: Address::repeat_byte(0x11),
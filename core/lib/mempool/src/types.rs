@@ -1,9 +1,11 @@
-use std::{cmp::Ordering, collections::HashMap};
+use std::{cmp::Ordering, collections::HashMap, fmt};
 
 use zksync_types::{
     fee::Fee, fee_model::BatchFeeInput, l2::L2Tx, Address, Nonce, Transaction, U256,
 };
 
+use crate::ordering::MempoolOrderingPolicy;
+
 /// Pending mempool transactions of account
 #[derive(Debug)]
 pub(crate) struct AccountTransactions {
@@ -12,6 +14,8 @@ pub(crate) struct AccountTransactions {
     /// account nonce in mempool
     /// equals to committed nonce in db + number of transactions sent to state keeper
     nonce: Nonce,
+    /// score of the transaction with the account nonce (if any) inserted into the priority queue
+    queued_score: Option<MempoolScore>,
 }
 
 impl AccountTransactions {
@@ -19,43 +23,45 @@ impl AccountTransactions {
         Self {
             transactions: HashMap::new(),
             nonce,
+            queued_score: None,
         }
     }
 
     /// Inserts new transaction for given account. Returns insertion metadata
-    pub fn insert(&mut self, transaction: L2Tx) -> InsertionMetadata {
+    pub fn insert(
+        &mut self,
+        transaction: L2Tx,
+        policy: &dyn MempoolOrderingPolicy,
+    ) -> InsertionMetadata {
         let mut metadata = InsertionMetadata::default();
         let nonce = transaction.common_data.nonce;
         // skip insertion if transaction is old
         if nonce < self.nonce {
             return metadata;
         }
-        let new_score = Self::score_for_transaction(&transaction);
-        let previous_score = self
-            .transactions
-            .insert(nonce, transaction)
-            .map(|tx| Self::score_for_transaction(&tx));
-        metadata.is_new = previous_score.is_none();
-        if nonce == self.nonce {
+        let new_score =
+            (nonce == self.nonce).then(|| Self::score_for_transaction(&transaction, policy));
+        metadata.is_new = self.transactions.insert(nonce, transaction).is_none();
+        if let Some(new_score) = new_score {
+            metadata.previous_score = self.queued_score.replace(new_score.clone());
             metadata.new_score = Some(new_score);
-            metadata.previous_score = previous_score;
         }
         metadata
     }
 
     /// Returns next transaction to be included in block and optional score of its successor
     /// Panics if no such transaction exists
-    pub fn next(&mut self) -> (L2Tx, Option<MempoolScore>) {
+    pub fn next(&mut self, policy: &dyn MempoolOrderingPolicy) -> (L2Tx, Option<MempoolScore>) {
         let transaction = self
             .transactions
             .remove(&self.nonce)
             .expect("missing transaction in mempool");
         self.nonce += 1;
-        let score = self
+        self.queued_score = self
             .transactions
             .get(&self.nonce)
-            .map(Self::score_for_transaction);
-        (transaction, score)
+            .map(|tx| Self::score_for_transaction(tx, policy));
+        (transaction, self.queued_score.clone())
     }
 
     /// Handles transaction rejection. Returns optional score of its successor
@@ -65,17 +71,19 @@ impl AccountTransactions {
             .nonce()
             .expect("nonce is not set for L2 transaction");
         self.nonce = self.nonce.min(tx_nonce);
-        self.transactions
-            .get(&(tx_nonce + 1))
-            .map(Self::score_for_transaction)
+        self.queued_score.take()
     }
 
     pub fn len(&self) -> usize {
         self.transactions.len()
     }
 
-    fn score_for_transaction(transaction: &L2Tx) -> MempoolScore {
+    fn score_for_transaction(
+        transaction: &L2Tx,
+        policy: &dyn MempoolOrderingPolicy,
+    ) -> MempoolScore {
         MempoolScore {
+            priority: policy.priority(transaction),
             account: transaction.initiator_account(),
             received_at_ms: transaction.received_timestamp_ms,
             fee_data: transaction.common_data.fee.clone(),
@@ -83,10 +91,11 @@ impl AccountTransactions {
     }
 }
 
-/// Mempool score of transaction. Used to prioritize L2 transactions in mempool
-/// Currently trivial ordering is used based on received at timestamp
+/// Mempool score of transaction. Used to prioritize L2 transactions in mempool.
+/// Transactions are ordered by the priority assigned by [`MempoolOrderingPolicy`], then by received at timestamp.
 #[derive(Eq, PartialEq, Clone, Debug, Hash)]
 pub struct MempoolScore {
+    pub priority: U256,
     pub account: Address,
     pub received_at_ms: u64,
     // Not used for actual scoring, but state keeper would request
@@ -105,6 +114,10 @@ impl MempoolScore {
 
 impl Ord for MempoolScore {
     fn cmp(&self, other: &MempoolScore) -> Ordering {
+        match self.priority.cmp(&other.priority) {
+            Ordering::Equal => {}
+            ordering => return ordering,
+        }
         match self.received_at_ms.cmp(&other.received_at_ms).reverse() {
             Ordering::Equal => {}
             ordering => return ordering,
@@ -119,6 +132,92 @@ impl PartialOrd for MempoolScore {
     }
 }
 
+/// Limits applied to pending L2 transactions of a single initiator account.
+///
+/// The limits are enforced by the API server when inserting a transaction into the storage, atomically with the insertion.
+/// [`MempoolStore`](crate::MempoolStore) doesn't check them and mirrors the storage as is.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AccountTxLimits {
+    /// Minimum fee bump (in percent) required to replace a pending transaction with the same nonce.
+    /// A replacement must not decrease either `max_fee_per_gas` or `gas_per_pubdata_limit`, and must increase
+    /// at least one of them by this percentage. If not set, transactions are replaced unconditionally.
+    pub min_replacement_fee_bump_percent: Option<u64>,
+    /// Maximum number of pending transactions per initiator account. If not set, the number is not limited.
+    pub max_pending_txs_per_account: Option<usize>,
+}
+
+impl AccountTxLimits {
+    /// Checks whether a pending transaction with the `existing` fee can be replaced with a transaction
+    /// with the `replacement` fee.
+    pub fn check_replacement(
+        &self,
+        existing: &Fee,
+        replacement: &Fee,
+    ) -> Result<(), L2TxRejection> {
+        let Some(bump_percent) = self.min_replacement_fee_bump_percent else {
+            return Ok(());
+        };
+        let bumped = |value: U256| value + value * bump_percent / 100;
+        let required_max_fee_per_gas = bumped(existing.max_fee_per_gas);
+        let required_gas_per_pubdata_limit = bumped(existing.gas_per_pubdata_limit);
+
+        let no_decrease = replacement.max_fee_per_gas >= existing.max_fee_per_gas
+            && replacement.gas_per_pubdata_limit >= existing.gas_per_pubdata_limit;
+        let is_bumped = replacement.max_fee_per_gas >= required_max_fee_per_gas
+            || replacement.gas_per_pubdata_limit >= required_gas_per_pubdata_limit;
+        if no_decrease && is_bumped {
+            Ok(())
+        } else {
+            Err(L2TxRejection::ReplacementUnderpriced {
+                required_max_fee_per_gas,
+                required_gas_per_pubdata_limit,
+            })
+        }
+    }
+
+    /// Checks whether a new transaction can be added for an account with `pending_count` pending transactions.
+    pub fn check_pending_count(&self, pending_count: usize) -> Result<(), L2TxRejection> {
+        match self.max_pending_txs_per_account {
+            Some(limit) if pending_count >= limit => {
+                Err(L2TxRejection::TooManyPendingTransactions { limit })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Reason of an L2 transaction rejection caused by [`AccountTxLimits`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum L2TxRejection {
+    /// Transaction replaces a pending transaction with the same nonce, but doesn't bump fees enough.
+    ReplacementUnderpriced {
+        required_max_fee_per_gas: U256,
+        required_gas_per_pubdata_limit: U256,
+    },
+    /// Initiator account has too many pending transactions.
+    TooManyPendingTransactions { limit: usize },
+}
+
+impl fmt::Display for L2TxRejection {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ReplacementUnderpriced {
+                required_max_fee_per_gas,
+                required_gas_per_pubdata_limit,
+            } => write!(
+                formatter,
+                "replacement transaction underpriced; either max fee per gas must be at least \
+                 {required_max_fee_per_gas} or gas per pubdata limit must be at least \
+                 {required_gas_per_pubdata_limit}, without decreasing the other value"
+            ),
+            Self::TooManyPendingTransactions { limit } => write!(
+                formatter,
+                "too many pending transactions for the account; at most {limit} are allowed"
+            ),
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 pub(crate) struct InsertionMetadata {
     pub new_score: Option<MempoolScore>,
@@ -158,6 +257,7 @@ mod tests {
         const GAS_PER_PUBDATA_LIMIT: u32 = 100u32;
 
         let score = MempoolScore {
+            priority: U256::zero(),
             account: Address::random(),
             received_at_ms: Default::default(), // Not important
             fee_data: Fee {
@@ -1,15 +1,22 @@
-use std::{collections::HashMap, fmt, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::Arc,
+};
 
 use once_cell::sync::OnceCell;
 use zksync_state::{StoragePtr, WriteStorage};
 use zksync_types::{
-    get_code_key, get_nonce_key, web3::keccak256, AccountTreeId, Address, StorageKey, StorageValue,
-    H160, H256, L2_BASE_TOKEN_ADDRESS, U256,
+    get_code_key, get_nonce_key, web3::keccak256, AccountTreeId, Address, StorageKey,
+    ACCOUNT_CODE_STORAGE_ADDRESS, H256, L2_BASE_TOKEN_ADDRESS, NONCE_HOLDER_ADDRESS, U256,
 };
-use zksync_utils::{address_to_h256, h256_to_u256};
+use zksync_utils::{address_to_h256, h256_to_account_address, h256_to_u256, u256_to_h256};
+
+use crate::glue::tracers::IntoOldVmTracer;
 
 pub mod vm_1_4_1;
 pub mod vm_1_4_2;
+pub mod vm_boojum_integration;
 pub mod vm_latest;
 pub mod vm_refunds_enhancement;
 pub mod vm_virtual_blocks;
@@ -45,124 +52,197 @@ impl fmt::Display for Account {
     }
 }
 
-type State = HashMap<Address, Account>;
+pub type State = HashMap<Address, Account>;
 
+/// Tracer collecting the state of accounts touched during execution, similar to the Geth `prestateTracer`.
+///
+/// The result is a `(pre, post)` pair. Without the diff mode, both states contain all touched accounts.
+/// In the diff mode, only modified accounts are retained, and unchanged fields are removed from the post-state.
 #[derive(Debug, Clone)]
 pub struct PrestateTracer {
-    pub pre: State,
-    pub post: State,
     pub config: PrestateTracerConfig,
     pub result: Arc<OnceCell<(State, State)>>,
+    /// VM timestamp at the start of the traced execution. Storage accesses are collected starting from it.
+    start_timestamp: Option<u32>,
 }
 
 impl PrestateTracer {
     pub fn new(diff_mode: bool, result: Arc<OnceCell<(State, State)>>) -> Self {
         Self {
-            pre: Default::default(),
-            post: Default::default(),
             config: PrestateTracerConfig { diff_mode },
             result,
+            start_timestamp: None,
         }
     }
+
+    fn start_timestamp(&self) -> u32 {
+        self.start_timestamp.unwrap_or(0)
+    }
+
+    fn record_start(&mut self, timestamp: u32) {
+        self.start_timestamp.get_or_insert(timestamp);
+    }
+
+    fn store_result<S: WriteStorage>(
+        &mut self,
+        accesses: impl Iterator<Item = StorageAccess>,
+        storage: &StoragePtr<S>,
+    ) {
+        let mut storage = storage.borrow_mut();
+        let (pre, post) = collect_state(accesses, &mut *storage);
+        let result = if self.config.diff_mode {
+            diff_states(pre, post)
+        } else {
+            (pre, post)
+        };
+        // The tracer may be invoked for multiple executions; we're only interested in the first one.
+        self.result.set(result).ok();
+    }
 }
 
-#[derive(Debug, Clone)]
+/// Old VM versions are not supported; the tracer result will not be set for them.
+impl IntoOldVmTracer for PrestateTracer {}
+
+#[derive(Debug, Clone, Copy)]
 pub struct PrestateTracerConfig {
     diff_mode: bool,
 }
 
-pub fn process_modified_storage_keys<S>(
-    prestate: State,
-    storage: &StoragePtr<S>,
-) -> HashMap<H160, Account>
-where
-    S: WriteStorage,
-{
-    let cloned_storage = &storage.clone();
-    let mut initial_storage_ref = cloned_storage.as_ref().borrow_mut();
-
-    initial_storage_ref
-        .modified_storage_keys()
-        .clone()
-        .iter()
-        .filter(|k| !prestate.contains_key(k.0.account().address()))
-        .map(|k| {
-            (
-                *(k.0.account().address()),
-                Account {
-                    balance: Some(h256_to_u256(
-                        initial_storage_ref.read_value(&get_balance_key(k.0.account())),
-                    )),
-                    code: Some(h256_to_u256(
-                        initial_storage_ref.read_value(&get_code_key(k.0.account().address())),
-                    )),
-                    nonce: Some(h256_to_u256(
-                        initial_storage_ref.read_value(&get_nonce_key(k.0.account().address())),
-                    )),
-                    storage: Some(get_storage_if_present(
-                        k.0.account(),
-                        initial_storage_ref.modified_storage_keys(),
-                    )),
-                },
-            )
-        })
-        .collect::<State>()
+/// Storage access recorded by the VM during the traced execution.
+#[derive(Debug, Clone, Copy)]
+struct StorageAccess {
+    key: StorageKey,
+    read_value: U256,
+    written_value: Option<U256>,
 }
 
-fn get_balance_key(account: &AccountTreeId) -> StorageKey {
-    let address_h256 = address_to_h256(account.address());
-    let bytes = [address_h256.as_bytes(), &[0; 32]].concat();
-    let balance_key: H256 = keccak256(&bytes).into();
-    StorageKey::new(AccountTreeId::new(L2_BASE_TOKEN_ADDRESS), balance_key)
+impl StorageAccess {
+    /// Creates an access from a VM log query. Rollback queries undo a write made in a reverted frame,
+    /// so they restore the value read by the original write (cf. `StorageLog::from_log_query()`).
+    fn new(
+        address: Address,
+        key: U256,
+        read_value: U256,
+        written_value: Option<U256>,
+        rollback: bool,
+    ) -> Self {
+        let written_value = if rollback {
+            written_value.map(|_| read_value)
+        } else {
+            written_value
+        };
+        Self {
+            key: StorageKey::new(AccountTreeId::new(address), u256_to_h256(key)),
+            read_value,
+            written_value,
+        }
+    }
 }
 
-fn get_storage_if_present(
-    account: &AccountTreeId,
-    modified_storage_keys: &HashMap<StorageKey, StorageValue>,
-) -> HashMap<H256, H256> {
-    //check if there is a Storage Key struct with an account field that matches the account and return the key as the key and the Storage Value as the value
-    modified_storage_keys
-        .iter()
-        .filter(|(k, _)| k.account() == account)
-        .map(|(k, v)| (*k.key(), *v))
-        .collect()
-}
+/// Returns the pre- and post-state for all accounts touched by the provided storage accesses.
+fn collect_state<S: WriteStorage>(
+    accesses: impl Iterator<Item = StorageAccess>,
+    storage: &mut S,
+) -> (State, State) {
+    // `(pre, post)` values for each touched key; the pre value is taken from the first access.
+    let mut touched_keys = HashMap::<StorageKey, (U256, U256)>::new();
+    for access in accesses {
+        let post_value = access.written_value.unwrap_or(access.read_value);
+        touched_keys
+            .entry(access.key)
+            .and_modify(|(_, post)| *post = post_value)
+            .or_insert((access.read_value, post_value));
+    }
 
-fn process_result(result: &Arc<OnceCell<(State, State)>>, mut pre: State, post: State) {
-    pre.retain(|k, v| {
-        if let Some(post_v) = post.get(k) {
-            if v != post_v {
-                return true;
-            }
+    let mut accounts: HashSet<Address> = touched_keys
+        .keys()
+        .map(|key| *key.account().address())
+        .collect();
+    // Nonces and code hashes are keyed by the account address, so accounts that were only touched
+    // via system contracts can be recovered as well.
+    accounts.extend(touched_keys.keys().filter_map(|key| {
+        let address = *key.account().address();
+        (address == NONCE_HOLDER_ADDRESS || address == ACCOUNT_CODE_STORAGE_ADDRESS)
+            .then(|| h256_to_account_address(key.key()))
+    }));
+
+    let mut values = |key: StorageKey| {
+        touched_keys.get(&key).copied().unwrap_or_else(|| {
+            // The key wasn't accessed, so its value hasn't changed during execution.
+            let value = h256_to_u256(storage.read_value(&key));
+            (value, value)
+        })
+    };
+
+    let mut pre = State::new();
+    let mut post = State::new();
+    for address in accounts {
+        let account_id = AccountTreeId::new(address);
+        let (pre_balance, post_balance) = values(get_balance_key(&account_id));
+        let (pre_code, post_code) = values(get_code_key(&address));
+        let (pre_nonce, post_nonce) = values(get_nonce_key(&address));
+
+        let mut pre_storage = HashMap::new();
+        let mut post_storage = HashMap::new();
+        let slots = touched_keys
+            .iter()
+            .filter(|(key, _)| *key.account() == account_id);
+        for (key, (pre_value, post_value)) in slots {
+            pre_storage.insert(*key.key(), u256_to_h256(*pre_value));
+            post_storage.insert(*key.key(), u256_to_h256(*post_value));
         }
-        false
-    });
-    result.set((pre, post)).unwrap();
+
+        pre.insert(
+            address,
+            Account {
+                balance: Some(pre_balance),
+                code: Some(pre_code),
+                nonce: Some(pre_nonce),
+                storage: Some(pre_storage),
+            },
+        );
+        post.insert(
+            address,
+            Account {
+                balance: Some(post_balance),
+                code: Some(post_code),
+                nonce: Some(post_nonce),
+                storage: Some(post_storage),
+            },
+        );
+    }
+    (pre, post)
 }
 
-fn get_account_data<T: StorageAccess>(
-    account_key: &StorageKey,
-    state: &T,
-    storage: &HashMap<StorageKey, StorageValue>,
-) -> (Address, Account) {
-    let address = *(account_key.account().address());
-    let balance = state.read_from_storage(&get_balance_key(account_key.account()));
-    let code = state.read_from_storage(&get_code_key(account_key.account().address()));
-    let nonce = state.read_from_storage(&get_nonce_key(account_key.account().address()));
-    let storage = get_storage_if_present(account_key.account(), storage);
-
-    (
-        address,
-        Account {
-            balance: Some(balance),
-            code: Some(code),
-            nonce: Some(nonce),
-            storage: Some(storage),
-        },
-    )
+/// Retains only modified accounts and storage slots. Additionally, unchanged fields are removed from the post-state.
+fn diff_states(mut pre: State, mut post: State) -> (State, State) {
+    pre.retain(|address, account| post.get(address) != Some(account));
+    post.retain(|address, _| pre.contains_key(address));
+
+    for (address, post_account) in &mut post {
+        let pre_account = pre.get_mut(address).unwrap();
+        if post_account.balance == pre_account.balance {
+            post_account.balance = None;
+        }
+        if post_account.code == pre_account.code {
+            post_account.code = None;
+        }
+        if post_account.nonce == pre_account.nonce {
+            post_account.nonce = None;
+        }
+        if let (Some(post_storage), Some(pre_storage)) =
+            (&mut post_account.storage, &mut pre_account.storage)
+        {
+            post_storage.retain(|key, value| pre_storage.get(key) != Some(value));
+            pre_storage.retain(|key, _| post_storage.contains_key(key));
+        }
+    }
+    (pre, post)
 }
 
-// Define a trait that abstracts storage access
-trait StorageAccess {
-    fn read_from_storage(&self, key: &StorageKey) -> U256;
+fn get_balance_key(account: &AccountTreeId) -> StorageKey {
+    let address_h256 = address_to_h256(account.address());
+    let bytes = [address_h256.as_bytes(), &[0; 32]].concat();
+    let balance_key: H256 = keccak256(&bytes).into();
+    StorageKey::new(AccountTreeId::new(L2_BASE_TOKEN_ADDRESS), balance_key)
 }
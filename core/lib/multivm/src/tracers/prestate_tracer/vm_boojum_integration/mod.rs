@@ -0,0 +1,49 @@
+use zk_evm_1_4_0::{
+    aux_structures::Timestamp,
+    tracing::{BeforeExecutionData, VmLocalStateData},
+};
+use zksync_state::{StoragePtr, WriteStorage};
+
+use super::{PrestateTracer, StorageAccess};
+use crate::{
+    interface::dyn_tracers::vm_1_4_0::DynTracer,
+    vm_boojum_integration::{BootloaderState, HistoryMode, SimpleMemory, VmTracer, ZkSyncVmState},
+};
+
+impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for PrestateTracer {
+    fn before_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        _data: BeforeExecutionData,
+        _memory: &SimpleMemory<H>,
+        _storage: StoragePtr<S>,
+    ) {
+        self.record_start(state.vm_local_state.timestamp);
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for PrestateTracer {
+    fn after_vm_execution(
+        &mut self,
+        state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: crate::interface::tracer::VmExecutionStopReason,
+    ) {
+        let accesses = state
+            .storage
+            .storage_log_queries_after_timestamp(Timestamp(self.start_timestamp()))
+            .iter()
+            .map(|log| {
+                let query = &log.log_query;
+                StorageAccess::new(
+                    query.address,
+                    query.key,
+                    query.read_value,
+                    query.rw_flag.then_some(query.written_value),
+                    query.rollback,
+                )
+            });
+        let storage = state.storage.storage.get_ptr();
+        self.store_result(accesses, &storage);
+    }
+}
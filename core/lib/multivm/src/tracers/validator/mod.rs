@@ -40,6 +40,9 @@ pub struct ValidationTracer<H> {
     trusted_slots: HashSet<(Address, U256)>,
     trusted_addresses: HashSet<Address>,
     trusted_address_slots: HashSet<(Address, U256)>,
+    trusted_slots_in_all_contracts: HashSet<U256>,
+    allow_meta_opcode: bool,
+    stop_after_validation: bool,
     computational_gas_used: u32,
     computational_gas_limit: u32,
     vm_version: VmVersion,
@@ -66,6 +69,9 @@ impl<H> ValidationTracer<H> {
                 trusted_slots: params.trusted_slots,
                 trusted_addresses: params.trusted_addresses,
                 trusted_address_slots: params.trusted_address_slots,
+                trusted_slots_in_all_contracts: params.trusted_slots_in_all_contracts,
+                allow_meta_opcode: params.allow_meta_opcode,
+                stop_after_validation: params.stop_after_validation,
                 computational_gas_used: 0,
                 computational_gas_limit: params.computational_gas_limit,
                 vm_version,
@@ -121,6 +127,7 @@ impl<H> ValidationTracer<H> {
         if self.trusted_slots.contains(&(address, key))
             || self.trusted_addresses.contains(&address)
             || self.trusted_address_slots.contains(&(address, key))
+            || self.trusted_slots_in_all_contracts.contains(&key)
         {
             return true;
         }
@@ -187,6 +194,9 @@ impl<H> ValidationTracer<H> {
             trusted_slots: self.trusted_slots.clone(),
             trusted_addresses: self.trusted_addresses.clone(),
             trusted_address_slots: self.trusted_address_slots.clone(),
+            trusted_slots_in_all_contracts: self.trusted_slots_in_all_contracts.clone(),
+            allow_meta_opcode: self.allow_meta_opcode,
+            stop_after_validation: self.stop_after_validation,
             computational_gas_limit: self.computational_gas_limit,
         }
     }
@@ -33,6 +33,14 @@ pub struct ValidationTracerParams {
     /// They are needed to work correctly with beacon proxy, where the address of the implementation is
     /// stored in the beacon.
     pub trusted_address_slots: HashSet<(Address, U256)>,
+    /// Slots that are trusted in all contracts. Configured by the operator.
+    pub trusted_slots_in_all_contracts: HashSet<U256>,
+    /// Whether the user is allowed to use the `meta` context opcode (which exposes block-dependent data).
+    /// Configured by the operator.
+    pub allow_meta_opcode: bool,
+    /// Whether to stop execution once the validation step of the transaction has ended. Should be set to `false`
+    /// if the transaction must be executed in full (e.g., in the state keeper).
+    pub stop_after_validation: bool,
     /// Number of computational gas that validation step is allowed to use.
     pub computational_gas_limit: u32,
 }
@@ -85,7 +85,7 @@ impl<H: HistoryMode> ValidationTracer<H> {
             }
             Opcode::Context(context) => {
                 match context {
-                    ContextOpcode::Meta => {
+                    ContextOpcode::Meta if !self.allow_meta_opcode => {
                         return Err(ViolatedValidationRule::TouchedUnallowedContext);
                     }
                     ContextOpcode::ErgsLeft => {
@@ -169,7 +169,7 @@ impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H::Vm1_5_0>>
             }
             (_, VmHook::ValidationStepEndeded) => {
                 // The validation step has ended.
-                self.should_stop_execution = true;
+                self.should_stop_execution = self.stop_after_validation;
             }
             (_, _) => {
                 // The hook is not relevant to the validation tracer. Ignore.
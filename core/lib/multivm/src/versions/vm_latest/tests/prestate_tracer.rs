@@ -141,3 +141,36 @@ fn test_prestate_tracer_diff_mode() {
         Some(U256::from(200000))
     );
 }
+
+#[test]
+fn test_prestate_tracer_diff_mode_with_reverted_call() {
+    let mut vm = VmTesterBuilder::new(HistoryEnabled)
+        .with_empty_in_memory_storage()
+        .with_random_rich_accounts(1)
+        .with_deployer()
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .build();
+
+    vm.deploy_test_contract();
+    let contract_address = vm.test_contract.unwrap();
+    let account = &mut vm.rich_accounts[0];
+    let sender = account.address;
+    // The counter increment is reverted, but the transaction fee is still charged.
+    let tx = account.get_test_contract_transaction(contract_address, true, None, false, TxType::L2);
+    vm.vm.push_transaction(tx);
+
+    let prestate_tracer_result = Arc::new(OnceCell::default());
+    let prestate_tracer = PrestateTracer::new(true, prestate_tracer_result.clone());
+    let tracer_ptr = prestate_tracer.into_tracer_pointer();
+    let res = vm.vm.inspect(tracer_ptr.into(), VmExecutionMode::OneTx);
+    assert!(res.result.is_failed(), "{:?}", res.result);
+
+    let (pre, post) = Arc::try_unwrap(prestate_tracer_result)
+        .unwrap()
+        .take()
+        .unwrap();
+    assert!(!pre.contains_key(&contract_address), "{pre:?}");
+    assert!(!post.contains_key(&contract_address), "{post:?}");
+    assert!(post[&sender].balance.unwrap() < pre[&sender].balance.unwrap());
+}
@@ -0,0 +1,256 @@
+//! Object store decorator transparently compressing objects.
+
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+pub use zksync_config::configs::object_store::ObjectCompressionCodec;
+
+use crate::raw::{BoxedError, Bucket, ObjectStore, ObjectStoreError};
+
+/// Magic bytes prepended to compressed objects. Followed by a single byte identifying the codec.
+/// Objects not starting with these bytes are returned as-is, so that uncompressed objects remain readable.
+const MAGIC: [u8; 6] = *b"\0zkcmp";
+const GZIP_ID: u8 = 1;
+const ZSTD_ID: u8 = 2;
+
+fn codec_id(codec: ObjectCompressionCodec) -> u8 {
+    match codec {
+        ObjectCompressionCodec::Gzip => GZIP_ID,
+        ObjectCompressionCodec::Zstd => ZSTD_ID,
+    }
+}
+
+fn compress(codec: ObjectCompressionCodec, data: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut output = Vec::with_capacity(MAGIC.len() + 1 + data.len() / 2);
+    output.extend_from_slice(&MAGIC);
+    output.push(codec_id(codec));
+    match codec {
+        ObjectCompressionCodec::Gzip => {
+            let mut encoder = GzEncoder::new(output, Compression::default());
+            encoder.write_all(data)?;
+            encoder.finish()
+        }
+        ObjectCompressionCodec::Zstd => {
+            zstd::stream::copy_encode(data, &mut output, zstd::DEFAULT_COMPRESSION_LEVEL)?;
+            Ok(output)
+        }
+    }
+}
+
+fn is_compressed(data: &[u8]) -> bool {
+    data.starts_with(&MAGIC)
+}
+
+fn decompress(data: &[u8]) -> Result<Vec<u8>, BoxedError> {
+    let compressed = data
+        .strip_prefix(&MAGIC)
+        .ok_or("object is not compressed")?;
+    let (&codec_id, compressed) = compressed
+        .split_first()
+        .ok_or("compressed object misses codec ID")?;
+    match codec_id {
+        GZIP_ID => {
+            let mut decompressed = Vec::with_capacity(compressed.len() * 2);
+            GzDecoder::new(compressed).read_to_end(&mut decompressed)?;
+            Ok(decompressed)
+        }
+        ZSTD_ID => Ok(zstd::stream::decode_all(compressed)?),
+        _ => Err(format!("unknown compression codec ID: {codec_id}").into()),
+    }
+}
+
+/// [`ObjectStore`] decorator that compresses objects before putting them into the wrapped store.
+///
+/// The codec is chosen per bucket; buckets without a codec are passed through to the wrapped store as is.
+/// In buckets with a codec, compressed objects are prefixed with a header identifying the codec, so objects
+/// can be read regardless of which codec is configured; uncompressed objects (e.g., ones put before
+/// compression was enabled) are returned as-is.
+///
+/// **Important.** Since objects are only decompressed in buckets with a codec, all readers of a bucket must be
+/// upgraded and configured with a codec before any writer starts compressing objects in it.
+#[derive(Debug)]
+pub struct CompressingObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    codecs: HashMap<Bucket, ObjectCompressionCodec>,
+}
+
+impl CompressingObjectStore {
+    /// Wraps the provided store. Initially, no objects are compressed on write; use [`Self::with_codec()`]
+    /// to enable compression.
+    pub fn new(inner: Arc<dyn ObjectStore>) -> Self {
+        Self {
+            inner,
+            codecs: HashMap::new(),
+        }
+    }
+
+    /// Sets the codec for the specified bucket.
+    #[must_use]
+    pub fn with_codec(mut self, bucket: Bucket, codec: ObjectCompressionCodec) -> Self {
+        self.codecs.insert(bucket, codec);
+        self
+    }
+}
+
+#[async_trait]
+impl ObjectStore for CompressingObjectStore {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        let data = self.inner.get_raw(bucket, key).await?;
+        if !self.codecs.contains_key(&bucket) || !is_compressed(&data) {
+            return Ok(data);
+        }
+        tokio::task::spawn_blocking(move || decompress(&data))
+            .await
+            .expect("decompression panicked")
+            .map_err(ObjectStoreError::Serialization)
+    }
+
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        let value = if let Some(&codec) = self.codecs.get(&bucket) {
+            let original_len = value.len();
+            let compressed = tokio::task::spawn_blocking(move || compress(codec, &value))
+                .await
+                .expect("compression panicked")
+                .map_err(|err| ObjectStoreError::Serialization(err.into()))?;
+            tracing::trace!(
+                "Compressed object {bucket}/{key} with {codec:?}: {original_len} -> {} bytes",
+                compressed.len()
+            );
+            compressed
+        } else {
+            value
+        };
+        self.inner.put_raw(bucket, key, value).await
+    }
+
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        self.inner.remove_raw(bucket, key).await
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        self.inner.storage_prefix_raw(bucket)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+    use crate::MockObjectStore;
+
+    const CODECS: [ObjectCompressionCodec; 2] =
+        [ObjectCompressionCodec::Gzip, ObjectCompressionCodec::Zstd];
+
+    #[test]
+    fn compression_roundtrip() {
+        let data = b"test data ".repeat(100);
+        for codec in CODECS {
+            let compressed = compress(codec, &data).unwrap();
+            assert!(compressed.starts_with(&MAGIC));
+            assert!(compressed.len() < data.len(), "{codec:?}");
+            assert!(is_compressed(&compressed));
+            assert_eq!(decompress(&compressed).unwrap(), data);
+        }
+
+        assert!(!is_compressed(&data));
+        assert!(!is_compressed(&[]));
+    }
+
+    #[test]
+    fn decompressing_corrupted_data() {
+        decompress(b"test data").unwrap_err();
+        decompress(&MAGIC).unwrap_err();
+        decompress(&[&MAGIC[..], &[42, 1, 2, 3]].concat()).unwrap_err();
+        decompress(&[&MAGIC[..], &[ZSTD_ID, 1, 2, 3]].concat()).unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn compressing_store_basics() {
+        let inner = MockObjectStore::arc();
+        let store = CompressingObjectStore::new(inner.clone())
+            .with_codec(Bucket::ProverJobsFri, ObjectCompressionCodec::Zstd);
+        let data = b"test data ".repeat(100);
+
+        store
+            .put_raw(Bucket::ProverJobsFri, "test.bin", data.clone())
+            .await
+            .unwrap();
+        let stored = inner
+            .get_raw(Bucket::ProverJobsFri, "test.bin")
+            .await
+            .unwrap();
+        assert!(stored.len() < data.len());
+        let value = store
+            .get_raw(Bucket::ProverJobsFri, "test.bin")
+            .await
+            .unwrap();
+        assert_eq!(value, data);
+
+        // Objects in other buckets are not compressed.
+        store
+            .put_raw(Bucket::StorageSnapshot, "test.bin", data.clone())
+            .await
+            .unwrap();
+        let stored = inner
+            .get_raw(Bucket::StorageSnapshot, "test.bin")
+            .await
+            .unwrap();
+        assert_eq!(stored, data);
+
+        store
+            .remove_raw(Bucket::ProverJobsFri, "test.bin")
+            .await
+            .unwrap();
+        let err = store
+            .get_raw(Bucket::ProverJobsFri, "test.bin")
+            .await
+            .unwrap_err();
+        assert_matches!(err, ObjectStoreError::KeyNotFound(_));
+    }
+
+    #[tokio::test]
+    async fn reading_objects_after_codec_change() {
+        let inner = MockObjectStore::arc();
+        let data = b"test data ".repeat(100);
+        inner
+            .put_raw(Bucket::WitnessInput, "raw.bin", data.clone())
+            .await
+            .unwrap();
+        for (i, codec) in CODECS.into_iter().enumerate() {
+            CompressingObjectStore::new(inner.clone())
+                .with_codec(Bucket::WitnessInput, codec)
+                .put_raw(Bucket::WitnessInput, &format!("{i}.bin"), data.clone())
+                .await
+                .unwrap();
+        }
+
+        // A reader with any codec configured for the bucket can read all objects.
+        for codec in CODECS {
+            let store =
+                CompressingObjectStore::new(inner.clone()).with_codec(Bucket::WitnessInput, codec);
+            for key in ["raw.bin", "0.bin", "1.bin"] {
+                let value = store.get_raw(Bucket::WitnessInput, key).await.unwrap();
+                assert_eq!(value, data, "{key}");
+            }
+        }
+
+        // Buckets without a codec are passed through as is.
+        let store = CompressingObjectStore::new(inner.clone());
+        let value = store.get_raw(Bucket::WitnessInput, "0.bin").await.unwrap();
+        assert_eq!(
+            value,
+            inner.get_raw(Bucket::WitnessInput, "0.bin").await.unwrap()
+        );
+    }
+}
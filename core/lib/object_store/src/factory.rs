@@ -5,11 +5,13 @@ use tokio::sync::OnceCell;
 use zksync_config::configs::object_store::{ObjectStoreConfig, ObjectStoreMode};
 
 use crate::{
+    compression::CompressingObjectStore,
     file::FileBackedObjectStore,
     gcs::{GoogleCloudStore, GoogleCloudStoreAuthMode},
     mirror::MirroringObjectStore,
-    raw::{ObjectStore, ObjectStoreError},
+    raw::{Bucket, ObjectStore, ObjectStoreError},
     retries::StoreWithRetries,
+    s3::{S3CredentialsMode, S3Store},
 };
 
 /// Factory of [`ObjectStore`]s that caches the store instance once it's created. Used mainly for legacy reasons.
@@ -61,7 +63,7 @@ impl ObjectStoreFactory {
         config: &ObjectStoreConfig,
     ) -> Result<Arc<dyn ObjectStore>, ObjectStoreError> {
         tracing::trace!("Initializing object store with configuration {config:?}");
-        match &config.mode {
+        let store = match &config.mode {
             ObjectStoreMode::GCS { bucket_base_url } => {
                 let store = StoreWithRetries::try_new(config.max_retries, || {
                     GoogleCloudStore::new(
@@ -70,7 +72,7 @@ impl ObjectStoreFactory {
                     )
                 })
                 .await?;
-                Self::wrap_mirroring(store, config.local_mirror_path.as_ref()).await
+                Self::wrap_mirroring(store, config.local_mirror_path.as_ref()).await?
             }
             ObjectStoreMode::GCSWithCredentialFile {
                 bucket_base_url,
@@ -85,7 +87,7 @@ impl ObjectStoreFactory {
                     )
                 })
                 .await?;
-                Self::wrap_mirroring(store, config.local_mirror_path.as_ref()).await
+                Self::wrap_mirroring(store, config.local_mirror_path.as_ref()).await?
             }
             ObjectStoreMode::GCSAnonymousReadOnly { bucket_base_url } => {
                 let store = StoreWithRetries::try_new(config.max_retries, || {
@@ -95,7 +97,30 @@ impl ObjectStoreFactory {
                     )
                 })
                 .await?;
-                Self::wrap_mirroring(store, config.local_mirror_path.as_ref()).await
+                Self::wrap_mirroring(store, config.local_mirror_path.as_ref()).await?
+            }
+            ObjectStoreMode::S3 {
+                bucket,
+                region,
+                endpoint,
+                addressing_style,
+                s3_credential_file_path,
+            } => {
+                let credentials_mode = match s3_credential_file_path {
+                    Some(path) => S3CredentialsMode::File(path.clone()),
+                    None => S3CredentialsMode::Env,
+                };
+                let store = StoreWithRetries::try_new(config.max_retries, || {
+                    S3Store::new(
+                        bucket.clone(),
+                        region.clone(),
+                        endpoint.clone(),
+                        *addressing_style,
+                        credentials_mode.clone(),
+                    )
+                })
+                .await?;
+                Self::wrap_mirroring(store, config.local_mirror_path.as_ref()).await?
             }
 
             ObjectStoreMode::FileBacked {
@@ -109,9 +134,34 @@ impl ObjectStoreFactory {
                 if let Some(mirror_path) = &config.local_mirror_path {
                     tracing::warn!("Mirroring doesn't make sense with file-backed object store; ignoring mirror path `{mirror_path}`");
                 }
-                Ok(Arc::new(store))
+                Arc::new(store)
             }
+        };
+
+        Self::wrap_compression(store, config)
+    }
+
+    fn wrap_compression(
+        store: Arc<dyn ObjectStore>,
+        config: &ObjectStoreConfig,
+    ) -> Result<Arc<dyn ObjectStore>, ObjectStoreError> {
+        if config.compression.is_empty() {
+            return Ok(store);
+        }
+
+        let mut store = CompressingObjectStore::new(store);
+        for (bucket_name, codec) in config.compression.iter() {
+            let bucket =
+                bucket_name
+                    .parse::<Bucket>()
+                    .map_err(|err| ObjectStoreError::Initialization {
+                        source: err.into(),
+                        is_transient: false,
+                    })?;
+            tracing::info!("Compressing objects in bucket `{bucket}` with {codec:?}");
+            store = store.with_codec(bucket, codec);
         }
+        Ok(Arc::new(store))
     }
 
     async fn wrap_mirroring(
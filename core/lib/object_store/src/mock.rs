@@ -46,11 +46,15 @@ impl ObjectStore for MockObjectStore {
     }
 
     async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        // Mimics the file-backed store, which returns an error when removing a missing object.
         let mut lock = self.inner.lock().await;
-        let Some(bucket_map) = lock.get_mut(&bucket) else {
-            return Ok(());
-        };
-        bucket_map.remove(key);
+        let removed = lock
+            .get_mut(&bucket)
+            .and_then(|bucket_map| bucket_map.remove(key));
+        if removed.is_none() {
+            let error_message = format!("missing key: {key} in bucket {bucket}");
+            return Err(ObjectStoreError::KeyNotFound(error_message.into()));
+        }
         Ok(())
     }
 
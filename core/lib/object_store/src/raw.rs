@@ -1,4 +1,4 @@
-use std::{error, fmt};
+use std::{error, fmt, str::FromStr};
 
 use async_trait::async_trait;
 
@@ -20,9 +20,28 @@ pub enum Bucket {
     StorageSnapshot,
     DataAvailability,
     TeeVerifierInput,
+    VmTraces,
 }
 
 impl Bucket {
+    const ALL: [Self; 15] = [
+        Self::ProverJobs,
+        Self::WitnessInput,
+        Self::LeafAggregationWitnessJobs,
+        Self::NodeAggregationWitnessJobs,
+        Self::SchedulerWitnessJobs,
+        Self::ProverJobsFri,
+        Self::LeafAggregationWitnessJobsFri,
+        Self::NodeAggregationWitnessJobsFri,
+        Self::SchedulerWitnessJobsFri,
+        Self::ProofsFri,
+        Self::ProofsTee,
+        Self::StorageSnapshot,
+        Self::DataAvailability,
+        Self::TeeVerifierInput,
+        Self::VmTraces,
+    ];
+
     pub(crate) fn as_str(self) -> &'static str {
         match self {
             Self::ProverJobs => "prover_jobs",
@@ -39,6 +58,7 @@ impl Bucket {
             Self::StorageSnapshot => "storage_logs_snapshots",
             Self::DataAvailability => "data_availability",
             Self::TeeVerifierInput => "tee_verifier_inputs",
+            Self::VmTraces => "vm_traces",
         }
     }
 }
@@ -49,6 +69,17 @@ impl fmt::Display for Bucket {
     }
 }
 
+impl FromStr for Bucket {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|bucket| bucket.as_str() == s)
+            .ok_or_else(|| format!("unknown bucket `{s}`"))
+    }
+}
+
 /// Thread-safe boxed error.
 pub type BoxedError = Box<dyn error::Error + Send + Sync>;
 
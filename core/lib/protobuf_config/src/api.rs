@@ -1,6 +1,7 @@
-use std::num::NonZeroUsize;
+use std::num::{NonZeroU32, NonZeroUsize};
 
 use anyhow::Context as _;
+use zksync_basic_types::U256;
 use zksync_config::configs::{api, ApiConfig};
 use zksync_protobuf::{
     repr::{read_required_repr, ProtoRepr},
@@ -9,6 +10,24 @@ use zksync_protobuf::{
 
 use crate::{parse_h160, parse_h256, proto::api as proto};
 
+impl proto::PubSubNotificationsSource {
+    fn new(source: &api::PubSubNotificationsSource) -> Self {
+        use api::PubSubNotificationsSource as From;
+        match source {
+            From::Postgres => Self::Postgres,
+            From::InProcess => Self::InProcess,
+        }
+    }
+
+    fn parse(&self) -> api::PubSubNotificationsSource {
+        use api::PubSubNotificationsSource as To;
+        match self {
+            Self::Postgres => To::Postgres,
+            Self::InProcess => To::InProcess,
+        }
+    }
+}
+
 impl ProtoRepr for proto::Api {
     type Type = ApiConfig;
     fn read(&self) -> anyhow::Result<Self::Type> {
@@ -69,6 +88,19 @@ impl ProtoRepr for proto::Web3JsonRpc {
             })
             .collect::<anyhow::Result<_>>()
             .context("max_response_body_size_overrides")?;
+        let method_compute_units = self
+            .method_compute_units
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let method = required(&entry.method).with_context(|| format!("[{i}].method"))?;
+                let units = required(&entry.units)
+                    .and_then(|&units| NonZeroU32::new(units).context("is zero"))
+                    .with_context(|| format!("[{i}].units"))?;
+                Ok((method.clone(), units))
+            })
+            .collect::<anyhow::Result<_>>()
+            .context("method_compute_units")?;
         let api_namespaces = if self.api_namespaces.is_empty() {
             None
         } else {
@@ -159,6 +191,41 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .collect::<Result<Vec<_>, _>>()
                 .context("account_pks")?,
             extended_api_tracing: self.extended_api_tracing.unwrap_or_default(),
+            pubsub_notifications: self
+                .pubsub_notifications
+                .map(|x| anyhow::Ok(proto::PubSubNotificationsSource::try_from(x)?.parse()))
+                .transpose()
+                .context("pubsub_notifications")?,
+            tx_deny_list_path: self.tx_deny_list_path.clone(),
+            tx_method_allowlist_path: self.tx_method_allowlist_path.clone(),
+            max_tx_calldata_size: self
+                .max_tx_calldata_size
+                .map(|x| x.try_into())
+                .transpose()
+                .context("max_tx_calldata_size")?,
+            max_tx_value: self
+                .max_tx_value
+                .as_ref()
+                .map(|value| {
+                    let hex_value = value.strip_prefix("0x").context("missing 0x prefix")?;
+                    U256::from_str_radix(hex_value, 16).map_err(|err| anyhow::anyhow!("{err}"))
+                })
+                .transpose()
+                .context("max_tx_value")?,
+            tx_filter_lists_reload_interval_ms: self.tx_filter_lists_reload_interval_ms,
+            compute_units_per_second_limit: self
+                .compute_units_per_second_limit
+                .map(|x| NonZeroU32::new(x).context("is zero"))
+                .transpose()
+                .context("compute_units_per_second_limit")?,
+            compute_units_burst_limit: self
+                .compute_units_burst_limit
+                .map(|x| NonZeroU32::new(x).context("is zero"))
+                .transpose()
+                .context("compute_units_burst_limit")?,
+            method_compute_units,
+            api_key_header: self.api_key_header.clone(),
+            client_ip_header: self.client_ip_header.clone(),
             api_namespaces,
         })
     }
@@ -229,7 +296,28 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .map(|k| format!("{:?}", k))
                 .collect(),
             extended_api_tracing: Some(this.extended_api_tracing),
+            pubsub_notifications: this
+                .pubsub_notifications
+                .as_ref()
+                .map(|source| proto::PubSubNotificationsSource::new(source).into()),
             api_namespaces: this.api_namespaces.clone().unwrap_or_default(),
+            tx_deny_list_path: this.tx_deny_list_path.clone(),
+            tx_method_allowlist_path: this.tx_method_allowlist_path.clone(),
+            max_tx_calldata_size: this.max_tx_calldata_size.map(|x| x.try_into().unwrap()),
+            max_tx_value: this.max_tx_value.map(|value| format!("{value:#x}")),
+            tx_filter_lists_reload_interval_ms: this.tx_filter_lists_reload_interval_ms,
+            compute_units_per_second_limit: this.compute_units_per_second_limit.map(|x| x.get()),
+            compute_units_burst_limit: this.compute_units_burst_limit.map(|x| x.get()),
+            method_compute_units: this
+                .method_compute_units
+                .iter()
+                .map(|(method, units)| proto::MethodComputeUnits {
+                    method: Some(method.to_owned()),
+                    units: Some(units.get()),
+                })
+                .collect(),
+            api_key_header: this.api_key_header.clone(),
+            client_ip_header: this.client_ip_header.clone(),
         }
     }
 }
@@ -2,7 +2,7 @@ use anyhow::Context as _;
 use zksync_config::configs;
 use zksync_protobuf::{repr::ProtoRepr, required};
 
-use crate::proto::chain as proto;
+use crate::{parse_h160, proto::chain as proto};
 
 impl proto::FeeModelVersion {
     fn new(n: &configs::chain::FeeModelVersion) -> Self {
@@ -22,6 +22,26 @@ impl proto::FeeModelVersion {
     }
 }
 
+impl proto::MempoolOrderingPolicy {
+    fn new(n: &configs::chain::MempoolOrderingPolicyKind) -> Self {
+        use configs::chain::MempoolOrderingPolicyKind as From;
+        match n {
+            From::Fifo => Self::Fifo,
+            From::HighestFeeFirst => Self::HighestFeeFirst,
+            From::FairShare => Self::FairShare,
+        }
+    }
+
+    fn parse(&self) -> configs::chain::MempoolOrderingPolicyKind {
+        use configs::chain::MempoolOrderingPolicyKind as To;
+        match self {
+            Self::Fifo => To::Fifo,
+            Self::HighestFeeFirst => To::HighestFeeFirst,
+            Self::FairShare => To::FairShare,
+        }
+    }
+}
+
 impl ProtoRepr for proto::StateKeeper {
     type Type = configs::chain::StateKeeperConfig;
     fn read(&self) -> anyhow::Result<Self::Type> {
@@ -74,6 +94,27 @@ impl ProtoRepr for proto::StateKeeper {
                 .parse(),
             validation_computational_gas_limit: *required(&self.validation_computational_gas_limit)
                 .context("validation_computational_gas_limit")?,
+            validation_trusted_addresses: self
+                .validation_trusted_addresses
+                .iter()
+                .enumerate()
+                .map(|(i, address)| parse_h160(address).context(i))
+                .collect::<anyhow::Result<_>>()
+                .context("validation_trusted_addresses")?,
+            validation_trusted_slots: self
+                .validation_trusted_slots
+                .iter()
+                .enumerate()
+                .map(|(i, pattern)| pattern.parse().context(i))
+                .collect::<anyhow::Result<_>>()
+                .context("validation_trusted_slots")?,
+            validation_allowed_opcodes: self
+                .validation_allowed_opcodes
+                .iter()
+                .enumerate()
+                .map(|(i, opcode)| opcode.parse().context(i))
+                .collect::<anyhow::Result<_>>()
+                .context("validation_allowed_opcodes")?,
             save_call_traces: *required(&self.save_call_traces).context("save_call_traces")?,
             max_circuits_per_batch: required(&self.max_circuits_per_batch)
                 .and_then(|x| Ok((*x).try_into()?))
@@ -117,6 +158,21 @@ impl ProtoRepr for proto::StateKeeper {
             max_pubdata_per_batch: Some(this.max_pubdata_per_batch),
             fee_model_version: Some(proto::FeeModelVersion::new(&this.fee_model_version).into()),
             validation_computational_gas_limit: Some(this.validation_computational_gas_limit),
+            validation_trusted_addresses: this
+                .validation_trusted_addresses
+                .iter()
+                .map(|address| format!("{address:?}"))
+                .collect(),
+            validation_trusted_slots: this
+                .validation_trusted_slots
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            validation_allowed_opcodes: this
+                .validation_allowed_opcodes
+                .iter()
+                .map(|opcode| opcode.as_str().to_owned())
+                .collect(),
             save_call_traces: Some(this.save_call_traces),
             max_circuits_per_batch: Some(this.max_circuits_per_batch.try_into().unwrap()),
             protective_reads_persistence_enabled: Some(this.protective_reads_persistence_enabled),
@@ -151,6 +207,18 @@ impl ProtoRepr for proto::Mempool {
             stuck_tx_timeout: *required(&self.stuck_tx_timeout).context("stuck_tx_timeout")?,
             remove_stuck_txs: *required(&self.remove_stuck_txs).context("remove_stuck_txs")?,
             delay_interval: *required(&self.delay_interval).context("delay_interval")?,
+            replacement_min_fee_bump_percent: self.replacement_min_fee_bump_percent,
+            max_pending_txs_per_account: self
+                .max_pending_txs_per_account
+                .map(|x| x.try_into())
+                .transpose()
+                .context("max_pending_txs_per_account")?,
+            ordering_policy: self
+                .ordering_policy
+                .map(|x| anyhow::Ok(proto::MempoolOrderingPolicy::try_from(x)?.parse()))
+                .transpose()
+                .context("ordering_policy")?
+                .unwrap_or_default(),
         })
     }
 
@@ -162,6 +230,11 @@ impl ProtoRepr for proto::Mempool {
             stuck_tx_timeout: Some(this.stuck_tx_timeout),
             remove_stuck_txs: Some(this.remove_stuck_txs),
             delay_interval: Some(this.delay_interval),
+            replacement_min_fee_bump_percent: this.replacement_min_fee_bump_percent,
+            max_pending_txs_per_account: this
+                .max_pending_txs_per_account
+                .map(|x| x.try_into().unwrap()),
+            ordering_policy: Some(proto::MempoolOrderingPolicy::new(&this.ordering_policy).into()),
         }
     }
 }
@@ -2,7 +2,7 @@ use anyhow::Context as _;
 use zksync_config::configs::{self};
 use zksync_protobuf::{required, ProtoRepr};
 
-use crate::{proto::eth as proto, read_optional_repr};
+use crate::{parse_h160, proto::eth as proto, read_optional_repr};
 
 impl proto::ProofSendingMode {
     fn new(x: &configs::eth_sender::ProofSendingMode) -> Self {
@@ -52,6 +52,7 @@ impl ProtoRepr for proto::Eth {
             sender: read_optional_repr(&self.sender).context("sender")?,
             gas_adjuster: read_optional_repr(&self.gas_adjuster).context("gas_adjuster")?,
             watcher: read_optional_repr(&self.watcher).context("watcher")?,
+            remote_signer: read_optional_repr(&self.remote_signer).context("remote_signer")?,
         })
     }
 
@@ -60,6 +61,7 @@ impl ProtoRepr for proto::Eth {
             sender: this.sender.as_ref().map(ProtoRepr::build),
             gas_adjuster: this.gas_adjuster.as_ref().map(ProtoRepr::build),
             watcher: this.watcher.as_ref().map(ProtoRepr::build),
+            remote_signer: this.remote_signer.as_ref().map(ProtoRepr::build),
         }
     }
 }
@@ -111,6 +113,9 @@ impl ProtoRepr for proto::Sender {
                 .and_then(|x| Ok(proto::PubdataSendingMode::try_from(*x)?))
                 .context("pubdata_sending_mode")?
                 .parse(),
+            fee_spike_ratio: self.fee_spike_ratio,
+            cheap_blob_fee_ratio: self.cheap_blob_fee_ratio,
+            max_fee_postponement_seconds: self.max_fee_postponement_seconds,
         })
     }
 
@@ -141,6 +146,9 @@ impl ProtoRepr for proto::Sender {
             pubdata_sending_mode: Some(
                 proto::PubdataSendingMode::new(&this.pubdata_sending_mode).into(),
             ),
+            fee_spike_ratio: this.fee_spike_ratio,
+            cheap_blob_fee_ratio: this.cheap_blob_fee_ratio,
+            max_fee_postponement_seconds: this.max_fee_postponement_seconds,
         }
     }
 }
@@ -207,6 +215,13 @@ impl ProtoRepr for proto::EthWatch {
             confirmations_for_eth_event: self.confirmations_for_eth_event,
             eth_node_poll_interval: *required(&self.eth_node_poll_interval)
                 .context("eth_node_poll_interval")?,
+            event_subscriptions: self
+                .event_subscriptions
+                .iter()
+                .enumerate()
+                .map(|(i, subscription)| subscription.read().with_context(|| format!("[{i}]")))
+                .collect::<anyhow::Result<_>>()
+                .context("event_subscriptions")?,
         })
     }
 
@@ -214,6 +229,81 @@ impl ProtoRepr for proto::EthWatch {
         Self {
             confirmations_for_eth_event: this.confirmations_for_eth_event,
             eth_node_poll_interval: Some(this.eth_node_poll_interval),
+            event_subscriptions: this
+                .event_subscriptions
+                .iter()
+                .map(ProtoRepr::build)
+                .collect(),
+        }
+    }
+}
+
+impl ProtoRepr for proto::L1EventSubscription {
+    type Type = configs::eth_watch::L1EventSubscriptionConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            name: required(&self.name).context("name")?.clone(),
+            contract_address: required(&self.contract_address)
+                .and_then(|address| parse_h160(address))
+                .context("contract_address")?,
+            event_signatures: self.event_signatures.clone(),
+            confirmations: self.confirmations,
+            start_block: self.start_block,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            name: Some(this.name.clone()),
+            contract_address: Some(format!("{:?}", this.contract_address)),
+            event_signatures: this.event_signatures.clone(),
+            confirmations: this.confirmations,
+            start_block: this.start_block,
+        }
+    }
+}
+
+impl ProtoRepr for proto::RemoteSigner {
+    type Type = configs::eth_sender::RemoteSignerConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            url: required(&self.url)
+                .and_then(|url| Ok(url.parse()?))
+                .context("url")?,
+            operator_address: required(&self.operator_address)
+                .and_then(|address| parse_h160(address))
+                .context("operator_address")?,
+            blob_operator_address: self
+                .blob_operator_address
+                .as_ref()
+                .map(|address| parse_h160(address))
+                .transpose()
+                .context("blob_operator_address")?,
+            previous_operator_address: self
+                .previous_operator_address
+                .as_ref()
+                .map(|address| parse_h160(address))
+                .transpose()
+                .context("previous_operator_address")?,
+            request_timeout_ms: self
+                .request_timeout_ms
+                .unwrap_or_else(Self::Type::default_request_timeout_ms),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            url: Some(this.url.expose_str().to_owned()),
+            operator_address: Some(format!("{:?}", this.operator_address)),
+            blob_operator_address: this
+                .blob_operator_address
+                .map(|address| format!("{address:?}")),
+            previous_operator_address: this
+                .previous_operator_address
+                .map(|address| format!("{address:?}")),
+            request_timeout_ms: Some(this.request_timeout_ms),
         }
     }
 }
@@ -1,7 +1,8 @@
+use anyhow::Context as _;
 use zksync_config::configs::{self};
 use zksync_protobuf::ProtoRepr;
 
-use crate::proto::external_price_api_client as proto;
+use crate::{parse_h160, proto::external_price_api_client as proto};
 
 impl ProtoRepr for proto::ExternalPriceApiClient {
     type Type = configs::external_price_api_client::ExternalPriceApiClientConfig;
@@ -15,6 +16,33 @@ impl ProtoRepr for proto::ExternalPriceApiClient {
                 api_key: self.api_key.clone(),
                 forced_numerator: self.forced_numerator,
                 forced_denominator: self.forced_denominator,
+                uniswap_pool_address: self
+                    .uniswap_pool_address
+                    .as_ref()
+                    .map(|x| parse_h160(x))
+                    .transpose()
+                    .context("uniswap_pool_address")?,
+                uniswap_twap_window_secs: self.uniswap_twap_window_secs,
+                chainlink_feed_address: self
+                    .chainlink_feed_address
+                    .as_ref()
+                    .map(|x| parse_h160(x))
+                    .transpose()
+                    .context("chainlink_feed_address")?,
+                aggregated_sources: self
+                    .aggregated_sources
+                    .iter()
+                    .enumerate()
+                    .map(|(i, source)| source.read().with_context(|| format!("[{i}]")))
+                    .collect::<anyhow::Result<_>>()
+                    .context("aggregated_sources")?,
+                min_sources: self
+                    .min_sources
+                    .map(usize::try_from)
+                    .transpose()
+                    .context("min_sources")?,
+                max_quote_age_ms: self.max_quote_age_ms,
+                max_deviation_percent: self.max_deviation_percent,
             },
         )
     }
@@ -27,6 +55,23 @@ impl ProtoRepr for proto::ExternalPriceApiClient {
             client_timeout_ms: Some(this.client_timeout_ms),
             forced_numerator: this.forced_numerator,
             forced_denominator: this.forced_denominator,
+            uniswap_pool_address: this
+                .uniswap_pool_address
+                .map(|address| format!("{address:?}")),
+            uniswap_twap_window_secs: this.uniswap_twap_window_secs,
+            chainlink_feed_address: this
+                .chainlink_feed_address
+                .map(|address| format!("{address:?}")),
+            aggregated_sources: this
+                .aggregated_sources
+                .iter()
+                .map(ProtoRepr::build)
+                .collect(),
+            min_sources: this
+                .min_sources
+                .map(|count| count.try_into().expect("failed converting usize to u64")),
+            max_quote_age_ms: this.max_quote_age_ms,
+            max_deviation_percent: this.max_deviation_percent,
         }
     }
 }
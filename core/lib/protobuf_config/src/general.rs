@@ -45,6 +45,8 @@ impl ProtoRepr for proto::GeneralConfig {
                 &self.basic_witness_input_producer,
             )
             .context("basic_witness_input_producer")?,
+            trace_exporter_config: read_optional_repr(&self.trace_exporter)
+                .context("trace_exporter")?,
             core_object_store: read_optional_repr(&self.core_object_store)
                 .context("core_object_store")?,
             base_token_adjuster: read_optional_repr(&self.base_token_adjuster)
@@ -97,6 +99,7 @@ impl ProtoRepr for proto::GeneralConfig {
                 .basic_witness_input_producer_config
                 .as_ref()
                 .map(ProtoRepr::build),
+            trace_exporter: this.trace_exporter_config.as_ref().map(ProtoRepr::build),
             commitment_generator: this.commitment_generator.as_ref().map(ProtoRepr::build),
             snapshot_recovery: this.snapshot_recovery.as_ref().map(ProtoRepr::build),
             pruning: this.pruning.as_ref().map(ProtoRepr::build),
@@ -49,6 +49,9 @@ impl ProtoRepr for proto::HouseKeeper {
                 .fri_gpu_prover_archiver_archiving_interval_ms,
             fri_gpu_prover_archiver_archive_after_secs: self
                 .fri_gpu_prover_archiver_archive_after_secs,
+            object_store_gc_interval_ms: self.object_store_gc_interval_ms,
+            object_store_gc_retention_period_secs: self.object_store_gc_retention_period_secs,
+            object_store_gc_dry_run: self.object_store_gc_dry_run.unwrap_or(false),
         })
     }
 
@@ -83,6 +86,9 @@ impl ProtoRepr for proto::HouseKeeper {
                 .fri_gpu_prover_archiver_archiving_interval_ms,
             fri_gpu_prover_archiver_archive_after_secs: this
                 .fri_gpu_prover_archiver_archive_after_secs,
+            object_store_gc_interval_ms: this.object_store_gc_interval_ms,
+            object_store_gc_retention_period_secs: this.object_store_gc_retention_period_secs,
+            object_store_gc_dry_run: Some(this.object_store_gc_dry_run),
         }
     }
 }
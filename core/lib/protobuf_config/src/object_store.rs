@@ -1,9 +1,43 @@
 use anyhow::Context as _;
-use zksync_config::configs::object_store::{ObjectStoreConfig, ObjectStoreMode};
+use zksync_config::configs::object_store::{
+    ObjectCompressionCodec, ObjectStoreConfig, ObjectStoreMode, S3AddressingStyle,
+};
 use zksync_protobuf::{repr::ProtoRepr, required};
 
 use crate::proto::object_store as proto;
 
+impl proto::S3AddressingStyle {
+    fn new(x: &S3AddressingStyle) -> Self {
+        match x {
+            S3AddressingStyle::VirtualHost => Self::VirtualHost,
+            S3AddressingStyle::Path => Self::Path,
+        }
+    }
+
+    fn parse(&self) -> S3AddressingStyle {
+        match self {
+            Self::VirtualHost => S3AddressingStyle::VirtualHost,
+            Self::Path => S3AddressingStyle::Path,
+        }
+    }
+}
+
+impl proto::ObjectCompressionCodec {
+    fn new(x: &ObjectCompressionCodec) -> Self {
+        match x {
+            ObjectCompressionCodec::Gzip => Self::Gzip,
+            ObjectCompressionCodec::Zstd => Self::Zstd,
+        }
+    }
+
+    fn parse(&self) -> ObjectCompressionCodec {
+        match self {
+            Self::Gzip => ObjectCompressionCodec::Gzip,
+            Self::Zstd => ObjectCompressionCodec::Zstd,
+        }
+    }
+}
+
 impl ProtoRepr for proto::ObjectStore {
     type Type = ObjectStoreConfig;
 
@@ -37,6 +71,18 @@ impl ProtoRepr for proto::ObjectStore {
                     .context("file_backed_base_path")?
                     .clone(),
             },
+            proto::object_store::Mode::S3(mode) => ObjectStoreMode::S3 {
+                bucket: required(&mode.bucket).context("bucket")?.clone(),
+                region: required(&mode.region).context("region")?.clone(),
+                endpoint: mode.endpoint.clone(),
+                addressing_style: mode
+                    .addressing_style
+                    .map(|x| anyhow::Ok(proto::S3AddressingStyle::try_from(x)?.parse()))
+                    .transpose()
+                    .context("addressing_style")?
+                    .unwrap_or_default(),
+                s3_credential_file_path: mode.s3_credential_file_path.clone(),
+            },
         };
 
         Ok(Self::Type {
@@ -45,6 +91,20 @@ impl ProtoRepr for proto::ObjectStore {
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("max_retries")?,
             local_mirror_path: self.local_mirror_path.clone(),
+            compression: self
+                .compression
+                .iter()
+                .enumerate()
+                .map(|(i, entry)| {
+                    let bucket =
+                        required(&entry.bucket).with_context(|| format!("[{i}].bucket"))?;
+                    let codec = required(&entry.codec)
+                        .and_then(|&x| Ok(proto::ObjectCompressionCodec::try_from(x)?.parse()))
+                        .with_context(|| format!("[{i}].codec"))?;
+                    Ok((bucket.clone(), codec))
+                })
+                .collect::<anyhow::Result<_>>()
+                .context("compression")?,
         })
     }
 
@@ -76,12 +136,33 @@ impl ProtoRepr for proto::ObjectStore {
             } => proto::object_store::Mode::FileBacked(proto::object_store::FileBacked {
                 file_backed_base_path: Some(file_backed_base_path.clone()),
             }),
+            ObjectStoreMode::S3 {
+                bucket,
+                region,
+                endpoint,
+                addressing_style,
+                s3_credential_file_path,
+            } => proto::object_store::Mode::S3(proto::object_store::S3 {
+                bucket: Some(bucket.clone()),
+                region: Some(region.clone()),
+                endpoint: endpoint.clone(),
+                addressing_style: Some(proto::S3AddressingStyle::new(addressing_style).into()),
+                s3_credential_file_path: s3_credential_file_path.clone(),
+            }),
         };
 
         Self {
             mode: Some(mode),
             max_retries: Some(this.max_retries.into()),
             local_mirror_path: this.local_mirror_path.clone(),
+            compression: this
+                .compression
+                .iter()
+                .map(|(bucket, codec)| proto::BucketCompressionCodec {
+                    bucket: Some(bucket.to_owned()),
+                    codec: Some(proto::ObjectCompressionCodec::new(&codec).into()),
+                })
+                .collect(),
         }
     }
 }
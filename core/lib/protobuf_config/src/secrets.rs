@@ -5,7 +5,7 @@ use secrecy::ExposeSecret;
 use zksync_basic_types::url::SensitiveUrl;
 use zksync_config::configs::{
     consensus::{AttesterSecretKey, ConsensusSecrets, NodeSecretKey, ValidatorSecretKey},
-    secrets::Secrets,
+    secrets::{ApiKey, ApiSecrets, Secrets},
     DatabaseSecrets, L1Secrets,
 };
 use zksync_protobuf::{required, ProtoRepr};
@@ -20,6 +20,7 @@ impl ProtoRepr for proto::Secrets {
             consensus: read_optional_repr(&self.consensus).context("consensus")?,
             database: read_optional_repr(&self.database).context("database")?,
             l1: read_optional_repr(&self.l1).context("l1")?,
+            api: read_optional_repr(&self.api).context("api")?,
         })
     }
 
@@ -28,6 +29,7 @@ impl ProtoRepr for proto::Secrets {
             database: this.database.as_ref().map(ProtoRepr::build),
             l1: this.l1.as_ref().map(ProtoRepr::build),
             consensus: this.consensus.as_ref().map(ProtoRepr::build),
+            api: this.api.as_ref().map(ProtoRepr::build),
         }
     }
 }
@@ -123,3 +125,26 @@ impl ProtoRepr for proto::ConsensusSecrets {
         }
     }
 }
+
+impl ProtoRepr for proto::ApiSecrets {
+    type Type = ApiSecrets;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            api_keys: self
+                .api_keys
+                .iter()
+                .map(|key| ApiKey(key.clone().into()))
+                .collect(),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            api_keys: this
+                .api_keys
+                .iter()
+                .map(|key| key.0.expose_secret().clone())
+                .collect(),
+        }
+    }
+}
@@ -23,6 +23,20 @@ impl ProtoRepr for proto::Wallets {
                 None
             };
 
+            let previous_operator = if let Some(previous_operator) = &self.previous_operator {
+                Some(Wallet::from_private_key_bytes(
+                    parse_h256(
+                        required(&previous_operator.private_key).context("previous operator")?,
+                    )?,
+                    previous_operator
+                        .address
+                        .as_ref()
+                        .and_then(|a| parse_h160(a).ok()),
+                )?)
+            } else {
+                None
+            };
+
             let operator_wallet = &self.operator.clone().context("Operator private key")?;
 
             let operator = Wallet::from_private_key_bytes(
@@ -36,6 +50,7 @@ impl ProtoRepr for proto::Wallets {
             Some(EthSender {
                 operator,
                 blob_operator,
+                previous_operator,
             })
         } else {
             None
@@ -60,32 +75,44 @@ impl ProtoRepr for proto::Wallets {
     }
 
     fn build(this: &Self::Type) -> Self {
-        let (operator, blob_operator) = if let Some(eth_sender) = &this.eth_sender {
-            let blob = eth_sender
-                .blob_operator
-                .as_ref()
-                .map(|blob| proto::PrivateKeyWallet {
-                    address: Some(format!("{:?}", blob.address())),
-                    private_key: Some(hex::encode(
-                        blob.private_key().expose_secret().secret_bytes(),
-                    )),
-                });
-            (
-                Some(proto::PrivateKeyWallet {
-                    address: Some(format!("{:?}", eth_sender.operator.address())),
-                    private_key: Some(hex::encode(
-                        eth_sender
-                            .operator
-                            .private_key()
-                            .expose_secret()
-                            .secret_bytes(),
-                    )),
-                }),
-                blob,
-            )
-        } else {
-            (None, None)
-        };
+        let (operator, blob_operator, previous_operator) =
+            if let Some(eth_sender) = &this.eth_sender {
+                let blob = eth_sender
+                    .blob_operator
+                    .as_ref()
+                    .map(|blob| proto::PrivateKeyWallet {
+                        address: Some(format!("{:?}", blob.address())),
+                        private_key: Some(hex::encode(
+                            blob.private_key().expose_secret().secret_bytes(),
+                        )),
+                    });
+                let previous =
+                    eth_sender
+                        .previous_operator
+                        .as_ref()
+                        .map(|previous| proto::PrivateKeyWallet {
+                            address: Some(format!("{:?}", previous.address())),
+                            private_key: Some(hex::encode(
+                                previous.private_key().expose_secret().secret_bytes(),
+                            )),
+                        });
+                (
+                    Some(proto::PrivateKeyWallet {
+                        address: Some(format!("{:?}", eth_sender.operator.address())),
+                        private_key: Some(hex::encode(
+                            eth_sender
+                                .operator
+                                .private_key()
+                                .expose_secret()
+                                .secret_bytes(),
+                        )),
+                    }),
+                    blob,
+                    previous,
+                )
+            } else {
+                (None, None, None)
+            };
 
         let fee_account = this
             .state_keeper
@@ -97,6 +124,7 @@ impl ProtoRepr for proto::Wallets {
             blob_operator,
             operator,
             fee_account,
+            previous_operator,
         }
     }
 }
@@ -1,3 +1,5 @@
+use std::collections::{BTreeMap, HashMap};
+
 use chrono::{DateTime, Utc};
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
@@ -12,7 +14,9 @@ pub use crate::transaction_request::{
     Eip712Meta, SerializationTransactionError, TransactionRequest,
 };
 use crate::{
+    api::state_override::StateOverride,
     protocol_version::L1VerifierConfig,
+    transaction_request::CallRequest,
     vm_trace::{Call, CallType},
     Address, L2BlockNumber, ProtocolVersionId,
 };
@@ -603,6 +607,48 @@ pub struct ResultDebugCall {
     pub result: DebugCall,
 }
 
+/// Result of tracing a transaction in a block with an arbitrary supported tracer.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultDebugTrace {
+    pub result: DebugTrace,
+}
+
+/// Output of a supported tracer.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum DebugTrace {
+    Call(DebugCall),
+    Prestate(PrestateTrace),
+}
+
+/// Output of the prestate tracer in the Geth-compatible format.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum PrestateTrace {
+    /// Returned in the diff mode.
+    Diff {
+        pre: HashMap<Address, PrestateAccount>,
+        post: HashMap<Address, PrestateAccount>,
+    },
+    Prestate(HashMap<Address, PrestateAccount>),
+}
+
+/// Account state returned by the prestate tracer. Fields are omitted if they are unknown, or (in the diff mode)
+/// if they weren't changed.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PrestateAccount {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub storage: HashMap<H256, H256>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum DebugCallType {
     Call,
@@ -726,24 +772,41 @@ impl ProtocolVersion {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub enum SupportedTracers {
     CallTracer,
+    PrestateTracer,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(default, rename_all = "camelCase")]
 pub struct CallTracerConfig {
     pub only_top_call: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PrestateTracerConfig {
+    /// If set, returns the difference between the pre- and post-state of modified accounts.
+    pub diff_mode: bool,
+}
+
+/// Options for all supported tracers. Options not applicable to the selected tracer are ignored.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+pub struct TracerOptions {
+    #[serde(flatten)]
+    pub call_tracer: CallTracerConfig,
+    #[serde(flatten)]
+    pub prestate_tracer: PrestateTracerConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
 #[serde(rename_all = "camelCase")]
 pub struct TracerConfig {
     pub tracer: SupportedTracers,
     #[serde(default)]
-    pub tracer_config: CallTracerConfig,
+    pub tracer_config: TracerOptions,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -810,6 +873,48 @@ pub struct Proof {
     pub storage_proof: Vec<StorageProof>,
 }
 
+/// Storage slot proof in the [EIP-1186] format.
+///
+/// [EIP-1186]: https://eips.ethereum.org/EIPS/eip-1186
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EIP1186StorageProof {
+    pub key: H256,
+    pub value: U256,
+    /// Merkle path in the ZKsync state tree from the leaf to the root.
+    pub proof: Vec<H256>,
+}
+
+/// Account proof in the [EIP-1186] format, as returned by `eth_getProof`.
+///
+/// ZKsync doesn't have per-account storage tries; all account data is stored in a single sparse Merkle tree.
+/// Thus, `storage_hash` is the root hash of this tree, and `account_proof` is the Merkle path
+/// for the account code hash entry (which is zero for accounts without deployed code).
+///
+/// [EIP-1186]: https://eips.ethereum.org/EIPS/eip-1186
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EIP1186Proof {
+    pub address: Address,
+    pub account_proof: Vec<H256>,
+    pub balance: U256,
+    pub code_hash: H256,
+    pub nonce: U256,
+    pub storage_hash: H256,
+    pub storage_proof: Vec<EIP1186StorageProof>,
+}
+
+/// Result of `eth_createAccessList`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListResult {
+    pub access_list: AccessList,
+    pub gas_used: U256,
+    /// Error message if the call has reverted or halted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionDetailedResult {
@@ -834,6 +939,114 @@ pub struct TransactionExecutionInfo {
     pub execution_info: Value,
 }
 
+/// Result of `txpool_status`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TxpoolStatus {
+    /// Number of transactions that can be executed given the current account nonces.
+    pub pending: U64,
+    /// Number of transactions that are blocked by a nonce gap.
+    pub queued: U64,
+}
+
+/// Pending and queued transactions of a single account keyed by nonce.
+/// Used as a result of `txpool_contentFrom`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "T: Serialize + de::DeserializeOwned")]
+pub struct TxpoolContentFrom<T> {
+    pub pending: BTreeMap<u64, T>,
+    pub queued: BTreeMap<u64, T>,
+}
+
+impl<T> Default for TxpoolContentFrom<T> {
+    fn default() -> Self {
+        Self {
+            pending: BTreeMap::new(),
+            queued: BTreeMap::new(),
+        }
+    }
+}
+
+/// Pending and queued transactions keyed by the initiator address and nonce. Used as a result of `txpool_content`
+/// (with `T = Transaction`) and `txpool_inspect` (with `T = String` containing a human-readable transaction summary).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "T: Serialize + de::DeserializeOwned")]
+pub struct TxpoolContent<T> {
+    pub pending: BTreeMap<Address, BTreeMap<u64, T>>,
+    pub queued: BTreeMap<Address, BTreeMap<u64, T>>,
+}
+
+impl<T> Default for TxpoolContent<T> {
+    fn default() -> Self {
+        Self {
+            pending: BTreeMap::new(),
+            queued: BTreeMap::new(),
+        }
+    }
+}
+
+/// Payload of `eth_simulateV1`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+    /// Simulated blocks. Each block is executed on top of the state produced by the preceding ones.
+    pub block_state_calls: Vec<SimulateBlock>,
+}
+
+/// Block simulated by `eth_simulateV1`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateBlock {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_overrides: Option<SimulateBlockOverrides>,
+    /// State overrides applied before executing the block. Only supported for the first simulated block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<StateOverride>,
+    /// Calls executed in the block in order. Each call observes the changes made by the preceding ones.
+    #[serde(default)]
+    pub calls: Vec<CallRequest>,
+}
+
+/// Block environment overrides for `eth_simulateV1`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateBlockOverrides {
+    /// Block timestamp. Must be greater than the timestamp of the previous simulated block; cannot be overridden
+    /// for the first simulated block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<U64>,
+}
+
+/// Block returned by `eth_simulateV1`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedBlock {
+    pub number: U64,
+    pub timestamp: U64,
+    pub gas_used: U256,
+    pub calls: Vec<SimulatedCall>,
+}
+
+/// Result of a single call in a block returned by `eth_simulateV1`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedCall {
+    /// 1 if the call succeeded, 0 otherwise.
+    pub status: U64,
+    /// Call output; for reverted calls, the revert data.
+    pub return_data: Bytes,
+    pub gas_used: U256,
+    pub logs: Vec<Log>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<SimulatedCallError>,
+}
+
+/// Error of a call in a block returned by `eth_simulateV1`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimulatedCallError {
+    pub code: i64,
+    pub message: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -867,4 +1080,77 @@ mod tests {
         serde_json::from_str::<OldProtocolVersion>(&serde_json::to_string(&new_version).unwrap())
             .unwrap();
     }
+
+    #[test]
+    fn deserializing_tracer_config() {
+        let config: TracerConfig = serde_json::from_value(serde_json::json!({
+            "tracer": "callTracer",
+            "tracerConfig": { "onlyTopCall": true },
+        }))
+        .unwrap();
+        assert_eq!(config.tracer, SupportedTracers::CallTracer);
+        assert!(config.tracer_config.call_tracer.only_top_call);
+        assert!(!config.tracer_config.prestate_tracer.diff_mode);
+
+        let config: TracerConfig = serde_json::from_value(serde_json::json!({
+            "tracer": "prestateTracer",
+            "tracerConfig": { "diffMode": true },
+        }))
+        .unwrap();
+        assert_eq!(config.tracer, SupportedTracers::PrestateTracer);
+        assert!(!config.tracer_config.call_tracer.only_top_call);
+        assert!(config.tracer_config.prestate_tracer.diff_mode);
+
+        let config: TracerConfig =
+            serde_json::from_value(serde_json::json!({ "tracer": "prestateTracer" })).unwrap();
+        assert!(!config.tracer_config.prestate_tracer.diff_mode);
+    }
+
+    #[test]
+    fn serializing_prestate_trace() {
+        let address = Address::repeat_byte(1);
+        let account = PrestateAccount {
+            balance: Some(123.into()),
+            nonce: Some(1),
+            code: None,
+            storage: HashMap::from([(H256::zero(), H256::repeat_byte(0xff))]),
+        };
+        let trace = DebugTrace::Prestate(PrestateTrace::Prestate(HashMap::from([(
+            address,
+            account.clone(),
+        )])));
+        let json = serde_json::to_value(&trace).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                format!("{address:?}"): {
+                    "balance": "0x7b",
+                    "nonce": 1,
+                    "storage": {
+                        format!("{:?}", H256::zero()): format!("{:?}", H256::repeat_byte(0xff)),
+                    },
+                },
+            })
+        );
+        let restored: DebugTrace = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, trace);
+
+        let diff = DebugTrace::Prestate(PrestateTrace::Diff {
+            pre: HashMap::from([(address, account)]),
+            post: HashMap::from([(
+                address,
+                PrestateAccount {
+                    balance: Some(456.into()),
+                    ..PrestateAccount::default()
+                },
+            )]),
+        });
+        let json = serde_json::to_value(&diff).unwrap();
+        assert_eq!(
+            json["post"],
+            serde_json::json!({ format!("{address:?}"): { "balance": "0x1c8" } })
+        );
+        let restored: DebugTrace = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, diff);
+    }
 }
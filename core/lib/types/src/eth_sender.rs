@@ -48,7 +48,8 @@ pub struct EthTx {
     pub created_at_timestamp: u64,
     pub predicted_gas_cost: u64,
     /// If this field is `Some` then it contains address of a custom operator that has sent
-    /// this transaction. If it is set to `None` this transaction was sent by the main operator.
+    /// this transaction (e.g., the blob operator, or the main operator whose key was rotated out).
+    /// If it is set to `None` this transaction was sent by the current main operator.
     pub from_addr: Option<Address>,
     pub blob_sidecar: Option<EthTxBlobSidecar>,
 }
@@ -89,3 +90,16 @@ pub struct TxHistoryToSend {
     pub signed_raw_tx: Vec<u8>,
     pub nonce: Nonce,
 }
+
+/// Rotation of the main operator key recorded in the database.
+///
+/// When a rotation starts, all transactions of the main operator are attributed to the previous operator address,
+/// and the new operator starts its own nonce sequence. Transactions of the previous operator are still monitored
+/// (and resent if necessary) until all of them are confirmed, at which point the rotation is completed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OperatorRotation {
+    pub previous_operator: Address,
+    pub new_operator: Address,
+    pub started_at_timestamp: u64,
+    pub completed_at_timestamp: Option<u64>,
+}
@@ -0,0 +1,53 @@
+use anyhow::Context as _;
+
+use crate::{web3::Log, Address, L1BlockNumber, H256};
+
+/// L1 event captured by one of the `eth_watch` event subscriptions.
+///
+/// Events are stored as is; decoding them according to the emitting contract ABI is up to the consumer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct L1Event {
+    pub l1_block_number: L1BlockNumber,
+    pub l1_block_hash: H256,
+    pub tx_hash: H256,
+    /// Index of the event in the L1 block.
+    pub log_index: u32,
+    pub address: Address,
+    /// Event topics; the first topic is the hashed event signature for non-anonymous events.
+    pub topics: Vec<H256>,
+    pub data: Vec<u8>,
+}
+
+impl TryFrom<Log> for L1Event {
+    type Error = anyhow::Error;
+
+    fn try_from(log: Log) -> Result<Self, Self::Error> {
+        let l1_block_number = log.block_number.context("missing block number")?;
+        let l1_block_number = u32::try_from(l1_block_number.as_u64())
+            .map_err(|_| anyhow::anyhow!("block number {l1_block_number} overflows u32"))?;
+        let log_index = log.log_index.context("missing log index")?;
+        anyhow::ensure!(
+            log_index <= u32::MAX.into(),
+            "log index {log_index} overflows u32"
+        );
+        anyhow::ensure!(log.topics.len() <= 4, "too many topics: {:?}", log.topics);
+
+        Ok(Self {
+            l1_block_number: L1BlockNumber(l1_block_number),
+            l1_block_hash: log.block_hash.context("missing block hash")?,
+            tx_hash: log.transaction_hash.context("missing transaction hash")?,
+            log_index: log_index.as_u32(),
+            address: log.address,
+            topics: log.topics,
+            data: log.data.0,
+        })
+    }
+}
+
+/// Progress of an `eth_watch` event subscription: the last processed L1 block together with its hash,
+/// which is used to detect L1 reorgs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct L1EventCheckpoint {
+    pub l1_block_number: L1BlockNumber,
+    pub l1_block_hash: H256,
+}
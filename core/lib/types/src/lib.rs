@@ -59,6 +59,7 @@ pub mod zk_evm_types;
 pub mod api;
 pub mod base_token_ratio;
 pub mod eth_sender;
+pub mod eth_watch;
 pub mod helpers;
 pub mod proto;
 pub mod transaction_request;
@@ -31,7 +31,21 @@ impl From<PubdataSendingMode> for PubdataDA {
 #[derive(Debug, Clone)]
 pub struct DataAvailabilityBlob {
     pub l1_batch_number: L1BatchNumber,
+    /// ID of the blob. If the pubdata was split into several chunks, this is the ID of the first chunk.
     pub blob_id: String,
+    /// IDs of blobs for each pubdata chunk if the pubdata was split into several chunks; empty otherwise.
+    pub chunk_blob_ids: Vec<String>,
     pub inclusion_data: Option<Vec<u8>>,
     pub sent_at: DateTime<Utc>,
 }
+
+impl DataAvailabilityBlob {
+    /// Returns IDs of all blobs the pubdata was dispatched in.
+    pub fn blob_ids(&self) -> Vec<&str> {
+        if self.chunk_blob_ids.is_empty() {
+            vec![self.blob_id.as_str()]
+        } else {
+            self.chunk_blob_ids.iter().map(String::as_str).collect()
+        }
+    }
+}
@@ -387,6 +387,7 @@ impl fmt::Debug for LegacyCall {
 pub enum ViolatedValidationRule {
     TouchedUnallowedStorageSlots(Address, U256),
     CalledContractWithNoCode(Address),
+    /// Raised if the `meta` context opcode is used, unless it's explicitly allowed by the operator.
     TouchedUnallowedContext,
     TookTooManyComputationalGas(u32),
 }
@@ -404,7 +405,10 @@ impl Display for ViolatedValidationRule {
                 write!(f, "Called contract with no code: {}", hex::encode(contract))
             }
             ViolatedValidationRule::TouchedUnallowedContext => {
-                write!(f, "Touched unallowed context")
+                write!(
+                    f,
+                    "Touched unallowed context: `meta` opcode is not allowed during validation"
+                )
             }
             ViolatedValidationRule::TookTooManyComputationalGas(gas_limit) => {
                 write!(
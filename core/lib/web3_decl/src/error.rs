@@ -25,6 +25,11 @@ pub enum Web3Error {
     PrunedBlock(L2BlockNumber),
     #[error("L1 batch with such an ID is pruned; the first retained L1 batch is {0}")]
     PrunedL1Batch(L1BatchNumber),
+    #[error(
+        "Block {0} is pruned and not the last block in its L1 batch; archived state is only available \
+         for the last blocks in L1 batches, e.g. block {1}"
+    )]
+    NonFinalArchivedBlock(L2BlockNumber, L2BlockNumber),
     #[error("{}", _0.as_ref())]
     ProxyError(#[from] EnrichedClientError),
     #[error("{0}")]
@@ -39,6 +44,8 @@ pub enum Web3Error {
     LogsLimitExceeded(usize, u32, u32),
     #[error("invalid filter: if blockHash is supplied fromBlock and toBlock must not be")]
     InvalidFilterBlockHash,
+    #[error("Invalid simulation request: {0}")]
+    InvalidSimulation(String),
     /// Weaker form of a "method not found" error; the method implementation is technically present,
     /// but the node configuration prevents the method from functioning.
     #[error("Method not implemented")]
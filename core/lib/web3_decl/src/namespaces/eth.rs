@@ -3,8 +3,8 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
     api::{
-        state_override::StateOverride, BlockId, BlockIdVariant, BlockNumber, Transaction,
-        TransactionVariant,
+        state_override::StateOverride, AccessListResult, BlockId, BlockIdVariant, BlockNumber,
+        EIP1186Proof, SimulatePayload, SimulatedBlock, Transaction, TransactionVariant,
     },
     transaction_request::CallRequest,
     Address, H256,
@@ -49,6 +49,21 @@ pub trait EthNamespace {
         state_override: Option<StateOverride>,
     ) -> RpcResult<U256>;
 
+    #[method(name = "createAccessList")]
+    async fn create_access_list(
+        &self,
+        req: CallRequest,
+        block: Option<BlockIdVariant>,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<AccessListResult>;
+
+    #[method(name = "simulateV1")]
+    async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<Vec<SimulatedBlock>>;
+
     #[method(name = "gasPrice")]
     async fn gas_price(&self) -> RpcResult<U256>;
 
@@ -120,6 +135,14 @@ pub trait EthNamespace {
         block: Option<BlockIdVariant>,
     ) -> RpcResult<H256>;
 
+    #[method(name = "getProof")]
+    async fn get_proof(
+        &self,
+        address: Address,
+        keys: Vec<H256>,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<Option<EIP1186Proof>>;
+
     #[method(name = "getTransactionCount")]
     async fn get_transaction_count(
         &self,
@@ -0,0 +1,34 @@
+#[cfg_attr(not(feature = "server"), allow(unused_imports))]
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+use zksync_types::{
+    api::{Transaction, TxpoolContent, TxpoolContentFrom, TxpoolStatus},
+    Address,
+};
+
+use crate::client::{ForNetwork, L2};
+
+/// Introspection of transactions that are accepted by the node, but are not included into an L2 block yet.
+/// Mirrors the `txpool` namespace of Geth. Transactions are split into `pending` ones (executable given
+/// the current account nonces) and `queued` ones (blocked by a nonce gap).
+#[cfg_attr(
+    feature = "server",
+    rpc(server, client, namespace = "txpool", client_bounds(Self: ForNetwork<Net = L2>))
+)]
+#[cfg_attr(
+    not(feature = "server"),
+    rpc(client, namespace = "txpool", client_bounds(Self: ForNetwork<Net = L2>))
+)]
+pub trait TxpoolNamespace {
+    #[method(name = "status")]
+    async fn status(&self) -> RpcResult<TxpoolStatus>;
+
+    #[method(name = "content")]
+    async fn content(&self) -> RpcResult<TxpoolContent<Transaction>>;
+
+    #[method(name = "contentFrom")]
+    async fn content_from(&self, address: Address) -> RpcResult<TxpoolContentFrom<Transaction>>;
+
+    #[method(name = "inspect")]
+    async fn inspect(&self) -> RpcResult<TxpoolContent<String>>;
+}
@@ -62,6 +62,8 @@ pub enum Component {
     BaseTokenRatioPersister,
     /// VM runner-based component that saves VM execution data for basic witness generation.
     VmRunnerBwip,
+    /// VM runner-based component that exports call traces and state diffs to the object store.
+    VmRunnerTraceExporter,
 }
 
 #[derive(Debug)]
@@ -106,6 +108,7 @@ impl FromStr for Components {
                 Ok(Components(vec![Component::BaseTokenRatioPersister]))
             }
             "vm_runner_bwip" => Ok(Components(vec![Component::VmRunnerBwip])),
+            "vm_runner_trace_exporter" => Ok(Components(vec![Component::VmRunnerTraceExporter])),
             other => Err(format!("{} is not a valid component name", other)),
         }
     }
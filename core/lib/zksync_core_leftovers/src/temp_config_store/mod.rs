@@ -10,7 +10,7 @@ use zksync_config::{
         },
         fri_prover_group::FriProverGroupConfig,
         house_keeper::HouseKeeperConfig,
-        vm_runner::BasicWitnessInputProducerConfig,
+        vm_runner::{BasicWitnessInputProducerConfig, TraceExporterConfig},
         wallets::{AddressWallet, EthSender, StateKeeper, Wallet, Wallets},
         CommitmentGeneratorConfig, DatabaseSecrets, ExternalPriceApiClientConfig,
         FriProofCompressorConfig, FriProverConfig, FriProverGatewayConfig,
@@ -71,6 +71,7 @@ pub struct TempConfigStore {
     pub da_dispatcher_config: Option<DADispatcherConfig>,
     pub protective_reads_writer_config: Option<ProtectiveReadsWriterConfig>,
     pub basic_witness_input_producer_config: Option<BasicWitnessInputProducerConfig>,
+    pub trace_exporter_config: Option<TraceExporterConfig>,
     pub core_object_store: Option<ObjectStoreConfig>,
     pub base_token_adjuster_config: Option<BaseTokenAdjusterConfig>,
     pub commitment_generator: Option<CommitmentGeneratorConfig>,
@@ -105,6 +106,7 @@ impl TempConfigStore {
             da_dispatcher_config: self.da_dispatcher_config.clone(),
             protective_reads_writer_config: self.protective_reads_writer_config.clone(),
             basic_witness_input_producer_config: self.basic_witness_input_producer_config.clone(),
+            trace_exporter_config: self.trace_exporter_config.clone(),
             core_object_store: self.core_object_store.clone(),
             base_token_adjuster: self.base_token_adjuster_config.clone(),
             commitment_generator: self.commitment_generator.clone(),
@@ -127,6 +129,7 @@ impl TempConfigStore {
             Some(EthSender {
                 operator,
                 blob_operator,
+                previous_operator: None,
             })
         });
         let state_keeper = self
@@ -177,6 +180,7 @@ fn load_env_config() -> anyhow::Result<TempConfigStore> {
         da_dispatcher_config: DADispatcherConfig::from_env().ok(),
         protective_reads_writer_config: ProtectiveReadsWriterConfig::from_env().ok(),
         basic_witness_input_producer_config: BasicWitnessInputProducerConfig::from_env().ok(),
+        trace_exporter_config: TraceExporterConfig::from_env().ok(),
         core_object_store: ObjectStoreConfig::from_env().ok(),
         base_token_adjuster_config: BaseTokenAdjusterConfig::from_env().ok(),
         commitment_generator: None,
@@ -93,7 +93,12 @@ impl<'a> Sandbox<'a> {
         .context("cannot create `PostgresStorage`")?
         .with_caches(shared_args.caches.clone());
 
-        let storage_with_overrides = StorageWithOverrides::new(storage, state_override);
+        let pending_state = shared_args
+            .pending_state
+            .clone()
+            .filter(|_| block_args.is_pending_l2_block());
+        let storage_with_overrides =
+            StorageWithOverrides::new(storage, pending_state, state_override);
         let storage_view = StorageView::new(storage_with_overrides);
         let (system_env, l1_batch_env) = Self::prepare_env(
             shared_args,
@@ -305,6 +310,45 @@ pub(super) fn apply_vm_in_sandbox<T>(
         Transaction,
         ProtocolVersionId,
     ) -> T,
+) -> anyhow::Result<T> {
+    apply_vm_in_sandbox_with_env(
+        vm_permit,
+        shared_args,
+        adjust_pubdata_price,
+        execution_args,
+        connection_pool,
+        tx,
+        block_args,
+        state_override,
+        |vm, _, tx, env| apply(vm, tx, env.protocol_version),
+    )
+}
+
+/// Environment of the VM instantiated by [`apply_vm_in_sandbox_with_env()`].
+#[derive(Debug, Clone, Copy)]
+pub(super) struct SandboxEnv {
+    pub protocol_version: ProtocolVersionId,
+    /// Environment of the first L2 block executed by the VM.
+    pub first_l2_block: L2BlockEnv,
+}
+
+/// Same as [`apply_vm_in_sandbox()`], but additionally provides the VM storage and environment to the `apply` closure.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn apply_vm_in_sandbox_with_env<T>(
+    vm_permit: VmPermit,
+    shared_args: TxSharedArgs,
+    adjust_pubdata_price: bool,
+    execution_args: &TxExecutionArgs,
+    connection_pool: &ConnectionPool<Core>,
+    tx: Transaction,
+    block_args: BlockArgs,
+    state_override: Option<StateOverride>,
+    apply: impl FnOnce(
+        &mut VmInstance<VmStorageView<'_>, HistoryDisabled>,
+        &StoragePtr<VmStorageView<'_>>,
+        Transaction,
+        SandboxEnv,
+    ) -> T,
 ) -> anyhow::Result<T> {
     let stage_started_at = Instant::now();
     let span = tracing::debug_span!("initialization").entered();
@@ -326,7 +370,10 @@ pub(super) fn apply_vm_in_sandbox<T>(
         block_args,
         state_override.as_ref().unwrap_or(&StateOverride::default()),
     ))?;
-    let protocol_version = sandbox.system_env.version;
+    let env = SandboxEnv {
+        protocol_version: sandbox.system_env.version,
+        first_l2_block: sandbox.l1_batch_env.first_l2_block,
+    };
     let (mut vm, storage_view) = sandbox.into_vm(&tx, adjust_pubdata_price);
 
     SANDBOX_METRICS.sandbox[&SandboxStage::Initialization].observe(stage_started_at.elapsed());
@@ -339,7 +386,7 @@ pub(super) fn apply_vm_in_sandbox<T>(
     );
 
     let execution_latency = SANDBOX_METRICS.sandbox[&SandboxStage::Execution].start();
-    let result = apply(&mut vm, tx, protocol_version);
+    let result = apply(&mut vm, &storage_view, tx, env);
     let vm_execution_took = execution_latency.observe();
 
     let memory_metrics = vm.record_vm_memory_metrics();
@@ -423,7 +470,7 @@ pub(crate) struct ResolvedBlockInfo {
 }
 
 impl BlockArgs {
-    fn is_pending_l2_block(&self) -> bool {
+    pub(crate) fn is_pending_l2_block(&self) -> bool {
         matches!(
             self.block_id,
             api::BlockId::Number(api::BlockNumber::Pending)
@@ -4,13 +4,19 @@ use anyhow::Context as _;
 use tracing::{span, Level};
 use zksync_dal::{ConnectionPool, Core};
 use zksync_multivm::{
-    interface::{TxExecutionMode, VmExecutionResultAndLogs, VmInterface},
+    interface::{L2BlockEnv, TxExecutionMode, VmExecutionResultAndLogs, VmInterface},
     tracers::StorageInvocations,
+    utils::get_bootloader_max_txs_in_batch,
     MultiVMTracer,
 };
+use zksync_state::ReadStorage;
+use zksync_system_constants::{
+    SYSTEM_CONTEXT_ADDRESS, SYSTEM_CONTEXT_CURRENT_TX_ROLLING_HASH_POSITION,
+};
 use zksync_types::{
-    fee::TransactionExecutionMetrics, l2::L2Tx, transaction_request::CallOverrides,
-    ExecuteTransactionCommon, Nonce, PackedEthSignature, Transaction, U256,
+    block::L2BlockHasher, fee::TransactionExecutionMetrics, l2::L2Tx,
+    transaction_request::CallOverrides, AccountTreeId, ExecuteTransactionCommon, L2BlockNumber,
+    Nonce, PackedEthSignature, StorageKey, Transaction, U256,
 };
 
 use super::{
@@ -39,6 +45,17 @@ impl TxExecutionArgs {
         }
     }
 
+    /// Arguments for re-executing transactions from a sealed L2 block.
+    pub fn for_replay() -> Self {
+        Self {
+            execution_mode: TxExecutionMode::VerifyExecute,
+            enforced_nonce: None,
+            added_balance: U256::zero(),
+            enforced_base_fee: None,
+            missed_storage_invocation_limit: usize::MAX,
+        }
+    }
+
     fn for_eth_call(
         enforced_base_fee: Option<u64>,
         vm_execution_cache_misses_limit: Option<usize>,
@@ -77,6 +94,67 @@ impl TxExecutionArgs {
     }
 }
 
+/// Block of transactions simulated by [`TransactionExecutor::simulate_blocks_in_sandbox()`].
+#[derive(Debug)]
+pub(crate) struct SimulationBlock {
+    /// Overridden block timestamp. If not set, the timestamp is derived from the previous block.
+    pub timestamp: Option<u64>,
+    pub txs: Vec<Transaction>,
+}
+
+/// Output of a block simulated by [`TransactionExecutor::simulate_blocks_in_sandbox()`].
+#[derive(Debug)]
+pub(crate) struct SimulatedBlockOutput {
+    pub number: L2BlockNumber,
+    pub timestamp: u64,
+    /// Execution results for each transaction in the block.
+    pub results: Vec<VmExecutionResultAndLogs>,
+}
+
+/// Errors that can occur when simulating blocks in the sandbox.
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum SimulationError {
+    /// Simulated blocks are invalid, e.g. have non-monotonic timestamps.
+    #[error("{0}")]
+    InvalidBlocks(String),
+    #[error(transparent)]
+    Internal(#[from] anyhow::Error),
+}
+
+/// Resolves timestamps of simulated blocks given the timestamp of the first block, which is defined by the VM
+/// environment and cannot be overridden. Timestamps of the following blocks must strictly increase.
+pub(crate) fn resolve_block_timestamps(
+    blocks: &[SimulationBlock],
+    first_timestamp: u64,
+) -> Result<Vec<u64>, SimulationError> {
+    let Some(first_block) = blocks.first() else {
+        return Ok(vec![]);
+    };
+    if first_block
+        .timestamp
+        .is_some_and(|ts| ts != first_timestamp)
+    {
+        return Err(SimulationError::InvalidBlocks(
+            "timestamp of the first simulated block cannot be overridden".to_owned(),
+        ));
+    }
+
+    let mut timestamps = Vec::with_capacity(blocks.len());
+    timestamps.push(first_timestamp);
+    for (i, block) in blocks.iter().enumerate().skip(1) {
+        let prev_timestamp = timestamps[i - 1];
+        let timestamp = block.timestamp.unwrap_or(prev_timestamp + 1);
+        if timestamp <= prev_timestamp {
+            return Err(SimulationError::InvalidBlocks(format!(
+                "timestamp of simulated block #{i} ({timestamp}) must be greater than \
+                 the timestamp of the previous block ({prev_timestamp})"
+            )));
+        }
+        timestamps.push(timestamp);
+    }
+    Ok(timestamps)
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct TransactionExecutionOutput {
     /// Output of the VM.
@@ -162,6 +240,196 @@ impl TransactionExecutor {
         })
     }
 
+    /// Sequentially executes transactions in a single VM instance, so that each transaction observes
+    /// the changes made by the preceding ones. Each transaction is executed with its own set of tracers.
+    #[tracing::instrument(skip_all)]
+    pub async fn replay_txs_in_sandbox(
+        &self,
+        vm_permit: VmPermit,
+        shared_args: TxSharedArgs,
+        connection_pool: ConnectionPool<Core>,
+        block_args: BlockArgs,
+        txs: Vec<(Transaction, Vec<ApiTracer>)>,
+    ) -> anyhow::Result<Vec<VmExecutionResultAndLogs>> {
+        if let Self::Mock(mock_executor) = self {
+            return txs
+                .iter()
+                .map(|(tx, _)| Ok(mock_executor.execute_tx(tx, &block_args)?.vm))
+                .collect();
+        }
+        let Some((first_tx, _)) = txs.first() else {
+            return Ok(vec![]);
+        };
+        let first_tx = first_tx.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let span = span!(Level::DEBUG, "replay_in_sandbox").entered();
+            let execution_args = TxExecutionArgs::for_replay();
+            let result = apply::apply_vm_in_sandbox(
+                vm_permit,
+                shared_args,
+                false,
+                &execution_args,
+                &connection_pool,
+                first_tx,
+                block_args,
+                None,
+                |vm, _, _| {
+                    txs.into_iter()
+                        .map(|(tx, custom_tracers)| {
+                            let custom_tracers: Vec<_> = custom_tracers
+                                .into_iter()
+                                .map(|tracer| tracer.into_boxed())
+                                .collect();
+                            let (_, result) = vm.inspect_transaction_with_bytecode_compression(
+                                custom_tracers.into(),
+                                tx,
+                                true,
+                            );
+                            result
+                        })
+                        .collect()
+                },
+            );
+            span.exit();
+            result
+        })
+        .await
+        .context("transaction replay panicked")?
+    }
+
+    /// Simulates one or more L2 blocks in a single VM instance, so that each transaction observes the changes
+    /// made by the preceding ones, including transactions in the preceding blocks. Transactions are executed
+    /// in the `eth_call` mode.
+    #[allow(clippy::too_many_arguments)]
+    #[tracing::instrument(skip_all)]
+    pub async fn simulate_blocks_in_sandbox(
+        &self,
+        vm_permit: VmPermit,
+        shared_args: TxSharedArgs,
+        connection_pool: ConnectionPool<Core>,
+        enforced_base_fee: Option<u64>,
+        vm_execution_cache_misses_limit: Option<usize>,
+        block_args: BlockArgs,
+        state_override: Option<StateOverride>,
+        blocks: Vec<SimulationBlock>,
+    ) -> Result<Vec<SimulatedBlockOutput>, SimulationError> {
+        if let Self::Mock(mock_executor) = self {
+            // The mock executor doesn't have a VM environment, so the L1 batch timestamp stands in
+            // for the timestamp of the first simulated block.
+            let first_timestamp = block_args.l1_batch_timestamp_s.unwrap_or_default();
+            let timestamps = resolve_block_timestamps(&blocks, first_timestamp)?;
+            let first_block_number = block_args.resolved_block_number();
+            return blocks
+                .into_iter()
+                .zip(timestamps)
+                .enumerate()
+                .map(|(i, (block, timestamp))| {
+                    let results = block
+                        .txs
+                        .iter()
+                        .map(|tx| Ok(mock_executor.execute_tx(tx, &block_args)?.vm))
+                        .collect::<anyhow::Result<_>>()?;
+                    Ok(SimulatedBlockOutput {
+                        number: first_block_number + i as u32,
+                        timestamp,
+                        results,
+                    })
+                })
+                .collect();
+        }
+
+        let first_tx = blocks
+            .iter()
+            .flat_map(|block| &block.txs)
+            .next()
+            .context("no transactions to simulate")?
+            .clone();
+        let execution_args =
+            TxExecutionArgs::for_eth_call(enforced_base_fee, vm_execution_cache_misses_limit);
+
+        tokio::task::spawn_blocking(move || {
+            let span = span!(Level::DEBUG, "simulate_in_sandbox").entered();
+            let result = apply::apply_vm_in_sandbox_with_env(
+                vm_permit,
+                shared_args,
+                false,
+                &execution_args,
+                &connection_pool,
+                first_tx,
+                block_args,
+                state_override,
+                |vm, storage_view, _, env| {
+                    let tx_count: usize = blocks.iter().map(|block| block.txs.len()).sum();
+                    let max_tx_count = get_bootloader_max_txs_in_batch(env.protocol_version.into());
+                    if tx_count > max_tx_count {
+                        return Err(SimulationError::InvalidBlocks(format!(
+                            "too many calls to simulate: {tx_count}, max {max_tx_count}"
+                        )));
+                    }
+
+                    let rolling_hash_key = StorageKey::new(
+                        AccountTreeId::new(SYSTEM_CONTEXT_ADDRESS),
+                        SYSTEM_CONTEXT_CURRENT_TX_ROLLING_HASH_POSITION,
+                    );
+                    let timestamps =
+                        resolve_block_timestamps(&blocks, env.first_l2_block.timestamp)?;
+                    let mut l2_block = env.first_l2_block;
+                    let mut outputs = Vec::with_capacity(blocks.len());
+                    for (i, (block, timestamp)) in blocks.into_iter().zip(timestamps).enumerate() {
+                        if i > 0 {
+                            // The bootloader maintains the rolling hash of transactions in the current L2 block
+                            // in the system context storage, so we can use it to compute the previous block hash.
+                            let txs_rolling_hash =
+                                storage_view.borrow_mut().read_value(&rolling_hash_key);
+                            let prev_block_hash = L2BlockHasher::hash(
+                                L2BlockNumber(l2_block.number),
+                                l2_block.timestamp,
+                                l2_block.prev_block_hash,
+                                txs_rolling_hash,
+                                env.protocol_version,
+                            );
+                            l2_block = L2BlockEnv {
+                                number: l2_block.number + 1,
+                                timestamp,
+                                prev_block_hash,
+                                max_virtual_blocks_to_create: 1,
+                            };
+                            vm.start_new_l2_block(l2_block);
+                        }
+
+                        let results = block
+                            .txs
+                            .into_iter()
+                            .map(|tx| {
+                                let storage_invocation_tracer = StorageInvocations::new(
+                                    execution_args.missed_storage_invocation_limit,
+                                );
+                                let tracers = vec![storage_invocation_tracer.into_tracer_pointer()];
+                                let (_, result) = vm.inspect_transaction_with_bytecode_compression(
+                                    tracers.into(),
+                                    tx,
+                                    true,
+                                );
+                                result
+                            })
+                            .collect();
+                        outputs.push(SimulatedBlockOutput {
+                            number: L2BlockNumber(l2_block.number),
+                            timestamp: l2_block.timestamp,
+                            results,
+                        });
+                    }
+                    Ok(outputs)
+                },
+            );
+            span.exit();
+            result
+        })
+        .await
+        .context("block simulation panicked")??
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn execute_tx_eth_call(
         &self,
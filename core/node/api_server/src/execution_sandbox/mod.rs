@@ -8,6 +8,8 @@ use rand::{thread_rng, Rng};
 use tokio::runtime::Handle;
 use zksync_dal::{pruning_dal::PruningInfo, Connection, Core, CoreDal, DalError};
 use zksync_state::PostgresStorageCaches;
+pub use zksync_state_keeper::CustomValidationRules;
+use zksync_state_keeper::PendingStateSnapshot;
 use zksync_types::{
     api, fee_model::BatchFeeInput, AccountTreeId, Address, L1BatchNumber, L2BlockNumber, L2ChainId,
 };
@@ -15,7 +17,10 @@ use zksync_types::{
 use self::vm_metrics::SandboxStage;
 pub(super) use self::{
     error::SandboxExecutionError,
-    execute::{TransactionExecutor, TxExecutionArgs},
+    execute::{
+        SimulatedBlockOutput, SimulationBlock, SimulationError, TransactionExecutor,
+        TxExecutionArgs,
+    },
     tracers::ApiTracer,
     validate::ValidationError,
     vm_metrics::{SubmitTxStage, SANDBOX_METRICS},
@@ -173,6 +178,8 @@ pub(crate) struct TxSharedArgs {
     pub validation_computational_gas_limit: u32,
     pub chain_id: L2ChainId,
     pub whitelisted_tokens_for_aa: Vec<Address>,
+    /// State keeper pending state applied on top of the persisted state when executing in the pending L2 block.
+    pub pending_state: Option<PendingStateSnapshot>,
 }
 
 impl TxSharedArgs {
@@ -186,6 +193,7 @@ impl TxSharedArgs {
             validation_computational_gas_limit: u32::MAX,
             chain_id: L2ChainId::default(),
             whitelisted_tokens_for_aa: Vec::new(),
+            pending_state: None,
         }
     }
 }
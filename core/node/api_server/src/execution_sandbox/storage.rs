@@ -6,18 +6,22 @@ use std::{
 };
 
 use zksync_state::ReadStorage;
+use zksync_state_keeper::PendingStateSnapshot;
 use zksync_types::{
     api::state_override::{OverrideState, StateOverride},
     get_code_key, get_nonce_key,
     utils::{decompose_full_nonce, nonces_to_full_nonce, storage_key_for_eth_balance},
-    AccountTreeId, StorageKey, StorageValue, H256,
+    AccountTreeId, StorageKey, StorageValue, H256, SYSTEM_CONTEXT_ADDRESS,
 };
 use zksync_utils::{h256_to_u256, u256_to_h256};
 
 /// A storage view that allows to override some of the storage values.
+///
+/// Besides explicit overrides, the storage can apply the state keeper pending state on top of the underlying storage.
 #[derive(Debug)]
 pub(super) struct StorageWithOverrides<S> {
     storage_handle: S,
+    pending_state: Option<PendingStateSnapshot>,
     overridden_slots: HashMap<StorageKey, H256>,
     overridden_factory_deps: HashMap<H256, Vec<u8>>,
     overridden_accounts: HashSet<AccountTreeId>,
@@ -25,9 +29,14 @@ pub(super) struct StorageWithOverrides<S> {
 
 impl<S: ReadStorage> StorageWithOverrides<S> {
     /// Creates a new storage view based on the underlying storage.
-    pub(super) fn new(storage: S, state_override: &StateOverride) -> Self {
+    pub(super) fn new(
+        storage: S,
+        pending_state: Option<PendingStateSnapshot>,
+        state_override: &StateOverride,
+    ) -> Self {
         let mut this = Self {
             storage_handle: storage,
+            pending_state,
             overridden_slots: HashMap::new(),
             overridden_factory_deps: HashMap::new(),
             overridden_accounts: HashSet::new(),
@@ -88,6 +97,16 @@ impl<S: ReadStorage> StorageWithOverrides<S> {
             .map(|(&slot, &value)| (StorageKey::new(account, slot), value));
         self.overridden_slots.extend(account_slots);
     }
+
+    fn read_pending_value(&self, key: &StorageKey) -> Option<StorageValue> {
+        // `SystemContext` slots describe the pending L2 block in the state keeper, which generally differs
+        // from the L2 block used in the sandbox (e.g., by number and timestamp). Using them would lead
+        // to the VM halting on the block consistency checks.
+        if *key.address() == SYSTEM_CONTEXT_ADDRESS {
+            return None;
+        }
+        self.pending_state.as_ref()?.read_value(key)
+    }
 }
 
 impl<S: ReadStorage + fmt::Debug> ReadStorage for StorageWithOverrides<S> {
@@ -98,10 +117,17 @@ impl<S: ReadStorage + fmt::Debug> ReadStorage for StorageWithOverrides<S> {
         if self.overridden_accounts.contains(key.account()) {
             return H256::zero();
         }
+        if let Some(value) = self.read_pending_value(key) {
+            return value;
+        }
         self.storage_handle.read_value(key)
     }
 
     fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+        if self.read_pending_value(key).is_some() {
+            // The slot was written to in the pending L1 batch, so the write is not initial.
+            return false;
+        }
         self.storage_handle.is_write_initial(key)
     }
 
@@ -109,6 +135,10 @@ impl<S: ReadStorage + fmt::Debug> ReadStorage for StorageWithOverrides<S> {
         self.overridden_factory_deps
             .get(&hash)
             .cloned()
+            .or_else(|| {
+                let pending_state = self.pending_state.as_ref()?;
+                Some(pending_state.load_factory_dep(hash)?.to_vec())
+            })
             .or_else(|| self.storage_handle.load_factory_dep(hash))
     }
 
@@ -120,9 +150,10 @@ impl<S: ReadStorage + fmt::Debug> ReadStorage for StorageWithOverrides<S> {
 #[cfg(test)]
 mod tests {
     use zksync_state::InMemoryStorage;
+    use zksync_state_keeper::PendingStatePublisher;
     use zksync_types::{
         api::state_override::{Bytecode, OverrideAccount},
-        Address,
+        Address, L1BatchNumber, L2BlockNumber, StorageLog, StorageLogWithPreviousValue,
     };
 
     use super::*;
@@ -181,7 +212,7 @@ mod tests {
         storage.set_value(retained_key, H256::repeat_byte(0xfe));
         let erased_key = StorageKey::new(AccountTreeId::new(Address::repeat_byte(5)), H256::zero());
         storage.set_value(erased_key, H256::repeat_byte(1));
-        let mut storage = StorageWithOverrides::new(storage, &overrides);
+        let mut storage = StorageWithOverrides::new(storage, None, &overrides);
 
         let balance = storage.read_value(&storage_key_for_eth_balance(&Address::repeat_byte(1)));
         assert_eq!(balance, H256::from_low_u64_be(1));
@@ -198,4 +229,57 @@ mod tests {
         let erased_value = storage.read_value(&erased_key);
         assert_eq!(erased_value, H256::zero());
     }
+
+    #[test]
+    fn reading_pending_state() {
+        let pending_key =
+            StorageKey::new(AccountTreeId::new(Address::repeat_byte(1)), H256::zero());
+        let overridden_key =
+            StorageKey::new(AccountTreeId::new(Address::repeat_byte(2)), H256::zero());
+        let system_key = StorageKey::new(AccountTreeId::new(SYSTEM_CONTEXT_ADDRESS), H256::zero());
+        let mut storage = InMemoryStorage::default();
+        for key in [pending_key, overridden_key, system_key] {
+            storage.set_value(key, H256::repeat_byte(0xff));
+        }
+
+        let publisher = PendingStatePublisher::new();
+        publisher.start_l1_batch(L1BatchNumber(1), L2BlockNumber(1));
+        let storage_logs: Vec<_> = [pending_key, overridden_key, system_key]
+            .into_iter()
+            .map(|key| StorageLogWithPreviousValue {
+                log: StorageLog::new_write_log(key, H256::repeat_byte(1)),
+                previous_value: H256::repeat_byte(0xff),
+            })
+            .collect();
+        publisher.extend(
+            &storage_logs,
+            [(H256::repeat_byte(3), vec![3; 32])].into_iter(),
+        );
+        // The write must remain visible after the L2 block containing it is sealed.
+        publisher.push_l2_block(L2BlockNumber(2));
+        let snapshot = publisher.subscribe().snapshot().unwrap();
+
+        let overrides = StateOverride::new(HashMap::from([(
+            Address::repeat_byte(2),
+            OverrideAccount {
+                state: Some(OverrideState::StateDiff(HashMap::from([(
+                    H256::zero(),
+                    H256::repeat_byte(2),
+                )]))),
+                ..OverrideAccount::default()
+            },
+        )]));
+        let mut storage = StorageWithOverrides::new(storage, Some(snapshot), &overrides);
+
+        assert_eq!(storage.read_value(&pending_key), H256::repeat_byte(1));
+        assert!(!storage.is_write_initial(&pending_key));
+        // Explicit overrides take precedence over the pending state.
+        assert_eq!(storage.read_value(&overridden_key), H256::repeat_byte(2));
+        // `SystemContext` writes in the pending state are ignored.
+        assert_eq!(storage.read_value(&system_key), H256::repeat_byte(0xff));
+        assert_eq!(
+            storage.load_factory_dep(H256::repeat_byte(3)),
+            Some(vec![3; 32])
+        );
+    }
 }
@@ -51,6 +51,14 @@ impl MockTransactionExecutor {
         self.call_responses = self.wrap_responses(responses);
     }
 
+    #[cfg(test)]
+    pub(crate) fn set_call_responses_with_logs<F>(&mut self, responses: F)
+    where
+        F: Fn(&Transaction, &BlockArgs) -> VmExecutionResultAndLogs + 'static + Send + Sync,
+    {
+        self.call_responses = Box::new(responses);
+    }
+
     #[cfg(test)]
     pub(crate) fn set_tx_responses<F>(&mut self, responses: F)
     where
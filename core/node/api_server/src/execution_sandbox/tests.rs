@@ -1,12 +1,32 @@
 //! Tests for the VM execution sandbox.
 
+use std::collections::HashMap;
+
 use assert_matches::assert_matches;
+use zksync_config::configs::chain::ValidationSlotPattern;
+use zksync_contracts::read_bytecode;
 use zksync_dal::ConnectionPool;
+use zksync_multivm::tracers::validator;
 use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
 use zksync_node_test_utils::{create_l2_block, create_l2_transaction, prepare_recovery_snapshot};
+use zksync_state_keeper::PendingStatePublisher;
+use zksync_system_constants::{
+    BOOTLOADER_ADDRESS, DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE, L2_BASE_TOKEN_ADDRESS,
+};
+use zksync_types::{
+    fee::Fee, get_code_key, get_is_account_key, l2::L2Tx, transaction_request::CallOverrides,
+    utils::storage_key_for_eth_balance, L1BatchNumber, Nonce, PackedEthSignature, StorageKey,
+    StorageLog, StorageLogWithPreviousValue, H256, U256,
+};
+use zksync_utils::{bytecode::hash_bytecode, h256_to_u256, u256_to_h256};
 
 use super::*;
-use crate::{execution_sandbox::apply::apply_vm_in_sandbox, tx_sender::ApiContracts};
+use crate::{
+    execution_sandbox::{
+        apply::apply_vm_in_sandbox, execute::resolve_block_timestamps, validate::ValidationError,
+    },
+    tx_sender::ApiContracts,
+};
 
 #[tokio::test]
 async fn creating_block_args() {
@@ -205,3 +225,290 @@ async fn test_instantiating_vm(pool: ConnectionPool<Core>, block_args: BlockArgs
     .expect("VM instantiation panicked")
     .expect("VM instantiation errored");
 }
+
+#[test]
+fn resolving_simulated_block_timestamps() {
+    let block = |timestamp: Option<u64>| SimulationBlock {
+        timestamp,
+        txs: vec![],
+    };
+
+    let timestamps = resolve_block_timestamps(&[block(None), block(None)], 100).unwrap();
+    assert_eq!(timestamps, [100, 101]);
+    let blocks = [block(Some(100)), block(Some(110)), block(None)];
+    let timestamps = resolve_block_timestamps(&blocks, 100).unwrap();
+    assert_eq!(timestamps, [100, 110, 111]);
+
+    let err = resolve_block_timestamps(&[block(Some(1_000))], 100).unwrap_err();
+    assert_matches!(err, SimulationError::InvalidBlocks(msg) if msg.contains("first simulated block"));
+    let err = resolve_block_timestamps(&[block(None), block(Some(100))], 100).unwrap_err();
+    assert_matches!(err, SimulationError::InvalidBlocks(msg) if msg.contains("must be greater"));
+    let blocks = [block(None), block(Some(110)), block(Some(105))];
+    let err = resolve_block_timestamps(&blocks, 100).unwrap_err();
+    assert_matches!(err, SimulationError::InvalidBlocks(msg) if msg.contains("#2"));
+}
+
+fn transfer_tx(from: Address, to: Address, value: U256) -> L2Tx {
+    let fee = Fee {
+        gas_limit: 10_000_000.into(),
+        max_fee_per_gas: 0.into(),
+        max_priority_fee_per_gas: 0.into(),
+        gas_per_pubdata_limit: DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE.into(),
+    };
+    let mut tx = L2Tx::new(
+        to,
+        vec![],
+        Nonce(0),
+        fee,
+        from,
+        value,
+        vec![],
+        Default::default(),
+    );
+    tx.common_data.signature = PackedEthSignature::default().serialize_packed().into();
+    tx.set_input(vec![], H256::random());
+    tx
+}
+
+#[tokio::test]
+async fn simulating_blocks_observes_state_from_previous_blocks() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    let sender = Address::repeat_byte(1);
+    let recipient = Address::repeat_byte(2);
+    let value = U256::from(1_000_000);
+    let balance_log = StorageLog::new_write_log(
+        storage_key_for_eth_balance(&sender),
+        u256_to_h256(U256::one() << 64),
+    );
+    storage
+        .storage_logs_dal()
+        .append_storage_logs(L2BlockNumber(0), &[balance_log])
+        .await
+        .unwrap();
+    let block_args = BlockArgs::pending(&mut storage).await.unwrap();
+    drop(storage);
+
+    let eth_call_contracts = ApiContracts::load_from_disk().await.unwrap().eth_call;
+    let (vm_concurrency_limiter, _) = VmConcurrencyLimiter::new(1);
+    // The recipient only has funds to send back in the second block if it observes the transfer from the first one.
+    let blocks = vec![
+        SimulationBlock {
+            timestamp: None,
+            txs: vec![transfer_tx(sender, recipient, value).into()],
+        },
+        SimulationBlock {
+            timestamp: None,
+            txs: vec![transfer_tx(recipient, sender, value).into()],
+        },
+    ];
+    let outputs = TransactionExecutor::Real
+        .simulate_blocks_in_sandbox(
+            vm_concurrency_limiter.acquire().await.unwrap(),
+            TxSharedArgs::mock(eth_call_contracts.clone()),
+            pool.clone(),
+            Some(0),
+            None,
+            block_args,
+            None,
+            blocks,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(outputs.len(), 2);
+    assert_eq!(outputs[1].number, outputs[0].number + 1);
+    assert_eq!(outputs[1].timestamp, outputs[0].timestamp + 1);
+    for output in &outputs {
+        let [result] = output.results.as_slice() else {
+            panic!("unexpected results: {:?}", output.results);
+        };
+        assert!(!result.result.is_failed(), "{:?}", result.result);
+    }
+
+    // Sanity check: without the first block, the recipient has no funds to transfer.
+    let blocks = vec![SimulationBlock {
+        timestamp: None,
+        txs: vec![transfer_tx(recipient, sender, value).into()],
+    }];
+    let outputs = TransactionExecutor::Real
+        .simulate_blocks_in_sandbox(
+            vm_concurrency_limiter.acquire().await.unwrap(),
+            TxSharedArgs::mock(eth_call_contracts),
+            pool,
+            Some(0),
+            None,
+            block_args,
+            None,
+            blocks,
+        )
+        .await
+        .unwrap();
+    assert!(outputs[0].results[0].result.is_failed());
+}
+
+#[tokio::test]
+async fn pending_eth_call_observes_pending_state() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    let block_args = BlockArgs::pending(&mut storage).await.unwrap();
+    drop(storage);
+
+    // The sender is only funded in the pending state, which is not persisted to Postgres.
+    let sender = Address::repeat_byte(1);
+    let publisher = PendingStatePublisher::new();
+    publisher.start_l1_batch(L1BatchNumber(1), block_args.resolved_block_number());
+    let balance_log = StorageLogWithPreviousValue {
+        log: StorageLog::new_write_log(
+            storage_key_for_eth_balance(&sender),
+            u256_to_h256(U256::one() << 64),
+        ),
+        previous_value: H256::zero(),
+    };
+    publisher.extend(&[balance_log], std::iter::empty());
+    let snapshot = publisher.subscribe().snapshot().unwrap();
+    assert!(snapshot.covers(block_args.resolved_block_number()));
+
+    let eth_call_contracts = ApiContracts::load_from_disk().await.unwrap().eth_call;
+    let (vm_concurrency_limiter, _) = VmConcurrencyLimiter::new(1);
+    let tx = transfer_tx(sender, Address::repeat_byte(2), 1_000_000.into());
+    let mut shared_args = TxSharedArgs::mock(eth_call_contracts.clone());
+    shared_args.pending_state = Some(snapshot);
+    let result = TransactionExecutor::Real
+        .execute_tx_eth_call(
+            vm_concurrency_limiter.acquire().await.unwrap(),
+            shared_args,
+            pool.clone(),
+            CallOverrides {
+                enforced_base_fee: Some(0),
+            },
+            tx.clone(),
+            block_args,
+            None,
+            vec![],
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(!result.result.is_failed(), "{:?}", result.result);
+
+    // Sanity check: without the pending state, the sender has no funds to transfer.
+    let result = TransactionExecutor::Real
+        .execute_tx_eth_call(
+            vm_concurrency_limiter.acquire().await.unwrap(),
+            TxSharedArgs::mock(eth_call_contracts),
+            pool,
+            CallOverrides {
+                enforced_base_fee: Some(0),
+            },
+            tx,
+            block_args,
+            None,
+            vec![],
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(result.result.is_failed());
+}
+
+/// Deploys a `CustomAccount` which reads the bootloader balance during validation, violating the default rules.
+async fn deploy_rule_violating_account(pool: &ConnectionPool<Core>) -> Address {
+    const PATH: &str =
+        "etc/contracts-test-data/artifacts-zk/contracts/custom-account/custom-account.sol/CustomAccount.json";
+
+    let bytecode = read_bytecode(PATH);
+    let bytecode_hash = hash_bytecode(&bytecode);
+    let address = Address::repeat_byte(0xaa);
+    let storage_logs = [
+        StorageLog::new_write_log(get_code_key(&address), bytecode_hash),
+        StorageLog::new_write_log(get_is_account_key(&address), u256_to_h256(1.into())),
+        // Sets the `violateValidationRules` flag.
+        StorageLog::new_write_log(
+            StorageKey::new(AccountTreeId::new(address), H256::zero()),
+            u256_to_h256(1.into()),
+        ),
+    ];
+    let mut storage = pool.connection().await.unwrap();
+    storage
+        .storage_logs_dal()
+        .append_storage_logs(L2BlockNumber(0), &storage_logs)
+        .await
+        .unwrap();
+    storage
+        .factory_deps_dal()
+        .insert_factory_deps(
+            L2BlockNumber(0),
+            &HashMap::from([(bytecode_hash, bytecode)]),
+        )
+        .await
+        .unwrap();
+    address
+}
+
+#[tokio::test]
+async fn validating_tx_with_custom_rules() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    drop(storage);
+    let account = deploy_rule_violating_account(&pool).await;
+    let mut storage = pool.connection().await.unwrap();
+    let block_args = BlockArgs::pending(&mut storage).await.unwrap();
+    drop(storage);
+
+    let mut tx = transfer_tx(account, Address::repeat_byte(2), 0.into());
+    let signed_hash = tx.get_signed_bytes(L2ChainId::default());
+    let signature = [signed_hash.as_bytes(), account.as_bytes()].concat();
+    tx.set_raw_signature(signature);
+
+    let validation_contracts = ApiContracts::load_from_disk().await.unwrap().eth_call;
+    let (vm_concurrency_limiter, _) = VmConcurrencyLimiter::new(1);
+    let err = TransactionExecutor::Real
+        .validate_tx_in_sandbox(
+            pool.clone(),
+            vm_concurrency_limiter.acquire().await.unwrap(),
+            tx.clone(),
+            TxSharedArgs::mock(validation_contracts.clone()),
+            block_args,
+            u32::MAX,
+            &CustomValidationRules::default(),
+        )
+        .await
+        .unwrap_err();
+    assert_matches!(
+        err,
+        ValidationError::Vm(validator::ValidationError::ViolatedRule(_))
+    );
+
+    // Allowlisting the accessed slot makes the transaction pass validation.
+    let bootloader_balance_key = storage_key_for_eth_balance(&BOOTLOADER_ADDRESS);
+    let custom_rules = CustomValidationRules::new(
+        &[],
+        &[ValidationSlotPattern {
+            address: Some(L2_BASE_TOKEN_ADDRESS),
+            slot: h256_to_u256(*bootloader_balance_key.key()),
+        }],
+        &[],
+    );
+    TransactionExecutor::Real
+        .validate_tx_in_sandbox(
+            pool,
+            vm_concurrency_limiter.acquire().await.unwrap(),
+            tx,
+            TxSharedArgs::mock(validation_contracts),
+            block_args,
+            u32::MAX,
+            &custom_rules,
+        )
+        .await
+        .unwrap();
+}
@@ -1,5 +1,3 @@
-use std::collections::HashSet;
-
 use anyhow::Context as _;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_multivm::{
@@ -11,7 +9,8 @@ use zksync_multivm::{
     vm_latest::HistoryDisabled,
     MultiVMTracer,
 };
-use zksync_types::{l2::L2Tx, Address, Transaction, TRUSTED_ADDRESS_SLOTS, TRUSTED_TOKEN_SLOTS};
+use zksync_state_keeper::CustomValidationRules;
+use zksync_types::{l2::L2Tx, Address, Transaction};
 
 use super::{
     apply,
@@ -39,6 +38,7 @@ impl TransactionExecutor {
         shared_args: TxSharedArgs,
         block_args: BlockArgs,
         computational_gas_limit: u32,
+        custom_rules: &CustomValidationRules,
     ) -> Result<(), ValidationError> {
         if let Self::Mock(mock) = self {
             return mock.validate_tx(tx, &block_args);
@@ -54,6 +54,7 @@ impl TransactionExecutor {
             &tx,
             computational_gas_limit,
             &shared_args.whitelisted_tokens_for_aa,
+            custom_rules,
         )
         .await
         .context("failed getting validation params")?;
@@ -119,14 +120,12 @@ impl TransactionExecutor {
     }
 }
 
-/// Some slots can be marked as "trusted". That is needed for slots which can not be
-/// trusted to change between validation and execution in general case, but
-/// sometimes we can safely rely on them to not change often.
 async fn get_validation_params(
     connection: &mut Connection<'_, Core>,
     tx: &L2Tx,
     computational_gas_limit: u32,
     whitelisted_tokens_for_aa: &[Address],
+    custom_rules: &CustomValidationRules,
 ) -> anyhow::Result<ValidationTracerParams> {
     let method_latency = EXECUTION_METRICS.get_validation_params.start();
     let user_address = tx.common_data.initiator_address;
@@ -136,42 +135,23 @@ async fn get_validation_params(
     // we may need to introduce some kind of caching.
     let all_bridged_tokens = connection.tokens_dal().get_all_l2_token_addresses().await?;
     let all_tokens: Vec<_> = all_bridged_tokens
-        .iter()
-        .chain(whitelisted_tokens_for_aa)
+        .into_iter()
+        .chain(whitelisted_tokens_for_aa.iter().copied())
         .collect();
     EXECUTION_METRICS.tokens_amount.set(all_tokens.len());
 
     let span = tracing::debug_span!("compute_trusted_slots_for_validation").entered();
-    let trusted_slots: HashSet<_> = all_tokens
-        .iter()
-        .flat_map(|&token| TRUSTED_TOKEN_SLOTS.iter().map(move |&slot| (*token, slot)))
-        .collect();
-
-    // We currently don't support any specific trusted addresses.
-    let trusted_addresses = HashSet::new();
-
-    // The slots the value of which will be added as allowed address on the fly.
-    // Required for working with transparent proxies.
-    let trusted_address_slots: HashSet<_> = all_tokens
-        .into_iter()
-        .flat_map(|token| {
-            TRUSTED_ADDRESS_SLOTS
-                .iter()
-                .map(move |&slot| (*token, slot))
-        })
-        .collect();
+    let params = custom_rules.tracer_params(
+        user_address,
+        paymaster_address,
+        &all_tokens,
+        computational_gas_limit,
+    );
     EXECUTION_METRICS
         .trusted_address_slots_amount
-        .set(trusted_address_slots.len());
+        .set(params.trusted_address_slots.len());
     span.exit();
 
     method_latency.observe();
-    Ok(ValidationTracerParams {
-        user_address,
-        paymaster_address,
-        trusted_slots,
-        trusted_addresses,
-        trusted_address_slots,
-        computational_gas_limit,
-    })
+    Ok(params)
 }
@@ -0,0 +1,467 @@
+//! Policy filters for transactions submitted via `TxSender`.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+    time::{Duration, Instant, SystemTime},
+};
+
+use anyhow::Context as _;
+use zksync_multivm::interface::VmExecutionResultAndLogs;
+use zksync_types::{l2::L2Tx, Address, H256, U256};
+
+/// Error returned by a [`TxFilter`].
+#[derive(Debug, thiserror::Error)]
+pub enum TxFilterError {
+    /// Transaction is rejected by the filter policy. The reason is returned to the caller.
+    #[error("{0}")]
+    Rejected(String),
+    /// Filter has failed to check the transaction (e.g., because of an I/O error).
+    #[error(transparent)]
+    Internal(#[from] anyhow::Error),
+}
+
+/// Policy hook allowing to reject transactions before they are propagated to the mempool.
+///
+/// Filters are evaluated by `TxSender` in the order they were added, after the transaction has passed basic checks
+/// and was executed in the sandbox; the first rejection is returned to the caller.
+#[async_trait::async_trait]
+pub trait TxFilter: fmt::Debug + Send + Sync + 'static {
+    /// Checks whether the transaction may be accepted. `execution` is the result of the transaction dry run.
+    async fn check(
+        &self,
+        tx: &L2Tx,
+        execution: &VmExecutionResultAndLogs,
+    ) -> Result<(), TxFilterError>;
+}
+
+#[derive(Debug)]
+struct ListState<T> {
+    value: Arc<T>,
+    modified_at: Option<SystemTime>,
+    checked_at: Instant,
+}
+
+/// List loaded from a JSON file that is reloaded if the file is modified. Modification is checked lazily,
+/// at most once per `reload_interval`. If reloading fails, the previously loaded list is retained.
+struct ReloadableList<T> {
+    path: PathBuf,
+    reload_interval: Duration,
+    parse: fn(&str) -> anyhow::Result<T>,
+    state: RwLock<ListState<T>>,
+}
+
+impl<T> fmt::Debug for ReloadableList<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("ReloadableList")
+            .field("path", &self.path)
+            .field("reload_interval", &self.reload_interval)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Send + Sync + 'static> ReloadableList<T> {
+    async fn new(
+        path: PathBuf,
+        reload_interval: Duration,
+        parse: fn(&str) -> anyhow::Result<T>,
+    ) -> anyhow::Result<Self> {
+        let modified_at = Self::modified_at(&path).await;
+        let value = Self::load(&path, parse).await?;
+        Ok(Self {
+            path,
+            reload_interval,
+            parse,
+            state: RwLock::new(ListState {
+                value: Arc::new(value),
+                modified_at,
+                checked_at: Instant::now(),
+            }),
+        })
+    }
+
+    async fn modified_at(path: &Path) -> Option<SystemTime> {
+        let metadata = tokio::fs::metadata(path).await.ok()?;
+        metadata.modified().ok()
+    }
+
+    async fn load(path: &Path, parse: fn(&str) -> anyhow::Result<T>) -> anyhow::Result<T> {
+        let contents = tokio::fs::read_to_string(path)
+            .await
+            .with_context(|| format!("failed reading list from `{}`", path.display()))?;
+        parse(&contents).with_context(|| format!("failed parsing list from `{}`", path.display()))
+    }
+
+    async fn get(&self) -> Arc<T> {
+        {
+            let state = self.state.read().unwrap();
+            if state.checked_at.elapsed() < self.reload_interval {
+                return state.value.clone();
+            }
+        }
+        let prev_modified_at = {
+            let mut state = self.state.write().unwrap();
+            if state.checked_at.elapsed() < self.reload_interval {
+                // The list is being reloaded or was just reloaded by a concurrent call.
+                return state.value.clone();
+            }
+            // Prevents concurrent reloads; the current list is used until reloading is completed.
+            state.checked_at = Instant::now();
+            state.modified_at
+        };
+
+        let modified_at = Self::modified_at(&self.path).await;
+        if modified_at.is_some() && modified_at == prev_modified_at {
+            return self.state.read().unwrap().value.clone();
+        }
+        match Self::load(&self.path, self.parse).await {
+            Ok(value) => {
+                tracing::info!(
+                    "Reloaded transaction filter list from `{}`",
+                    self.path.display()
+                );
+                let value = Arc::new(value);
+                let mut state = self.state.write().unwrap();
+                state.value = value.clone();
+                state.modified_at = modified_at;
+                value
+            }
+            Err(err) => {
+                tracing::warn!("Failed reloading transaction filter list, retaining the previous version: {err:#}");
+                self.state.read().unwrap().value.clone()
+            }
+        }
+    }
+}
+
+/// Rejects transactions involving deny-listed addresses.
+///
+/// Checked addresses include the transaction initiator, recipient and paymaster, as well as emitters and address-like
+/// indexed topics of events produced during the transaction dry run (e.g., recipients of token transfers).
+/// The list is loaded from a JSON file containing an array of hex-encoded addresses.
+#[derive(Debug)]
+pub struct AddressDenyListFilter {
+    list: ReloadableList<HashSet<Address>>,
+}
+
+impl AddressDenyListFilter {
+    pub async fn new(path: PathBuf, reload_interval: Duration) -> anyhow::Result<Self> {
+        Ok(Self {
+            list: ReloadableList::new(path, reload_interval, |contents| {
+                Ok(serde_json::from_str(contents)?)
+            })
+            .await?,
+        })
+    }
+
+    fn address_from_topic(topic: &H256) -> Option<Address> {
+        let (padding, address) = topic.as_bytes().split_at(12);
+        (padding.iter().all(|&byte| byte == 0) && address.iter().any(|&byte| byte != 0))
+            .then(|| Address::from_slice(address))
+    }
+}
+
+#[async_trait::async_trait]
+impl TxFilter for AddressDenyListFilter {
+    async fn check(
+        &self,
+        tx: &L2Tx,
+        execution: &VmExecutionResultAndLogs,
+    ) -> Result<(), TxFilterError> {
+        let deny_list = self.list.get().await;
+        let tx_addresses = [tx.initiator_account(), tx.recipient_account(), tx.payer()];
+        let event_addresses = execution.logs.events.iter().flat_map(|event| {
+            // The first topic is the event signature, so it's skipped.
+            let topic_addresses = event
+                .indexed_topics
+                .iter()
+                .skip(1)
+                .filter_map(Self::address_from_topic);
+            [event.address].into_iter().chain(topic_addresses)
+        });
+
+        for address in tx_addresses.into_iter().chain(event_addresses) {
+            if deny_list.contains(&address) {
+                return Err(TxFilterError::Rejected(format!(
+                    "transaction involves deny-listed address {address:?}"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+type MethodAllowlist = HashMap<Address, HashSet<[u8; 4]>>;
+
+/// Only allows calling methods of contracts in the allowlist.
+///
+/// The list is loaded from a JSON file containing an object that maps contract addresses to arrays of allowed
+/// hex-encoded 4-byte method selectors; an empty array allows all methods of the contract. Transactions with empty
+/// calldata (i.e., plain base token transfers) are not restricted. Non-empty calldata shorter than a method selector
+/// invokes the contract fallback, so it's only allowed for contracts with all methods allowed.
+#[derive(Debug)]
+pub struct ContractMethodAllowlistFilter {
+    list: ReloadableList<MethodAllowlist>,
+}
+
+impl ContractMethodAllowlistFilter {
+    pub async fn new(path: PathBuf, reload_interval: Duration) -> anyhow::Result<Self> {
+        Ok(Self {
+            list: ReloadableList::new(path, reload_interval, Self::parse).await?,
+        })
+    }
+
+    fn parse(contents: &str) -> anyhow::Result<MethodAllowlist> {
+        let raw: HashMap<Address, Vec<String>> = serde_json::from_str(contents)?;
+        raw.into_iter()
+            .map(|(address, selectors)| {
+                let selectors = selectors.iter().map(|selector| {
+                    let hex_selector = selector.strip_prefix("0x").unwrap_or(selector);
+                    let bytes = hex::decode(hex_selector).ok();
+                    bytes
+                        .and_then(|bytes| <[u8; 4]>::try_from(bytes).ok())
+                        .with_context(|| {
+                            format!(
+                                "invalid method selector `{selector}` for {address:?}; expected 4 hex-encoded bytes"
+                            )
+                        })
+                });
+                Ok((address, selectors.collect::<anyhow::Result<_>>()?))
+            })
+            .collect()
+    }
+}
+
+#[async_trait::async_trait]
+impl TxFilter for ContractMethodAllowlistFilter {
+    async fn check(
+        &self,
+        tx: &L2Tx,
+        _execution: &VmExecutionResultAndLogs,
+    ) -> Result<(), TxFilterError> {
+        let calldata = &tx.execute.calldata;
+        if calldata.is_empty() {
+            return Ok(());
+        }
+        let allowlist = self.list.get().await;
+        let contract = tx.recipient_account();
+        let Some(allowed_selectors) = allowlist.get(&contract) else {
+            return Err(TxFilterError::Rejected(format!(
+                "calling contract {contract:?} is not allowed"
+            )));
+        };
+        if allowed_selectors.is_empty() {
+            return Ok(());
+        }
+        let Some(selector) = calldata.get(..4) else {
+            return Err(TxFilterError::Rejected(format!(
+                "calling fallback of contract {contract:?} is not allowed"
+            )));
+        };
+        if !allowed_selectors.contains(selector) {
+            return Err(TxFilterError::Rejected(format!(
+                "calling method 0x{} of contract {contract:?} is not allowed",
+                hex::encode(selector)
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Limits calldata size and transferred value of transactions.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TxLimitsFilter {
+    /// Max calldata size in bytes.
+    pub max_calldata_size: Option<usize>,
+    /// Max transferred value in wei.
+    pub max_value: Option<U256>,
+}
+
+#[async_trait::async_trait]
+impl TxFilter for TxLimitsFilter {
+    async fn check(
+        &self,
+        tx: &L2Tx,
+        _execution: &VmExecutionResultAndLogs,
+    ) -> Result<(), TxFilterError> {
+        let calldata_size = tx.execute.calldata.len();
+        if let Some(max_size) = self.max_calldata_size {
+            if calldata_size > max_size {
+                return Err(TxFilterError::Rejected(format!(
+                    "calldata size {calldata_size} exceeds the limit of {max_size} bytes"
+                )));
+            }
+        }
+        if let Some(max_value) = self.max_value {
+            if tx.execute.value > max_value {
+                return Err(TxFilterError::Rejected(format!(
+                    "transferred value {} exceeds the limit of {max_value} wei",
+                    tx.execute.value
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_multivm::interface::ExecutionResult;
+    use zksync_node_test_utils::create_l2_transaction;
+    use zksync_types::VmEvent;
+
+    use super::*;
+
+    fn write_list(dir: &tempfile::TempDir, name: &str, contents: &str) -> PathBuf {
+        let path = dir.path().join(name);
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn mock_execution() -> VmExecutionResultAndLogs {
+        VmExecutionResultAndLogs {
+            result: ExecutionResult::Success { output: vec![] },
+            logs: Default::default(),
+            statistics: Default::default(),
+            refunds: Default::default(),
+        }
+    }
+
+    fn mock_tx(recipient: Address, calldata: Vec<u8>) -> L2Tx {
+        let mut tx = create_l2_transaction(10, 100);
+        tx.execute.contract_address = recipient;
+        tx.execute.calldata = calldata;
+        tx
+    }
+
+    #[tokio::test]
+    async fn deny_list_filter() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let denied = Address::repeat_byte(0xde);
+        let path = write_list(&dir, "deny.json", &format!("[\"{denied:?}\"]"));
+        let filter = AddressDenyListFilter::new(path.clone(), Duration::ZERO)
+            .await
+            .unwrap();
+
+        let execution = mock_execution();
+        let tx = mock_tx(Address::repeat_byte(1), vec![]);
+        filter.check(&tx, &execution).await.unwrap();
+        let err = filter
+            .check(&mock_tx(denied, vec![]), &execution)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("deny-listed"), "{err}");
+
+        // Transfer to the deny-listed address within the transaction.
+        let mut execution_with_transfer = mock_execution();
+        execution_with_transfer.logs.events.push(VmEvent {
+            address: Address::repeat_byte(2),
+            indexed_topics: vec![
+                H256::repeat_byte(0xff),
+                tx.initiator_account().into(),
+                denied.into(),
+            ],
+            ..VmEvent::default()
+        });
+        filter
+            .check(&tx, &execution_with_transfer)
+            .await
+            .unwrap_err();
+
+        // Reloading the list.
+        std::fs::write(&path, "[]").unwrap();
+        let mut reloaded = false;
+        for _ in 0..50 {
+            if filter.check(&tx, &execution_with_transfer).await.is_ok() {
+                reloaded = true;
+                break;
+            }
+            // File modification time may have coarse granularity.
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            std::fs::write(&path, "[]").unwrap();
+        }
+        assert!(reloaded);
+    }
+
+    #[tokio::test]
+    async fn method_allowlist_filter() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let token = Address::repeat_byte(1);
+        let router = Address::repeat_byte(2);
+        let contents = format!("{{ \"{token:?}\": [\"0xa9059cbb\"], \"{router:?}\": [] }}");
+        let path = write_list(&dir, "allow.json", &contents);
+        let filter = ContractMethodAllowlistFilter::new(path, Duration::from_secs(60))
+            .await
+            .unwrap();
+        let execution = mock_execution();
+
+        let transfer = mock_tx(token, vec![0xa9, 0x05, 0x9c, 0xbb, 0]);
+        filter.check(&transfer, &execution).await.unwrap();
+        let approve = mock_tx(token, vec![0x09, 0x5e, 0xa7, 0xb3, 0]);
+        let err = filter.check(&approve, &execution).await.unwrap_err();
+        assert!(err.to_string().contains("0x095ea7b3"), "{err}");
+        let router_call = mock_tx(router, vec![1, 2, 3, 4]);
+        filter.check(&router_call, &execution).await.unwrap();
+        let other_call = mock_tx(Address::repeat_byte(3), vec![1, 2, 3, 4]);
+        filter.check(&other_call, &execution).await.unwrap_err();
+        let plain_transfer = mock_tx(Address::repeat_byte(3), vec![]);
+        filter.check(&plain_transfer, &execution).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn method_allowlist_filter_with_short_calldata() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let token = Address::repeat_byte(1);
+        let router = Address::repeat_byte(2);
+        let contents = format!("{{ \"{token:?}\": [\"0xa9059cbb\"], \"{router:?}\": [] }}");
+        let path = write_list(&dir, "allow.json", &contents);
+        let filter = ContractMethodAllowlistFilter::new(path, Duration::from_secs(60))
+            .await
+            .unwrap();
+        let execution = mock_execution();
+
+        for calldata_len in 1..4 {
+            let calldata = vec![0xa9; calldata_len];
+            let other_call = mock_tx(Address::repeat_byte(3), calldata.clone());
+            let err = filter.check(&other_call, &execution).await.unwrap_err();
+            assert!(err.to_string().contains("is not allowed"), "{err}");
+            // Calling the fallback of a contract with only some methods allowed is rejected as well.
+            let token_call = mock_tx(token, calldata.clone());
+            let err = filter.check(&token_call, &execution).await.unwrap_err();
+            assert!(err.to_string().contains("fallback"), "{err}");
+            let router_call = mock_tx(router, calldata);
+            filter.check(&router_call, &execution).await.unwrap();
+        }
+    }
+
+    #[test]
+    fn parsing_invalid_method_allowlist() {
+        let contents = format!("{{ \"{:?}\": [\"0xa9059c\"] }}", Address::repeat_byte(1));
+        let err = ContractMethodAllowlistFilter::parse(&contents).unwrap_err();
+        assert!(
+            format!("{err:#}").contains("expected 4 hex-encoded bytes"),
+            "{err:#}"
+        );
+    }
+
+    #[tokio::test]
+    async fn limits_filter() {
+        let filter = TxLimitsFilter {
+            max_calldata_size: Some(4),
+            max_value: Some(100.into()),
+        };
+        let execution = mock_execution();
+        let mut tx = mock_tx(Address::repeat_byte(1), vec![0; 4]);
+        tx.execute.value = 100.into();
+        filter.check(&tx, &execution).await.unwrap();
+
+        tx.execute.value = 101.into();
+        filter.check(&tx, &execution).await.unwrap_err();
+        tx.execute.value = 0.into();
+        tx.execute.calldata = vec![0; 5];
+        filter.check(&tx, &execution).await.unwrap_err();
+    }
+}
@@ -1,7 +1,12 @@
 use std::collections::hash_map::{Entry, HashMap};
 
 use tokio::sync::Mutex;
-use zksync_dal::{transactions_dal::L2TxSubmissionResult, ConnectionPool, Core, CoreDal};
+use zksync_dal::{
+    notifications::{StorageNotification, StorageNotifications},
+    transactions_dal::L2TxSubmissionResult,
+    Connection, ConnectionPool, Core, CoreDal, DalError,
+};
+use zksync_mempool::AccountTxLimits;
 use zksync_shared_metrics::{TxStage, APP_METRICS};
 use zksync_types::{fee::TransactionExecutionMetrics, l2::L2Tx, Address, Nonce, H256};
 
@@ -13,6 +18,8 @@ use crate::web3::metrics::API_METRICS;
 pub struct MasterPoolSink {
     master_pool: ConnectionPool<Core>,
     inflight_requests: Mutex<HashMap<(Address, Nonce), H256>>,
+    notifications: Option<StorageNotifications>,
+    account_limits: AccountTxLimits,
 }
 
 impl MasterPoolSink {
@@ -20,8 +27,80 @@ impl MasterPoolSink {
         Self {
             master_pool,
             inflight_requests: Mutex::new(HashMap::new()),
+            notifications: None,
+            account_limits: AccountTxLimits::default(),
         }
     }
+
+    /// Sets limits for pending transactions of a single account. The limits are checked in the same DB transaction
+    /// as the transaction insertion, so they cannot be circumvented by concurrent submissions.
+    #[must_use]
+    pub fn with_account_limits(mut self, limits: AccountTxLimits) -> Self {
+        self.account_limits = limits;
+        self
+    }
+
+    /// Enables sending notifications about transactions inserted into the mempool.
+    pub fn with_notifications(mut self, notifications: StorageNotifications) -> Self {
+        self.notifications = Some(notifications);
+        self
+    }
+
+    async fn insert_tx(
+        &self,
+        connection: &mut Connection<'_, Core>,
+        tx: &L2Tx,
+        execution_metrics: TransactionExecutionMetrics,
+    ) -> Result<L2TxSubmissionResult, SubmitTxError> {
+        if self.account_limits == AccountTxLimits::default() {
+            return connection
+                .transactions_dal()
+                .insert_transaction_l2(tx, execution_metrics)
+                .await
+                .map_err(|err| err.generalize().into());
+        }
+
+        let initiator_account = tx.initiator_account();
+        let mut transaction = connection
+            .start_transaction()
+            .await
+            .map_err(DalError::generalize)?;
+        transaction
+            .transactions_dal()
+            .lock_pending_l2_txs(initiator_account)
+            .await
+            .map_err(DalError::generalize)?;
+        let pending_tx = transaction
+            .transactions_web3_dal()
+            .get_pending_l2_tx_fee(initiator_account, tx.nonce())
+            .await
+            .map_err(DalError::generalize)?;
+        match pending_tx {
+            // Duplicate transactions are handled by the insertion logic.
+            Some((pending_tx_hash, _)) if pending_tx_hash == tx.hash() => {}
+            Some((_, pending_tx_fee)) => {
+                self.account_limits
+                    .check_replacement(&pending_tx_fee, &tx.common_data.fee)?;
+            }
+            None if self.account_limits.max_pending_txs_per_account.is_some() => {
+                let pending_count = transaction
+                    .transactions_web3_dal()
+                    .get_pending_l2_txs_count(initiator_account)
+                    .await
+                    .map_err(DalError::generalize)?;
+                self.account_limits.check_pending_count(pending_count)?;
+            }
+            None => {}
+        }
+
+        let submission_res_handle = transaction
+            .transactions_dal()
+            .insert_transaction_l2(tx, execution_metrics)
+            .await
+            .map_err(DalError::generalize)?;
+        transaction.commit().await.map_err(DalError::generalize)?;
+        Ok(submission_res_handle)
+    }
 }
 
 #[async_trait::async_trait]
@@ -52,15 +131,13 @@ impl TxSink for MasterPoolSink {
         drop(lock);
 
         let result = match self.master_pool.connection_tagged("api").await {
-            Ok(mut connection) => connection
-                .transactions_dal()
-                .insert_transaction_l2(tx, execution_metrics)
+            Ok(mut connection) => self
+                .insert_tx(&mut connection, tx, execution_metrics)
                 .await
                 .map(|submission_res_handle| {
                     APP_METRICS.processed_txs[&TxStage::Mempool(submission_res_handle)].inc();
                     submission_res_handle
-                })
-                .map_err(|err| err.generalize().into()),
+                }),
             Err(err) => Err(err.generalize().into()),
         };
 
@@ -70,6 +147,19 @@ impl TxSink for MasterPoolSink {
             .remove(&address_and_nonce);
         API_METRICS.inflight_tx_submissions.dec_by(1);
 
+        if let (
+            Some(notifications),
+            Ok(L2TxSubmissionResult::Added | L2TxSubmissionResult::Replaced),
+        ) = (&self.notifications, &result)
+        {
+            let notification = StorageNotification::L2TxInserted(tx.hash());
+            if let Err(err) = notifications.send(notification).await {
+                tracing::warn!(
+                    "Failed sending notification about inserted transaction {:?}: {err:#}",
+                    tx.hash()
+                );
+            }
+        }
         result
     }
 }
@@ -4,11 +4,15 @@ use std::{sync::Arc, time::Instant};
 
 use anyhow::Context as _;
 use tokio::sync::RwLock;
-use zksync_config::configs::{api::Web3JsonRpcConfig, chain::StateKeeperConfig};
+use zksync_config::configs::{
+    api::Web3JsonRpcConfig,
+    chain::{MempoolConfig, StateKeeperConfig},
+};
 use zksync_contracts::BaseSystemContracts;
 use zksync_dal::{
-    transactions_dal::L2TxSubmissionResult, Connection, ConnectionPool, Core, CoreDal,
+    transactions_dal::L2TxSubmissionResult, Connection, ConnectionPool, Core, CoreDal, DalError,
 };
+use zksync_mempool::AccountTxLimits;
 use zksync_multivm::{
     interface::VmExecutionResultAndLogs,
     utils::{
@@ -21,7 +25,7 @@ use zksync_node_fee_model::{ApiFeeInputProvider, BatchFeeModelInputProvider};
 use zksync_state::PostgresStorageCaches;
 use zksync_state_keeper::{
     seal_criteria::{ConditionalSealer, NoopSealer, SealData},
-    SequencerSealer,
+    PendingStateReader, PendingStateSnapshot, SequencerSealer,
 };
 use zksync_types::{
     api::state_override::StateOverride,
@@ -31,22 +35,24 @@ use zksync_types::{
     l2::{error::TxCheckError::TxDuplication, L2Tx},
     transaction_request::CallOverrides,
     utils::storage_key_for_eth_balance,
-    AccountTreeId, Address, ExecuteTransactionCommon, L2ChainId, Nonce, PackedEthSignature,
-    ProtocolVersionId, Transaction, VmVersion, H160, H256, MAX_L2_TX_GAS_LIMIT,
+    AccountTreeId, Address, ExecuteTransactionCommon, L2BlockNumber, L2ChainId, Nonce,
+    PackedEthSignature, ProtocolVersionId, Transaction, VmVersion, H160, H256, MAX_L2_TX_GAS_LIMIT,
     MAX_NEW_FACTORY_DEPS, U256,
 };
 use zksync_utils::h256_to_u256;
 
 pub(super) use self::result::SubmitTxError;
-use self::{master_pool_sink::MasterPoolSink, tx_sink::TxSink};
+use self::{filter::TxFilter, master_pool_sink::MasterPoolSink, tx_sink::TxSink};
 use crate::{
     execution_sandbox::{
-        BlockArgs, SubmitTxStage, TransactionExecutor, TxExecutionArgs, TxSharedArgs,
-        VmConcurrencyBarrier, VmConcurrencyLimiter, VmPermit, SANDBOX_METRICS,
+        BlockArgs, CustomValidationRules, SimulatedBlockOutput, SimulationBlock, SimulationError,
+        SubmitTxStage, TransactionExecutor, TxExecutionArgs, TxSharedArgs, VmConcurrencyBarrier,
+        VmConcurrencyLimiter, VmPermit, SANDBOX_METRICS,
     },
     tx_sender::result::ApiCallResult,
 };
 
+pub mod filter;
 pub mod master_pool_sink;
 pub mod proxy;
 mod result;
@@ -64,7 +70,8 @@ pub async fn build_tx_sender(
     storage_caches: PostgresStorageCaches,
 ) -> anyhow::Result<(TxSender, VmConcurrencyBarrier)> {
     let sequencer_sealer = SequencerSealer::new(state_keeper_config.clone());
-    let master_pool_sink = MasterPoolSink::new(master_pool);
+    let master_pool_sink =
+        MasterPoolSink::new(master_pool).with_account_limits(tx_sender_config.account_tx_limits);
     let tx_sender_builder = TxSenderBuilder::new(
         tx_sender_config.clone(),
         replica_pool.clone(),
@@ -212,6 +219,10 @@ pub struct TxSenderBuilder {
     sealer: Option<Arc<dyn ConditionalSealer>>,
     /// Cache for tokens that are white-listed for AA.
     whitelisted_tokens_for_aa_cache: Option<Arc<RwLock<Vec<Address>>>>,
+    /// Pending state of the state keeper running in the same process.
+    pending_state: Option<PendingStateReader>,
+    /// Policy filters for submitted transactions.
+    tx_filters: Vec<Arc<dyn TxFilter>>,
 }
 
 impl TxSenderBuilder {
@@ -226,6 +237,8 @@ impl TxSenderBuilder {
             tx_sink,
             sealer: None,
             whitelisted_tokens_for_aa_cache: None,
+            pending_state: None,
+            tx_filters: Vec::new(),
         }
     }
 
@@ -239,6 +252,19 @@ impl TxSenderBuilder {
         self
     }
 
+    /// Makes the `pending` block reflect the pending state of the state keeper. Only makes sense if the state keeper
+    /// runs in the same process.
+    pub fn with_pending_state(mut self, pending_state: PendingStateReader) -> Self {
+        self.pending_state = Some(pending_state);
+        self
+    }
+
+    /// Adds a policy filter for submitted transactions. Filters are evaluated in the order they were added.
+    pub fn with_tx_filter(mut self, filter: Arc<dyn TxFilter>) -> Self {
+        self.tx_filters.push(filter);
+        self
+    }
+
     pub fn build(
         self,
         batch_fee_input_provider: Arc<dyn BatchFeeModelInputProvider>,
@@ -263,6 +289,8 @@ impl TxSenderBuilder {
             storage_caches,
             whitelisted_tokens_for_aa_cache,
             sealer,
+            pending_state: self.pending_state,
+            tx_filters: self.tx_filters,
             executor: TransactionExecutor::Real,
         }))
     }
@@ -282,6 +310,11 @@ pub struct TxSenderConfig {
     pub validation_computational_gas_limit: u32,
     pub chain_id: L2ChainId,
     pub whitelisted_tokens_for_aa: Vec<Address>,
+    /// Operator-defined account validation rules applied on top of the default ones.
+    pub custom_validation_rules: CustomValidationRules,
+    /// Limits for pending transactions of a single account. Should be consistent with the limits used by
+    /// the [`MasterPoolSink`] on the main node.
+    pub account_tx_limits: AccountTxLimits,
 }
 
 impl TxSenderConfig {
@@ -301,8 +334,24 @@ impl TxSenderConfig {
                 .validation_computational_gas_limit,
             chain_id,
             whitelisted_tokens_for_aa: web3_json_config.whitelisted_tokens_for_aa.clone(),
+            custom_validation_rules: CustomValidationRules::new(
+                &state_keeper_config.validation_trusted_addresses,
+                &state_keeper_config.validation_trusted_slots,
+                &state_keeper_config.validation_allowed_opcodes,
+            ),
+            account_tx_limits: AccountTxLimits::default(),
         }
     }
+
+    /// Sets limits for pending transactions of a single account based on the mempool config.
+    #[must_use]
+    pub fn with_mempool_config(mut self, mempool_config: &MempoolConfig) -> Self {
+        self.account_tx_limits = AccountTxLimits {
+            min_replacement_fee_bump_percent: mempool_config.replacement_min_fee_bump_percent,
+            max_pending_txs_per_account: mempool_config.max_pending_txs_per_account,
+        };
+        self
+    }
 }
 
 pub struct TxSenderInner {
@@ -321,6 +370,10 @@ pub struct TxSenderInner {
     pub(super) whitelisted_tokens_for_aa_cache: Arc<RwLock<Vec<Address>>>,
     /// Batch sealer used to check whether transaction can be executed by the sequencer.
     sealer: Arc<dyn ConditionalSealer>,
+    /// Pending state of the state keeper running in the same process.
+    pub(super) pending_state: Option<PendingStateReader>,
+    /// Policy filters for submitted transactions.
+    pub(super) tx_filters: Vec<Arc<dyn TxFilter>>,
     pub(super) executor: TransactionExecutor,
 }
 
@@ -334,6 +387,17 @@ impl std::fmt::Debug for TxSender {
 }
 
 impl TxSender {
+    /// Returns a snapshot of the state keeper pending state that can be applied on top of the persisted state
+    /// with the specified pending L2 block. Returns `None` if there is no such snapshot, e.g. because the state keeper
+    /// has already advanced to the next L1 batch.
+    pub(crate) fn pending_state_snapshot(
+        &self,
+        pending_l2_block: L2BlockNumber,
+    ) -> Option<PendingStateSnapshot> {
+        let snapshot = self.0.pending_state.as_ref()?.snapshot()?;
+        snapshot.covers(pending_l2_block).then_some(snapshot)
+    }
+
     pub(crate) fn vm_concurrency_limiter(&self) -> Arc<VmConcurrencyLimiter> {
         Arc::clone(&self.0.vm_concurrency_limiter)
     }
@@ -394,6 +458,9 @@ impl TxSender {
             "Submit tx {tx_hash:?} with execution metrics {:?}",
             execution_output.metrics
         );
+        for filter in &self.0.tx_filters {
+            filter.check(&tx, &execution_output.vm).await?;
+        }
         stage_latency.observe();
 
         let stage_latency =
@@ -409,6 +476,7 @@ impl TxSender {
                 shared_args,
                 block_args,
                 computational_gas_limit,
+                &self.0.sender_config.custom_validation_rules,
             )
             .await;
         stage_latency.observe();
@@ -480,6 +548,7 @@ impl TxSender {
                 .validation_computational_gas_limit,
             chain_id: self.0.sender_config.chain_id,
             whitelisted_tokens_for_aa: self.read_whitelisted_tokens_for_aa_cache().await,
+            pending_state: None,
         })
     }
 
@@ -553,6 +622,7 @@ impl TxSender {
         // We still double-check the nonce manually
         // to make sure that only the correct nonce is submitted and the transaction's hashes never repeat
         self.validate_account_nonce(tx).await?;
+        self.validate_account_tx_limits(tx).await?;
         // Even though without enough balance the tx will not pass anyway
         // we check the user for enough balance explicitly here for better DevEx.
         self.validate_enough_balance(tx).await?;
@@ -590,6 +660,42 @@ impl TxSender {
         }
     }
 
+    /// Checks replacement rules and the pending transactions limit for the transaction initiator.
+    /// This check is best-effort, since it uses the replica pool which may lag behind the master one; it allows
+    /// to reject transactions before executing them. The authoritative check is performed by [`MasterPoolSink`].
+    async fn validate_account_tx_limits(&self, tx: &L2Tx) -> Result<(), SubmitTxError> {
+        let limits = &self.0.sender_config.account_tx_limits;
+        if *limits == AccountTxLimits::default() {
+            return Ok(()); // No limits are configured
+        }
+
+        let initiator_account = tx.initiator_account();
+        let mut storage = self.acquire_replica_connection().await?;
+        let pending_tx = storage
+            .transactions_web3_dal()
+            .get_pending_l2_tx_fee(initiator_account, tx.nonce())
+            .await
+            .map_err(DalError::generalize)?;
+        match pending_tx {
+            // Duplicate transactions are handled by the transaction sink.
+            Some((pending_tx_hash, _)) if pending_tx_hash == tx.hash() => Ok(()),
+            Some((_, pending_tx_fee)) => {
+                limits.check_replacement(&pending_tx_fee, &tx.common_data.fee)?;
+                Ok(())
+            }
+            None if limits.max_pending_txs_per_account.is_some() => {
+                let pending_count = storage
+                    .transactions_web3_dal()
+                    .get_pending_l2_txs_count(initiator_account)
+                    .await
+                    .map_err(DalError::generalize)?;
+                limits.check_pending_count(pending_count)?;
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
     async fn get_expected_nonce(&self, initiator_account: Address) -> anyhow::Result<Nonce> {
         let mut storage = self.acquire_replica_connection().await?;
         let latest_block_number = storage
@@ -725,6 +831,7 @@ impl TxSender {
             caches: self.storage_caches(),
             chain_id: config.chain_id,
             whitelisted_tokens_for_aa: self.read_whitelisted_tokens_for_aa_cache().await,
+            pending_state: None,
         }
     }
 
@@ -991,15 +1098,35 @@ impl TxSender {
         tx: L2Tx,
         state_override: Option<StateOverride>,
     ) -> Result<Vec<u8>, SubmitTxError> {
+        self.eth_call_with_logs(block_args, call_overrides, tx, state_override)
+            .await?
+            .into_api_call_result()
+    }
+
+    /// Same as [`Self::eth_call()`], but returns the full VM execution result (including storage logs)
+    /// instead of the call output. Unlike `eth_call()`, reverted or halted execution doesn't produce an error.
+    pub(super) async fn eth_call_with_logs(
+        &self,
+        block_args: BlockArgs,
+        call_overrides: CallOverrides,
+        tx: L2Tx,
+        state_override: Option<StateOverride>,
+    ) -> Result<VmExecutionResultAndLogs, SubmitTxError> {
         let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
         let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
 
         let vm_execution_cache_misses_limit = self.0.sender_config.vm_execution_cache_misses_limit;
-        self.0
+        let mut shared_args = self.shared_args().await?;
+        if block_args.is_pending_l2_block() {
+            shared_args.pending_state =
+                self.pending_state_snapshot(block_args.resolved_block_number());
+        }
+        let result = self
+            .0
             .executor
             .execute_tx_eth_call(
                 vm_permit,
-                self.shared_args().await?,
+                shared_args,
                 self.0.replica_connection_pool.clone(),
                 call_overrides,
                 tx,
@@ -1008,8 +1135,36 @@ impl TxSender {
                 vec![],
                 state_override,
             )
-            .await?
-            .into_api_call_result()
+            .await?;
+        Ok(result)
+    }
+
+    /// Simulates a sequence of L2 blocks on top of the state specified by `block_args`. Transactions are executed
+    /// in the `eth_call` mode; each transaction observes the changes made by the preceding ones.
+    pub(super) async fn simulate_blocks(
+        &self,
+        block_args: BlockArgs,
+        enforced_base_fee: Option<u64>,
+        state_override: Option<StateOverride>,
+        blocks: Vec<SimulationBlock>,
+    ) -> Result<Vec<SimulatedBlockOutput>, SimulationError> {
+        let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
+        let vm_permit = vm_permit.context("cannot acquire VM permit")?;
+
+        let vm_execution_cache_misses_limit = self.0.sender_config.vm_execution_cache_misses_limit;
+        self.0
+            .executor
+            .simulate_blocks_in_sandbox(
+                vm_permit,
+                self.shared_args().await?,
+                self.0.replica_connection_pool.clone(),
+                enforced_base_fee,
+                vm_execution_cache_misses_limit,
+                block_args,
+                state_override,
+                blocks,
+            )
+            .await
     }
 
     pub async fn gas_price(&self) -> anyhow::Result<u64> {
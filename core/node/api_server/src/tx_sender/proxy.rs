@@ -142,6 +142,25 @@ impl TxCache {
         }
     }
 
+    /// Returns cached transactions, optionally filtered by the initiator address. If there are several transactions
+    /// with the same initiator and nonce (i.e., a transaction was replaced locally), only the latest received one
+    /// is returned.
+    async fn get_latest_txs(&self, initiator_address: Option<Address>) -> Vec<L2Tx> {
+        let inner = self.inner.read().await;
+        inner
+            .tx_hashes_by_initiator
+            .iter()
+            .filter(|((address, _), _)| initiator_address.map_or(true, |filter| filter == *address))
+            .filter_map(|(_, tx_hashes)| {
+                tx_hashes
+                    .iter()
+                    .filter_map(|hash| inner.transactions_by_hash.get(hash))
+                    .max_by_key(|tx| tx.received_timestamp_ms)
+                    .cloned()
+            })
+            .collect()
+    }
+
     async fn get_nonces_for_account(&self, account_address: Address) -> BTreeSet<Nonce> {
         let inner = self.inner.read().await;
         if let Some(nonces) = inner.nonces_by_account.get(&account_address) {
@@ -378,6 +397,40 @@ impl TxSink for TxProxy {
         }
         Ok(None)
     }
+
+    async fn lookup_pending_txs(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        initiator_address: Option<Address>,
+    ) -> Result<Option<Vec<api::Transaction>>, Web3Error> {
+        let txs = self.tx_cache.get_latest_txs(initiator_address).await;
+        let addresses: Vec<_> = txs
+            .iter()
+            .map(L2Tx::initiator_account)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let nonces_for_accounts = storage
+            .storage_web3_dal()
+            .get_nonces_for_addresses(&addresses)
+            .await
+            .map_err(DalError::generalize)?;
+
+        // Transactions with stale nonces are included in an L2 block or replaced; they will be removed
+        // from the cache by `TxCache::run_updates()`, so we just skip them here.
+        let pending_txs = txs
+            .into_iter()
+            .filter(|tx| {
+                let stored_nonce = nonces_for_accounts
+                    .get(&tx.initiator_account())
+                    .copied()
+                    .unwrap_or(Nonce(0));
+                tx.nonce() >= stored_nonce
+            })
+            .map(Into::into)
+            .collect();
+        Ok(Some(pending_txs))
+    }
 }
 
 #[cfg(test)]
@@ -442,6 +495,20 @@ mod tests {
             .unwrap()
             .expect("no transaction");
         assert_eq!(tx_details.initiator_address, tx.initiator_account());
+
+        let pending_txs = proxy
+            .lookup_pending_txs(&mut storage, None)
+            .await
+            .unwrap()
+            .expect("no pending transactions");
+        let pending_hashes: Vec<_> = pending_txs.iter().map(|tx| tx.hash).collect();
+        assert_eq!(pending_hashes, [tx.hash()]);
+        let pending_txs = proxy
+            .lookup_pending_txs(&mut storage, Some(Address::repeat_byte(1)))
+            .await
+            .unwrap()
+            .expect("no pending transactions");
+        assert!(pending_txs.is_empty());
     }
 
     #[tokio::test]
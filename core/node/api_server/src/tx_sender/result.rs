@@ -1,8 +1,10 @@
 use thiserror::Error;
+use zksync_mempool::L2TxRejection;
 use zksync_multivm::interface::{ExecutionResult, VmExecutionResultAndLogs};
 use zksync_types::{l2::error::TxCheckError, U256};
 use zksync_web3_decl::error::EnrichedClientError;
 
+use super::filter::TxFilterError;
 use crate::execution_sandbox::{SandboxExecutionError, ValidationError};
 
 /// Errors that con occur submitting a transaction or estimating gas for its execution.
@@ -14,6 +16,13 @@ pub enum SubmitTxError {
     NonceIsTooLow(u32, u32, u32),
     #[error("insertion of another transaction with the same nonce is in progress")]
     InsertionInProgress,
+    #[error(
+        "replacement transaction underpriced. max fee per gas must be at least {0} or gas per pubdata limit \
+        must be at least {1}, without decreasing the other value"
+    )]
+    ReplacementUnderpriced(U256, U256),
+    #[error("too many pending transactions for the account. at most {0} are allowed")]
+    TooManyPendingTransactions(usize),
     #[error("{0}")]
     IncorrectTx(#[from] TxCheckError),
     #[error("insufficient funds for gas + value. balance: {0}, fee: {1}, value: {2}")]
@@ -72,6 +81,8 @@ pub enum SubmitTxError {
     ProxyError(#[from] EnrichedClientError),
     #[error("not enough gas to publish compressed bytecodes")]
     FailedToPublishCompressedBytecodes,
+    #[error("transaction rejected by policy: {0}")]
+    RejectedByPolicy(String),
     /// Catch-all internal error (e.g., database error) that should not be exposed to the caller.
     #[error("internal error")]
     Internal(#[from] anyhow::Error),
@@ -83,6 +94,8 @@ impl SubmitTxError {
             Self::NonceIsTooHigh(_, _, _) => "nonce-is-too-high",
             Self::NonceIsTooLow(_, _, _) => "nonce-is-too-low",
             Self::InsertionInProgress => "insertion-in-progress",
+            Self::ReplacementUnderpriced(_, _) => "replacement-underpriced",
+            Self::TooManyPendingTransactions(_) => "too-many-pending-transactions",
             Self::IncorrectTx(_) => "incorrect-tx",
             Self::NotEnoughBalanceForFeeValue(_, _, _) => "not-enough-balance-for-fee",
             Self::ExecutionReverted(_, _) => "execution-reverted",
@@ -107,6 +120,7 @@ impl SubmitTxError {
             Self::IntrinsicGas => "intrinsic-gas",
             Self::ProxyError(_) => "proxy-error",
             Self::FailedToPublishCompressedBytecodes => "failed-to-publish-compressed-bytecodes",
+            Self::RejectedByPolicy(_) => "rejected-by-policy",
             Self::Internal(_) => "internal",
         }
     }
@@ -149,6 +163,32 @@ impl From<SandboxExecutionError> for SubmitTxError {
     }
 }
 
+impl From<L2TxRejection> for SubmitTxError {
+    fn from(err: L2TxRejection) -> Self {
+        match err {
+            L2TxRejection::ReplacementUnderpriced {
+                required_max_fee_per_gas,
+                required_gas_per_pubdata_limit,
+            } => Self::ReplacementUnderpriced(
+                required_max_fee_per_gas,
+                required_gas_per_pubdata_limit,
+            ),
+            L2TxRejection::TooManyPendingTransactions { limit } => {
+                Self::TooManyPendingTransactions(limit)
+            }
+        }
+    }
+}
+
+impl From<TxFilterError> for SubmitTxError {
+    fn from(err: TxFilterError) -> Self {
+        match err {
+            TxFilterError::Rejected(reason) => Self::RejectedByPolicy(reason),
+            TxFilterError::Internal(err) => Self::Internal(err),
+        }
+    }
+}
+
 impl From<ValidationError> for SubmitTxError {
     fn from(err: ValidationError) -> Self {
         match err {
@@ -1,5 +1,7 @@
 //! Tests for the transaction sender.
 
+use std::sync::Mutex;
+
 use assert_matches::assert_matches;
 use zksync_multivm::interface::ExecutionResult;
 use zksync_node_fee_model::MockBatchFeeParamsProvider;
@@ -108,15 +110,16 @@ async fn getting_nonce_for_account_after_snapshot_recovery() {
     assert_eq!(nonce, Nonce(0));
 }
 
-#[tokio::test]
-async fn submitting_tx_requires_one_connection() {
-    let pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+/// Creates a transaction with its initiator funded in the genesis state, and a tx sender using `tx_executor`.
+async fn create_funded_tx_sender(
+    pool: ConnectionPool<Core>,
+    tx_executor: MockTransactionExecutor,
+) -> (TxSender, L2Tx) {
     let mut storage = pool.connection().await.unwrap();
     insert_genesis_batch(&mut storage, &GenesisParams::mock())
         .await
         .unwrap();
 
-    let l2_chain_id = L2ChainId::default();
     let fee_input = MockBatchFeeParamsProvider::default()
         .get_batch_fee_input_scaled(1.0, 1.0)
         .await
@@ -124,7 +127,6 @@ async fn submitting_tx_requires_one_connection() {
     let (base_fee, gas_per_pubdata) =
         derive_base_fee_and_gas_per_pubdata(fee_input, ProtocolVersionId::latest().into());
     let tx = create_l2_transaction(base_fee, gas_per_pubdata);
-    let tx_hash = tx.hash();
 
     // Manually set sufficient balance for the tx initiator.
     let balance_key = storage_key_for_eth_balance(&tx.initiator_account());
@@ -136,16 +138,30 @@ async fn submitting_tx_requires_one_connection() {
         .unwrap();
     drop(storage);
 
+    let (tx_sender, _) =
+        create_test_tx_sender(pool, L2ChainId::default(), tx_executor.into()).await;
+    (tx_sender, tx)
+}
+
+#[tokio::test]
+async fn submitting_tx_requires_one_connection() {
+    let pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+    let executed_tx_hashes = Arc::new(Mutex::new(vec![]));
     let mut tx_executor = MockTransactionExecutor::default();
-    tx_executor.set_tx_responses(move |received_tx, _| {
-        assert_eq!(received_tx.hash(), tx_hash);
-        ExecutionResult::Success { output: vec![] }
+    tx_executor.set_tx_responses({
+        let executed_tx_hashes = executed_tx_hashes.clone();
+        move |received_tx, _| {
+            executed_tx_hashes.lock().unwrap().push(received_tx.hash());
+            ExecutionResult::Success { output: vec![] }
+        }
     });
-    let tx_executor = tx_executor.into();
-    let (tx_sender, _) = create_test_tx_sender(pool.clone(), l2_chain_id, tx_executor).await;
+    let (tx_sender, tx) = create_funded_tx_sender(pool.clone(), tx_executor).await;
+    let tx_hash = tx.hash();
+    let l2_chain_id = L2ChainId::default();
 
     let submission_result = tx_sender.submit_tx(tx).await.unwrap();
     assert_matches!(submission_result.0, L2TxSubmissionResult::Added);
+    assert_eq!(*executed_tx_hashes.lock().unwrap(), [tx_hash]);
 
     let mut storage = pool.connection().await.unwrap();
     storage
@@ -155,3 +171,135 @@ async fn submitting_tx_requires_one_connection() {
         .unwrap()
         .expect("transaction is not persisted");
 }
+
+#[tokio::test]
+async fn validating_account_tx_limits() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    let tx = create_l2_transaction(100, 50);
+    storage
+        .transactions_dal()
+        .insert_transaction_l2(&tx, TransactionExecutionMetrics::default())
+        .await
+        .unwrap();
+    drop(storage);
+
+    let tx_executor = MockTransactionExecutor::default().into();
+    let (mut tx_sender, _) =
+        create_test_tx_sender(pool.clone(), L2ChainId::default(), tx_executor).await;
+    Arc::get_mut(&mut tx_sender.0)
+        .unwrap()
+        .sender_config
+        .account_tx_limits = AccountTxLimits {
+        min_replacement_fee_bump_percent: Some(10),
+        max_pending_txs_per_account: Some(1),
+    };
+
+    // The same transaction is not checked.
+    tx_sender.validate_account_tx_limits(&tx).await.unwrap();
+
+    let replacement_tx = |max_fee_per_gas: u64| {
+        let mut replacement = tx.clone();
+        replacement.common_data.fee.max_fee_per_gas = max_fee_per_gas.into();
+        replacement.set_input(H256::random().0.to_vec(), H256::random());
+        replacement
+    };
+    let err = tx_sender
+        .validate_account_tx_limits(&replacement_tx(105))
+        .await
+        .unwrap_err();
+    assert_matches!(
+        err,
+        SubmitTxError::ReplacementUnderpriced(max_fee_per_gas, gas_per_pubdata_limit)
+            if max_fee_per_gas == 110.into() && gas_per_pubdata_limit == 55.into()
+    );
+    tx_sender
+        .validate_account_tx_limits(&replacement_tx(110))
+        .await
+        .unwrap();
+
+    let mut next_tx = replacement_tx(100);
+    next_tx.common_data.nonce = Nonce(1);
+    let err = tx_sender
+        .validate_account_tx_limits(&next_tx)
+        .await
+        .unwrap_err();
+    assert_matches!(err, SubmitTxError::TooManyPendingTransactions(1));
+}
+
+#[tokio::test]
+async fn enforcing_account_tx_limits_for_concurrent_submissions() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut tx_executor = MockTransactionExecutor::default();
+    tx_executor.set_tx_responses(|_, _| ExecutionResult::Success { output: vec![] });
+    let (mut tx_sender, tx) = create_funded_tx_sender(pool.clone(), tx_executor).await;
+    let initiator_account = tx.initiator_account();
+    let mut next_tx = tx.clone();
+    next_tx.common_data.nonce = Nonce(1);
+    next_tx.set_input(H256::random().0.to_vec(), H256::random());
+
+    let limits = AccountTxLimits {
+        min_replacement_fee_bump_percent: None,
+        max_pending_txs_per_account: Some(1),
+    };
+    let inner = Arc::get_mut(&mut tx_sender.0).unwrap();
+    inner.sender_config.account_tx_limits = limits;
+    inner.tx_sink = Arc::new(MasterPoolSink::new(pool.clone()).with_account_limits(limits));
+
+    // The best-effort check on the replica may pass for both transactions; the sink must still reject one of them.
+    let (first_result, second_result) =
+        tokio::join!(tx_sender.submit_tx(tx), tx_sender.submit_tx(next_tx));
+    let (submitted, rejected) = match (first_result, second_result) {
+        (Ok((submitted, _)), Err(rejected)) | (Err(rejected), Ok((submitted, _))) => {
+            (submitted, rejected)
+        }
+        (first_result, second_result) => {
+            panic!("unexpected submission results: {first_result:?}, {second_result:?}")
+        }
+    };
+    assert_matches!(submitted, L2TxSubmissionResult::Added);
+    assert_matches!(rejected, SubmitTxError::TooManyPendingTransactions(1));
+
+    let mut storage = pool.connection().await.unwrap();
+    let pending_count = storage
+        .transactions_web3_dal()
+        .get_pending_l2_txs_count(initiator_account)
+        .await
+        .unwrap();
+    assert_eq!(pending_count, 1);
+}
+
+#[tokio::test]
+async fn submitting_tx_rejected_by_filter() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut tx_executor = MockTransactionExecutor::default();
+    tx_executor.set_tx_responses(|_, _| ExecutionResult::Success { output: vec![] });
+    let (mut tx_sender, mut tx) = create_funded_tx_sender(pool.clone(), tx_executor).await;
+    tx.execute.calldata = vec![0; 10];
+    let tx_hash = tx.hash();
+
+    Arc::get_mut(&mut tx_sender.0)
+        .unwrap()
+        .tx_filters
+        .push(Arc::new(filter::TxLimitsFilter {
+            max_calldata_size: Some(4),
+            max_value: None,
+        }));
+
+    let err = tx_sender.submit_tx(tx).await.unwrap_err();
+    assert_matches!(
+        err,
+        SubmitTxError::RejectedByPolicy(reason) if reason.contains("calldata size 10")
+    );
+
+    let mut storage = pool.connection().await.unwrap();
+    let persisted_tx = storage
+        .transactions_web3_dal()
+        .get_transaction_by_hash(tx_hash, L2ChainId::default())
+        .await
+        .unwrap();
+    assert!(persisted_tx.is_none());
+}
@@ -59,4 +59,15 @@ pub trait TxSink: std::fmt::Debug + Send + Sync + 'static {
     ) -> Result<Option<TransactionDetails>, Web3Error> {
         Ok(None)
     }
+
+    /// Attempts to list pending transactions (optionally, only ones with the specified initiator) in the sink-specific
+    /// storage. Returns `Ok(None)` if the sink doesn't keep track of pending transactions itself; in this case,
+    /// they should be loaded from Postgres. By default, returns `Ok(None)`.
+    async fn lookup_pending_txs(
+        &self,
+        _storage: &mut Connection<'_, Core>,
+        _initiator_address: Option<Address>,
+    ) -> Result<Option<Vec<Transaction>>, Web3Error> {
+        Ok(None)
+    }
 }
@@ -0,0 +1,192 @@
+//! Archive mode: serving historical storage reads for L2 blocks pruned in Postgres from the Merkle tree versions
+//! retained by the tree pruner.
+//!
+//! The Merkle tree only stores states at L1 batch boundaries, so only the last L2 block in each archived L1 batch
+//! can be served; reads for this block return the state at the end of the batch (same as for `eth_getProof`).
+//! Other pruned L2 blocks are rejected, since their state cannot be recovered from the tree. The returned
+//! values can be verified with `zks_getProof` for the same L1 batch, which is also served in the archive mode.
+//!
+//! VM execution (e.g., `eth_call`) is not supported for archived blocks: besides storage, it requires L2 block
+//! headers and bytecodes that are pruned in Postgres. Such requests fail with the same error as with the archive
+//! mode disabled.
+
+use zksync_dal::{Connection, Core};
+use zksync_metadata_calculator::api_server::{TreeApiClient, TreeApiError, TreeEntryWithProof};
+use zksync_system_constants::{
+    SYSTEM_CONTEXT_ADDRESS, SYSTEM_CONTEXT_CURRENT_L2_BLOCK_INFO_POSITION,
+};
+use zksync_types::{
+    api, block::unpack_block_info, AccountTreeId, L1BatchNumber, L2BlockNumber, StorageKey, H256,
+    U256,
+};
+use zksync_utils::h256_to_u256;
+use zksync_web3_decl::error::Web3Error;
+
+use super::state::{tree_api_error, RpcState};
+
+/// L2 block pruned in Postgres, but available in the Merkle tree.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ArchivedBlock {
+    pub number: L2BlockNumber,
+    pub l1_batch: L1BatchNumber,
+}
+
+impl RpcState {
+    fn archive_tree_api(&self) -> Result<&dyn TreeApiClient, Web3Error> {
+        self.tree_api
+            .as_deref()
+            .ok_or(Web3Error::MethodNotImplemented)
+    }
+
+    /// Resolves the specified block ID to an archived L2 block. Returns `Ok(None)` if the archive mode is disabled
+    /// or the block is not pruned; in this case, the block should be processed as usual. Returns an error
+    /// if the block is pruned both in Postgres and in the Merkle tree, or if it's not the last block
+    /// in its L1 batch.
+    pub(crate) async fn resolve_archived_block(
+        &self,
+        connection: &mut Connection<'_, Core>,
+        block: api::BlockId,
+    ) -> Result<Option<ArchivedBlock>, Web3Error> {
+        if !self.api_config.archive_mode {
+            return Ok(None);
+        }
+        let block_number = match block {
+            api::BlockId::Number(api::BlockNumber::Number(number)) => match u32::try_from(number) {
+                Ok(number) => L2BlockNumber(number),
+                Err(_) => return Ok(None),
+            },
+            api::BlockId::Number(api::BlockNumber::Earliest) => L2BlockNumber(0),
+            _ => return Ok(None),
+        };
+        let first_l2_block = self.start_info.first_l2_block(connection).await?;
+        if block_number >= first_l2_block {
+            return Ok(None);
+        }
+        let first_l1_batch = self.start_info.first_l1_batch(connection).await?;
+
+        let tree_info = self
+            .archive_tree_api()?
+            .get_info()
+            .await
+            .map_err(tree_api_error)?;
+        let min_l1_batch = match tree_info.min_l1_batch_number {
+            Some(number) if number < first_l1_batch => number,
+            _ => return Err(Web3Error::PrunedBlock(first_l2_block)),
+        };
+
+        // The oldest retained tree version doesn't allow to determine the first L2 block in the corresponding
+        // L1 batch (unless it's the genesis batch), so we don't serve blocks from it.
+        let mut left = if min_l1_batch == L1BatchNumber(0) {
+            min_l1_batch
+        } else {
+            let last_unavailable_block = self.archived_last_l2_block(min_l1_batch).await?;
+            if block_number <= last_unavailable_block {
+                return Err(Web3Error::PrunedBlock(last_unavailable_block + 1));
+            }
+            min_l1_batch + 1
+        };
+        // The last L1 batch pruned in Postgres ends with `first_l2_block - 1`, so it necessarily contains
+        // the requested block or ends after it.
+        let mut right = first_l1_batch - 1;
+        // Find the first L1 batch ending at or after the requested L2 block.
+        while left < right {
+            let mid = left + (right.0 - left.0) / 2;
+            if self.archived_last_l2_block(mid).await? >= block_number {
+                right = mid;
+            } else {
+                left = mid + 1;
+            }
+        }
+
+        let last_l2_block = self.archived_last_l2_block(left).await?;
+        if block_number != last_l2_block {
+            return Err(Web3Error::NonFinalArchivedBlock(
+                block_number,
+                last_l2_block,
+            ));
+        }
+        Ok(Some(ArchivedBlock {
+            number: block_number,
+            l1_batch: left,
+        }))
+    }
+
+    /// Returns the last L2 block in the specified archived L1 batch. This information is persisted
+    /// in the system context contract storage.
+    async fn archived_last_l2_block(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<L2BlockNumber, Web3Error> {
+        let block_info_key = StorageKey::new(
+            AccountTreeId::new(SYSTEM_CONTEXT_ADDRESS),
+            SYSTEM_CONTEXT_CURRENT_L2_BLOCK_INFO_POSITION,
+        );
+        let [block_info] = self
+            .read_archived_values(l1_batch_number, [block_info_key])
+            .await?;
+        let (block_number, _) = unpack_block_info(h256_to_u256(block_info));
+        let block_number = u32::try_from(block_number).map_err(|_| {
+            anyhow::anyhow!(
+                "L2 block number {block_number} in L1 batch #{l1_batch_number} is out of range"
+            )
+        })?;
+        Ok(L2BlockNumber(block_number))
+    }
+
+    /// Reads storage values at the end of the specified archived L1 batch.
+    pub(crate) async fn read_archived_values<const N: usize>(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        keys: [StorageKey; N],
+    ) -> Result<[H256; N], Web3Error> {
+        let hashed_keys = keys.iter().map(StorageKey::hashed_key_u256).collect();
+        let entries = self
+            .get_archived_tree_proofs(l1_batch_number, hashed_keys)
+            .await?;
+        let values: Vec<_> = entries.into_iter().map(|entry| entry.value).collect();
+        values.try_into().map_err(|values: Vec<_>| {
+            Web3Error::InternalError(anyhow::anyhow!(
+                "unexpected number of entries returned by tree API: expected {N}, got {}",
+                values.len()
+            ))
+        })
+    }
+
+    /// Checks whether the specified L1 batch is pruned in Postgres, but should be served from the Merkle tree
+    /// in the archive mode. If the archive mode is disabled, returns an error for pruned L1 batches.
+    pub(crate) async fn is_archived_l1_batch(
+        &self,
+        connection: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<bool, Web3Error> {
+        if !self.api_config.archive_mode {
+            self.start_info
+                .ensure_not_pruned(l1_batch_number, connection)
+                .await?;
+            return Ok(false);
+        }
+        let first_l1_batch = self.start_info.first_l1_batch(connection).await?;
+        Ok(l1_batch_number < first_l1_batch)
+    }
+
+    /// Requests Merkle proofs for an L1 batch that is pruned in Postgres.
+    pub(crate) async fn get_archived_tree_proofs(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<Vec<TreeEntryWithProof>, Web3Error> {
+        let tree_api = self.archive_tree_api()?;
+        match tree_api.get_proofs(l1_batch_number, hashed_keys).await {
+            Ok(proofs) => Ok(proofs),
+            Err(TreeApiError::NoVersion(_)) => {
+                // The version is pruned in the tree as well; report the first retained L1 batch.
+                let tree_info = tree_api.get_info().await.map_err(tree_api_error)?;
+                let min_l1_batch = tree_info
+                    .min_l1_batch_number
+                    .unwrap_or(tree_info.next_l1_batch_number);
+                Err(Web3Error::PrunedL1Batch(min_l1_batch))
+            }
+            Err(err) => Err(tree_api_error(err)),
+        }
+    }
+}
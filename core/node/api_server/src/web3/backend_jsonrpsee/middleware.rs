@@ -1,31 +1,43 @@
 use std::{
     cell::RefCell,
     collections::HashSet,
+    fmt,
     future::Future,
+    net::IpAddr,
     num::NonZeroU32,
     pin::Pin,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Weak,
+    },
     task::{Context, Poll},
     time::{Duration, Instant},
 };
 
+use anyhow::Context as _;
 use governor::{
-    clock::DefaultClock,
+    clock::{Clock, DefaultClock},
     middleware::NoOpMiddleware,
-    state::{InMemoryState, NotKeyed},
-    Quota, RateLimiter,
+    state::{keyed::DefaultKeyedStateStore, InMemoryState, NotKeyed},
+    NegativeMultiDecision, Quota, RateLimiter,
 };
+use http::{HeaderMap, HeaderName};
 use once_cell::sync::OnceCell;
 use pin_project_lite::pin_project;
 use rand::{rngs::SmallRng, RngCore, SeedableRng};
+use secrecy::ExposeSecret;
 use tokio::sync::watch;
 use tracing::instrument::{Instrument, Instrumented};
 use vise::{
     Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, GaugeGuard, Histogram, Metrics,
 };
+use zksync_config::configs::{
+    api::{ComputeUnitLimits, MethodComputeUnits},
+    secrets::ApiKey,
+};
 use zksync_web3_decl::jsonrpsee::{
     server::middleware::rpc::{layer::ResponseFuture, RpcServiceT},
-    types::{error::ErrorCode, ErrorObject, Request},
+    types::{error::ErrorCode, ErrorObject, ErrorObjectOwned, Request},
     MethodResponse,
 };
 
@@ -105,6 +117,289 @@ where
     }
 }
 
+/// Error code returned when a client exceeds its compute unit limit, as per EIP-1474.
+const LIMIT_EXCEEDED_CODE: i32 = -32005;
+
+/// Kind of a [`ClientKey`] used in metrics labels. API keys and IP addresses aren't used directly
+/// to bound label cardinality and not to expose client identities via metrics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "client", rename_all = "snake_case")]
+enum ClientKind {
+    ApiKey,
+    Ip,
+    Anonymous,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "api_jsonrpc_compute_units")]
+struct ComputeUnitsMetrics {
+    /// Number of compute units consumed by clients.
+    consumed: Family<ClientKind, Counter>,
+    /// Number of calls rejected because the client has exceeded its compute unit limit.
+    rate_limited: Family<ClientKind, Counter>,
+    /// Number of requests with an API key not recognized by the server.
+    unknown_api_keys: Counter,
+}
+
+#[vise::register]
+static COMPUTE_UNITS_METRICS: vise::Global<ComputeUnitsMetrics> = vise::Global::new();
+
+/// Identity of a JSON-RPC client used for compute unit accounting. Extracted from HTTP headers by [`ClientKeyLayer`]
+/// and propagated to RPC-level middleware via request extensions (for WS, headers are captured on connection upgrade).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum ClientKey {
+    /// API key recognized by the server.
+    ApiKey(Arc<str>),
+    Ip(IpAddr),
+    /// Client that cannot be identified. All such clients share a single bucket.
+    Anonymous,
+}
+
+impl ClientKey {
+    fn kind(&self) -> ClientKind {
+        match self {
+            Self::ApiKey(_) => ClientKind::ApiKey,
+            Self::Ip(_) => ClientKind::Ip,
+            Self::Anonymous => ClientKind::Anonymous,
+        }
+    }
+}
+
+/// HTTP-level middleware layer that identifies clients for [`ComputeUnitsMiddleware`].
+///
+/// `jsonrpsee` doesn't expose the remote socket address to middleware, so the client IP can only be obtained
+/// from a header set by a reverse proxy (e.g., `X-Forwarded-For`).
+#[derive(Clone)]
+pub(crate) struct ClientKeyLayer {
+    api_key_header: Option<HeaderName>,
+    client_ip_header: Option<HeaderName>,
+    /// API keys recognized by the server. Unknown keys are ignored, so that clients cannot obtain fresh buckets
+    /// by making up keys.
+    api_keys: Arc<HashSet<String>>,
+}
+
+impl fmt::Debug for ClientKeyLayer {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("ClientKeyLayer")
+            .field("api_key_header", &self.api_key_header)
+            .field("client_ip_header", &self.client_ip_header)
+            .field("api_keys_len", &self.api_keys.len())
+            .finish()
+    }
+}
+
+impl ClientKeyLayer {
+    fn new(limits: &ComputeUnitLimits, api_keys: &[ApiKey]) -> anyhow::Result<Self> {
+        let parse_header = |name: Option<&str>| {
+            name.map(|name| {
+                HeaderName::try_from(name).with_context(|| format!("invalid header name: {name:?}"))
+            })
+            .transpose()
+        };
+        Ok(Self {
+            api_key_header: parse_header(limits.api_key_header.as_deref())
+                .context("api_key_header")?,
+            client_ip_header: parse_header(limits.client_ip_header.as_deref())
+                .context("client_ip_header")?,
+            api_keys: Arc::new(
+                api_keys
+                    .iter()
+                    .map(|key| key.0.expose_secret().clone())
+                    .collect(),
+            ),
+        })
+    }
+
+    fn client_key(&self, headers: &HeaderMap) -> ClientKey {
+        let header_value = |name: &Option<HeaderName>| {
+            let value = headers.get(name.as_ref()?)?.to_str().ok()?;
+            Some(value.trim()).filter(|value| !value.is_empty())
+        };
+
+        if let Some(api_key) = header_value(&self.api_key_header) {
+            if self.api_keys.contains(api_key) {
+                return ClientKey::ApiKey(api_key.into());
+            }
+            COMPUTE_UNITS_METRICS.unknown_api_keys.inc();
+        }
+        // Proxies append to `X-Forwarded-For`-like headers, so we take the last entry; preceding entries
+        // can be set by the client itself.
+        let ip = header_value(&self.client_ip_header)
+            .and_then(|value| value.rsplit(',').next())
+            .and_then(|ip| ip.trim().parse::<IpAddr>().ok());
+        ip.map_or(ClientKey::Anonymous, ClientKey::Ip)
+    }
+}
+
+impl<S> tower::Layer<S> for ClientKeyLayer {
+    type Service = ClientKeyService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ClientKeyService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// Service created by [`ClientKeyLayer`].
+#[derive(Debug, Clone)]
+pub(crate) struct ClientKeyService<S> {
+    inner: S,
+    layer: ClientKeyLayer,
+}
+
+impl<S, B> tower::Service<http::Request<B>> for ClientKeyService<S>
+where
+    S: tower::Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: http::Request<B>) -> Self::Future {
+        let client_key = self.layer.client_key(request.headers());
+        request.extensions_mut().insert(client_key);
+        self.inner.call(request)
+    }
+}
+
+type KeyedRateLimiter =
+    RateLimiter<ClientKey, DefaultKeyedStateStore<ClientKey>, DefaultClock, NoOpMiddleware>;
+
+/// Token bucket limiter for compute units. A single limiter should be shared among all API servers of a node
+/// (i.e., HTTP and WS), so that clients cannot multiply their quota by using several transports.
+pub struct ComputeUnitLimiter {
+    client_key_layer: ClientKeyLayer,
+    rate_limiter: KeyedRateLimiter,
+    method_costs: MethodComputeUnits,
+    burst: NonZeroU32,
+    cleanup_started: AtomicBool,
+}
+
+impl fmt::Debug for ComputeUnitLimiter {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("ComputeUnitLimiter")
+            .field("client_key_layer", &self.client_key_layer)
+            .field("method_costs", &self.method_costs)
+            .field("burst", &self.burst)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ComputeUnitLimiter {
+    /// Interval between evicting buckets of inactive clients.
+    const CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
+    /// Creates a limiter. Clients are only identified by API keys contained in `api_keys`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `limits` contain invalid header names.
+    pub fn new(limits: &ComputeUnitLimits, api_keys: &[ApiKey]) -> anyhow::Result<Self> {
+        let client_key_layer = ClientKeyLayer::new(limits, api_keys)?;
+        let quota = Quota::per_second(limits.per_second).allow_burst(limits.burst);
+        Ok(Self {
+            client_key_layer,
+            rate_limiter: RateLimiter::keyed(quota),
+            method_costs: limits.method_costs.clone(),
+            burst: limits.burst,
+            cleanup_started: AtomicBool::new(false),
+        })
+    }
+
+    pub(crate) fn client_key_layer(&self) -> ClientKeyLayer {
+        self.client_key_layer.clone()
+    }
+
+    fn check(&self, client_key: &ClientKey, method: &str) -> Result<(), ErrorObjectOwned> {
+        let cost = self.method_costs.get(method);
+        let label = client_key.kind();
+        let err = match self.rate_limiter.check_key_n(client_key, cost) {
+            Ok(()) => {
+                COMPUTE_UNITS_METRICS.consumed[&label].inc_by(cost.get().into());
+                return Ok(());
+            }
+            Err(NegativeMultiDecision::BatchNonConforming(_, not_until)) => {
+                let retry_after = not_until.wait_time_from(DefaultClock::default().now());
+                let retry_after_ms = u64::try_from(retry_after.as_millis()).unwrap_or(u64::MAX);
+                ErrorObject::owned(
+                    LIMIT_EXCEEDED_CODE,
+                    "Compute unit limit exceeded",
+                    Some(serde_json::json!({ "retryAfterMs": retry_after_ms })),
+                )
+            }
+            Err(NegativeMultiDecision::InsufficientCapacity(_)) => ErrorObject::owned(
+                LIMIT_EXCEEDED_CODE,
+                format!(
+                    "Method `{method}` costs {cost} compute units, which exceeds the burst limit ({})",
+                    self.burst
+                ),
+                None::<()>,
+            ),
+        };
+        COMPUTE_UNITS_METRICS.rate_limited[&label].inc();
+        Err(err)
+    }
+
+    /// Spawns a task periodically evicting buckets of clients that are indistinguishable from fresh ones, so that
+    /// the limiter state doesn't grow indefinitely. The task is only spawned once per limiter, and terminates
+    /// once the limiter is dropped.
+    pub(crate) fn spawn_cleanup(self: &Arc<Self>) {
+        if !self.cleanup_started.swap(true, Ordering::Relaxed) {
+            tokio::spawn(Self::run_cleanup(Arc::downgrade(self)));
+        }
+    }
+
+    async fn run_cleanup(this: Weak<Self>) {
+        let mut interval = tokio::time::interval(Self::CLEANUP_INTERVAL);
+        loop {
+            interval.tick().await;
+            let Some(this) = this.upgrade() else {
+                return;
+            };
+            this.rate_limiter.retain_recent();
+            this.rate_limiter.shrink_to_fit();
+        }
+    }
+}
+
+/// RPC-level middleware charging each call with compute units according to [`MethodComputeUnits`]. Calls in a batch
+/// are charged separately. Clients are identified by [`ClientKey`]s inserted by [`ClientKeyLayer`].
+#[derive(Debug)]
+pub(crate) struct ComputeUnitsMiddleware<S> {
+    inner: S,
+    limiter: Arc<ComputeUnitLimiter>,
+}
+
+impl<S> ComputeUnitsMiddleware<S> {
+    pub fn new(inner: S, limiter: Arc<ComputeUnitLimiter>) -> Self {
+        Self { inner, limiter }
+    }
+}
+
+impl<'a, S> RpcServiceT<'a> for ComputeUnitsMiddleware<S>
+where
+    S: Send + Sync + RpcServiceT<'a>,
+{
+    type Future = ResponseFuture<S::Future>;
+
+    fn call(&self, request: Request<'a>) -> Self::Future {
+        let client_key = request.extensions().get::<ClientKey>();
+        let client_key = client_key.unwrap_or(&ClientKey::Anonymous);
+        match self.limiter.check(client_key, request.method_name()) {
+            Ok(()) => ResponseFuture::future(self.inner.call(request)),
+            Err(err) => ResponseFuture::ready(MethodResponse::error(request.id, err)),
+        }
+    }
+}
+
 /// RPC-level middleware that adds [`MethodCall`] metadata to method logic. Method handlers can then access this metadata
 /// using [`MethodTracer`], which is a part of `RpcState`. When the handler completes or is dropped, the results are reported
 /// as metrics.
@@ -412,4 +707,69 @@ mod tests {
         let elapsed = now.elapsed();
         assert!(elapsed >= Duration::from_millis(15), "{elapsed:?}");
     }
+
+    fn compute_unit_limits() -> ComputeUnitLimits {
+        ComputeUnitLimits {
+            per_second: NonZeroU32::new(10).unwrap(),
+            burst: NonZeroU32::new(20).unwrap(),
+            method_costs: "eth_getLogs=15,debug_trace*=50".parse().unwrap(),
+            api_key_header: Some("x-api-key".to_owned()),
+            client_ip_header: Some("x-forwarded-for".to_owned()),
+        }
+    }
+
+    fn api_keys() -> Vec<ApiKey> {
+        ["secret", "alice", "bob"]
+            .map(|key| ApiKey(key.to_owned().into()))
+            .into()
+    }
+
+    #[test]
+    fn extracting_client_key() {
+        let layer = ClientKeyLayer::new(&compute_unit_limits(), &api_keys()).unwrap();
+        let mut headers = HeaderMap::new();
+        assert_eq!(layer.client_key(&headers), ClientKey::Anonymous);
+
+        headers.insert("x-forwarded-for", "1.2.3.4, 10.0.0.1".parse().unwrap());
+        let expected_ip = "10.0.0.1".parse().unwrap();
+        assert_eq!(layer.client_key(&headers), ClientKey::Ip(expected_ip));
+        headers.insert("x-api-key", "secret".parse().unwrap());
+        assert_eq!(
+            layer.client_key(&headers),
+            ClientKey::ApiKey("secret".into())
+        );
+        headers.insert("x-api-key", " ".parse().unwrap());
+        assert_eq!(layer.client_key(&headers), ClientKey::Ip(expected_ip));
+        // Unknown API keys must be ignored.
+        headers.insert("x-api-key", "made-up".parse().unwrap());
+        assert_eq!(layer.client_key(&headers), ClientKey::Ip(expected_ip));
+        headers.insert("x-forwarded-for", "unknown".parse().unwrap());
+        assert_eq!(layer.client_key(&headers), ClientKey::Anonymous);
+
+        let mut limits = compute_unit_limits();
+        limits.api_key_header = Some("invalid header".to_owned());
+        ClientKeyLayer::new(&limits, &api_keys()).unwrap_err();
+    }
+
+    #[test]
+    fn compute_unit_limiter_basics() {
+        let limiter = ComputeUnitLimiter::new(&compute_unit_limits(), &api_keys()).unwrap();
+        let alice = ClientKey::ApiKey("alice".into());
+        limiter.check(&alice, "eth_getLogs").unwrap();
+        limiter.check(&alice, "eth_call").unwrap();
+        let err = limiter.check(&alice, "eth_getLogs").unwrap_err();
+        assert_eq!(err.code(), LIMIT_EXCEEDED_CODE);
+        assert!(err.data().is_some());
+
+        // Other clients should have separate buckets.
+        let bob = ClientKey::ApiKey("bob".into());
+        limiter.check(&bob, "eth_getLogs").unwrap();
+        let err = limiter.check(&bob, "debug_traceCall").unwrap_err();
+        assert_eq!(err.code(), LIMIT_EXCEEDED_CODE);
+        assert!(
+            err.message().contains("exceeds the burst limit"),
+            "{}",
+            err.message()
+        );
+    }
 }
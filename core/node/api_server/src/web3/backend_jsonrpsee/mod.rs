@@ -7,13 +7,15 @@ use zksync_web3_decl::{
     jsonrpsee::types::{error::ErrorCode, ErrorObjectOwned},
 };
 
+pub use self::middleware::ComputeUnitLimiter;
 pub(crate) use self::{
     metadata::{MethodMetadata, MethodTracer},
     middleware::{
-        CorrelationMiddleware, LimitMiddleware, MetadataLayer, ShutdownMiddleware, TrafficTracker,
+        ComputeUnitsMiddleware, CorrelationMiddleware, LimitMiddleware, MetadataLayer,
+        ShutdownMiddleware, TrafficTracker,
     },
 };
-use crate::tx_sender::SubmitTxError;
+use crate::{execution_sandbox::SimulationError, tx_sender::SubmitTxError};
 
 mod metadata;
 mod middleware;
@@ -36,9 +38,11 @@ impl MethodTracer {
             Web3Error::NoBlock
             | Web3Error::PrunedBlock(_)
             | Web3Error::PrunedL1Batch(_)
+            | Web3Error::NonFinalArchivedBlock(..)
             | Web3Error::TooManyTopics
             | Web3Error::FilterNotFound
             | Web3Error::InvalidFilterBlockHash
+            | Web3Error::InvalidSimulation(_)
             | Web3Error::LogsLimitExceeded(_, _, _) => ErrorCode::InvalidParams.code(),
             Web3Error::SubmitTransactionError(_, _)
             | Web3Error::SerializationError(_)
@@ -66,3 +70,12 @@ impl From<SubmitTxError> for Web3Error {
         }
     }
 }
+
+impl From<SimulationError> for Web3Error {
+    fn from(err: SimulationError) -> Self {
+        match err {
+            SimulationError::InvalidBlocks(message) => Self::InvalidSimulation(message),
+            SimulationError::Internal(err) => Self::InternalError(err),
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use zksync_types::{
     api::{
-        state_override::StateOverride, Block, BlockId, BlockIdVariant, BlockNumber, Log,
-        Transaction, TransactionId, TransactionReceipt, TransactionVariant,
+        state_override::StateOverride, AccessListResult, Block, BlockId, BlockIdVariant,
+        BlockNumber, EIP1186Proof, Log, SimulatePayload, SimulatedBlock, Transaction,
+        TransactionId, TransactionReceipt, TransactionVariant,
     },
     transaction_request::CallRequest,
     web3::{Bytes, FeeHistory, Index, SyncState},
@@ -49,6 +50,27 @@ impl EthNamespaceServer for EthNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn create_access_list(
+        &self,
+        req: CallRequest,
+        block: Option<BlockIdVariant>,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<AccessListResult> {
+        self.create_access_list_impl(req, block.map(Into::into), state_override)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<Vec<SimulatedBlock>> {
+        self.simulate_v1_impl(payload, block.map(Into::into))
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn gas_price(&self) -> RpcResult<U256> {
         self.gas_price_impl()
             .await
@@ -171,6 +193,17 @@ impl EthNamespaceServer for EthNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn get_proof(
+        &self,
+        address: Address,
+        keys: Vec<H256>,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<Option<EIP1186Proof>> {
+        self.get_proof_impl(address, keys, block.map(Into::into))
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn get_transaction_count(
         &self,
         address: Address,
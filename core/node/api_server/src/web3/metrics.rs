@@ -170,6 +170,7 @@ enum Web3ErrorKind {
     FilterNotFound,
     LogsLimitExceeded,
     InvalidFilterBlockHash,
+    InvalidSimulation,
     TreeApiUnavailable,
     Internal,
 }
@@ -178,7 +179,9 @@ impl Web3ErrorKind {
     fn new(err: &Web3Error) -> Self {
         match err {
             Web3Error::NoBlock => Self::NoBlock,
-            Web3Error::PrunedBlock(_) | Web3Error::PrunedL1Batch(_) => Self::Pruned,
+            Web3Error::PrunedBlock(_)
+            | Web3Error::PrunedL1Batch(_)
+            | Web3Error::NonFinalArchivedBlock(..) => Self::Pruned,
             Web3Error::SubmitTransactionError(..) => Self::SubmitTransaction,
             Web3Error::ProxyError(_) => Self::Proxy,
             Web3Error::SerializationError(_) => Self::TransactionSerialization,
@@ -186,6 +189,7 @@ impl Web3ErrorKind {
             Web3Error::FilterNotFound => Self::FilterNotFound,
             Web3Error::LogsLimitExceeded(..) => Self::LogsLimitExceeded,
             Web3Error::InvalidFilterBlockHash => Self::InvalidFilterBlockHash,
+            Web3Error::InvalidSimulation(_) => Self::InvalidSimulation,
             Web3Error::TreeApiUnavailable => Self::TreeApiUnavailable,
             Web3Error::InternalError(_) | Web3Error::MethodNotImplemented => Self::Internal,
         }
@@ -10,7 +10,9 @@ use tokio::{
 };
 use tower_http::{cors::CorsLayer, metrics::InFlightRequestsLayer};
 use zksync_config::configs::api::{MaxResponseSize, MaxResponseSizeOverrides};
-use zksync_dal::{helpers::wait_for_l1_batch, ConnectionPool, Core};
+use zksync_dal::{
+    helpers::wait_for_l1_batch, notifications::StorageNotifications, ConnectionPool, Core,
+};
 use zksync_health_check::{HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_metadata_calculator::api_server::TreeApiClient;
 use zksync_node_sync::SyncState;
@@ -24,22 +26,22 @@ use zksync_web3_decl::{
     },
     namespaces::{
         DebugNamespaceServer, EnNamespaceServer, EthNamespaceServer, EthPubSubServer,
-        NetNamespaceServer, SnapshotsNamespaceServer, UnstableNamespaceServer, Web3NamespaceServer,
-        ZksNamespaceServer,
+        NetNamespaceServer, SnapshotsNamespaceServer, TxpoolNamespaceServer,
+        UnstableNamespaceServer, Web3NamespaceServer, ZksNamespaceServer,
     },
     types::Filter,
 };
 
 use self::{
     backend_jsonrpsee::{
-        CorrelationMiddleware, LimitMiddleware, MetadataLayer, MethodTracer, ShutdownMiddleware,
-        TrafficTracker,
+        ComputeUnitLimiter, ComputeUnitsMiddleware, CorrelationMiddleware, LimitMiddleware,
+        MetadataLayer, MethodTracer, ShutdownMiddleware, TrafficTracker,
     },
     mempool_cache::MempoolCache,
     metrics::API_METRICS,
     namespaces::{
         DebugNamespace, EnNamespace, EthNamespace, NetNamespace, SnapshotsNamespace,
-        UnstableNamespace, Web3Namespace, ZksNamespace,
+        TxpoolNamespace, UnstableNamespace, Web3Namespace, ZksNamespace,
     },
     pubsub::{EthSubscribe, EthSubscriptionIdProvider, PubSubEvent},
     state::{Filters, InternalApiConfig, RpcState, SealedL2BlockNumber},
@@ -49,6 +51,7 @@ use crate::{
     tx_sender::TxSender,
 };
 
+mod archive;
 pub mod backend_jsonrpsee;
 pub mod mempool_cache;
 pub(super) mod metrics;
@@ -100,6 +103,7 @@ pub enum Namespace {
     Pubsub,
     Snapshots,
     Unstable,
+    Txpool,
 }
 
 impl Namespace {
@@ -132,9 +136,11 @@ struct OptionalApiParams {
     batch_request_size_limit: Option<usize>,
     response_body_size_limit: Option<MaxResponseSize>,
     websocket_requests_per_minute_limit: Option<NonZeroU32>,
+    compute_unit_limiter: Option<Arc<ComputeUnitLimiter>>,
     tree_api: Option<Arc<dyn TreeApiClient>>,
     mempool_cache: Option<MempoolCache>,
     extended_tracing: bool,
+    storage_notifications: Option<StorageNotifications>,
     pub_sub_events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
 }
 
@@ -249,6 +255,13 @@ impl ApiBuilder {
         self
     }
 
+    /// Limits compute units spent by clients. The same limiter should be passed to all servers of a node,
+    /// so that compute units are accounted across transports.
+    pub fn with_compute_unit_limiter(mut self, limiter: Arc<ComputeUnitLimiter>) -> Self {
+        self.optional.compute_unit_limiter = Some(limiter);
+        self
+    }
+
     pub fn with_sync_state(mut self, sync_state: SyncState) -> Self {
         self.optional.sync_state = Some(sync_state);
         self
@@ -285,6 +298,14 @@ impl ApiBuilder {
         self
     }
 
+    /// Configures storage notifications used to drive pub/sub subscriptions. If not set, pub/sub notifiers
+    /// poll Postgres with the configured polling interval.
+    pub fn with_storage_notifications(mut self, notifications: StorageNotifications) -> Self {
+        tracing::info!("Using storage notifications for pub/sub: {notifications:?}");
+        self.optional.storage_notifications = Some(notifications);
+        self
+    }
+
     // Intended for tests only.
     #[doc(hidden)]
     fn with_pub_sub_events(mut self, sender: mpsc::UnboundedSender<PubSubEvent>) -> Self {
@@ -412,6 +433,10 @@ impl ApiServer {
             rpc.merge(SnapshotsNamespace::new(rpc_state.clone()).into_rpc())
                 .context("cannot merge snapshots namespace")?;
         }
+        if namespaces.contains(&Namespace::Txpool) {
+            rpc.merge(TxpoolNamespace::new(rpc_state.clone()).into_rpc())
+                .context("cannot merge txpool namespace")?;
+        }
         if namespaces.contains(&Namespace::Unstable) {
             rpc.merge(UnstableNamespace::new(rpc_state).into_rpc())
                 .context("cannot merge unstable namespace")?;
@@ -499,6 +524,7 @@ impl ApiServer {
             tasks.extend(pub_sub.spawn_notifiers(
                 self.pool.clone(),
                 self.polling_interval,
+                self.optional.storage_notifications.clone(),
                 stop_receiver.clone(),
             ));
             Some(pub_sub)
@@ -630,6 +656,7 @@ impl ApiServer {
                 (u32::MAX, MaxResponseSizeOverrides::empty())
             };
         let websocket_requests_per_minute_limit = self.optional.websocket_requests_per_minute_limit;
+        let compute_unit_limiter = self.optional.compute_unit_limiter.clone();
         let subscriptions_limit = self.optional.subscriptions_limit;
         let vm_barrier = self.optional.vm_barrier.clone();
         let health_updater = self.health_updater.clone();
@@ -665,10 +692,19 @@ impl ApiServer {
                 future::ready(())
             }),
         );
+        // Setup compute unit limits. Clients are identified by the HTTP middleware and are then charged by the RPC middleware.
+        let client_key_layer = compute_unit_limiter.as_ref().map(|limiter| {
+            tracing::info!(
+                "Limiting compute units for {transport_str} API server clients: {limiter:?}"
+            );
+            limiter.spawn_cleanup();
+            limiter.client_key_layer()
+        });
         // Assemble server middleware.
         let middleware = tower::ServiceBuilder::new()
             .layer(in_flight_requests)
-            .option_layer(cors);
+            .option_layer(cors)
+            .option_layer(client_key_layer);
 
         // Settings shared by HTTP and WS servers.
         let max_connections = !is_http
@@ -701,6 +737,9 @@ impl ApiServer {
                 tower::layer::layer_fn(move |svc| {
                     LimitMiddleware::new(svc, websocket_requests_per_minute_limit)
                 })
+            }))
+            .option_layer(compute_unit_limiter.map(|limiter| {
+                tower::layer::layer_fn(move |svc| ComputeUnitsMiddleware::new(svc, limiter.clone()))
             }));
 
         let server_builder = ServerBuilder::default()
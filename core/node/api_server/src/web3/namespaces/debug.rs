@@ -1,21 +1,28 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use anyhow::Context as _;
 use once_cell::sync::OnceCell;
-use zksync_dal::{CoreDal, DalError};
+use zksync_dal::{Connection, Core, CoreDal, DalError};
 use zksync_multivm::{
-    interface::ExecutionResult, vm_latest::constants::BATCH_COMPUTATIONAL_GAS_LIMIT,
+    interface::ExecutionResult, tracers::prestate_tracer,
+    vm_latest::constants::BATCH_COMPUTATIONAL_GAS_LIMIT,
 };
 use zksync_system_constants::MAX_ENCODED_TX_SIZE;
 use zksync_types::{
-    api::{BlockId, BlockNumber, DebugCall, ResultDebugCall, TracerConfig},
+    api::{
+        BlockId, BlockNumber, DebugCall, DebugTrace, PrestateAccount, PrestateTrace,
+        ResultDebugCall, ResultDebugTrace, SupportedTracers, TracerConfig,
+    },
     debug_flat_call::{flatten_debug_calls, DebugCallFlat},
     fee_model::BatchFeeInput,
     l2::L2Tx,
     transaction_request::CallRequest,
+    utils::decompose_full_nonce,
     vm_trace::Call,
-    AccountTreeId, H256,
+    web3::Bytes,
+    AccountTreeId, Address, L2BlockNumber, Transaction, H256,
 };
+use zksync_utils::u256_to_h256;
 use zksync_web3_decl::error::Web3Error;
 
 use crate::{
@@ -63,6 +70,52 @@ impl DebugNamespace {
         &self,
         block_id: BlockId,
         options: Option<TracerConfig>,
+    ) -> Result<Vec<ResultDebugTrace>, Web3Error> {
+        let Some(TracerConfig {
+            tracer: SupportedTracers::PrestateTracer,
+            tracer_config,
+        }) = options
+        else {
+            let call_traces = self.trace_block_calls(block_id, options).await?;
+            return Ok(call_traces
+                .into_iter()
+                .map(|ResultDebugCall { result }| ResultDebugTrace {
+                    result: DebugTrace::Call(result),
+                })
+                .collect());
+        };
+
+        self.current_method().set_block_id(block_id);
+        if matches!(block_id, BlockId::Number(BlockNumber::Pending)) {
+            return Ok(vec![]);
+        }
+        let mut connection = self.state.acquire_connection().await?;
+        let block_number = self.state.resolve_block(&mut connection, block_id).await?;
+        self.current_method()
+            .set_block_diff(self.state.last_sealed_l2_block.diff(block_number));
+        let txs = connection
+            .transactions_web3_dal()
+            .get_raw_l2_block_transactions(block_number)
+            .await
+            .map_err(DalError::generalize)?;
+        drop(connection);
+
+        let diff_mode = tracer_config.prestate_tracer.diff_mode;
+        let traces = self
+            .replay_l2_block_with_prestate(block_number, txs, 0, diff_mode)
+            .await?;
+        Ok(traces
+            .into_iter()
+            .map(|trace| ResultDebugTrace {
+                result: DebugTrace::Prestate(trace),
+            })
+            .collect())
+    }
+
+    async fn trace_block_calls(
+        &self,
+        block_id: BlockId,
+        options: Option<TracerConfig>,
     ) -> Result<Vec<ResultDebugCall>, Web3Error> {
         self.current_method().set_block_id(block_id);
         if matches!(block_id, BlockId::Number(BlockNumber::Pending)) {
@@ -71,7 +124,7 @@ impl DebugNamespace {
         }
 
         let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
+            .map(|options| options.tracer_config.call_tracer.only_top_call)
             .unwrap_or(false);
         let mut connection = self.state.acquire_connection().await?;
         let block_number = self.state.resolve_block(&mut connection, block_id).await?;
@@ -101,7 +154,7 @@ impl DebugNamespace {
         block_id: BlockId,
         options: Option<TracerConfig>,
     ) -> Result<Vec<DebugCallFlat>, Web3Error> {
-        let call_trace = self.debug_trace_block_impl(block_id, options).await?;
+        let call_trace = self.trace_block_calls(block_id, options).await?;
         let call_trace_flat = flatten_debug_calls(call_trace);
         Ok(call_trace_flat)
     }
@@ -110,9 +163,21 @@ impl DebugNamespace {
         &self,
         tx_hash: H256,
         options: Option<TracerConfig>,
-    ) -> Result<Option<DebugCall>, Web3Error> {
+    ) -> Result<Option<DebugTrace>, Web3Error> {
+        if let Some(TracerConfig {
+            tracer: SupportedTracers::PrestateTracer,
+            tracer_config,
+        }) = options
+        {
+            let diff_mode = tracer_config.prestate_tracer.diff_mode;
+            let trace = self
+                .trace_transaction_with_prestate(tx_hash, diff_mode)
+                .await?;
+            return Ok(trace.map(DebugTrace::Prestate));
+        }
+
         let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
+            .map(|options| options.tracer_config.call_tracer.only_top_call)
             .unwrap_or(false);
         let mut connection = self.state.acquire_connection().await?;
         let call_trace = connection
@@ -125,22 +190,182 @@ impl DebugNamespace {
             if only_top_call {
                 result.calls = vec![];
             }
-            result
+            DebugTrace::Call(result)
         }))
     }
 
+    async fn trace_transaction_with_prestate(
+        &self,
+        tx_hash: H256,
+        diff_mode: bool,
+    ) -> Result<Option<PrestateTrace>, Web3Error> {
+        let mut connection = self.state.acquire_connection().await?;
+        let api_tx = connection
+            .transactions_web3_dal()
+            .get_transaction_by_hash(tx_hash, self.sender_config().chain_id)
+            .await
+            .map_err(DalError::generalize)?;
+        let Some(block_number) = api_tx.and_then(|tx| tx.block_number) else {
+            return Ok(None); // The transaction is unknown or not executed yet
+        };
+        let block_number = L2BlockNumber(block_number.as_u32());
+        self.current_method()
+            .set_block_diff(self.state.last_sealed_l2_block.diff(block_number));
+
+        let mut txs = connection
+            .transactions_web3_dal()
+            .get_raw_l2_block_transactions(block_number)
+            .await
+            .map_err(DalError::generalize)?;
+        drop(connection);
+        let Some(tx_index) = txs.iter().position(|tx| tx.hash() == tx_hash) else {
+            return Ok(None); // The block was reverted concurrently
+        };
+        // Transactions following the traced one don't influence its execution
+        txs.truncate(tx_index + 1);
+
+        let mut traces = self
+            .replay_l2_block_with_prestate(block_number, txs, tx_index, diff_mode)
+            .await?;
+        Ok(traces.pop())
+    }
+
+    /// Re-executes transactions from the specified L2 block on top of the state after the previous block,
+    /// tracing transactions starting from `traced_from` with the prestate tracer.
+    ///
+    /// Note that transactions are executed in the context of the previous L2 block, so the block number
+    /// and timestamp observed by the transactions may differ from the original execution.
+    async fn replay_l2_block_with_prestate(
+        &self,
+        block_number: L2BlockNumber,
+        txs: Vec<Transaction>,
+        traced_from: usize,
+        diff_mode: bool,
+    ) -> Result<Vec<PrestateTrace>, Web3Error> {
+        let Some(prev_block_number) = block_number.0.checked_sub(1) else {
+            return Ok(vec![]); // The genesis block doesn't contain transactions
+        };
+        let mut connection = self.state.acquire_connection().await?;
+        let block_args = self
+            .state
+            .resolve_block_args(
+                &mut connection,
+                BlockId::Number(BlockNumber::Number(prev_block_number.into())),
+            )
+            .await?;
+        // Transactions must be replayed with the fee input they were originally executed with; otherwise,
+        // traced gas and balances would differ from the original execution.
+        let block_header = connection
+            .blocks_dal()
+            .get_l2_block_header(block_number)
+            .await
+            .map_err(DalError::generalize)?
+            .with_context(|| format!("L2 block #{block_number} disappeared from storage"))?;
+        drop(connection);
+
+        let mut tracer_results = vec![];
+        let txs = txs
+            .into_iter()
+            .enumerate()
+            .map(|(i, tx)| {
+                if i < traced_from {
+                    return (tx, vec![]);
+                }
+                let result = Arc::new(OnceCell::default());
+                tracer_results.push(result.clone());
+                let tracer = ApiTracer::PrestateTracer { diff_mode, result };
+                (tx, vec![tracer])
+            })
+            .collect();
+
+        let shared_args = TxSharedArgs {
+            fee_input: block_header.batch_fee_input,
+            ..self.shared_args().await
+        };
+        let vm_permit = self
+            .state
+            .tx_sender
+            .vm_concurrency_limiter()
+            .acquire()
+            .await;
+        let vm_permit = vm_permit.context("cannot acquire VM permit")?;
+        let executor = &self.state.tx_sender.0.executor;
+        executor
+            .replay_txs_in_sandbox(
+                vm_permit,
+                shared_args,
+                self.state.connection_pool.clone(),
+                block_args,
+                txs,
+            )
+            .await?;
+
+        let mut traces = Vec::with_capacity(tracer_results.len());
+        for result in tracer_results {
+            let state = Arc::try_unwrap(result).unwrap().take().unwrap_or_default();
+            traces.push(self.prestate_trace(state, diff_mode).await?);
+        }
+        Ok(traces)
+    }
+
+    /// Converts the prestate tracer output into the API format.
+    async fn prestate_trace(
+        &self,
+        (pre, post): (prestate_tracer::State, prestate_tracer::State),
+        diff_mode: bool,
+    ) -> Result<PrestateTrace, Web3Error> {
+        let mut connection = self.state.acquire_connection().await?;
+        let pre = Self::convert_prestate(&mut connection, pre).await?;
+        Ok(if diff_mode {
+            let post = Self::convert_prestate(&mut connection, post).await?;
+            PrestateTrace::Diff { pre, post }
+        } else {
+            PrestateTrace::Prestate(pre)
+        })
+    }
+
+    async fn convert_prestate(
+        connection: &mut Connection<'_, Core>,
+        state: prestate_tracer::State,
+    ) -> Result<HashMap<Address, PrestateAccount>, Web3Error> {
+        let mut accounts = HashMap::with_capacity(state.len());
+        for (address, account) in state {
+            let code = match account.code {
+                Some(code_hash) if !code_hash.is_zero() => connection
+                    .factory_deps_dal()
+                    .get_sealed_factory_dep(u256_to_h256(code_hash))
+                    .await
+                    .map_err(DalError::generalize)?
+                    .map(Bytes::from),
+                _ => None,
+            };
+            let nonce = account
+                .nonce
+                .map(|full_nonce| decompose_full_nonce(full_nonce).0.as_u64());
+            let account = PrestateAccount {
+                balance: account.balance,
+                nonce,
+                code,
+                storage: account.storage.unwrap_or_default(),
+            };
+            accounts.insert(address, account);
+        }
+        Ok(accounts)
+    }
+
     pub async fn debug_trace_call_impl(
         &self,
         mut request: CallRequest,
         block_id: Option<BlockId>,
         options: Option<TracerConfig>,
-    ) -> Result<DebugCall, Web3Error> {
+    ) -> Result<DebugTrace, Web3Error> {
         let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
         self.current_method().set_block_id(block_id);
 
-        let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
-            .unwrap_or(false);
+        let tracer = options.map_or(SupportedTracers::CallTracer, |options| options.tracer);
+        let tracer_config = options
+            .map(|options| options.tracer_config)
+            .unwrap_or_default();
 
         let mut connection = self.state.acquire_connection().await?;
         let block_args = self
@@ -180,10 +405,17 @@ impl DebugNamespace {
 
         // We don't need properly trace if we only need top call
         let call_tracer_result = Arc::new(OnceCell::default());
-        let custom_tracers = if only_top_call {
-            vec![]
-        } else {
-            vec![ApiTracer::CallTracer(call_tracer_result.clone())]
+        let prestate_tracer_result = Arc::new(OnceCell::default());
+        let diff_mode = tracer_config.prestate_tracer.diff_mode;
+        let custom_tracers = match tracer {
+            SupportedTracers::CallTracer if tracer_config.call_tracer.only_top_call => vec![],
+            SupportedTracers::CallTracer => {
+                vec![ApiTracer::CallTracer(call_tracer_result.clone())]
+            }
+            SupportedTracers::PrestateTracer => vec![ApiTracer::PrestateTracer {
+                diff_mode,
+                result: prestate_tracer_result.clone(),
+            }],
         };
 
         let executor = &self.state.tx_sender.0.executor;
@@ -201,6 +433,16 @@ impl DebugNamespace {
             )
             .await?;
 
+        if tracer == SupportedTracers::PrestateTracer {
+            // Unlike the call tracer, the prestate is returned even if the transaction has halted
+            let state = Arc::try_unwrap(prestate_tracer_result)
+                .unwrap()
+                .take()
+                .unwrap_or_default();
+            let trace = self.prestate_trace(state, diff_mode).await?;
+            return Ok(DebugTrace::Prestate(trace));
+        }
+
         let (output, revert_reason) = match result.result {
             ExecutionResult::Success { output, .. } => (output, None),
             ExecutionResult::Revert { output } => (vec![], Some(output.to_string())),
@@ -226,7 +468,7 @@ impl DebugNamespace {
             revert_reason,
             trace,
         );
-        Ok(call.into())
+        Ok(DebugTrace::Call(call.into()))
     }
 
     async fn shared_args(&self) -> TxSharedArgs {
@@ -243,6 +485,7 @@ impl DebugNamespace {
                 .tx_sender
                 .read_whitelisted_tokens_for_aa_cache()
                 .await,
+            pending_state: None,
         }
     }
 }
@@ -1,30 +1,38 @@
 use anyhow::Context as _;
 use zksync_dal::{CoreDal, DalError};
+use zksync_multivm::interface::ExecutionResult;
+use zksync_state_keeper::PendingStateSnapshot;
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
-        state_override::StateOverride, BlockId, BlockNumber, GetLogsFilter, Transaction,
-        TransactionId, TransactionReceipt, TransactionVariant,
+        state_override::StateOverride, AccessListResult, BlockId, BlockNumber, EIP1186Proof,
+        EIP1186StorageProof, GetLogsFilter, SimulatePayload, SimulatedBlock, SimulatedCall,
+        SimulatedCallError, Transaction, TransactionId, TransactionReceipt, TransactionVariant,
     },
+    get_code_key, get_nonce_key,
     l2::{L2Tx, TransactionType},
-    transaction_request::CallRequest,
-    utils::decompose_full_nonce,
-    web3::{self, Bytes, FeeHistory, SyncInfo, SyncState},
-    AccountTreeId, L2BlockNumber, StorageKey, H256, L2_BASE_TOKEN_ADDRESS, U256,
+    transaction_request::{CallOverrides, CallRequest},
+    utils::{decompose_full_nonce, storage_key_for_standard_token_balance},
+    web3::{self, AccessList, AccessListItem, Bytes, FeeHistory, SyncInfo, SyncState},
+    AccountTreeId, L1BatchNumber, L2BlockNumber, PackedEthSignature, StorageKey,
+    StorageLogWithPreviousValue, H256, L2_BASE_TOKEN_ADDRESS, U256,
 };
-use zksync_utils::u256_to_h256;
+use zksync_utils::{h256_to_u256, u256_to_h256};
 use zksync_web3_decl::{
     error::Web3Error,
     types::{Address, Block, Filter, FilterChanges, Log, U64},
 };
 
 use crate::{
+    execution_sandbox::{BlockArgs, SimulatedBlockOutput, SimulationBlock},
     utils::open_readonly_transaction,
     web3::{backend_jsonrpsee::MethodTracer, metrics::API_METRICS, state::RpcState, TypedFilter},
 };
 
 pub const EVENT_TOPIC_NUMBER_LIMIT: usize = 4;
 pub const PROTOCOL_VERSION: &str = "zks/1";
+/// Maximum number of blocks that can be simulated by a single `eth_simulateV1` call.
+const MAX_SIMULATED_BLOCKS: usize = 256;
 
 #[derive(Debug)]
 pub(crate) struct EthNamespace {
@@ -53,14 +61,235 @@ impl EthNamespace {
 
     pub async fn call_impl(
         &self,
-        mut request: CallRequest,
+        request: CallRequest,
         block_id: Option<BlockId>,
         state_override: Option<StateOverride>,
     ) -> Result<Bytes, Web3Error> {
+        let (block_args, call_overrides, tx) = self.prepare_call(request, block_id).await?;
+        // It is assumed that the previous checks has already enforced that the `max_fee_per_gas` is at most u64.
+        let call_result: Vec<u8> = self
+            .state
+            .tx_sender
+            .eth_call(block_args, call_overrides, tx, state_override)
+            .await?;
+        Ok(call_result.into())
+    }
+
+    pub async fn create_access_list_impl(
+        &self,
+        request: CallRequest,
+        block_id: Option<BlockId>,
+        state_override: Option<StateOverride>,
+    ) -> Result<AccessListResult, Web3Error> {
+        let (block_args, call_overrides, tx) = self.prepare_call(request, block_id).await?;
+        let result = self
+            .state
+            .tx_sender
+            .eth_call_with_logs(block_args, call_overrides, tx, state_override)
+            .await?;
+
+        let error = match &result.result {
+            ExecutionResult::Success { .. } => None,
+            ExecutionResult::Revert { output } => Some(output.to_user_friendly_string()),
+            ExecutionResult::Halt { reason } => Some(reason.to_string()),
+        };
+        Ok(AccessListResult {
+            access_list: build_access_list(&result.logs.storage_logs),
+            gas_used: result.statistics.gas_used.into(),
+            error,
+        })
+    }
+
+    pub async fn simulate_v1_impl(
+        &self,
+        payload: SimulatePayload,
+        block_id: Option<BlockId>,
+    ) -> Result<Vec<SimulatedBlock>, Web3Error> {
+        let block_count = payload.block_state_calls.len();
+        if block_count == 0 {
+            return Err(Web3Error::InvalidSimulation(
+                "no blocks to simulate".to_owned(),
+            ));
+        }
+        if block_count > MAX_SIMULATED_BLOCKS {
+            return Err(Web3Error::InvalidSimulation(format!(
+                "too many blocks to simulate: {block_count}, max {MAX_SIMULATED_BLOCKS}"
+            )));
+        }
+
+        let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
+        self.current_method().set_block_id(block_id);
+        let mut connection = self.state.acquire_connection().await?;
+        let block_args = self
+            .state
+            .resolve_block_args(&mut connection, block_id)
+            .await?;
+        self.current_method().set_block_diff(
+            self.state
+                .last_sealed_l2_block
+                .diff_with_block_args(&block_args),
+        );
+        drop(connection);
+
+        let default_gas = self
+            .state
+            .tx_sender
+            .get_default_eth_call_gas(block_args)
+            .await
+            .map_err(Web3Error::InternalError)?;
+        let mut state_override = None;
+        // The base fee is shared by all simulated blocks, so we choose it so that all calls can pay for gas.
+        let mut enforced_base_fee = None::<u64>;
+        let mut blocks = Vec::with_capacity(block_count);
+        let mut tx_hashes = Vec::with_capacity(block_count);
+        for (i, block) in payload.block_state_calls.into_iter().enumerate() {
+            if block.state_overrides.is_some() {
+                if i > 0 {
+                    return Err(Web3Error::InvalidSimulation(
+                        "state overrides are only supported for the first simulated block"
+                            .to_owned(),
+                    ));
+                }
+                state_override = block.state_overrides;
+            }
+            // The VM cannot start a new L2 block on top of an empty one.
+            if block.calls.is_empty() && i + 1 < block_count {
+                return Err(Web3Error::InvalidSimulation(format!(
+                    "simulated block #{i} has no calls; only the last simulated block may be empty"
+                )));
+            }
+
+            let mut txs = Vec::with_capacity(block.calls.len());
+            let mut block_tx_hashes = Vec::with_capacity(block.calls.len());
+            for mut request in block.calls {
+                if request.gas.is_none() {
+                    request.gas = Some(default_gas.into());
+                }
+                if let Some(base_fee) = request.get_call_overrides()?.enforced_base_fee {
+                    enforced_base_fee =
+                        Some(enforced_base_fee.map_or(base_fee, |fee| fee.min(base_fee)));
+                }
+                let mut tx = L2Tx::from_request(request.into(), self.state.api_config.max_tx_size)?;
+                if tx.common_data.signature.is_empty() {
+                    tx.common_data.signature =
+                        PackedEthSignature::default().serialize_packed().into();
+                }
+                block_tx_hashes.push(tx.hash());
+                txs.push(tx.into());
+            }
+            blocks.push(SimulationBlock {
+                timestamp: block
+                    .block_overrides
+                    .and_then(|overrides| overrides.time)
+                    .map(|time| time.as_u64()),
+                txs,
+            });
+            tx_hashes.push(block_tx_hashes);
+        }
+        if blocks.iter().all(|block| block.txs.is_empty()) {
+            return Err(Web3Error::InvalidSimulation(
+                "no calls to simulate".to_owned(),
+            ));
+        }
+
+        let outputs = self
+            .state
+            .tx_sender
+            .simulate_blocks(block_args, enforced_base_fee, state_override, blocks)
+            .await?;
+        Ok(outputs
+            .into_iter()
+            .zip(tx_hashes)
+            .map(|(output, tx_hashes)| Self::simulated_block(output, &tx_hashes))
+            .collect())
+    }
+
+    /// Converts the output of a simulated block into the API format.
+    fn simulated_block(output: SimulatedBlockOutput, tx_hashes: &[H256]) -> SimulatedBlock {
+        let mut gas_used = U256::zero();
+        let mut log_index = 0_u32;
+        let calls = output
+            .results
+            .into_iter()
+            .zip(tx_hashes)
+            .enumerate()
+            .map(|(tx_index, (result, &tx_hash))| {
+                let logs = result
+                    .logs
+                    .events
+                    .iter()
+                    .enumerate()
+                    .map(|(tx_log_index, event)| {
+                        let log = Log {
+                            address: event.address,
+                            topics: event.indexed_topics.clone(),
+                            data: Bytes(event.value.clone()),
+                            block_hash: None,
+                            block_number: Some(output.number.0.into()),
+                            l1_batch_number: None,
+                            transaction_hash: Some(tx_hash),
+                            transaction_index: Some((tx_index as u32).into()),
+                            log_index: Some(log_index.into()),
+                            transaction_log_index: Some((tx_log_index as u32).into()),
+                            log_type: None,
+                            removed: Some(false),
+                            block_timestamp: Some(output.timestamp.into()),
+                        };
+                        log_index += 1;
+                        log
+                    })
+                    .collect();
+
+                let call_gas_used = U256::from(result.statistics.gas_used);
+                gas_used += call_gas_used;
+                // Error codes correspond to ones used by `eth_simulateV1` in Geth.
+                let (status, return_data, error) = match result.result {
+                    ExecutionResult::Success { output } => (1, output, None),
+                    ExecutionResult::Revert { output } => {
+                        let error = SimulatedCallError {
+                            code: 3,
+                            message: output.to_user_friendly_string(),
+                        };
+                        (0, output.encoded_data(), Some(error))
+                    }
+                    ExecutionResult::Halt { reason } => {
+                        let error = SimulatedCallError {
+                            code: -32015,
+                            message: reason.to_string(),
+                        };
+                        (0, vec![], Some(error))
+                    }
+                };
+                SimulatedCall {
+                    status: status.into(),
+                    return_data: return_data.into(),
+                    gas_used: call_gas_used,
+                    logs,
+                    error,
+                }
+            })
+            .collect();
+
+        SimulatedBlock {
+            number: output.number.0.into(),
+            timestamp: output.timestamp.into(),
+            gas_used,
+            calls,
+        }
+    }
+
+    /// Resolves the block args and converts the provided request into a transaction for `eth_call`-like methods.
+    async fn prepare_call(
+        &self,
+        mut request: CallRequest,
+        block_id: Option<BlockId>,
+    ) -> Result<(BlockArgs, CallOverrides, L2Tx), Web3Error> {
         let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
         self.current_method().set_block_id(block_id);
 
         let mut connection = self.state.acquire_connection().await?;
+        // Archived blocks are intentionally not resolved here; VM execution isn't supported for them
+        // (see the `archive` module docs), so they are rejected as pruned.
         let block_args = self
             .state
             .resolve_block_args(&mut connection, block_id)
@@ -84,14 +313,7 @@ impl EthNamespace {
         }
         let call_overrides = request.get_call_overrides()?;
         let tx = L2Tx::from_request(request.into(), self.state.api_config.max_tx_size)?;
-
-        // It is assumed that the previous checks has already enforced that the `max_fee_per_gas` is at most u64.
-        let call_result: Vec<u8> = self
-            .state
-            .tx_sender
-            .eth_call(block_args, call_overrides, tx, state_override)
-            .await?;
-        Ok(call_result.into())
+        Ok((block_args, call_overrides, tx))
     }
 
     pub async fn estimate_gas_impl(
@@ -163,18 +385,42 @@ impl EthNamespace {
         let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
         self.current_method().set_block_id(block_id);
 
+        let balance_key = storage_key_for_standard_token_balance(
+            AccountTreeId::new(L2_BASE_TOKEN_ADDRESS),
+            &address,
+        );
         let mut connection = self.state.acquire_connection().await?;
+        if let Some(archived) = self
+            .state
+            .resolve_archived_block(&mut connection, block_id)
+            .await?
+        {
+            drop(connection);
+            self.set_block_diff(archived.number);
+            let [balance] = self
+                .state
+                .read_archived_values(archived.l1_batch, [balance_key])
+                .await?;
+            return Ok(h256_to_u256(balance));
+        }
         let block_number = self.state.resolve_block(&mut connection, block_id).await?;
 
-        let balance = connection
-            .storage_web3_dal()
-            .standard_token_historical_balance(
-                AccountTreeId::new(L2_BASE_TOKEN_ADDRESS),
-                AccountTreeId::new(address),
-                block_number,
-            )
-            .await
-            .map_err(DalError::generalize)?;
+        let pending_value = self
+            .pending_state(block_id, block_number)
+            .and_then(|snapshot| snapshot.read_value(&balance_key));
+        let balance = if let Some(value) = pending_value {
+            h256_to_u256(value)
+        } else {
+            connection
+                .storage_web3_dal()
+                .standard_token_historical_balance(
+                    AccountTreeId::new(L2_BASE_TOKEN_ADDRESS),
+                    AccountTreeId::new(address),
+                    block_number,
+                )
+                .await
+                .map_err(DalError::generalize)?
+        };
         self.set_block_diff(block_number);
 
         Ok(balance)
@@ -185,6 +431,18 @@ impl EthNamespace {
         self.current_method().set_block_diff(diff);
     }
 
+    /// Returns the state keeper pending state if `block_id` refers to the pending block resolved to `block_number`.
+    fn pending_state(
+        &self,
+        block_id: BlockId,
+        block_number: L2BlockNumber,
+    ) -> Option<PendingStateSnapshot> {
+        if block_id != BlockId::Number(BlockNumber::Pending) {
+            return None;
+        }
+        self.state.tx_sender.pending_state_snapshot(block_number)
+    }
+
     pub async fn get_logs_impl(&self, mut filter: Filter) -> Result<Vec<Log>, Web3Error> {
         self.state.resolve_filter_block_hash(&mut filter).await?;
         let (from_block, to_block) = self.state.resolve_filter_block_range(&filter).await?;
@@ -410,8 +668,27 @@ impl EthNamespace {
 
         let storage_key = StorageKey::new(AccountTreeId::new(address), u256_to_h256(idx));
         let mut connection = self.state.acquire_connection().await?;
+        if let Some(archived) = self
+            .state
+            .resolve_archived_block(&mut connection, block_id)
+            .await?
+        {
+            drop(connection);
+            self.set_block_diff(archived.number);
+            let [value] = self
+                .state
+                .read_archived_values(archived.l1_batch, [storage_key])
+                .await?;
+            return Ok(value);
+        }
         let block_number = self.state.resolve_block(&mut connection, block_id).await?;
         self.set_block_diff(block_number);
+        let pending_value = self
+            .pending_state(block_id, block_number)
+            .and_then(|snapshot| snapshot.read_value(&storage_key));
+        if let Some(value) = pending_value {
+            return Ok(value);
+        }
         let value = connection
             .storage_web3_dal()
             .get_historical_value_unchecked(storage_key.hashed_key(), block_number)
@@ -420,6 +697,103 @@ impl EthNamespace {
         Ok(value)
     }
 
+    /// Returns an EIP-1186 proof for the account and the specified storage slots.
+    ///
+    /// The Merkle tree only stores states at L1 batch boundaries, so the proof is provided for the state
+    /// at the end of the L1 batch containing the requested L2 block. If the batch is not sealed yet,
+    /// the last sealed L1 batch is used. Returns `None` if the tree has not processed the batch yet.
+    pub async fn get_proof_impl(
+        &self,
+        address: Address,
+        keys: Vec<H256>,
+        block_id: Option<BlockId>,
+    ) -> Result<Option<EIP1186Proof>, Web3Error> {
+        let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Latest));
+        self.current_method().set_block_id(block_id);
+
+        let mut connection = self.state.acquire_connection().await?;
+        let block_number = self.state.resolve_block(&mut connection, block_id).await?;
+        self.set_block_diff(block_number);
+        let resolved_batch = connection
+            .storage_web3_dal()
+            .resolve_l1_batch_number_of_l2_block(block_number)
+            .await
+            .map_err(DalError::generalize)?;
+        let l1_batch_number = match resolved_batch.block_l1_batch {
+            Some(number) => number,
+            None => match resolved_batch.pending_l1_batch.0.checked_sub(1) {
+                Some(number) => L1BatchNumber(number),
+                None => return Ok(None),
+            },
+        };
+        self.state
+            .start_info
+            .ensure_not_pruned(l1_batch_number, &mut connection)
+            .await?;
+        let Some(storage_hash) = connection
+            .blocks_dal()
+            .get_l1_batch_state_root(l1_batch_number)
+            .await
+            .map_err(DalError::generalize)?
+        else {
+            return Ok(None);
+        };
+        drop(connection);
+
+        let account_keys = [
+            get_code_key(&address),
+            storage_key_for_standard_token_balance(
+                AccountTreeId::new(L2_BASE_TOKEN_ADDRESS),
+                &address,
+            ),
+            get_nonce_key(&address),
+        ];
+        let slot_keys = keys
+            .iter()
+            .map(|key| StorageKey::new(AccountTreeId::new(address), *key));
+        let hashed_keys = account_keys
+            .into_iter()
+            .chain(slot_keys)
+            .map(|key| key.hashed_key_u256())
+            .collect();
+        let Some(mut proofs) = self
+            .state
+            .get_tree_proofs(l1_batch_number, hashed_keys)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let storage_proofs = proofs.split_off(account_keys.len());
+        let [code_proof, balance_proof, nonce_proof] =
+            <[_; 3]>::try_from(proofs).map_err(|proofs| {
+                Web3Error::InternalError(anyhow::anyhow!(
+                    "unexpected number of proofs returned by tree API: {}",
+                    proofs.len() + storage_proofs.len()
+                ))
+            })?;
+        let (nonce, _) = decompose_full_nonce(h256_to_u256(nonce_proof.value));
+        let storage_proof = storage_proofs
+            .into_iter()
+            .zip(keys)
+            .map(|(proof, key)| EIP1186StorageProof {
+                key,
+                value: h256_to_u256(proof.value),
+                proof: proof.merkle_path,
+            })
+            .collect();
+
+        Ok(Some(EIP1186Proof {
+            address,
+            account_proof: code_proof.merkle_path,
+            balance: h256_to_u256(balance_proof.value),
+            code_hash: code_proof.value,
+            nonce,
+            storage_hash,
+            storage_proof,
+        }))
+    }
+
     /// Account nonce.
     pub async fn get_transaction_count_impl(
         &self,
@@ -430,14 +804,35 @@ impl EthNamespace {
         self.current_method().set_block_id(block_id);
 
         let mut connection = self.state.acquire_connection().await?;
+        if let Some(archived) = self
+            .state
+            .resolve_archived_block(&mut connection, block_id)
+            .await?
+        {
+            drop(connection);
+            self.set_block_diff(archived.number);
+            let [full_nonce] = self
+                .state
+                .read_archived_values(archived.l1_batch, [get_nonce_key(&address)])
+                .await?;
+            let (account_nonce, _) = decompose_full_nonce(h256_to_u256(full_nonce));
+            return Ok(account_nonce);
+        }
 
         let block_number = self.state.resolve_block(&mut connection, block_id).await?;
         self.set_block_diff(block_number);
-        let full_nonce = connection
-            .storage_web3_dal()
-            .get_address_historical_nonce(address, block_number)
-            .await
-            .map_err(DalError::generalize)?;
+        let pending_value = self
+            .pending_state(block_id, block_number)
+            .and_then(|snapshot| snapshot.read_value(&get_nonce_key(&address)));
+        let full_nonce = if let Some(value) = pending_value {
+            h256_to_u256(value)
+        } else {
+            connection
+                .storage_web3_dal()
+                .get_address_historical_nonce(address, block_number)
+                .await
+                .map_err(DalError::generalize)?
+        };
 
         // TODO (SMA-1612): currently account nonce is returning always, but later we will
         //  return account nonce for account abstraction and deployment nonce for non account abstraction.
@@ -876,3 +1271,40 @@ impl EthNamespace {
     // - `compile_solidity`.
     // - `compile_serpent`.
 }
+
+/// Checks whether the address belongs to the kernel space (i.e., is a system contract or a precompile).
+fn is_kernel_space_address(address: &Address) -> bool {
+    address.as_bytes()[..18].iter().all(|&byte| byte == 0)
+}
+
+/// Builds an access list from the storage logs produced by a VM execution. Accounts and storage slots
+/// are ordered by their first access; each slot is listed once regardless of the number of accesses.
+///
+/// Similarly to other clients excluding precompiles, system contracts (e.g., the nonce holder or the base token
+/// contract storing balances) are not included. They are accessed by the bootloader for every transaction,
+/// so listing them doesn't provide any information about the transaction.
+fn build_access_list(storage_logs: &[StorageLogWithPreviousValue]) -> AccessList {
+    let mut access_list = AccessList::new();
+    for log in storage_logs {
+        let address = *log.log.key.address();
+        if is_kernel_space_address(&address) {
+            continue;
+        }
+        let slot = *log.log.key.key();
+        let item_idx = access_list
+            .iter()
+            .position(|item| item.address == address)
+            .unwrap_or_else(|| {
+                access_list.push(AccessListItem {
+                    address,
+                    storage_keys: vec![],
+                });
+                access_list.len() - 1
+            });
+        let storage_keys = &mut access_list[item_idx].storage_keys;
+        if !storage_keys.contains(&slot) {
+            storage_keys.push(slot);
+        }
+    }
+    access_list
+}
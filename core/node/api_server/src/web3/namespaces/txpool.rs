@@ -0,0 +1,190 @@
+use std::collections::BTreeMap;
+
+use zksync_dal::{Connection, Core, CoreDal, DalError};
+use zksync_types::{
+    api::{Transaction, TxpoolContent, TxpoolContentFrom, TxpoolStatus},
+    Address, Nonce, U64,
+};
+use zksync_web3_decl::error::Web3Error;
+
+use crate::web3::{backend_jsonrpsee::MethodTracer, RpcState};
+
+#[derive(Debug)]
+pub(crate) struct TxpoolNamespace {
+    state: RpcState,
+}
+
+impl TxpoolNamespace {
+    pub fn new(state: RpcState) -> Self {
+        Self { state }
+    }
+
+    pub(crate) fn current_method(&self) -> &MethodTracer {
+        &self.state.current_method
+    }
+
+    /// Loads pending transactions and splits them into pending and queued ones based on the current account nonces.
+    async fn load_content(
+        &self,
+        initiator_address: Option<Address>,
+    ) -> Result<TxpoolContent<Transaction>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        let txs = if let Some(txs) = self
+            .state
+            .tx_sink()
+            .lookup_pending_txs(&mut storage, initiator_address)
+            .await?
+        {
+            txs
+        } else {
+            // No pending transactions in the sink: load them from the mempool
+            storage
+                .transactions_web3_dal()
+                .get_pending_l2_transactions(
+                    initiator_address,
+                    self.state.api_config.req_entities_limit,
+                    self.state.api_config.l2_chain_id,
+                )
+                .await
+                .map_err(DalError::generalize)?
+        };
+
+        let mut txs_by_account = BTreeMap::<_, BTreeMap<_, _>>::new();
+        for tx in txs {
+            let Some(initiator_address) = tx.from else {
+                continue;
+            };
+            txs_by_account
+                .entry(initiator_address)
+                .or_default()
+                .insert(tx.nonce.as_u64(), tx);
+        }
+        Self::split_by_nonces(&mut storage, txs_by_account).await
+    }
+
+    /// Splits transactions grouped by the initiator address into pending and queued ones based on
+    /// the current account nonces.
+    async fn split_by_nonces<T>(
+        storage: &mut Connection<'_, Core>,
+        txs_by_account: BTreeMap<Address, BTreeMap<u64, T>>,
+    ) -> Result<TxpoolContent<T>, Web3Error> {
+        let addresses: Vec<_> = txs_by_account.keys().copied().collect();
+        let nonces_for_accounts = storage
+            .storage_web3_dal()
+            .get_nonces_for_addresses(&addresses)
+            .await
+            .map_err(DalError::generalize)?;
+
+        let mut content = TxpoolContent::default();
+        for (address, txs) in txs_by_account {
+            let stored_nonce = nonces_for_accounts.get(&address).copied();
+            let stored_nonce = u64::from(stored_nonce.unwrap_or(Nonce(0)).0);
+            let mut next_nonce = stored_nonce;
+            let mut pending = BTreeMap::new();
+            let mut queued = BTreeMap::new();
+            // Transactions with nonces below the stored one are already executed, but the node hasn't caught up yet.
+            for (nonce, tx) in txs.into_iter().filter(|&(nonce, _)| nonce >= stored_nonce) {
+                if nonce == next_nonce && queued.is_empty() {
+                    pending.insert(nonce, tx);
+                    next_nonce += 1;
+                } else {
+                    queued.insert(nonce, tx);
+                }
+            }
+
+            if !pending.is_empty() {
+                content.pending.insert(address, pending);
+            }
+            if !queued.is_empty() {
+                content.queued.insert(address, queued);
+            }
+        }
+        Ok(content)
+    }
+
+    pub async fn status_impl(&self) -> Result<TxpoolStatus, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        let nonces = if let Some(txs) = self
+            .state
+            .tx_sink()
+            .lookup_pending_txs(&mut storage, None)
+            .await?
+        {
+            txs.into_iter()
+                .filter_map(|tx| Some((tx.from?, tx.nonce.as_u64())))
+                .collect()
+        } else {
+            // Unlike with the transaction content, counts must not be affected by the entities limit.
+            storage
+                .transactions_web3_dal()
+                .get_pending_l2_tx_nonces()
+                .await
+                .map_err(DalError::generalize)?
+                .into_iter()
+                .map(|(address, nonce)| (address, u64::from(nonce.0)))
+                .collect::<Vec<_>>()
+        };
+
+        let mut nonces_by_account = BTreeMap::<_, BTreeMap<_, _>>::new();
+        for (address, nonce) in nonces {
+            nonces_by_account
+                .entry(address)
+                .or_default()
+                .insert(nonce, ());
+        }
+        let content = Self::split_by_nonces(&mut storage, nonces_by_account).await?;
+        let count = |txs: &BTreeMap<Address, BTreeMap<u64, ()>>| {
+            U64::from(txs.values().map(BTreeMap::len).sum::<usize>())
+        };
+        Ok(TxpoolStatus {
+            pending: count(&content.pending),
+            queued: count(&content.queued),
+        })
+    }
+
+    pub async fn content_impl(&self) -> Result<TxpoolContent<Transaction>, Web3Error> {
+        self.load_content(None).await
+    }
+
+    pub async fn content_from_impl(
+        &self,
+        address: Address,
+    ) -> Result<TxpoolContentFrom<Transaction>, Web3Error> {
+        let mut content = self.load_content(Some(address)).await?;
+        Ok(TxpoolContentFrom {
+            pending: content.pending.remove(&address).unwrap_or_default(),
+            queued: content.queued.remove(&address).unwrap_or_default(),
+        })
+    }
+
+    pub async fn inspect_impl(&self) -> Result<TxpoolContent<String>, Web3Error> {
+        let content = self.load_content(None).await?;
+        let summarize = |txs: BTreeMap<Address, BTreeMap<u64, Transaction>>| -> BTreeMap<_, _> {
+            txs.into_iter()
+                .map(|(address, txs)| {
+                    let summaries = txs
+                        .into_iter()
+                        .map(|(nonce, tx)| (nonce, Self::summarize_tx(&tx)))
+                        .collect();
+                    (address, summaries)
+                })
+                .collect()
+        };
+        Ok(TxpoolContent {
+            pending: summarize(content.pending),
+            queued: summarize(content.queued),
+        })
+    }
+
+    /// Formats a transaction in the same way as Geth does in `txpool_inspect`.
+    fn summarize_tx(tx: &Transaction) -> String {
+        let recipient = tx
+            .to
+            .map_or_else(|| "contract creation".to_owned(), |to| format!("{to:?}"));
+        let gas_price = tx.gas_price.unwrap_or_default();
+        format!(
+            "{recipient}: {} wei + {} gas × {gas_price} wei",
+            tx.value, tx.gas
+        )
+    }
+}
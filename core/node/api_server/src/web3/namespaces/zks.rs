@@ -2,7 +2,6 @@ use std::{collections::HashMap, convert::TryInto};
 
 use anyhow::Context as _;
 use zksync_dal::{Connection, Core, CoreDal, DalError};
-use zksync_metadata_calculator::api_server::TreeApiError;
 use zksync_mini_merkle_tree::MiniMerkleTree;
 use zksync_multivm::interface::VmExecutionResultAndLogs;
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
@@ -502,39 +501,28 @@ impl ZksNamespace {
         l1_batch_number: L1BatchNumber,
     ) -> Result<Option<Proof>, Web3Error> {
         let mut storage = self.state.acquire_connection().await?;
-        self.state
-            .start_info
-            .ensure_not_pruned(l1_batch_number, &mut storage)
+        let is_archived = self
+            .state
+            .is_archived_l1_batch(&mut storage, l1_batch_number)
             .await?;
+        drop(storage);
         let hashed_keys = keys
             .iter()
             .map(|key| StorageKey::new(AccountTreeId::new(address), *key).hashed_key_u256())
             .collect();
-        let tree_api = self
-            .state
-            .tree_api
-            .as_deref()
-            .ok_or(Web3Error::MethodNotImplemented)?;
-        let proofs_result = tree_api.get_proofs(l1_batch_number, hashed_keys).await;
-        let proofs = match proofs_result {
-            Ok(proofs) => proofs,
-            Err(TreeApiError::NotReady(_)) => return Err(Web3Error::TreeApiUnavailable),
-            Err(TreeApiError::NoVersion(err)) => {
-                return if err.missing_version > err.version_count {
-                    Ok(None)
-                } else {
-                    Err(Web3Error::InternalError(anyhow::anyhow!(
-                        "L1 batch #{l1_batch_number} is pruned in Merkle tree, but not in Postgres"
-                    )))
-                };
-            }
-            Err(TreeApiError::Internal(err)) => return Err(Web3Error::InternalError(err)),
-            Err(_) => {
-                // This branch is not expected to be executed, but has to be provided since the error is non-exhaustive.
-                return Err(Web3Error::InternalError(anyhow::anyhow!(
-                    "Unspecified tree API error"
-                )));
-            }
+        let proofs = if is_archived {
+            self.state
+                .get_archived_tree_proofs(l1_batch_number, hashed_keys)
+                .await?
+        } else {
+            let Some(proofs) = self
+                .state
+                .get_tree_proofs(l1_batch_number, hashed_keys)
+                .await?
+            else {
+                return Ok(None);
+            };
+            proofs
         };
 
         let storage_proof = proofs
@@ -5,10 +5,13 @@ use futures::FutureExt;
 use tokio::{
     sync::{broadcast, mpsc, watch},
     task::JoinHandle,
-    time::{interval, Duration},
+    time::{interval, Duration, Interval},
 };
 use tracing::Instrument as _;
-use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_dal::{
+    notifications::{StorageNotification, StorageNotifications, StorageNotificationsReceiver},
+    ConnectionPool, Core, CoreDal,
+};
 use zksync_types::{L2BlockNumber, H128, H256};
 use zksync_web3_decl::{
     jsonrpsee::{
@@ -53,10 +56,79 @@ struct PubSubNotifier {
     sender: broadcast::Sender<Vec<PubSubResult>>,
     connection_pool: ConnectionPool<Core>,
     polling_interval: Duration,
+    storage_notifications: Option<StorageNotifications>,
     events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
 }
 
 impl PubSubNotifier {
+    /// Subscribes to storage notifications if they are configured. If subscribing fails, the notifier
+    /// will fall back to polling Postgres.
+    async fn subscribe_to_storage_notifications(&self) -> Option<StorageNotificationsReceiver> {
+        let notifications = self.storage_notifications.as_ref()?;
+        match notifications.subscribe().await {
+            Ok(receiver) => Some(receiver),
+            Err(err) => {
+                tracing::warn!(
+                    "Failed subscribing to storage notifications, falling back to polling: {err:#}"
+                );
+                None
+            }
+        }
+    }
+
+    /// Waits until Postgres may contain new data for the subscription. If storage notifications are available,
+    /// waits for a notification satisfying `is_relevant`; otherwise, waits for the next polling `timer` tick.
+    async fn wait_for_updates(
+        &self,
+        receiver: &mut Option<StorageNotificationsReceiver>,
+        is_relevant: fn(&StorageNotification) -> bool,
+        timer: &mut Interval,
+        stop_receiver: &mut watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        let Some(notifications) = receiver else {
+            timer.tick().await;
+            return Ok(());
+        };
+
+        let notification = loop {
+            let notification = tokio::select! {
+                notification = notifications.recv() => notification,
+                _ = stop_receiver.changed() => return Ok(()),
+            };
+            match notification {
+                Ok(Some(notification)) if !is_relevant(&notification) => continue,
+                Ok(notification) => break notification,
+                Err(err) => {
+                    tracing::warn!(
+                        "Storage notifications are no longer available, falling back to polling: {err:#}"
+                    );
+                    *receiver = None;
+                    return Ok(());
+                }
+            }
+        };
+
+        // Notifications are sent after changes are committed to the main database, while notifiers may read
+        // from a lagging replica. Hence, we poll the replica until it catches up with the notified L2 block.
+        // There's no such check for new transactions; a transaction missed because of the replica lag
+        // will be reported after the next notification.
+        if let Some(StorageNotification::L2BlockSealed(notified_block)) = notification {
+            loop {
+                let mut storage = self.connection_pool.connection_tagged("api").await?;
+                let sealed_block = storage.blocks_dal().get_sealed_l2_block_number().await?;
+                drop(storage);
+                if sealed_block >= Some(notified_block) {
+                    break;
+                }
+                tokio::select! {
+                    _ = timer.tick() => {}
+                    _ = stop_receiver.changed() => break,
+                }
+            }
+        }
+        Ok(())
+    }
+
     // Notifier tasks are spawned independently of the main server task, so we need to wait for
     // Postgres to be non-empty separately.
     async fn get_starting_l2_block_number(
@@ -90,6 +162,7 @@ impl PubSubNotifier {
 
 impl PubSubNotifier {
     async fn notify_blocks(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let mut notifications = self.subscribe_to_storage_notifications().await;
         let Some(mut last_block_number) = self
             .get_starting_l2_block_number(&mut stop_receiver)
             .await?
@@ -104,7 +177,13 @@ impl PubSubNotifier {
                 tracing::info!("Stop signal received, pubsub_block_notifier is shutting down");
                 break;
             }
-            timer.tick().await;
+            self.wait_for_updates(
+                &mut notifications,
+                |notification| matches!(notification, StorageNotification::L2BlockSealed(_)),
+                &mut timer,
+                &mut stop_receiver,
+            )
+            .await?;
 
             let db_latency = PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::Blocks].start();
             let new_blocks = self.new_blocks(last_block_number).await?;
@@ -145,7 +224,8 @@ impl PubSubNotifier {
             .map_err(Into::into)
     }
 
-    async fn notify_txs(self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+    async fn notify_txs(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let mut notifications = self.subscribe_to_storage_notifications().await;
         let mut last_time = chrono::Utc::now().naive_utc();
         let mut timer = interval(self.polling_interval);
         loop {
@@ -153,7 +233,13 @@ impl PubSubNotifier {
                 tracing::info!("Stop signal received, pubsub_tx_notifier is shutting down");
                 break;
             }
-            timer.tick().await;
+            self.wait_for_updates(
+                &mut notifications,
+                |notification| matches!(notification, StorageNotification::L2TxInserted(_)),
+                &mut timer,
+                &mut stop_receiver,
+            )
+            .await?;
 
             let db_latency = PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::Txs].start();
             let new_txs = self.new_txs(last_time).await?;
@@ -186,6 +272,7 @@ impl PubSubNotifier {
     }
 
     async fn notify_logs(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let mut notifications = self.subscribe_to_storage_notifications().await;
         let Some(mut last_block_number) = self
             .get_starting_l2_block_number(&mut stop_receiver)
             .await?
@@ -200,7 +287,13 @@ impl PubSubNotifier {
                 tracing::info!("Stop signal received, pubsub_logs_notifier is shutting down");
                 break;
             }
-            timer.tick().await;
+            self.wait_for_updates(
+                &mut notifications,
+                |notification| matches!(notification, StorageNotification::L2BlockSealed(_)),
+                &mut timer,
+                &mut stop_receiver,
+            )
+            .await?;
 
             let db_latency = PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::Logs].start();
             let new_logs = self.new_logs(last_block_number).await?;
@@ -423,10 +516,15 @@ impl EthSubscribe {
     }
 
     /// Spawns notifier tasks. This should be called once per instance.
+    ///
+    /// If `storage_notifications` are provided, notifiers query Postgres only after receiving a relevant notification
+    /// (e.g., about a sealed L2 block). Otherwise, or if notifications become unavailable, notifiers poll Postgres
+    /// with the specified `polling_interval`.
     pub fn spawn_notifiers(
         &self,
         connection_pool: ConnectionPool<Core>,
         polling_interval: Duration,
+        storage_notifications: Option<StorageNotifications>,
         stop_receiver: watch::Receiver<bool>,
     ) -> Vec<JoinHandle<anyhow::Result<()>>> {
         let mut notifier_tasks = Vec::with_capacity(3);
@@ -435,6 +533,7 @@ impl EthSubscribe {
             sender: self.blocks.clone(),
             connection_pool: connection_pool.clone(),
             polling_interval,
+            storage_notifications: storage_notifications.clone(),
             events_sender: self.events_sender.clone(),
         };
         let notifier_task = tokio::spawn(notifier.notify_blocks(stop_receiver.clone()));
@@ -444,6 +543,7 @@ impl EthSubscribe {
             sender: self.transactions.clone(),
             connection_pool: connection_pool.clone(),
             polling_interval,
+            storage_notifications: storage_notifications.clone(),
             events_sender: self.events_sender.clone(),
         };
         let notifier_task = tokio::spawn(notifier.notify_txs(stop_receiver.clone()));
@@ -453,6 +553,7 @@ impl EthSubscribe {
             sender: self.logs.clone(),
             connection_pool,
             polling_interval,
+            storage_notifications,
             events_sender: self.events_sender.clone(),
         };
         let notifier_task = tokio::spawn(notifier.notify_logs(stop_receiver));
@@ -17,7 +17,7 @@ use zksync_config::{
     GenesisConfig,
 };
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalError};
-use zksync_metadata_calculator::api_server::TreeApiClient;
+use zksync_metadata_calculator::api_server::{TreeApiClient, TreeApiError, TreeEntryWithProof};
 use zksync_node_sync::SyncState;
 use zksync_types::{
     api, commitment::L1BatchCommitmentMode, l2::L2Tx, transaction_request::CallRequest, Address,
@@ -89,6 +89,22 @@ impl BlockStartInfo {
     }
 }
 
+/// Converts a generic tree API error. Errors requiring context (e.g., [`TreeApiError::NoVersion`]) should be handled
+/// by the caller.
+pub(super) fn tree_api_error(err: TreeApiError) -> Web3Error {
+    match err {
+        TreeApiError::NotReady(_) => Web3Error::TreeApiUnavailable,
+        TreeApiError::Internal(err) => Web3Error::InternalError(err),
+        TreeApiError::NoVersion(err) => {
+            Web3Error::InternalError(anyhow::anyhow!("unexpected tree API error: {err}"))
+        }
+        _ => {
+            // This branch is not expected to be executed, but has to be provided since the error is non-exhaustive.
+            Web3Error::InternalError(anyhow::anyhow!("Unspecified tree API error"))
+        }
+    }
+}
+
 /// Configuration values for the API.
 /// This structure is detached from `ZkSyncConfig`, since different node types (main, external, etc)
 /// may require different configuration layouts.
@@ -112,6 +128,9 @@ pub struct InternalApiConfig {
     pub filters_disabled: bool,
     pub dummy_verifier: bool,
     pub l1_batch_commit_data_generator_mode: L1BatchCommitmentMode,
+    /// Whether historical storage reads for pruned blocks are served from the Merkle tree. Requires the tree
+    /// to retain versions for pruned L1 batches.
+    pub archive_mode: bool,
 }
 
 impl InternalApiConfig {
@@ -163,6 +182,7 @@ impl InternalApiConfig {
             filters_disabled: web3_config.filters_disabled,
             dummy_verifier: genesis_config.dummy_verifier,
             l1_batch_commit_data_generator_mode: genesis_config.l1_batch_commit_data_generator_mode,
+            archive_mode: false,
         }
     }
 }
@@ -333,6 +353,32 @@ impl RpcState {
         }
     }
 
+    /// Requests Merkle proofs for the specified hashed storage keys from the tree API. Returns `Ok(None)`
+    /// if the tree has not processed the specified L1 batch yet.
+    pub(crate) async fn get_tree_proofs(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<Option<Vec<TreeEntryWithProof>>, Web3Error> {
+        let tree_api = self
+            .tree_api
+            .as_deref()
+            .ok_or(Web3Error::MethodNotImplemented)?;
+        match tree_api.get_proofs(l1_batch_number, hashed_keys).await {
+            Ok(proofs) => Ok(Some(proofs)),
+            Err(TreeApiError::NoVersion(err)) => {
+                if err.missing_version > err.version_count {
+                    Ok(None)
+                } else {
+                    Err(Web3Error::InternalError(anyhow::anyhow!(
+                        "L1 batch #{l1_batch_number} is pruned in Merkle tree, but not in Postgres"
+                    )))
+                }
+            }
+            Err(err) => Err(tree_api_error(err)),
+        }
+    }
+
     pub(crate) async fn resolve_block_args(
         &self,
         connection: &mut Connection<'_, Core>,
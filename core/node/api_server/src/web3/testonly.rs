@@ -8,6 +8,7 @@ use zksync_dal::ConnectionPool;
 use zksync_health_check::CheckHealth;
 use zksync_node_fee_model::MockBatchFeeParamsProvider;
 use zksync_state::PostgresStorageCaches;
+use zksync_state_keeper::PendingStateReader;
 use zksync_types::L2ChainId;
 
 use super::{metrics::ApiTransportLabel, *};
@@ -102,6 +103,27 @@ pub async fn spawn_http_server(
     tx_executor: MockTransactionExecutor,
     method_tracer: Arc<MethodTracer>,
     stop_receiver: watch::Receiver<bool>,
+) -> ApiServerHandles {
+    spawn_custom_http_server(
+        api_config,
+        pool,
+        tx_executor,
+        method_tracer,
+        None,
+        None,
+        stop_receiver,
+    )
+    .await
+}
+
+pub(crate) async fn spawn_custom_http_server(
+    api_config: InternalApiConfig,
+    pool: ConnectionPool<Core>,
+    tx_executor: MockTransactionExecutor,
+    method_tracer: Arc<MethodTracer>,
+    pending_state: Option<PendingStateReader>,
+    tree_api: Option<Arc<dyn TreeApiClient>>,
+    stop_receiver: watch::Receiver<bool>,
 ) -> ApiServerHandles {
     spawn_server(
         ApiTransportLabel::Http,
@@ -110,6 +132,8 @@ pub async fn spawn_http_server(
         None,
         tx_executor,
         method_tracer,
+        pending_state,
+        tree_api,
         stop_receiver,
     )
     .await
@@ -129,6 +153,8 @@ pub async fn spawn_ws_server(
         websocket_requests_per_minute_limit,
         MockTransactionExecutor::default(),
         Arc::default(),
+        None,
+        None,
         stop_receiver,
     )
     .await
@@ -141,14 +167,17 @@ async fn spawn_server(
     websocket_requests_per_minute_limit: Option<NonZeroU32>,
     tx_executor: MockTransactionExecutor,
     method_tracer: Arc<MethodTracer>,
+    pending_state: Option<PendingStateReader>,
+    tree_api: Option<Arc<dyn TreeApiClient>>,
     stop_receiver: watch::Receiver<bool>,
 ) -> (ApiServerHandles, mpsc::UnboundedReceiver<PubSubEvent>) {
-    let (tx_sender, vm_barrier) =
+    let (mut tx_sender, vm_barrier) =
         create_test_tx_sender(pool.clone(), api_config.l2_chain_id, tx_executor.into()).await;
+    Arc::get_mut(&mut tx_sender.0).unwrap().pending_state = pending_state;
     let (pub_sub_events_sender, pub_sub_events_receiver) = mpsc::unbounded_channel();
 
     let mut namespaces = Namespace::DEFAULT.to_vec();
-    namespaces.extend([Namespace::Debug, Namespace::Snapshots]);
+    namespaces.extend([Namespace::Debug, Namespace::Snapshots, Namespace::Txpool]);
 
     let server_builder = match transport {
         ApiTransportLabel::Http => ApiBuilder::jsonrpsee_backend(api_config, pool).http(0),
@@ -163,6 +192,11 @@ async fn spawn_server(
             builder
         }
     };
+    let server_builder = if let Some(tree_api) = tree_api {
+        server_builder.with_tree_api(tree_api)
+    } else {
+        server_builder
+    };
     let server_handles = server_builder
         .with_polling_interval(POLL_INTERVAL)
         .with_tx_sender(tx_sender)
@@ -0,0 +1,244 @@
+//! Tests for the archive mode, in which historical storage reads for pruned blocks are served from the Merkle tree.
+
+use test_casing::test_casing;
+use zksync_config::configs::database::MerkleTreeMode;
+use zksync_merkle_tree::NoVersionError;
+use zksync_metadata_calculator::{
+    api_server::{TreeApiError, TreeEntryWithProof},
+    MerkleTreeInfo,
+};
+use zksync_system_constants::{
+    SYSTEM_CONTEXT_ADDRESS, SYSTEM_CONTEXT_CURRENT_L2_BLOCK_INFO_POSITION,
+};
+use zksync_types::{
+    block::pack_block_info, transaction_request::CallRequest, utils::nonces_to_full_nonce,
+};
+
+use super::*;
+
+/// Mock tree API retaining versions for L1 batches #20..=#24; #20..=#23 are pruned in Postgres.
+#[derive(Debug)]
+struct MockArchiveTree {
+    values: HashMap<(L1BatchNumber, U256), H256>,
+}
+
+impl MockArchiveTree {
+    const MIN_L1_BATCH: L1BatchNumber = L1BatchNumber(20);
+    const NEXT_L1_BATCH: L1BatchNumber = L1BatchNumber(25);
+    /// Last L2 blocks in the L1 batches #20..=#23.
+    const LAST_L2_BLOCKS: [u32; 4] = [17, 19, 21, 23];
+
+    fn test_address() -> Address {
+        Address::repeat_byte(0x23)
+    }
+
+    fn balance_key() -> StorageKey {
+        storage_key_for_standard_token_balance(
+            AccountTreeId::new(L2_BASE_TOKEN_ADDRESS),
+            &Self::test_address(),
+        )
+    }
+
+    fn slot_key() -> StorageKey {
+        StorageKey::new(AccountTreeId::new(Self::test_address()), H256::zero())
+    }
+
+    fn new() -> Self {
+        let block_info_key = StorageKey::new(
+            AccountTreeId::new(SYSTEM_CONTEXT_ADDRESS),
+            SYSTEM_CONTEXT_CURRENT_L2_BLOCK_INFO_POSITION,
+        );
+        let mut values = HashMap::new();
+        for (i, last_l2_block) in Self::LAST_L2_BLOCKS.into_iter().enumerate() {
+            let l1_batch = Self::MIN_L1_BATCH + i as u32;
+            let entries = [
+                (
+                    block_info_key,
+                    u256_to_h256(pack_block_info(last_l2_block.into(), 1_000)),
+                ),
+                (Self::balance_key(), Self::expected_balance(l1_batch)),
+                (
+                    get_nonce_key(&Self::test_address()),
+                    u256_to_h256(nonces_to_full_nonce(l1_batch.0.into(), 1.into())),
+                ),
+                (Self::slot_key(), H256::repeat_byte(l1_batch.0 as u8)),
+            ];
+            for (key, value) in entries {
+                values.insert((l1_batch, key.hashed_key_u256()), value);
+            }
+        }
+        Self { values }
+    }
+
+    fn expected_balance(l1_batch: L1BatchNumber) -> H256 {
+        H256::from_low_u64_be(u64::from(l1_batch.0) * 1_000)
+    }
+}
+
+#[async_trait]
+impl TreeApiClient for MockArchiveTree {
+    async fn get_info(&self) -> Result<MerkleTreeInfo, TreeApiError> {
+        Ok(MerkleTreeInfo {
+            mode: MerkleTreeMode::Full,
+            root_hash: H256::zero(),
+            next_l1_batch_number: Self::NEXT_L1_BATCH,
+            min_l1_batch_number: Some(Self::MIN_L1_BATCH),
+            leaf_count: 0,
+        })
+    }
+
+    async fn get_proofs(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<Vec<TreeEntryWithProof>, TreeApiError> {
+        if l1_batch_number < Self::MIN_L1_BATCH {
+            return Err(TreeApiError::NoVersion(NoVersionError {
+                missing_version: l1_batch_number.0.into(),
+                version_count: Self::NEXT_L1_BATCH.0.into(),
+            }));
+        }
+        Ok(hashed_keys
+            .into_iter()
+            .map(|key| TreeEntryWithProof {
+                value: self
+                    .values
+                    .get(&(l1_batch_number, key))
+                    .copied()
+                    .unwrap_or_default(),
+                index: 0,
+                merkle_path: vec![],
+            })
+            .collect())
+    }
+}
+
+#[derive(Debug)]
+struct ArchiveModeTest {
+    archive_mode: bool,
+}
+
+#[async_trait]
+impl HttpTest for ArchiveModeTest {
+    fn storage_initialization(&self) -> StorageInitialization {
+        StorageInitialization::empty_recovery()
+    }
+
+    fn tree_api(&self) -> Option<Arc<dyn TreeApiClient>> {
+        Some(Arc::new(MockArchiveTree::new()))
+    }
+
+    fn archive_mode(&self) -> bool {
+        self.archive_mode
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let address = MockArchiveTree::test_address();
+        if !self.archive_mode {
+            let number = api::BlockIdVariant::BlockNumber(20.into());
+            let error = client.get_balance(address, Some(number)).await.unwrap_err();
+            assert_pruned_block_error(&error, StorageInitialization::SNAPSHOT_RECOVERY_BLOCK + 1);
+            let error = client
+                .get_proof(address, vec![H256::zero()], L1BatchNumber(21))
+                .await
+                .unwrap_err();
+            assert_pruned_l1_batch_error(
+                &error,
+                StorageInitialization::SNAPSHOT_RECOVERY_BATCH + 1,
+            );
+            return Ok(());
+        }
+
+        // Only the last L2 blocks in the archived L1 batches can be served.
+        let expected_l1_batches = [(19, 21), (21, 22), (23, 23)];
+        for (l2_block, l1_batch) in expected_l1_batches {
+            let l1_batch = L1BatchNumber(l1_batch);
+            let number = api::BlockIdVariant::BlockNumber(l2_block.into());
+            let balance = client.get_balance(address, Some(number)).await?;
+            assert_eq!(
+                u256_to_h256(balance),
+                MockArchiveTree::expected_balance(l1_batch)
+            );
+            let nonce = client.get_transaction_count(address, Some(number)).await?;
+            assert_eq!(nonce, l1_batch.0.into());
+            let value = client
+                .get_storage_at(address, U256::zero(), Some(number))
+                .await?;
+            assert_eq!(value, H256::repeat_byte(l1_batch.0 as u8));
+
+            let proof = client
+                .get_proof(address, vec![H256::zero()], l1_batch)
+                .await?
+                .expect("no proof for archived L1 batch");
+            assert_eq!(proof.storage_proof.len(), 1);
+            assert_eq!(proof.storage_proof[0].value, value);
+        }
+
+        // Intermediate L2 blocks in the archived L1 batches cannot be served, since their state isn't
+        // stored in the tree.
+        for (l2_block, last_l2_block) in [(18, 19), (20, 21), (22, 23)] {
+            let number = api::BlockIdVariant::BlockNumber(l2_block.into());
+            let error = client.get_balance(address, Some(number)).await.unwrap_err();
+            assert_non_final_archived_block_error(&error, L2BlockNumber(last_l2_block));
+            let error = client
+                .get_storage_at(address, U256::zero(), Some(number))
+                .await
+                .unwrap_err();
+            assert_non_final_archived_block_error(&error, L2BlockNumber(last_l2_block));
+        }
+
+        // VM execution is not supported for archived blocks.
+        let call_request = CallRequest {
+            to: Some(address),
+            ..CallRequest::default()
+        };
+        let number = api::BlockIdVariant::BlockNumber(23.into());
+        let error = client
+            .call(call_request, Some(number), None)
+            .await
+            .unwrap_err();
+        assert_pruned_block_error(&error, StorageInitialization::SNAPSHOT_RECOVERY_BLOCK + 1);
+
+        // Blocks in the oldest retained tree version (and earlier ones) cannot be served.
+        for l2_block in [0, 10, 17] {
+            let number = api::BlockIdVariant::BlockNumber(l2_block.into());
+            let error = client.get_balance(address, Some(number)).await.unwrap_err();
+            assert_pruned_block_error(&error, L2BlockNumber(18));
+        }
+        let error = client
+            .get_proof(address, vec![H256::zero()], L1BatchNumber(19))
+            .await
+            .unwrap_err();
+        assert_pruned_l1_batch_error(&error, MockArchiveTree::MIN_L1_BATCH);
+
+        // Non-pruned blocks must be served from Postgres as usual.
+        let number = api::BlockIdVariant::BlockNumber(api::BlockNumber::Latest);
+        let balance = client.get_balance(address, Some(number)).await?;
+        assert_eq!(balance, 0.into());
+        Ok(())
+    }
+}
+
+fn assert_non_final_archived_block_error(error: &ClientError, last_l2_block: L2BlockNumber) {
+    if let ClientError::Call(error) = error {
+        assert_eq!(error.code(), ErrorCode::InvalidParams.code());
+        assert!(
+            error
+                .message()
+                .contains(&format!("e.g. block {last_l2_block}")),
+            "{error:?}"
+        );
+    } else {
+        panic!("Unexpected error: {error:?}");
+    }
+}
+
+#[test_casing(2, [false, true])]
+#[tokio::test]
+async fn archive_mode(archive_mode: bool) {
+    test_http_server(ArchiveModeTest { archive_mode }).await;
+}
@@ -1,5 +1,6 @@
 //! Tests for the `debug` Web3 namespace.
 
+use zksync_multivm::interface::ExecutionResult;
 use zksync_types::{tx::TransactionExecutionResult, vm_trace::Call, BOOTLOADER_ADDRESS};
 use zksync_web3_decl::{
     client::{DynClient, L2},
@@ -61,7 +62,12 @@ impl HttpTest for TraceBlockTest {
 
             assert_eq!(block_traces.len(), tx_results.len()); // equals to the number of transactions in the block
             for (trace, tx_result) in block_traces.iter().zip(&tx_results) {
-                let api::ResultDebugCall { result } = trace;
+                let api::ResultDebugTrace {
+                    result: api::DebugTrace::Call(result),
+                } = trace
+                else {
+                    panic!("Unexpected trace: {trace:?}");
+                };
                 assert_eq!(result.from, Address::zero());
                 assert_eq!(result.to, BOOTLOADER_ADDRESS);
                 assert_eq!(result.gas, tx_result.transaction.gas_limit());
@@ -204,6 +210,9 @@ impl HttpTest for TraceTransactionTest {
             .trace_transaction(tx_results[0].hash, None)
             .await?
             .context("no transaction traces")?;
+        let api::DebugTrace::Call(result) = result else {
+            panic!("Unexpected trace: {result:?}");
+        };
         assert_eq!(result.from, Address::zero());
         assert_eq!(result.to, BOOTLOADER_ADDRESS);
         assert_eq!(result.gas, tx_results[0].transaction.gas_limit());
@@ -218,6 +227,69 @@ async fn tracing_transaction() {
     test_http_server(TraceTransactionTest).await;
 }
 
+#[derive(Debug)]
+struct TraceTransactionWithPrestateTracerTest;
+
+#[async_trait]
+impl HttpTest for TraceTransactionWithPrestateTracerTest {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        let mut tx_executor = MockTransactionExecutor::default();
+        tx_executor.set_tx_responses(|_, block_args| {
+            // Transactions must be replayed on top of the previous block.
+            assert_eq!(block_args.resolved_block_number(), L2BlockNumber(0));
+            ExecutionResult::Success { output: vec![] }
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let tx_results = [0, 1].map(execute_l2_transaction_with_traces);
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &tx_results).await?;
+        drop(storage);
+
+        let tracer_config = api::TracerConfig {
+            tracer: api::SupportedTracers::PrestateTracer,
+            tracer_config: api::TracerOptions::default(),
+        };
+        let trace = client
+            .trace_transaction(tx_results[1].hash, Some(tracer_config))
+            .await?
+            .context("no transaction traces")?;
+        // The mock executor doesn't run tracers, so the returned state is empty.
+        assert_matches!(
+            trace,
+            api::DebugTrace::Prestate(api::PrestateTrace::Prestate(state)) if state.is_empty()
+        );
+
+        let block_traces = client
+            .trace_block_by_number(1.into(), Some(tracer_config))
+            .await?;
+        assert_eq!(block_traces.len(), tx_results.len());
+        for trace in block_traces {
+            assert_matches!(
+                trace.result,
+                api::DebugTrace::Prestate(api::PrestateTrace::Prestate(state)) if state.is_empty()
+            );
+        }
+
+        let missing_trace = client
+            .trace_transaction(H256::repeat_byte(0xff), Some(tracer_config))
+            .await?;
+        assert!(missing_trace.is_none());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn tracing_transaction_with_prestate_tracer() {
+    test_http_server(TraceTransactionWithPrestateTracerTest).await;
+}
+
 #[derive(Debug)]
 struct TraceBlockTestWithSnapshotRecovery;
 
@@ -23,6 +23,7 @@ use zksync_node_test_utils::{
     create_l1_batch, create_l1_batch_metadata, create_l2_block, create_l2_transaction,
     l1_batch_metadata_to_commitment_artifacts, prepare_recovery_snapshot,
 };
+use zksync_state_keeper::{PendingStatePublisher, PendingStateReader};
 use zksync_types::{
     api,
     block::L2BlockHeader,
@@ -37,7 +38,7 @@ use zksync_types::{
     },
     utils::{storage_key_for_eth_balance, storage_key_for_standard_token_balance},
     AccountTreeId, Address, L1BatchNumber, Nonce, ProtocolVersionId, StorageKey, StorageLog,
-    VmEvent, H256, U64,
+    StorageLogWithPreviousValue, VmEvent, H256, L2_BASE_TOKEN_ADDRESS, U64,
 };
 use zksync_utils::u256_to_h256;
 use zksync_web3_decl::{
@@ -51,15 +52,18 @@ use zksync_web3_decl::{
             ErrorObjectOwned,
         },
     },
-    namespaces::{EnNamespaceClient, EthNamespaceClient, ZksNamespaceClient},
+    namespaces::{
+        EnNamespaceClient, EthNamespaceClient, TxpoolNamespaceClient, ZksNamespaceClient,
+    },
 };
 
 use super::*;
 use crate::{
     execution_sandbox::testonly::MockTransactionExecutor,
-    web3::testonly::{spawn_http_server, spawn_ws_server},
+    web3::testonly::{spawn_custom_http_server, spawn_ws_server},
 };
 
+mod archive;
 mod debug;
 mod filters;
 mod snapshots;
@@ -144,6 +148,14 @@ trait HttpTest: Send + Sync {
         Arc::default()
     }
 
+    fn pending_state(&self) -> Option<PendingStateReader> {
+        None
+    }
+
+    fn tree_api(&self) -> Option<Arc<dyn TreeApiClient>> {
+        None
+    }
+
     async fn test(&self, client: &DynClient<L2>, pool: &ConnectionPool<Core>)
         -> anyhow::Result<()>;
 
@@ -151,6 +163,16 @@ trait HttpTest: Send + Sync {
     fn filters_disabled(&self) -> bool {
         false
     }
+
+    /// Overrides the `archive_mode` configuration parameter for HTTP server startup
+    fn archive_mode(&self) -> bool {
+        false
+    }
+
+    /// Overrides the `req_entities_limit` configuration parameter for HTTP server startup
+    fn req_entities_limit(&self) -> Option<usize> {
+        None
+    }
 }
 
 /// Storage initialization strategy.
@@ -228,11 +250,17 @@ async fn test_http_server(test: impl HttpTest) {
     let genesis = GenesisConfig::for_tests();
     let mut api_config = InternalApiConfig::new(&web3_config, &contracts_config, &genesis);
     api_config.filters_disabled = test.filters_disabled();
-    let mut server_handles = spawn_http_server(
+    api_config.archive_mode = test.archive_mode();
+    if let Some(limit) = test.req_entities_limit() {
+        api_config.req_entities_limit = limit;
+    }
+    let mut server_handles = spawn_custom_http_server(
         api_config,
         pool.clone(),
         test.transaction_executor(),
         test.method_tracer(),
+        test.pending_state(),
+        test.tree_api(),
         stop_receiver,
     )
     .await;
@@ -735,6 +763,193 @@ async fn getting_transaction_count_for_account() {
     test_http_server(TransactionCountTest).await;
 }
 
+#[derive(Debug, Default)]
+struct PendingStateTest {
+    publisher: PendingStatePublisher,
+}
+
+#[async_trait]
+impl HttpTest for PendingStateTest {
+    fn pending_state(&self) -> Option<PendingStateReader> {
+        Some(self.publisher.subscribe())
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let test_address = Address::repeat_byte(11);
+        let balance_key = storage_key_for_standard_token_balance(
+            AccountTreeId::new(L2_BASE_TOKEN_ADDRESS),
+            &test_address,
+        );
+        let slot_key = StorageKey::new(AccountTreeId::new(test_address), H256::zero());
+        let storage_logs = [
+            StorageLog::new_write_log(get_nonce_key(&test_address), H256::from_low_u64_be(5)),
+            StorageLog::new_write_log(balance_key, H256::from_low_u64_be(123)),
+            StorageLog::new_write_log(slot_key, H256::repeat_byte(0xff)),
+        ];
+        let storage_logs: Vec<_> = storage_logs
+            .into_iter()
+            .map(|log| StorageLogWithPreviousValue {
+                log,
+                previous_value: H256::zero(),
+            })
+            .collect();
+        // The storage only contains the genesis block, so the pending block is #1.
+        self.publisher
+            .start_l1_batch(L1BatchNumber(1), L2BlockNumber(1));
+        self.publisher.extend(&storage_logs, std::iter::empty());
+
+        let pending_count = client.get_transaction_count(test_address, None).await?;
+        assert_eq!(pending_count, 5.into());
+        let pending_balance = client.get_balance(test_address, None).await?;
+        assert_eq!(pending_balance, 123.into());
+        let pending_value = client.get_storage_at(test_address, 0.into(), None).await?;
+        assert_eq!(pending_value, H256::repeat_byte(0xff));
+
+        let latest = api::BlockIdVariant::BlockNumber(api::BlockNumber::Latest);
+        let latest_count = client
+            .get_transaction_count(test_address, Some(latest))
+            .await?;
+        assert_eq!(latest_count, 0.into());
+        let latest_balance = client.get_balance(test_address, Some(latest)).await?;
+        assert_eq!(latest_balance, 0.into());
+        let latest_value = client
+            .get_storage_at(test_address, 0.into(), Some(latest))
+            .await?;
+        assert_eq!(latest_value, H256::zero());
+
+        // Emulate the state keeper advancing to the batch which is not persisted yet; the snapshot
+        // cannot be applied to the persisted state in this case.
+        self.publisher
+            .start_l1_batch(L1BatchNumber(3), L2BlockNumber(5));
+        self.publisher.extend(&storage_logs, std::iter::empty());
+        let pending_count = client.get_transaction_count(test_address, None).await?;
+        assert_eq!(pending_count, 0.into());
+        let pending_balance = client.get_balance(test_address, None).await?;
+        assert_eq!(pending_balance, 0.into());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn pending_block_reflects_state_keeper_state() {
+    test_http_server(PendingStateTest::default()).await;
+}
+
+#[derive(Debug)]
+struct TxpoolTest;
+
+#[async_trait]
+impl HttpTest for TxpoolTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let test_address = Address::repeat_byte(11);
+        let status = client.status().await?;
+        assert_eq!(status, api::TxpoolStatus::default());
+
+        let mut storage = pool.connection().await?;
+        let mut pending_txs = HashMap::new();
+        // Nonce 2 is missing, so the transaction with nonce 3 should be queued.
+        for nonce in [0, 1, 3] {
+            let mut pending_tx = create_l2_transaction(10, 200);
+            pending_tx.common_data.initiator_address = test_address;
+            pending_tx.common_data.nonce = Nonce(nonce);
+            storage
+                .transactions_dal()
+                .insert_transaction_l2(&pending_tx, TransactionExecutionMetrics::default())
+                .await
+                .unwrap();
+            pending_txs.insert(u64::from(nonce), pending_tx);
+        }
+
+        let status = client.status().await?;
+        assert_eq!(status.pending, 2.into());
+        assert_eq!(status.queued, 1.into());
+
+        let content = client.content().await?;
+        let pending_hashes: Vec<_> = content.pending[&test_address]
+            .iter()
+            .map(|(&nonce, tx)| (nonce, tx.hash))
+            .collect();
+        assert_eq!(
+            pending_hashes,
+            [(0, pending_txs[&0].hash()), (1, pending_txs[&1].hash())]
+        );
+        let queued_hashes: Vec<_> = content.queued[&test_address]
+            .iter()
+            .map(|(&nonce, tx)| (nonce, tx.hash))
+            .collect();
+        assert_eq!(queued_hashes, [(3, pending_txs[&3].hash())]);
+
+        let content_from = client.content_from(test_address).await?;
+        assert_eq!(content_from.pending, content.pending[&test_address]);
+        assert_eq!(content_from.queued, content.queued[&test_address]);
+        let content_from = client.content_from(Address::repeat_byte(1)).await?;
+        assert!(content_from.pending.is_empty());
+        assert!(content_from.queued.is_empty());
+
+        let inspect = client.inspect().await?;
+        let summary = &inspect.queued[&test_address][&3];
+        assert!(summary.contains(" gas × "), "{summary}");
+        assert_eq!(inspect.pending[&test_address].len(), 2);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn txpool_methods() {
+    test_http_server(TxpoolTest).await;
+}
+
+#[derive(Debug)]
+struct TxpoolStatusWithLimitTest;
+
+#[async_trait]
+impl HttpTest for TxpoolStatusWithLimitTest {
+    fn req_entities_limit(&self) -> Option<usize> {
+        Some(1)
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let test_address = Address::repeat_byte(11);
+        let mut storage = pool.connection().await?;
+        for nonce in [0, 1, 3] {
+            let mut pending_tx = create_l2_transaction(10, 200);
+            pending_tx.common_data.initiator_address = test_address;
+            pending_tx.common_data.nonce = Nonce(nonce);
+            storage
+                .transactions_dal()
+                .insert_transaction_l2(&pending_tx, TransactionExecutionMetrics::default())
+                .await
+                .unwrap();
+        }
+
+        // Content is truncated to the entities limit, but the status must account for all transactions.
+        let content = client.content().await?;
+        assert_eq!(content.pending[&test_address].len(), 1);
+        assert!(content.queued.is_empty());
+        let status = client.status().await?;
+        assert_eq!(status.pending, 2.into());
+        assert_eq!(status.queued, 1.into());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn txpool_status_is_not_limited() {
+    test_http_server(TxpoolStatusWithLimitTest).await;
+}
+
 #[derive(Debug)]
 struct TransactionCountAfterSnapshotRecoveryTest;
 
@@ -5,15 +5,16 @@ use std::sync::atomic::{AtomicU32, Ordering};
 use api::state_override::{OverrideAccount, StateOverride};
 use itertools::Itertools;
 use zksync_multivm::{
-    interface::{ExecutionResult, VmRevertReason},
+    interface::{ExecutionResult, VmExecutionStatistics, VmRevertReason},
     vm_latest::{VmExecutionLogs, VmExecutionResultAndLogs},
 };
 use zksync_types::{
     api::{ApiStorageLog, Log},
     get_intrinsic_constants,
     transaction_request::CallRequest,
+    web3::AccessListItem,
     K256PrivateKey, L2ChainId, PackedEthSignature, StorageLogKind, StorageLogWithPreviousValue,
-    U256,
+    KNOWN_CODES_STORAGE_ADDRESS, NONCE_HOLDER_ADDRESS, U256,
 };
 use zksync_utils::u256_to_h256;
 use zksync_web3_decl::namespaces::DebugNamespaceClient;
@@ -164,6 +165,246 @@ async fn call_method_after_snapshot_recovery() {
     test_http_server(CallTestAfterSnapshotRecovery).await;
 }
 
+#[derive(Debug)]
+struct CreateAccessListTest;
+
+impl CreateAccessListTest {
+    fn storage_logs() -> Vec<StorageLogWithPreviousValue> {
+        let slot = |address: Address, key: u64| {
+            StorageKey::new(AccountTreeId::new(address), H256::from_low_u64_be(key))
+        };
+        [
+            StorageLog::new_read_log(slot(Address::repeat_byte(2), 1), H256::zero()),
+            StorageLog::new_read_log(slot(Address::repeat_byte(3), 5), H256::zero()),
+            StorageLog::new_write_log(slot(Address::repeat_byte(2), 1), H256::repeat_byte(1)),
+            StorageLog::new_write_log(slot(Address::repeat_byte(2), 2), H256::repeat_byte(1)),
+            // System contract slots must not be included into the access list.
+            StorageLog::new_write_log(slot(NONCE_HOLDER_ADDRESS, 1), H256::repeat_byte(1)),
+            StorageLog::new_read_log(slot(KNOWN_CODES_STORAGE_ADDRESS, 1), H256::zero()),
+            StorageLog::new_write_log(slot(L2_BASE_TOKEN_ADDRESS, 3), H256::repeat_byte(1)),
+        ]
+        .into_iter()
+        .map(|log| StorageLogWithPreviousValue {
+            log,
+            previous_value: H256::zero(),
+        })
+        .collect()
+    }
+}
+
+#[async_trait]
+impl HttpTest for CreateAccessListTest {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        let mut tx_executor = MockTransactionExecutor::default();
+        tx_executor.set_call_responses_with_logs(|tx, _| {
+            let result = match tx.execute.calldata() {
+                b"success" => ExecutionResult::Success { output: vec![] },
+                b"revert" => ExecutionResult::Revert {
+                    output: VmRevertReason::VmError,
+                },
+                data => panic!("Unexpected calldata: {data:?}"),
+            };
+            VmExecutionResultAndLogs {
+                result,
+                logs: VmExecutionLogs {
+                    storage_logs: Self::storage_logs(),
+                    ..VmExecutionLogs::default()
+                },
+                statistics: VmExecutionStatistics {
+                    gas_used: 10_000,
+                    ..VmExecutionStatistics::default()
+                },
+                refunds: Default::default(),
+            }
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let expected_access_list = vec![
+            AccessListItem {
+                address: Address::repeat_byte(2),
+                storage_keys: vec![H256::from_low_u64_be(1), H256::from_low_u64_be(2)],
+            },
+            AccessListItem {
+                address: Address::repeat_byte(3),
+                storage_keys: vec![H256::from_low_u64_be(5)],
+            },
+        ];
+
+        let result = client
+            .create_access_list(CallTest::call_request(b"success"), None, None)
+            .await?;
+        assert_eq!(result.access_list, expected_access_list);
+        assert!(
+            result
+                .access_list
+                .iter()
+                .all(|item| item.address.as_bytes()[..18] != [0; 18]),
+            "{result:?}"
+        );
+        assert_eq!(result.gas_used, 10_000.into());
+        assert_eq!(result.error, None);
+
+        let latest_block = api::BlockIdVariant::BlockNumber(api::BlockNumber::Latest);
+        let result = client
+            .create_access_list(CallTest::call_request(b"revert"), Some(latest_block), None)
+            .await?;
+        assert_eq!(result.access_list, expected_access_list);
+        assert!(result.error.is_some(), "{result:?}");
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn create_access_list_basics() {
+    test_http_server(CreateAccessListTest).await;
+}
+
+#[derive(Debug)]
+struct SimulateV1Test;
+
+impl SimulateV1Test {
+    fn event() -> VmEvent {
+        VmEvent {
+            location: (L1BatchNumber(1), 0),
+            address: Address::repeat_byte(2),
+            indexed_topics: vec![H256::repeat_byte(0x11)],
+            value: b"event".to_vec(),
+        }
+    }
+
+    fn payload(blocks: Vec<Vec<&[u8]>>) -> api::SimulatePayload {
+        let block_state_calls = blocks
+            .into_iter()
+            .enumerate()
+            .map(|(i, calls)| api::SimulateBlock {
+                // The timestamp of the first block is defined by the node and cannot be overridden.
+                block_overrides: (i > 0).then(|| api::SimulateBlockOverrides {
+                    time: Some((1_000 + i as u64).into()),
+                }),
+                state_overrides: None,
+                calls: calls.into_iter().map(CallTest::call_request).collect(),
+            })
+            .collect();
+        api::SimulatePayload { block_state_calls }
+    }
+}
+
+#[async_trait]
+impl HttpTest for SimulateV1Test {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        let mut tx_executor = MockTransactionExecutor::default();
+        tx_executor.set_call_responses_with_logs(|tx, _| {
+            let (result, events) = match tx.execute.calldata() {
+                b"success" => (ExecutionResult::Success { output: vec![1] }, vec![]),
+                b"log" => (
+                    ExecutionResult::Success { output: vec![] },
+                    vec![Self::event(), Self::event()],
+                ),
+                b"revert" => (
+                    ExecutionResult::Revert {
+                        output: VmRevertReason::VmError,
+                    },
+                    vec![],
+                ),
+                data => panic!("Unexpected calldata: {data:?}"),
+            };
+            VmExecutionResultAndLogs {
+                result,
+                logs: VmExecutionLogs {
+                    events,
+                    ..VmExecutionLogs::default()
+                },
+                statistics: VmExecutionStatistics {
+                    gas_used: 10_000,
+                    ..VmExecutionStatistics::default()
+                },
+                refunds: Default::default(),
+            }
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let payload = Self::payload(vec![vec![b"success", b"log"], vec![b"revert", b"log"]]);
+        let blocks = client.simulate_v1(payload, None).await?;
+        assert_eq!(blocks.len(), 2);
+        // The pending block is resolved to 1, since the storage only contains the genesis block.
+        assert_eq!(blocks[0].number, 1.into());
+        assert_eq!(blocks[0].gas_used, 20_000.into());
+        assert_eq!(blocks[1].number, 2.into());
+        assert_eq!(blocks[1].timestamp, 1_001.into());
+
+        let [success_call, log_call] = blocks[0].calls.as_slice() else {
+            panic!("Unexpected calls: {:?}", blocks[0].calls);
+        };
+        assert_eq!(success_call.status, 1.into());
+        assert_eq!(success_call.return_data.0, [1]);
+        assert_eq!(success_call.error, None);
+        assert!(success_call.logs.is_empty());
+        assert_eq!(log_call.status, 1.into());
+        assert_eq!(log_call.logs.len(), 2);
+        for (i, log) in log_call.logs.iter().enumerate() {
+            assert_eq!(log.address, Address::repeat_byte(2));
+            assert_eq!(log.block_number, Some(1.into()));
+            assert_eq!(log.transaction_index, Some(1.into()));
+            assert_eq!(log.log_index, Some((i as u64).into()));
+            assert_eq!(log.transaction_log_index, Some((i as u64).into()));
+        }
+
+        let [revert_call, log_call] = blocks[1].calls.as_slice() else {
+            panic!("Unexpected calls: {:?}", blocks[1].calls);
+        };
+        assert_eq!(revert_call.status, 0.into());
+        let error = revert_call.error.as_ref().unwrap();
+        assert_eq!(error.code, 3);
+        assert_eq!(log_call.logs[0].block_number, Some(2.into()));
+        assert_eq!(log_call.logs[0].transaction_index, Some(1.into()));
+
+        // Only the last simulated block may be empty.
+        let payload = Self::payload(vec![vec![], vec![b"success"]]);
+        let error = client.simulate_v1(payload, None).await.unwrap_err();
+        assert_invalid_params(error);
+
+        let mut payload = Self::payload(vec![vec![b"success"], vec![b"success"]]);
+        payload.block_state_calls[0].block_overrides = Some(api::SimulateBlockOverrides {
+            time: Some(1_000.into()),
+        });
+        let error = client.simulate_v1(payload, None).await.unwrap_err();
+        assert_invalid_params(error);
+
+        let mut payload = Self::payload(vec![vec![b"success"], vec![b"success"], vec![]]);
+        payload.block_state_calls[2].block_overrides = Some(api::SimulateBlockOverrides {
+            time: Some(1_001.into()),
+        });
+        let error = client.simulate_v1(payload, None).await.unwrap_err();
+        assert_invalid_params(error);
+        Ok(())
+    }
+}
+
+fn assert_invalid_params(error: ClientError) {
+    if let ClientError::Call(error) = error {
+        assert_eq!(error.code(), ErrorCode::InvalidParams.code());
+    } else {
+        panic!("Unexpected error: {error:?}");
+    }
+}
+
+#[tokio::test]
+async fn simulate_v1_basics() {
+    test_http_server(SimulateV1Test).await;
+}
+
 #[derive(Debug)]
 struct SendRawTransactionTest {
     snapshot_recovery: bool,
@@ -393,7 +634,10 @@ async fn send_raw_transaction_with_detailed_output() {
 struct TraceCallTest;
 
 impl TraceCallTest {
-    fn assert_debug_call(call_request: &CallRequest, call_result: &api::DebugCall) {
+    fn assert_debug_call(call_request: &CallRequest, call_result: &api::DebugTrace) {
+        let api::DebugTrace::Call(call_result) = call_result else {
+            panic!("Unexpected trace: {call_result:?}");
+        };
         assert_eq!(call_result.from, Address::zero());
         assert_eq!(call_result.gas, call_request.gas.unwrap());
         assert_eq!(call_result.value, call_request.value.unwrap());
@@ -470,6 +714,58 @@ async fn trace_call_basics() {
     test_http_server(TraceCallTest).await;
 }
 
+#[derive(Debug)]
+struct TraceCallWithPrestateTracerTest;
+
+#[async_trait]
+impl HttpTest for TraceCallWithPrestateTracerTest {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        CallTest::create_executor(L2BlockNumber(0))
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        for diff_mode in [false, true] {
+            let tracer_config = api::TracerConfig {
+                tracer: api::SupportedTracers::PrestateTracer,
+                tracer_config: api::TracerOptions {
+                    prestate_tracer: api::PrestateTracerConfig { diff_mode },
+                    ..api::TracerOptions::default()
+                },
+            };
+            let trace = client
+                .trace_call(
+                    CallTest::call_request(b"pending"),
+                    None,
+                    Some(tracer_config),
+                )
+                .await?;
+
+            // The mock executor doesn't run tracers, so the returned state is empty.
+            let api::DebugTrace::Prestate(trace) = trace else {
+                panic!("Unexpected trace: {trace:?}");
+            };
+            if diff_mode {
+                assert_matches!(
+                    trace,
+                    api::PrestateTrace::Diff { pre, post } if pre.is_empty() && post.is_empty()
+                );
+            } else {
+                assert_matches!(trace, api::PrestateTrace::Prestate(state) if state.is_empty());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn trace_call_with_prestate_tracer() {
+    test_http_server(TraceCallWithPrestateTracerTest).await;
+}
+
 #[derive(Debug)]
 struct TraceCallTestAfterSnapshotRecovery;
 
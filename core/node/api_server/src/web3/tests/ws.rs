@@ -7,7 +7,10 @@ use async_trait::async_trait;
 use http::StatusCode;
 use tokio::sync::watch;
 use zksync_config::configs::chain::NetworkConfig;
-use zksync_dal::ConnectionPool;
+use zksync_dal::{
+    notifications::{StorageNotification, StorageNotifications},
+    ConnectionPool,
+};
 use zksync_types::{api, Address, L1BatchNumber, H160, H2048, H256, U64};
 use zksync_web3_decl::{
     client::{WsClient, L2},
@@ -112,7 +115,7 @@ async fn notifiers_start_after_snapshot_recovery() {
     let mut subscribe_logic = EthSubscribe::new();
     subscribe_logic.set_events_sender(events_sender);
     let notifier_handles =
-        subscribe_logic.spawn_notifiers(pool.clone(), POLL_INTERVAL, stop_receiver);
+        subscribe_logic.spawn_notifiers(pool.clone(), POLL_INTERVAL, None, stop_receiver);
     assert!(!notifier_handles.is_empty());
 
     // Wait a little doing nothing and check that notifier tasks are still active (i.e., have not panicked).
@@ -143,6 +146,68 @@ async fn notifiers_start_after_snapshot_recovery() {
     }
 }
 
+#[tokio::test]
+async fn notifiers_use_storage_notifications() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    prepare_recovery_snapshot(
+        &mut storage,
+        StorageInitialization::SNAPSHOT_RECOVERY_BATCH,
+        StorageInitialization::SNAPSHOT_RECOVERY_BLOCK,
+        &[],
+    )
+    .await;
+
+    let notifications = StorageNotifications::in_process(16);
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let (events_sender, mut events_receiver) = mpsc::unbounded_channel();
+    let mut subscribe_logic = EthSubscribe::new();
+    subscribe_logic.set_events_sender(events_sender);
+    // Use a large polling interval so that notifiers can only be woken up by storage notifications.
+    let notifier_handles = subscribe_logic.spawn_notifiers(
+        pool.clone(),
+        Duration::from_secs(3_600),
+        Some(notifications.clone()),
+        stop_receiver,
+    );
+
+    let StorageNotifications::InProcess(notifications_sender) = &notifications else {
+        unreachable!();
+    };
+    tokio::time::timeout(TEST_TIMEOUT, async {
+        while notifications_sender.receiver_count() < notifier_handles.len() {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    })
+    .await
+    .expect("Timed out waiting for notifiers to subscribe");
+
+    let first_local_l2_block = StorageInitialization::SNAPSHOT_RECOVERY_BLOCK + 1;
+    store_l2_block(&mut storage, first_local_l2_block, &[])
+        .await
+        .unwrap();
+    notifications
+        .send(StorageNotification::L2BlockSealed(first_local_l2_block))
+        .await
+        .unwrap();
+    wait_for_notifiers(
+        &mut events_receiver,
+        &[SubscriptionType::Blocks, SubscriptionType::Logs],
+    )
+    .await;
+
+    notifications
+        .send(StorageNotification::L2TxInserted(H256::repeat_byte(1)))
+        .await
+        .unwrap();
+    wait_for_notifiers(&mut events_receiver, &[SubscriptionType::Txs]).await;
+
+    stop_sender.send_replace(true);
+    for handle in notifier_handles {
+        handle.await.unwrap().expect("Notifier task failed");
+    }
+}
+
 #[async_trait]
 trait WsTest: Send + Sync {
     /// Prepares the storage before the server is started. The default implementation performs genesis.
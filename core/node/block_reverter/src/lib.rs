@@ -27,6 +27,9 @@ use zksync_types::{
     Address, L1BatchNumber, L2ChainId, H160, H256, U256,
 };
 
+pub use self::reconciliation::{EthSenderOperator, ReconciliationAction, ReconciliationPlan};
+
+mod reconciliation;
 #[cfg(test)]
 mod tests;
 
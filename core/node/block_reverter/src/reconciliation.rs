@@ -0,0 +1,615 @@
+//! Reconciliation of the `eth_sender` state in Postgres with the state on L1.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use anyhow::Context as _;
+use serde::{Serialize, Serializer};
+use zksync_dal::{Connection, Core, CoreDal};
+use zksync_eth_client::{
+    clients::{DynClient, L1},
+    encode_blob_tx_with_sidecar, BoundEthInterface, EthInterface, Options, RawTransactionBytes,
+};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType,
+    eth_sender::{EthTx, EthTxBlobSidecar, TxHistory},
+    web3::BlockNumber,
+    Address, L1BatchNumber, EIP_4844_TX_TYPE, H256, U256,
+};
+
+use crate::{BlockReverter, BlockReverterEthConfig};
+
+/// Gas limit for self-transfers used to cancel transactions.
+const CANCEL_TX_GAS_LIMIT: u64 = 21_000;
+/// Maximum number of nonces in a gap filled by a single plan. Larger gaps most probably mean misconfiguration
+/// (e.g., an operator address not matching the transactions in Postgres).
+const MAX_NONCE_GAP: u64 = 64;
+
+/// Operator account sending `eth_sender` transactions.
+#[derive(Debug, Clone, Copy)]
+pub struct EthSenderOperator {
+    /// Value of `from_addr` for transactions sent by the operator in Postgres. `None` for the main operator.
+    pub from_addr: Option<Address>,
+    /// Address of the operator account on L1.
+    pub address: Address,
+}
+
+impl EthSenderOperator {
+    /// Creates the main operator with the specified address.
+    pub fn main(address: Address) -> Self {
+        Self {
+            from_addr: None,
+            address,
+        }
+    }
+
+    /// Creates a custom operator (e.g., the blob operator or the previous main operator) with the specified address.
+    pub fn custom(address: Address) -> Self {
+        Self {
+            from_addr: Some(address),
+            address,
+        }
+    }
+}
+
+/// Single action of a [`ReconciliationPlan`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ReconciliationAction {
+    /// Marks a transaction as failed. This halts `eth_sender` until failed transactions are cleared.
+    MarkTxFailed { eth_tx_id: u32, reason: String },
+    /// Removes a transaction together with its history, so that `eth_sender` creates the aggregated operation anew.
+    RequeueOperation {
+        eth_tx_id: u32,
+        #[serde(serialize_with = "serialize_action_type")]
+        tx_type: AggregatedActionType,
+        first_l1_batch: L1BatchNumber,
+        last_l1_batch: L1BatchNumber,
+        reason: String,
+    },
+    /// Sends a zero-value self-transfer with the specified nonce, replacing a pending transaction
+    /// or filling a nonce gap. A pending blob (EIP-4844) transaction can only be replaced by another blob transaction,
+    /// so in this case, the self-transfer carries the blobs of the replaced transaction.
+    CancelNonce {
+        operator: Address,
+        nonce: u64,
+        replaced_eth_tx_id: Option<u32>,
+        reason: String,
+    },
+}
+
+fn serialize_action_type<S: Serializer>(
+    tx_type: &AggregatedActionType,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(tx_type)
+}
+
+impl fmt::Display for ReconciliationAction {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MarkTxFailed { eth_tx_id, reason } => {
+                write!(formatter, "mark eth_tx #{eth_tx_id} as failed: {reason}")
+            }
+            Self::RequeueOperation {
+                eth_tx_id,
+                tx_type,
+                first_l1_batch,
+                last_l1_batch,
+                reason,
+            } => write!(
+                formatter,
+                "re-queue {tx_type} operation for L1 batches #{first_l1_batch}..=#{last_l1_batch} \
+                 (eth_tx #{eth_tx_id}): {reason}"
+            ),
+            Self::CancelNonce {
+                operator,
+                nonce,
+                replaced_eth_tx_id,
+                reason,
+            } => {
+                write!(formatter, "cancel nonce {nonce} of operator {operator:?}")?;
+                if let Some(eth_tx_id) = replaced_eth_tx_id {
+                    write!(formatter, " (replacing eth_tx #{eth_tx_id})")?;
+                }
+                write!(formatter, ": {reason}")
+            }
+        }
+    }
+}
+
+/// Plan reconciling the `eth_sender` state in Postgres with the state on L1.
+#[derive(Debug, Default, Serialize)]
+pub struct ReconciliationPlan {
+    pub actions: Vec<ReconciliationAction>,
+}
+
+impl ReconciliationPlan {
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+}
+
+/// Aggregated operation re-queued by a plan.
+#[derive(Debug)]
+struct RequeuedOperation {
+    tx: EthTx,
+    first_l1_batch: L1BatchNumber,
+}
+
+/// Operations of later stages depend on operations of earlier stages for the same L1 batches.
+fn stage(tx_type: AggregatedActionType) -> u8 {
+    match tx_type {
+        AggregatedActionType::Commit => 0,
+        AggregatedActionType::PublishProofOnchain => 1,
+        AggregatedActionType::Execute => 2,
+    }
+}
+
+impl BlockReverter {
+    /// Compares the `eth_sender` state in Postgres with the state on L1 and returns a plan reconciling them.
+    /// This method doesn't change either state. The following discrepancies are detected:
+    ///
+    /// - Confirmed operations for L1 batches not processed according to the diamond proxy (e.g., after an L1 reorg)
+    ///   are re-queued.
+    /// - Transactions whose nonce was consumed on L1 without any of their sending attempts being mined
+    ///   (i.e., transactions replaced by another transaction) are re-queued. If their L1 batches are nevertheless
+    ///   processed on L1, they are marked as failed instead, since this requires manual investigation.
+    /// - Mined, but reverted transactions are marked as failed.
+    /// - Unconfirmed operations depending on the re-queued ones are re-queued as well. Re-queued transactions
+    ///   that are pending on L1 are cancelled.
+    /// - Gaps between the operator nonce on L1 and nonces of unconfirmed transactions are filled with cancellations.
+    ///
+    /// `operators` must include all operators sending transactions found in Postgres.
+    pub async fn eth_sender_reconciliation_plan(
+        &self,
+        eth_client: &DynClient<L1>,
+        eth_config: &BlockReverterEthConfig,
+        operators: &[EthSenderOperator],
+    ) -> anyhow::Result<ReconciliationPlan> {
+        tracing::info!("Computing eth_sender reconciliation plan for operators {operators:?}");
+
+        let mut storage = self.connection_pool.connection().await?;
+        let mut plan = ReconciliationPlan::default();
+        let mut requeued = vec![];
+        let mut resolved_tx_ids = HashSet::new();
+
+        let mut last_processed_l1_batches = HashMap::new();
+        for tx_type in [
+            AggregatedActionType::Commit,
+            AggregatedActionType::PublishProofOnchain,
+            AggregatedActionType::Execute,
+        ] {
+            let last_processed_l1_batch = Self::get_l1_batch_number_from_contract(
+                eth_client,
+                eth_config.diamond_proxy_addr,
+                tx_type,
+            )
+            .await?;
+            last_processed_l1_batches.insert(tx_type, last_processed_l1_batch);
+
+            let confirmed_txs = storage
+                .eth_sender_dal()
+                .get_confirmed_txs_after_l1_batch(tx_type, last_processed_l1_batch)
+                .await?;
+            for tx in confirmed_txs {
+                let reason = format!(
+                    "transaction is confirmed in Postgres, but the last L1 batch with {tx_type} operation on L1 \
+                     is #{last_processed_l1_batch}"
+                );
+                Self::requeue(&mut storage, &mut plan, &mut requeued, tx, reason).await?;
+            }
+        }
+
+        let mut operator_nonces = HashMap::new();
+        let mut unconfirmed_txs = vec![];
+        for operator in operators {
+            let latest_nonce =
+                Self::get_operator_nonce(eth_client, operator, BlockNumber::Latest).await?;
+            let pending_nonce =
+                Self::get_operator_nonce(eth_client, operator, BlockNumber::Pending).await?;
+            tracing::info!(
+                "Operator {operator:?} has nonces: latest {latest_nonce}, pending {pending_nonce}"
+            );
+            operator_nonces.insert(operator.from_addr, (latest_nonce, pending_nonce));
+
+            let txs = storage
+                .eth_sender_dal()
+                .get_unconfirmed_txs(operator.from_addr)
+                .await?;
+            for tx in txs {
+                if u64::from(tx.nonce.0) >= latest_nonce {
+                    unconfirmed_txs.push(tx);
+                    continue;
+                }
+
+                match Self::get_mined_attempt_status(eth_client, &mut storage, tx.id).await? {
+                    // The transaction will be confirmed by `eth_sender`.
+                    Some(true) => {}
+                    Some(false) => {
+                        resolved_tx_ids.insert(tx.id);
+                        plan.actions.push(ReconciliationAction::MarkTxFailed {
+                            eth_tx_id: tx.id,
+                            reason: "transaction is mined, but reverted".to_owned(),
+                        });
+                    }
+                    None => {
+                        resolved_tx_ids.insert(tx.id);
+                        let (_, last_l1_batch) =
+                            Self::get_l1_batch_range(&mut storage, &tx).await?;
+                        let last_processed_l1_batch = last_processed_l1_batches[&tx.tx_type];
+                        if last_l1_batch <= last_processed_l1_batch {
+                            plan.actions.push(ReconciliationAction::MarkTxFailed {
+                                eth_tx_id: tx.id,
+                                reason: format!(
+                                    "nonce {} is consumed by another transaction, but L1 batch #{last_l1_batch} \
+                                     is processed on L1; needs manual investigation",
+                                    tx.nonce
+                                ),
+                            });
+                        } else {
+                            let reason =
+                                format!("nonce {} is consumed by another transaction", tx.nonce);
+                            Self::requeue(&mut storage, &mut plan, &mut requeued, tx, reason)
+                                .await?;
+                        }
+                    }
+                }
+            }
+        }
+
+        // Re-queue unconfirmed operations depending on the re-queued ones until there are no more such operations.
+        let mut l1_batch_ranges = HashMap::new();
+        for tx in &unconfirmed_txs {
+            let range = Self::get_l1_batch_range(&mut storage, tx).await?;
+            l1_batch_ranges.insert(tx.id, range);
+        }
+        loop {
+            let dependent_tx = unconfirmed_txs.iter().find_map(|tx| {
+                if resolved_tx_ids.contains(&tx.id) {
+                    return None;
+                }
+                let (_, last_l1_batch) = l1_batch_ranges[&tx.id];
+                let dependency = requeued.iter().find(|op| {
+                    stage(op.tx.tx_type) <= stage(tx.tx_type) && last_l1_batch >= op.first_l1_batch
+                })?;
+                Some((tx, dependency.tx.id))
+            });
+            let Some((tx, dependency_id)) = dependent_tx else {
+                break;
+            };
+
+            resolved_tx_ids.insert(tx.id);
+            let reason = format!("operation depends on re-queued eth_tx #{dependency_id}");
+            Self::requeue(&mut storage, &mut plan, &mut requeued, tx.clone(), reason).await?;
+        }
+
+        let mut cancelled_nonces = HashSet::new();
+        for op in &requeued {
+            let tx = &op.tx;
+            let &(latest_nonce, _) = operator_nonces.get(&tx.from_addr).with_context(|| {
+                format!(
+                    "eth_tx #{} is sent by operator {:?}, which is not provided",
+                    tx.id, tx.from_addr
+                )
+            })?;
+            let nonce = u64::from(tx.nonce.0);
+            if nonce < latest_nonce {
+                continue; // The nonce is already consumed on L1
+            }
+            if !Self::is_pending_on_l1(eth_client, &mut storage, tx).await? {
+                continue;
+            }
+
+            let operator = operators
+                .iter()
+                .find(|operator| operator.from_addr == tx.from_addr)
+                .expect("operator must be present");
+            cancelled_nonces.insert((operator.address, nonce));
+            plan.actions.push(ReconciliationAction::CancelNonce {
+                operator: operator.address,
+                nonce,
+                replaced_eth_tx_id: Some(tx.id),
+                reason: "transaction of the re-queued operation is pending on L1".to_owned(),
+            });
+        }
+
+        for operator in operators {
+            let (latest_nonce, pending_nonce) = operator_nonces[&operator.from_addr];
+            let next_nonce = latest_nonce.max(pending_nonce);
+            let remaining_nonces: HashSet<_> = unconfirmed_txs
+                .iter()
+                .filter(|tx| {
+                    tx.from_addr == operator.from_addr && !resolved_tx_ids.contains(&tx.id)
+                })
+                .map(|tx| u64::from(tx.nonce.0))
+                .collect();
+            let Some(&max_nonce) = remaining_nonces.iter().max() else {
+                continue;
+            };
+            anyhow::ensure!(
+                max_nonce.saturating_sub(next_nonce) <= MAX_NONCE_GAP,
+                "Nonce gap for operator {operator:?} is too large: next nonce on L1 is {next_nonce}, \
+                 while Postgres contains a transaction with nonce {max_nonce}"
+            );
+
+            for nonce in next_nonce..max_nonce {
+                if remaining_nonces.contains(&nonce)
+                    || cancelled_nonces.contains(&(operator.address, nonce))
+                {
+                    continue;
+                }
+                plan.actions.push(ReconciliationAction::CancelNonce {
+                    operator: operator.address,
+                    nonce,
+                    replaced_eth_tx_id: None,
+                    reason: format!("nonce gap before transaction with nonce {max_nonce}"),
+                });
+            }
+        }
+
+        Ok(plan)
+    }
+
+    async fn requeue(
+        storage: &mut Connection<'_, Core>,
+        plan: &mut ReconciliationPlan,
+        requeued: &mut Vec<RequeuedOperation>,
+        tx: EthTx,
+        reason: String,
+    ) -> anyhow::Result<()> {
+        let (first_l1_batch, last_l1_batch) = Self::get_l1_batch_range(storage, &tx).await?;
+        plan.actions.push(ReconciliationAction::RequeueOperation {
+            eth_tx_id: tx.id,
+            tx_type: tx.tx_type,
+            first_l1_batch,
+            last_l1_batch,
+            reason,
+        });
+        requeued.push(RequeuedOperation { tx, first_l1_batch });
+        Ok(())
+    }
+
+    async fn get_operator_nonce(
+        eth_client: &DynClient<L1>,
+        operator: &EthSenderOperator,
+        block: BlockNumber,
+    ) -> anyhow::Result<u64> {
+        let nonce = eth_client
+            .nonce_at_for_account(operator.address, block)
+            .await
+            .with_context(|| format!("failed getting transaction count for {operator:?}"))?;
+        Ok(nonce.as_u64())
+    }
+
+    async fn get_l1_batch_range(
+        storage: &mut Connection<'_, Core>,
+        tx: &EthTx,
+    ) -> anyhow::Result<(L1BatchNumber, L1BatchNumber)> {
+        let l1_batches = storage
+            .blocks_dal()
+            .get_l1_batches_statistics_for_eth_tx_id(tx.id)
+            .await?;
+        let numbers = l1_batches.iter().map(|batch| batch.number);
+        let first_l1_batch = numbers.clone().min();
+        let last_l1_batch = numbers.max();
+        first_l1_batch
+            .zip(last_l1_batch)
+            .with_context(|| format!("eth_tx #{} doesn't reference any L1 batches", tx.id))
+    }
+
+    /// Returns the status of the mined sending attempt of the transaction, or `None` if none of attempts is mined.
+    async fn get_mined_attempt_status(
+        eth_client: &DynClient<L1>,
+        storage: &mut Connection<'_, Core>,
+        eth_tx_id: u32,
+    ) -> anyhow::Result<Option<bool>> {
+        let attempts = storage
+            .eth_sender_dal()
+            .get_tx_history_to_check(eth_tx_id)
+            .await?;
+        for attempt in attempts {
+            let receipt = eth_client
+                .tx_receipt(attempt.tx_hash)
+                .await
+                .with_context(|| format!("failed getting receipt for {:?}", attempt.tx_hash))?;
+            if let Some(receipt) = receipt {
+                return Ok(Some(receipt.status == Some(1.into())));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Checks whether any sending attempt of the transaction is known to L1, but is not mined.
+    async fn is_pending_on_l1(
+        eth_client: &DynClient<L1>,
+        storage: &mut Connection<'_, Core>,
+        tx: &EthTx,
+    ) -> anyhow::Result<bool> {
+        if Self::get_mined_attempt_status(eth_client, storage, tx.id)
+            .await?
+            .is_some()
+        {
+            return Ok(false);
+        }
+        let attempts = storage
+            .eth_sender_dal()
+            .get_tx_history_to_check(tx.id)
+            .await?;
+        for attempt in attempts {
+            let l1_tx = eth_client
+                .get_tx(attempt.tx_hash)
+                .await
+                .with_context(|| format!("failed getting transaction {:?}", attempt.tx_hash))?;
+            if l1_tx.is_some() {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Applies a plan returned by [`Self::eth_sender_reconciliation_plan()`]. Cancellation transactions are sent first
+    /// using signing clients from `eth_clients` (one for each operator with cancelled nonces); Postgres is then updated
+    /// in a single DB transaction.
+    ///
+    /// `eth_sender` must be stopped while computing and applying the plan, and restarted afterwards so that
+    /// it picks up operator nonces from L1.
+    pub async fn apply_eth_sender_reconciliation_plan(
+        &self,
+        plan: &ReconciliationPlan,
+        eth_config: &BlockReverterEthConfig,
+        eth_clients: &[&dyn BoundEthInterface],
+    ) -> anyhow::Result<()> {
+        let mut storage = self.connection_pool.connection().await?;
+        for action in &plan.actions {
+            let ReconciliationAction::CancelNonce {
+                operator,
+                nonce,
+                replaced_eth_tx_id,
+                ..
+            } = action
+            else {
+                continue;
+            };
+            let eth_client = eth_clients
+                .iter()
+                .find(|client| client.sender_account() == *operator)
+                .with_context(|| format!("no signing client for operator {operator:?}"))?;
+            let (last_attempt, blob_sidecar) = match replaced_eth_tx_id {
+                Some(eth_tx_id) => {
+                    let replaced_tx = storage
+                        .eth_sender_dal()
+                        .get_eth_tx(*eth_tx_id)
+                        .await?
+                        .with_context(|| format!("eth_tx #{eth_tx_id} is missing in Postgres"))?;
+                    let last_attempt = storage
+                        .eth_sender_dal()
+                        .get_last_sent_eth_tx(*eth_tx_id)
+                        .await?;
+                    (last_attempt, replaced_tx.blob_sidecar)
+                }
+                None => (None, None),
+            };
+            Self::send_cancel_transaction(
+                *eth_client,
+                eth_config,
+                *nonce,
+                last_attempt.as_ref(),
+                blob_sidecar.as_ref(),
+            )
+            .await?;
+        }
+
+        let mut transaction = storage.start_transaction().await?;
+        for action in &plan.actions {
+            match action {
+                ReconciliationAction::MarkTxFailed { eth_tx_id, .. } => {
+                    tracing::info!("Marking eth_tx #{eth_tx_id} as failed");
+                    transaction
+                        .eth_sender_dal()
+                        .mark_failed_transaction(*eth_tx_id)
+                        .await?;
+                }
+                ReconciliationAction::RequeueOperation { eth_tx_id, .. } => {
+                    tracing::info!("Deleting eth_tx #{eth_tx_id}");
+                    transaction
+                        .eth_sender_dal()
+                        .delete_eth_tx(*eth_tx_id)
+                        .await?;
+                }
+                ReconciliationAction::CancelNonce { .. } => { /* Already processed */ }
+            }
+        }
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    async fn send_cancel_transaction(
+        eth_client: &dyn BoundEthInterface,
+        eth_config: &BlockReverterEthConfig,
+        nonce: u64,
+        last_attempt: Option<&TxHistory>,
+        blob_sidecar: Option<&EthTxBlobSidecar>,
+    ) -> anyhow::Result<()> {
+        let operator = eth_client.sender_account();
+        let gas_price = eth_client
+            .as_ref()
+            .get_gas_price()
+            .await
+            .context("failed getting gas price")?;
+
+        let mut priority_fee = U256::from(eth_config.default_priority_fee_per_gas);
+        let mut max_fee = gas_price * U256::from(2);
+        if let Some(attempt) = last_attempt {
+            // A replacement transaction must bump both fees of the replaced one.
+            priority_fee = priority_fee.max(U256::from(attempt.priority_fee_per_gas * 2));
+            let last_max_fee =
+                U256::from(attempt.base_fee_per_gas) + U256::from(attempt.priority_fee_per_gas);
+            max_fee = max_fee.max(last_max_fee * U256::from(2));
+        }
+        let mut options = Options {
+            nonce: Some(nonce.into()),
+            gas: Some(CANCEL_TX_GAS_LIMIT.into()),
+            value: Some(U256::zero()),
+            max_fee_per_gas: Some(max_fee.max(priority_fee)),
+            max_priority_fee_per_gas: Some(priority_fee),
+            ..Options::default()
+        };
+        if let Some(EthTxBlobSidecar::EthTxBlobSidecarV1(sidecar)) = blob_sidecar {
+            let blob_base_fee = Self::get_blob_base_fee(eth_client.as_ref()).await?;
+            let mut max_fee_per_blob_gas = blob_base_fee * U256::from(2);
+            if let Some(last_blob_fee) =
+                last_attempt.and_then(|attempt| attempt.blob_base_fee_per_gas)
+            {
+                max_fee_per_blob_gas = max_fee_per_blob_gas.max(U256::from(last_blob_fee * 2));
+            }
+            options.transaction_type = Some(EIP_4844_TX_TYPE.into());
+            options.max_fee_per_blob_gas = Some(max_fee_per_blob_gas);
+            options.blob_versioned_hashes = Some(
+                sidecar
+                    .blobs
+                    .iter()
+                    .map(|blob| H256::from_slice(&blob.versioned_hash))
+                    .collect(),
+            );
+        }
+
+        let mut signed_tx = eth_client
+            .sign_prepared_tx_for_addr(vec![], operator, options)
+            .await
+            .context("cannot sign cancellation transaction")?;
+        if let Some(blob_sidecar) = blob_sidecar {
+            signed_tx.raw_tx = RawTransactionBytes::new_unchecked(encode_blob_tx_with_sidecar(
+                signed_tx.raw_tx.as_ref(),
+                blob_sidecar,
+            ));
+        }
+        let hash = eth_client
+            .as_ref()
+            .send_raw_tx(signed_tx.raw_tx)
+            .await
+            .with_context(|| {
+                format!("failed sending cancellation transaction for nonce {nonce}")
+            })?;
+        tracing::info!(
+            "Sent cancellation transaction {hash:?} for nonce {nonce} of operator {operator:?}"
+        );
+        Ok(())
+    }
+
+    /// Returns the blob base fee in the latest L1 block.
+    async fn get_blob_base_fee(eth_client: &DynClient<L1>) -> anyhow::Result<U256> {
+        let latest_block = eth_client
+            .block_number()
+            .await
+            .context("failed getting L1 block number")?;
+        let fees = eth_client
+            .base_fee_history(latest_block.as_usize(), 1)
+            .await
+            .context("failed getting L1 fee history")?;
+        let fees = fees.last().context("empty L1 fee history")?;
+        Ok(fees.base_fee_per_blob_gas)
+    }
+}
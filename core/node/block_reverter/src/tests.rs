@@ -7,6 +7,7 @@ use async_trait::async_trait;
 use test_casing::test_casing;
 use tokio::sync::watch;
 use zksync_dal::Connection;
+use zksync_eth_client::clients::MockEthereum;
 use zksync_merkle_tree::TreeInstruction;
 use zksync_object_store::{Bucket, MockObjectStore};
 use zksync_state::ReadStorage;
@@ -470,3 +471,219 @@ async fn reverter_handles_incomplete_snapshot() {
         assert_matches!(chunk_result.unwrap_err(), ObjectStoreError::KeyNotFound(_));
     }
 }
+
+const DIAMOND_PROXY_ADDR: Address = Address::repeat_byte(1);
+
+fn mock_eth_config() -> BlockReverterEthConfig {
+    BlockReverterEthConfig {
+        diamond_proxy_addr: DIAMOND_PROXY_ADDR,
+        validator_timelock_addr: Address::repeat_byte(2),
+        default_priority_fee_per_gas: 1,
+        hyperchain_id: L2ChainId::default(),
+    }
+}
+
+/// Creates a mock L1 client with the specified last committed L1 batch on the diamond proxy.
+fn mock_ethereum(last_committed_l1_batch: u32) -> MockEthereum {
+    MockEthereum::builder()
+        .with_call_handler(move |call, _| {
+            assert_eq!(call.to, Some(DIAMOND_PROXY_ADDR));
+            let contract = hyperchain_contract();
+            let data = call.data.as_ref().unwrap().0.as_slice();
+            let committed_selector = contract
+                .function("getTotalBatchesCommitted")
+                .unwrap()
+                .short_signature();
+            let l1_batch = if data == committed_selector {
+                last_committed_l1_batch
+            } else {
+                0
+            };
+            Token::Uint(l1_batch.into())
+        })
+        .build()
+}
+
+/// Mines a transaction not tracked by `eth_sender` with the specified nonce.
+async fn mine_foreign_tx(eth_client: &MockEthereum, nonce: u64) {
+    let options = Options {
+        nonce: Some(nonce.into()),
+        ..Options::default()
+    };
+    let signed_tx = eth_client
+        .sign_prepared_tx(vec![], Address::repeat_byte(0xff), options)
+        .unwrap();
+    let hash = eth_client
+        .as_ref()
+        .send_raw_tx(signed_tx.raw_tx)
+        .await
+        .unwrap();
+    eth_client.execute_tx(hash, true, 1);
+}
+
+async fn save_eth_tx(
+    storage: &mut Connection<'_, Core>,
+    tx_type: AggregatedActionType,
+    l1_batch: L1BatchNumber,
+    nonce: u64,
+    sent_tx_hash: Option<H256>,
+) -> u32 {
+    let eth_tx = storage
+        .eth_sender_dal()
+        .save_eth_tx(nonce, vec![], tx_type, DIAMOND_PROXY_ADDR, 0, None, None)
+        .await
+        .unwrap();
+    storage
+        .blocks_dal()
+        .set_eth_tx_id(l1_batch..=l1_batch, eth_tx.id, tx_type)
+        .await
+        .unwrap();
+    if let Some(tx_hash) = sent_tx_hash {
+        storage
+            .eth_sender_dal()
+            .insert_tx_history(eth_tx.id, 10, 1, None, tx_hash, &[], 0)
+            .await
+            .unwrap();
+    }
+    eth_tx.id
+}
+
+#[tokio::test]
+async fn reconciling_eth_sender_after_l1_reorg() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    setup_storage(&mut storage, &gen_storage_logs()).await;
+
+    let mut commit_tx_ids = vec![];
+    for nonce in 0..2 {
+        let tx_hash = H256::from_low_u64_be(nonce + 1);
+        let l1_batch = L1BatchNumber(nonce as u32 + 1);
+        let tx_type = AggregatedActionType::Commit;
+        let eth_tx_id = save_eth_tx(&mut storage, tx_type, l1_batch, nonce, Some(tx_hash)).await;
+        commit_tx_ids.push(eth_tx_id);
+        storage
+            .eth_sender_dal()
+            .confirm_tx(tx_hash, 0.into())
+            .await
+            .unwrap();
+    }
+    let reorged_tx_id = commit_tx_ids[1];
+    let prove_tx_id = save_eth_tx(
+        &mut storage,
+        AggregatedActionType::PublishProofOnchain,
+        L1BatchNumber(1),
+        2,
+        Some(H256::repeat_byte(3)),
+    )
+    .await;
+
+    // Only the first commit transaction remains on L1 after the reorg.
+    let eth_client = mock_ethereum(1);
+    mine_foreign_tx(&eth_client, 0).await;
+    let operator = EthSenderOperator::main(eth_client.sender_account());
+
+    let block_reverter = BlockReverter::new(NodeRole::Main, pool.clone());
+    let eth_config = mock_eth_config();
+    let plan = block_reverter
+        .eth_sender_reconciliation_plan(eth_client.as_ref(), &eth_config, &[operator])
+        .await
+        .unwrap();
+    assert_matches!(
+        plan.actions.as_slice(),
+        [
+            ReconciliationAction::RequeueOperation {
+                eth_tx_id,
+                tx_type: AggregatedActionType::Commit,
+                first_l1_batch: L1BatchNumber(2),
+                last_l1_batch: L1BatchNumber(2),
+                ..
+            },
+            ReconciliationAction::CancelNonce {
+                nonce: 1,
+                replaced_eth_tx_id: None,
+                ..
+            },
+        ] if *eth_tx_id == reorged_tx_id
+    );
+
+    block_reverter
+        .apply_eth_sender_reconciliation_plan(&plan, &eth_config, &[&eth_client])
+        .await
+        .unwrap();
+    assert_eq!(eth_client.sent_tx_count(), 2);
+    let reorged_tx = storage
+        .eth_sender_dal()
+        .get_eth_tx(reorged_tx_id)
+        .await
+        .unwrap();
+    assert!(reorged_tx.is_none());
+    let last_committed_l1_batch = storage
+        .blocks_dal()
+        .get_number_of_last_l1_batch_committed_on_eth()
+        .await
+        .unwrap();
+    assert_eq!(last_committed_l1_batch, Some(L1BatchNumber(1)));
+    let prove_tx = storage
+        .eth_sender_dal()
+        .get_eth_tx(prove_tx_id)
+        .await
+        .unwrap();
+    assert!(prove_tx.is_some());
+}
+
+#[tokio::test]
+async fn reconciling_eth_sender_after_replaced_transaction() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    setup_storage(&mut storage, &gen_storage_logs()).await;
+
+    let replaced_tx_id = save_eth_tx(
+        &mut storage,
+        AggregatedActionType::Commit,
+        L1BatchNumber(1),
+        0,
+        Some(H256::repeat_byte(1)),
+    )
+    .await;
+    let unsent_tx_id = save_eth_tx(
+        &mut storage,
+        AggregatedActionType::Commit,
+        L1BatchNumber(2),
+        1,
+        None,
+    )
+    .await;
+
+    // The nonce of the sent commit transaction is consumed by another transaction.
+    let eth_client = mock_ethereum(0);
+    mine_foreign_tx(&eth_client, 0).await;
+    let operator = EthSenderOperator::main(eth_client.sender_account());
+
+    let block_reverter = BlockReverter::new(NodeRole::Main, pool.clone());
+    let eth_config = mock_eth_config();
+    let plan = block_reverter
+        .eth_sender_reconciliation_plan(eth_client.as_ref(), &eth_config, &[operator])
+        .await
+        .unwrap();
+    let requeued_tx_ids: Vec<_> = plan
+        .actions
+        .iter()
+        .map(|action| match action {
+            ReconciliationAction::RequeueOperation { eth_tx_id, .. } => *eth_tx_id,
+            _ => panic!("unexpected action: {action}"),
+        })
+        .collect();
+    assert_eq!(requeued_tx_ids, [replaced_tx_id, unsent_tx_id]);
+
+    block_reverter
+        .apply_eth_sender_reconciliation_plan(&plan, &eth_config, &[])
+        .await
+        .unwrap();
+    assert_eq!(eth_client.sent_tx_count(), 1);
+    let unconfirmed_txs = storage
+        .eth_sender_dal()
+        .get_unconfirmed_txs(None)
+        .await
+        .unwrap();
+    assert!(unconfirmed_txs.is_empty(), "{unconfirmed_txs:?}");
+}
@@ -5,9 +5,15 @@ use chrono::{NaiveDateTime, Utc};
 use rand::Rng;
 use tokio::sync::watch::Receiver;
 use zksync_config::DADispatcherConfig;
-use zksync_da_client::{types::DAError, DataAvailabilityClient};
+use zksync_da_client::{
+    types::{DAError, InclusionData},
+    DataAvailabilityClient,
+};
 use zksync_dal::{ConnectionPool, Core, CoreDal};
-use zksync_types::L1BatchNumber;
+use zksync_types::{
+    ethabi::{self, Token},
+    L1BatchNumber,
+};
 
 use crate::metrics::METRICS;
 
@@ -80,18 +86,26 @@ impl DataAvailabilityDispatcher {
 
         for batch in batches {
             let dispatch_latency = METRICS.blob_dispatch_latency.start();
-            let dispatch_response = retry(self.config.max_retries(), batch.l1_batch_number, || {
-                self.client
-                    .dispatch_blob(batch.l1_batch_number.0, batch.pubdata.clone())
-            })
-            .await
-            .with_context(|| {
-                format!(
-                    "failed to dispatch a blob with batch_number: {}, pubdata_len: {}",
-                    batch.l1_batch_number,
-                    batch.pubdata.len()
-                )
-            })?;
+            let chunks = split_into_chunks(&batch.pubdata, self.client.blob_size_limit());
+            let mut blob_ids = Vec::with_capacity(chunks.len());
+            // If dispatching one of the chunks fails, all chunks will be re-dispatched on the next iteration.
+            for chunk in &chunks {
+                let dispatch_response =
+                    retry(self.config.max_retries(), batch.l1_batch_number, || {
+                        self.client
+                            .dispatch_blob(batch.l1_batch_number.0, chunk.to_vec())
+                    })
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "failed to dispatch a blob with batch_number: {}, pubdata_len: {}, chunk_len: {}",
+                            batch.l1_batch_number,
+                            batch.pubdata.len(),
+                            chunk.len()
+                        )
+                    })?;
+                blob_ids.push(dispatch_response.blob_id);
+            }
             let dispatch_latency_duration = dispatch_latency.observe();
 
             let sent_at =
@@ -99,11 +113,7 @@ impl DataAvailabilityDispatcher {
 
             let mut conn = self.pool.connection_tagged("da_dispatcher").await?;
             conn.data_availability_dal()
-                .insert_l1_batch_da(
-                    batch.l1_batch_number,
-                    dispatch_response.blob_id.as_str(),
-                    sent_at,
-                )
+                .insert_l1_batch_da_chunks(batch.l1_batch_number, &blob_ids, sent_at)
                 .await?;
             drop(conn);
 
@@ -111,10 +121,12 @@ impl DataAvailabilityDispatcher {
                 .last_dispatched_l1_batch
                 .set(batch.l1_batch_number.0 as usize);
             METRICS.blob_size.observe(batch.pubdata.len());
+            METRICS.blob_chunk_count.observe(chunks.len());
             tracing::info!(
-                "Dispatched a DA for batch_number: {}, pubdata_size: {}, dispatch_latency: {dispatch_latency_duration:?}",
+                "Dispatched a DA for batch_number: {}, pubdata_size: {}, chunks: {}, dispatch_latency: {dispatch_latency_duration:?}",
                 batch.l1_batch_number,
                 batch.pubdata.len(),
+                chunks.len(),
             );
         }
 
@@ -134,20 +146,26 @@ impl DataAvailabilityDispatcher {
             return Ok(());
         };
 
-        let inclusion_data = self
-            .client
-            .get_inclusion_data(blob_info.blob_id.as_str())
-            .await
-            .with_context(|| {
-                format!(
-                    "failed to get inclusion data for blob_id: {}, batch_number: {}",
-                    blob_info.blob_id, blob_info.l1_batch_number
-                )
-            })?;
-
-        let Some(inclusion_data) = inclusion_data else {
-            return Ok(());
-        };
+        let blob_ids = blob_info.blob_ids();
+        let mut chunks_inclusion_data = Vec::with_capacity(blob_ids.len());
+        for blob_id in blob_ids {
+            let inclusion_data =
+                self.client
+                    .get_inclusion_data(blob_id)
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "failed to get inclusion data for blob_id: {}, batch_number: {}",
+                            blob_id, blob_info.l1_batch_number
+                        )
+                    })?;
+
+            let Some(inclusion_data) = inclusion_data else {
+                return Ok(());
+            };
+            chunks_inclusion_data.push(inclusion_data);
+        }
+        let inclusion_data = aggregate_inclusion_data(chunks_inclusion_data);
 
         let mut conn = self.pool.connection_tagged("da_dispatcher").await?;
         conn.data_availability_dal()
@@ -176,6 +194,29 @@ impl DataAvailabilityDispatcher {
     }
 }
 
+/// Splits pubdata into chunks not exceeding the blob size limit of the DA client. Always returns at least one chunk.
+fn split_into_chunks(pubdata: &[u8], blob_size_limit: Option<usize>) -> Vec<&[u8]> {
+    match blob_size_limit {
+        Some(limit) if pubdata.len() > limit => pubdata.chunks(limit.max(1)).collect(),
+        _ => vec![pubdata],
+    }
+}
+
+/// Combines inclusion data for pubdata chunks into a single payload. Inclusion data for a single chunk is returned as is;
+/// otherwise, the payload is ABI-encoded as `bytes[]` with the inclusion data for each chunk in the chunk order.
+fn aggregate_inclusion_data(mut chunks: Vec<InclusionData>) -> InclusionData {
+    if chunks.len() == 1 {
+        return chunks.pop().unwrap();
+    }
+    let tokens = chunks
+        .into_iter()
+        .map(|chunk| Token::Bytes(chunk.data))
+        .collect();
+    InclusionData {
+        data: ethabi::encode(&[Token::Array(tokens)]),
+    }
+}
+
 async fn retry<T, Fut, F>(
     max_retries: u16,
     batch_number: L1BatchNumber,
@@ -209,3 +250,166 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use zksync_da_client::types::DispatchResponse;
+    use zksync_node_test_utils::create_l1_batch;
+    use zksync_types::ProtocolVersion;
+
+    use super::*;
+
+    /// DA client storing blobs in memory.
+    #[derive(Debug, Clone, Default)]
+    struct MockDAClient {
+        blob_size_limit: Option<usize>,
+        blobs: Arc<Mutex<Vec<Vec<u8>>>>,
+    }
+
+    #[async_trait]
+    impl DataAvailabilityClient for MockDAClient {
+        async fn dispatch_blob(
+            &self,
+            _batch_number: u32,
+            data: Vec<u8>,
+        ) -> Result<DispatchResponse, DAError> {
+            if let Some(limit) = self.blob_size_limit {
+                assert!(data.len() <= limit, "blob is too large");
+            }
+            let mut blobs = self.blobs.lock().unwrap();
+            blobs.push(data);
+            Ok(DispatchResponse {
+                blob_id: (blobs.len() - 1).to_string(),
+            })
+        }
+
+        async fn get_inclusion_data(
+            &self,
+            blob_id: &str,
+        ) -> Result<Option<InclusionData>, DAError> {
+            Ok(Some(InclusionData {
+                data: format!("proof{blob_id}").into_bytes(),
+            }))
+        }
+
+        fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
+            Box::new(self.clone())
+        }
+
+        fn blob_size_limit(&self) -> Option<usize> {
+            self.blob_size_limit
+        }
+    }
+
+    async fn insert_l1_batch(pool: &ConnectionPool<Core>, number: u32, pubdata: Vec<u8>) {
+        let mut conn = pool.connection().await.unwrap();
+        let mut header = create_l1_batch(number);
+        header.pubdata_input = Some(pubdata);
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&header)
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn splitting_pubdata_into_chunks() {
+        let pubdata: Vec<u8> = (0..25).collect();
+        assert_eq!(split_into_chunks(&pubdata, None), [&pubdata[..]]);
+        assert_eq!(split_into_chunks(&pubdata, Some(25)), [&pubdata[..]]);
+        assert_eq!(
+            split_into_chunks(&pubdata, Some(10)),
+            [&pubdata[..10], &pubdata[10..20], &pubdata[20..]]
+        );
+        assert_eq!(split_into_chunks(&[], Some(10)), [&[] as &[u8]]);
+    }
+
+    #[tokio::test]
+    async fn dispatching_pubdata_in_chunks() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        pool.connection()
+            .await
+            .unwrap()
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        let pubdata: Vec<u8> = (0..25).collect();
+        insert_l1_batch(&pool, 1, pubdata.clone()).await;
+        insert_l1_batch(&pool, 2, vec![1; 5]).await;
+
+        let client = MockDAClient {
+            blob_size_limit: Some(10),
+            ..MockDAClient::default()
+        };
+        let dispatcher = DataAvailabilityDispatcher::new(
+            pool.clone(),
+            DADispatcherConfig::for_tests(),
+            Box::new(client.clone()),
+        );
+        dispatcher.dispatch().await.unwrap();
+
+        let blobs = client.blobs.lock().unwrap().clone();
+        assert_eq!(blobs.len(), 4);
+        assert_eq!(blobs[..3].concat(), pubdata);
+        assert_eq!(blobs[3], [1; 5]);
+
+        let mut conn = pool.connection().await.unwrap();
+        let blob_info = conn
+            .data_availability_dal()
+            .get_first_da_blob_awaiting_inclusion()
+            .await
+            .unwrap()
+            .expect("no blob awaiting inclusion");
+        assert_eq!(blob_info.l1_batch_number, L1BatchNumber(1));
+        assert_eq!(blob_info.blob_id, "0");
+        assert_eq!(blob_info.blob_ids(), ["0", "1", "2"]);
+
+        dispatcher.poll_for_inclusion().await.unwrap();
+        let blob_info = conn
+            .data_availability_dal()
+            .get_first_da_blob_awaiting_inclusion()
+            .await
+            .unwrap()
+            .expect("no blob awaiting inclusion");
+        assert_eq!(blob_info.l1_batch_number, L1BatchNumber(2));
+        assert!(blob_info.chunk_blob_ids.is_empty());
+        assert_eq!(blob_info.blob_ids(), ["3"]);
+
+        dispatcher.poll_for_inclusion().await.unwrap();
+        let blob_info = conn
+            .data_availability_dal()
+            .get_first_da_blob_awaiting_inclusion()
+            .await
+            .unwrap();
+        assert!(blob_info.is_none(), "{blob_info:?}");
+    }
+
+    #[test]
+    fn aggregating_inclusion_data() {
+        let single = aggregate_inclusion_data(vec![InclusionData {
+            data: b"proof".to_vec(),
+        }]);
+        assert_eq!(single.data, b"proof");
+
+        let aggregated = aggregate_inclusion_data(vec![
+            InclusionData {
+                data: b"proof0".to_vec(),
+            },
+            InclusionData {
+                data: b"proof1".to_vec(),
+            },
+        ]);
+        let param = ethabi::ParamType::Array(Box::new(ethabi::ParamType::Bytes));
+        let tokens = ethabi::decode(&[param], &aggregated.data).unwrap();
+        assert_eq!(
+            tokens,
+            [Token::Array(vec![
+                Token::Bytes(b"proof0".to_vec()),
+                Token::Bytes(b"proof1".to_vec()),
+            ])]
+        );
+    }
+}
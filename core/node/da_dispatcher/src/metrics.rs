@@ -19,6 +19,9 @@ pub(super) struct DataAvailabilityDispatcherMetrics {
     /// Buckets are bytes ranging from 1 KB to 16 MB, which has to satisfy all blob size values.
     #[metrics(buckets = Buckets::exponential(1_024.0..=16.0 * 1_024.0 * 1_024.0, 2.0), unit = Unit::Bytes)]
     pub blob_size: Histogram<usize>,
+    /// Number of chunks the L1 batch pubdata was split into to satisfy the blob size limit of the DA client.
+    #[metrics(buckets = Buckets::exponential(1.0..=64.0, 2.0))]
+    pub blob_chunk_count: Histogram<usize>,
 
     /// Number of transactions resent by the DA dispatcher.
     #[metrics(buckets = Buckets::linear(0.0..=10.0, 1.0))]
@@ -38,6 +38,8 @@ pub(crate) struct L1BlockNumbers {
 pub(crate) enum OperatorType {
     NonBlob,
     Blob,
+    /// Previous main operator, which is drained during operator key rotation.
+    Previous,
 }
 
 #[async_trait]
@@ -56,6 +58,8 @@ pub(super) trait AbstractL1Interface: 'static + Sync + Send + fmt::Debug {
 
     fn get_blobs_operator_account(&self) -> Option<Address>;
 
+    fn get_previous_operator_account(&self) -> Option<Address>;
+
     async fn get_operator_nonce(
         &self,
         block_numbers: L1BlockNumbers,
@@ -76,12 +80,15 @@ pub(super) trait AbstractL1Interface: 'static + Sync + Send + fmt::Debug {
     fn ethereum_gateway(&self) -> &dyn BoundEthInterface;
 
     fn ethereum_gateway_blobs(&self) -> Option<&dyn BoundEthInterface>;
+
+    fn ethereum_gateway_previous(&self) -> Option<&dyn BoundEthInterface>;
 }
 
 #[derive(Debug)]
 pub(super) struct RealL1Interface {
     pub ethereum_gateway: Box<dyn BoundEthInterface>,
     pub ethereum_gateway_blobs: Option<Box<dyn BoundEthInterface>>,
+    pub ethereum_gateway_previous: Option<Box<dyn BoundEthInterface>>,
     pub wait_confirmations: Option<u64>,
 }
 
@@ -123,6 +130,11 @@ impl AbstractL1Interface for RealL1Interface {
             .map(|s| s.sender_account())
     }
 
+    fn get_previous_operator_account(&self) -> Option<Address> {
+        self.ethereum_gateway_previous()
+            .map(|gateway| gateway.sender_account())
+    }
+
     async fn get_operator_nonce(
         &self,
         block_numbers: L1BlockNumbers,
@@ -131,6 +143,7 @@ impl AbstractL1Interface for RealL1Interface {
         let gateway = match operator_type {
             OperatorType::NonBlob => Some(self.ethereum_gateway()),
             OperatorType::Blob => self.ethereum_gateway_blobs(),
+            OperatorType::Previous => self.ethereum_gateway_previous(),
         };
         match gateway {
             None => Ok(None),
@@ -162,8 +175,14 @@ impl AbstractL1Interface for RealL1Interface {
         // Chose the signing gateway. Use a custom one in case
         // the operator is in 4844 mode and the operation at hand is Commit.
         // then the optional gateway is used to send this transaction from a
-        // custom sender account.
-        let signing_gateway = if let Some(blobs_gateway) = self.ethereum_gateway_blobs() {
+        // custom sender account. Transactions of the previous operator (i.e., ones sent
+        // before operator key rotation) are always signed with the previous operator key.
+        let previous_gateway = self
+            .ethereum_gateway_previous()
+            .filter(|gateway| tx.from_addr == Some(gateway.sender_account()));
+        let signing_gateway = if let Some(previous_gateway) = previous_gateway {
+            previous_gateway
+        } else if let Some(blobs_gateway) = self.ethereum_gateway_blobs() {
             if tx.tx_type == AggregatedActionType::Commit {
                 blobs_gateway
             } else {
@@ -247,4 +266,8 @@ impl AbstractL1Interface for RealL1Interface {
     fn ethereum_gateway_blobs(&self) -> Option<&dyn BoundEthInterface> {
         self.ethereum_gateway_blobs.as_deref()
     }
+
+    fn ethereum_gateway_previous(&self) -> Option<&dyn BoundEthInterface> {
+        self.ethereum_gateway_previous.as_deref()
+    }
 }
@@ -4,6 +4,7 @@ use zksync_config::configs::eth_sender::{ProofSendingMode, SenderConfig};
 use zksync_contracts::BaseSystemContractsHashes;
 use zksync_dal::{Connection, Core, CoreDal};
 use zksync_l1_contract_interface::i_executor::methods::{ExecuteBatches, ProveBatches};
+use zksync_node_fee_model::l1_gas_price::L1TxParamsProvider;
 use zksync_object_store::{ObjectStore, ObjectStoreError};
 use zksync_prover_interface::outputs::L1BatchProofForL1;
 use zksync_types::{
@@ -18,8 +19,8 @@ use zksync_types::{
 use super::{
     aggregated_operations::AggregatedOperation,
     publish_criterion::{
-        DataSizeCriterion, GasCriterion, L1BatchPublishCriterion, NumberCriterion,
-        TimestampDeadlineCriterion,
+        DataSizeCriterion, FeeForecastCriterion, GasCriterion, L1BatchPublishCriterion,
+        NumberCriterion, TimestampDeadlineCriterion,
     },
 };
 
@@ -112,6 +113,21 @@ impl Aggregator {
         }
     }
 
+    /// Enables postponing commit operations based on the L1 fee history from `fee_provider`
+    /// if it is configured in [`SenderConfig`].
+    pub fn with_fee_forecast(mut self, fee_provider: Arc<dyn L1TxParamsProvider>) -> Self {
+        let criterion = FeeForecastCriterion::new(
+            AggregatedActionType::Commit,
+            &self.config,
+            self.pubdata_da,
+            fee_provider,
+        );
+        if let Some(criterion) = criterion {
+            self.commit_criteria.push(Box::new(criterion));
+        }
+        self
+    }
+
     pub async fn get_next_ready_operation(
         &mut self,
         storage: &mut Connection<'_, Core>,
@@ -494,6 +510,17 @@ async fn extract_ready_subrange(
     unpublished_l1_batches: Vec<L1BatchWithMetadata>,
     last_sealed_l1_batch: L1BatchNumber,
 ) -> Option<Vec<L1BatchWithMetadata>> {
+    let mut should_postpone = false;
+    for criterion in publish_criteria.iter_mut() {
+        // All criteria are polled so that they can track postponement state.
+        should_postpone |= criterion
+            .should_postpone(storage, &unpublished_l1_batches)
+            .await;
+    }
+    if should_postpone {
+        return None;
+    }
+
     let mut last_l1_batch: Option<L1BatchNumber> = None;
     for criterion in publish_criteria {
         let l1_batch_by_criterion = criterion
@@ -27,7 +27,7 @@ use zksync_types::{
 use super::aggregated_operations::AggregatedOperation;
 use crate::{
     metrics::{PubdataKind, METRICS},
-    utils::agg_l1_batch_base_cost,
+    utils::{agg_l1_batch_base_cost, start_operator_rotation},
     zksync_functions::ZkSyncFunctions,
     Aggregator, EthSenderError,
 };
@@ -61,6 +61,9 @@ pub struct EthTxAggregator {
     /// transactions. The `Some` then contains the address of this custom operator
     /// address.
     custom_commit_sender_addr: Option<Address>,
+    /// Address of the previous main operator if the operator key is being rotated. Used to attribute
+    /// transactions of the previous operator to it before assigning nonces for the current operator.
+    previous_operator_addr: Option<Address>,
     pool: ConnectionPool<Core>,
 }
 
@@ -81,6 +84,7 @@ impl EthTxAggregator {
         state_transition_chain_contract: Address,
         rollup_chain_id: L2ChainId,
         custom_commit_sender_addr: Option<Address>,
+        previous_operator_addr: Option<Address>,
     ) -> Self {
         let eth_client = eth_client.for_component("eth_tx_aggregator");
         let functions = ZkSyncFunctions::default();
@@ -109,12 +113,25 @@ impl EthTxAggregator {
             base_nonce_custom_commit_sender,
             rollup_chain_id,
             custom_commit_sender_addr,
+            previous_operator_addr,
             pool,
         }
     }
 
     pub async fn run(mut self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         let pool = self.pool.clone();
+        if let Some(previous_operator_addr) = self.previous_operator_addr {
+            // Must be performed before any nonces are assigned; otherwise, a new transaction could get
+            // a nonce from the previous operator sequence.
+            let mut storage = pool.connection_tagged("eth_sender").await.unwrap();
+            start_operator_rotation(
+                &mut storage,
+                previous_operator_addr,
+                self.eth_client.sender_account(),
+            )
+            .await?;
+        }
+
         loop {
             let mut storage = pool.connection_tagged("eth_sender").await.unwrap();
 
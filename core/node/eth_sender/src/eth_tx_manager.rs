@@ -1,15 +1,20 @@
 use std::{sync::Arc, time::Duration};
 
 use anyhow::Context as _;
+use serde::Serialize;
 use tokio::sync::watch;
 use zksync_config::configs::eth_sender::SenderConfig;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_eth_client::{
     encode_blob_tx_with_sidecar, BoundEthInterface, ExecutedTxStatus, RawTransactionBytes,
 };
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_node_fee_model::l1_gas_price::L1TxParamsProvider;
 use zksync_shared_metrics::BlockL1Stage;
-use zksync_types::{eth_sender::EthTx, Address, L1BlockNumber, H256, U256};
+use zksync_types::{
+    eth_sender::{EthTx, OperatorRotation},
+    Address, L1BlockNumber, H256, U256,
+};
 use zksync_utils::time::seconds_since_epoch;
 
 use super::{metrics::METRICS, EthSenderError};
@@ -19,19 +24,43 @@ use crate::{
     },
     eth_fees_oracle::{EthFees, EthFeesOracle, GasAdjusterFeesOracle},
     metrics::TransactionType,
+    utils::start_operator_rotation,
 };
 
+#[derive(Debug, Serialize)]
+struct EthTxManagerHealthDetails {
+    operator: Address,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    blob_operator: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    operator_rotation: Option<OperatorRotationHealthDetails>,
+}
+
+#[derive(Debug, Serialize)]
+struct OperatorRotationHealthDetails {
+    previous_operator: Address,
+    started_at_timestamp: u64,
+    unconfirmed_txs: usize,
+}
+
 /// The component is responsible for managing sending eth_txs attempts:
 /// Based on eth_tx queue the component generates new attempt with the minimum possible fee,
 /// save it to the database, and send it to Ethereum.
 /// Based on eth_tx_history queue the component can mark txs as stuck and create the new attempt
 /// with higher gas price
+///
+/// If the previous main operator is provided, the component performs operator key rotation: transactions
+/// of the previous operator are monitored (and resent if necessary) until all of them are confirmed,
+/// while new transactions are sent by the current operator with its own nonce sequence.
 #[derive(Debug)]
 pub struct EthTxManager {
     l1_interface: Box<dyn AbstractL1Interface>,
     config: SenderConfig,
     fees_oracle: Box<dyn EthFeesOracle>,
     pool: ConnectionPool<Core>,
+    /// Operator key rotation in progress, if any.
+    operator_rotation: Option<OperatorRotation>,
+    health_updater: HealthUpdater,
 }
 
 impl EthTxManager {
@@ -41,10 +70,13 @@ impl EthTxManager {
         gas_adjuster: Arc<dyn L1TxParamsProvider>,
         ethereum_gateway: Box<dyn BoundEthInterface>,
         ethereum_gateway_blobs: Option<Box<dyn BoundEthInterface>>,
+        ethereum_gateway_previous: Option<Box<dyn BoundEthInterface>>,
     ) -> Self {
         let ethereum_gateway = ethereum_gateway.for_component("eth_tx_manager");
         let ethereum_gateway_blobs =
             ethereum_gateway_blobs.map(|eth| eth.for_component("eth_tx_manager"));
+        let ethereum_gateway_previous =
+            ethereum_gateway_previous.map(|eth| eth.for_component("eth_tx_manager"));
         let fees_oracle = GasAdjusterFeesOracle {
             gas_adjuster,
             max_acceptable_priority_fee_in_gwei: config.max_acceptable_priority_fee_in_gwei,
@@ -53,14 +85,22 @@ impl EthTxManager {
             l1_interface: Box::new(RealL1Interface {
                 ethereum_gateway,
                 ethereum_gateway_blobs,
+                ethereum_gateway_previous,
                 wait_confirmations: config.wait_confirmations,
             }),
             config,
             fees_oracle: Box::new(fees_oracle),
             pool,
+            operator_rotation: None,
+            health_updater: ReactiveHealthCheck::new("eth_tx_manager").1,
         }
     }
 
+    /// Returns a health check for this manager.
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
     #[cfg(test)]
     pub(crate) fn l1_interface(&self) -> &dyn AbstractL1Interface {
         self.l1_interface.as_ref()
@@ -243,11 +283,83 @@ impl EthTxManager {
     }
 
     pub(crate) fn operator_address(&self, operator_type: OperatorType) -> Option<Address> {
-        if operator_type == OperatorType::NonBlob {
-            None
+        match operator_type {
+            OperatorType::NonBlob => None,
+            OperatorType::Blob => self.l1_interface.get_blobs_operator_account(),
+            OperatorType::Previous => self.l1_interface.get_previous_operator_account(),
+        }
+    }
+
+    /// Starts operator key rotation if the previous operator is provided, or resumes it
+    /// if it was started before.
+    pub(crate) async fn init_operator_rotation(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<()> {
+        let Some(previous_operator) = self.l1_interface.get_previous_operator_account() else {
+            return Ok(());
+        };
+        let operator = self.l1_interface.ethereum_gateway().sender_account();
+        let rotation = start_operator_rotation(storage, previous_operator, operator).await?;
+        if rotation.completed_at_timestamp.is_none() {
+            self.operator_rotation = Some(rotation);
         } else {
-            self.l1_interface.get_blobs_operator_account()
+            tracing::info!(
+                "Rotation of operator key {previous_operator:?} is already completed; \
+                 the previous operator key can be removed from the configuration"
+            );
         }
+        Ok(())
+    }
+
+    /// Completes operator key rotation once all transactions of the previous operator are confirmed,
+    /// and updates the health of the manager.
+    pub(crate) async fn update_operator_rotation(&mut self, storage: &mut Connection<'_, Core>) {
+        let Some(rotation) = &self.operator_rotation else {
+            self.update_health(None);
+            return;
+        };
+        let previous_operator = rotation.previous_operator;
+        let unconfirmed_txs = storage
+            .eth_sender_dal()
+            .get_unconfirmed_txs_count(previous_operator)
+            .await
+            .unwrap();
+
+        if unconfirmed_txs == 0 {
+            storage
+                .eth_sender_dal()
+                .complete_operator_rotation(previous_operator)
+                .await
+                .unwrap();
+            tracing::info!(
+                "Completed rotation of operator key from {previous_operator:?} to {:?}",
+                rotation.new_operator
+            );
+            self.operator_rotation = None;
+            self.update_health(None);
+        } else {
+            self.update_health(Some(OperatorRotationHealthDetails {
+                previous_operator,
+                started_at_timestamp: rotation.started_at_timestamp,
+                unconfirmed_txs,
+            }));
+        }
+    }
+
+    fn update_health(&self, operator_rotation: Option<OperatorRotationHealthDetails>) {
+        let status = if operator_rotation.is_some() {
+            HealthStatus::Affected
+        } else {
+            HealthStatus::Ready
+        };
+        let details = EthTxManagerHealthDetails {
+            operator: self.l1_interface.ethereum_gateway().sender_account(),
+            blob_operator: self.l1_interface.get_blobs_operator_account(),
+            operator_rotation,
+        };
+        self.health_updater
+            .update(Health::from(status).with_details(details));
     }
     // Monitors the in-flight transactions, marks mined ones as confirmed,
     // returns the one that has to be resent (if there is one).
@@ -533,6 +645,8 @@ impl EthTxManager {
                 .await
                 .context("get_l1_block_numbers()")?;
             let mut storage = pool.connection_tagged("eth_sender").await.unwrap();
+            self.init_operator_rotation(&mut storage).await?;
+            self.update_operator_rotation(&mut storage).await;
             self.send_unsent_txs(&mut storage, l1_block_numbers).await;
         }
 
@@ -631,16 +745,20 @@ impl EthTxManager {
     }
 
     #[tracing::instrument(skip(self, storage))]
-    async fn loop_iteration(
+    pub(crate) async fn loop_iteration(
         &mut self,
         storage: &mut Connection<'_, Core>,
         l1_block_numbers: L1BlockNumbers,
     ) {
         tracing::trace!("Loop iteration at block {}", l1_block_numbers.latest);
-        // We can treat those two operators independently as they have different nonces and
+        // We can treat those operators independently as they have different nonces and
         // aggregator makes sure that corresponding Commit transaction is confirmed before creating
         // a PublishProof transaction
-        for operator_type in [OperatorType::NonBlob, OperatorType::Blob] {
+        let mut operator_types = vec![OperatorType::NonBlob, OperatorType::Blob];
+        if self.operator_rotation.is_some() {
+            operator_types.push(OperatorType::Previous);
+        }
+        for operator_type in operator_types {
             self.send_new_eth_txs(storage, l1_block_numbers.latest, operator_type)
                 .await;
             let result = self
@@ -657,5 +775,6 @@ impl EthTxManager {
                 }
             }
         }
+        self.update_operator_rotation(storage).await;
     }
 }
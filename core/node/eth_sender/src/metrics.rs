@@ -107,6 +107,13 @@ pub(super) struct EthSenderMetrics {
     pub l1_blocks_waited_in_mempool: Family<ActionTypeLabel, Histogram<u64>>,
     /// Number of L1 batches aggregated for publishing with a specific reason.
     pub block_aggregation_reason: Family<AggregationReasonLabels, Counter>,
+    /// Number of times publishing L1 batches was postponed based on L1 fees, with a specific reason.
+    pub fee_postponements: Family<AggregationReasonLabels, Counter>,
+    /// Duration of postponing publishing L1 batches based on L1 fees.
+    #[metrics(buckets = Buckets::exponential(1.0..=4_096.0, 2.0))]
+    pub fee_postponement_duration: Family<AggregationReasonLabels, Histogram<Duration>>,
+    /// Estimated savings on L1 fees (in gwei) from postponing publishing L1 batches, with a specific reason.
+    pub estimated_fee_savings_gwei: Family<AggregationReasonLabels, Counter>,
     pub l1_transient_errors: Counter,
 }
 
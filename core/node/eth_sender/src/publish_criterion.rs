@@ -1,18 +1,23 @@
-use std::fmt;
+use std::{fmt, sync::Arc, time::Instant};
 
 use async_trait::async_trait;
 use chrono::Utc;
+use zksync_config::configs::eth_sender::SenderConfig;
 use zksync_dal::{Connection, Core, CoreDal};
 use zksync_l1_contract_interface::{i_executor::structures::CommitBatchInfo, Tokenizable};
+use zksync_node_fee_model::l1_gas_price::{L1FeeHistoryStats, L1TxParamsProvider};
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
     commitment::{L1BatchCommitmentMode, L1BatchWithMetadata},
     ethabi,
     pubdata_da::PubdataDA,
-    L1BatchNumber,
+    L1BatchNumber, U256,
 };
 
-use super::{metrics::METRICS, utils::agg_l1_batch_base_cost};
+use super::{
+    metrics::{AggregationReasonLabels, METRICS},
+    utils::agg_l1_batch_base_cost,
+};
 
 #[async_trait]
 pub trait L1BatchPublishCriterion: fmt::Debug + Send + Sync {
@@ -28,6 +33,15 @@ pub trait L1BatchPublishCriterion: fmt::Debug + Send + Sync {
         consecutive_l1_batches: &[L1BatchWithMetadata],
         last_sealed_l1_batch: L1BatchNumber,
     ) -> Option<L1BatchNumber>;
+
+    /// Returns `true` if publishing L1 batches should be postponed even if other criteria are triggered.
+    async fn should_postpone(
+        &mut self,
+        _storage: &mut Connection<'_, Core>,
+        _consecutive_l1_batches: &[L1BatchWithMetadata],
+    ) -> bool {
+        false
+    }
 }
 
 #[derive(Debug)]
@@ -252,3 +266,248 @@ impl L1BatchPublishCriterion for DataSizeCriterion {
         None
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FeePostponementReason {
+    /// L1 fees spike; publishing is postponed until they return to normal.
+    FeeSpike,
+    /// Blob space is expensive; publishing is postponed until more L1 batches can be packed together.
+    ExpensiveBlobSpace,
+}
+
+impl FeePostponementReason {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::FeeSpike => "fee_spike",
+            Self::ExpensiveBlobSpace => "expensive_blob_space",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct FeePostponement {
+    reason: FeePostponementReason,
+    started_at: Instant,
+    fee_stats: L1FeeHistoryStats,
+    l1_batch_count: usize,
+}
+
+fn saturating_u64(value: U256) -> u64 {
+    u64::try_from(value).unwrap_or(u64::MAX)
+}
+
+fn is_spiking(latest_fee: u64, median_fee: u64, spike_ratio: f64) -> bool {
+    median_fee > 0 && latest_fee as f64 > median_fee as f64 * spike_ratio
+}
+
+/// Criterion postponing publishing L1 batches based on the L1 fee history tracked by `GasAdjuster`:
+///
+/// - While L1 fees spike, publishing is postponed until fees return to their median values.
+/// - Unless blob space is cheap, publishing is postponed until `limit` L1 batches can be packed together,
+///   so that the fixed cost of an L1 transaction is amortized over more L1 batches. While blob space is cheap,
+///   L1 batches are published without delay.
+///
+/// In both cases, publishing is no longer postponed once the oldest L1 batch is `max_delay_seconds` old.
+/// This criterion never triggers publishing by itself.
+#[derive(Debug)]
+pub struct FeeForecastCriterion {
+    op: AggregatedActionType,
+    fee_provider: Arc<dyn L1TxParamsProvider>,
+    pubdata_da: PubdataDA,
+    fee_spike_ratio: Option<f64>,
+    cheap_blob_fee_ratio: Option<f64>,
+    /// Maximum number of L1 batches to be packed together.
+    limit: u32,
+    max_delay_seconds: u64,
+    postponement: Option<FeePostponement>,
+}
+
+impl FeeForecastCriterion {
+    /// Creates a criterion for the specified operation. Returns `None` if fee-based postponement is not configured.
+    pub fn new(
+        op: AggregatedActionType,
+        config: &SenderConfig,
+        pubdata_da: PubdataDA,
+        fee_provider: Arc<dyn L1TxParamsProvider>,
+    ) -> Option<Self> {
+        if config.fee_spike_ratio.is_none() && config.cheap_blob_fee_ratio.is_none() {
+            return None;
+        }
+        Some(Self {
+            op,
+            fee_provider,
+            pubdata_da,
+            fee_spike_ratio: config.fee_spike_ratio,
+            cheap_blob_fee_ratio: config.cheap_blob_fee_ratio,
+            limit: config.max_aggregated_blocks_to_commit,
+            max_delay_seconds: config.max_fee_postponement_seconds(),
+            postponement: None,
+        })
+    }
+
+    fn postponement_reason(
+        &self,
+        fee_stats: &L1FeeHistoryStats,
+        l1_batch_count: usize,
+    ) -> Option<FeePostponementReason> {
+        let uses_blobs = self.pubdata_da == PubdataDA::Blobs;
+        let latest_blob_base_fee = saturating_u64(fee_stats.latest_blob_base_fee);
+        let median_blob_base_fee = saturating_u64(fee_stats.median_blob_base_fee);
+
+        if let Some(spike_ratio) = self.fee_spike_ratio {
+            let base_fee_spikes = is_spiking(
+                fee_stats.latest_base_fee,
+                fee_stats.median_base_fee,
+                spike_ratio,
+            );
+            let blob_base_fee_spikes =
+                uses_blobs && is_spiking(latest_blob_base_fee, median_blob_base_fee, spike_ratio);
+            if base_fee_spikes || blob_base_fee_spikes {
+                return Some(FeePostponementReason::FeeSpike);
+            }
+        }
+        if let Some(cheap_ratio) = self.cheap_blob_fee_ratio {
+            let is_cheap = latest_blob_base_fee as f64 <= median_blob_base_fee as f64 * cheap_ratio;
+            if uses_blobs && !is_cheap && l1_batch_count < self.limit as usize {
+                return Some(FeePostponementReason::ExpensiveBlobSpace);
+            }
+        }
+        None
+    }
+
+    /// Estimates savings on L1 fees (in wei) from the finished `postponement`.
+    async fn estimate_savings(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        postponement: &FeePostponement,
+        fee_stats: &L1FeeHistoryStats,
+        consecutive_l1_batches: &[L1BatchWithMetadata],
+    ) -> U256 {
+        match postponement.reason {
+            FeePostponementReason::FeeSpike => {
+                // Compare fees for L1 batches that were ready to be published when the postponement has started.
+                let l1_batch_count = postponement
+                    .l1_batch_count
+                    .min(consecutive_l1_batches.len());
+                let l1_batches = &consecutive_l1_batches[..l1_batch_count];
+                let (Some(first), Some(last)) = (l1_batches.first(), l1_batches.last()) else {
+                    return U256::zero();
+                };
+                let gas = storage
+                    .blocks_dal()
+                    .get_l1_batches_predicted_gas(first.header.number..=last.header.number, self.op)
+                    .await
+                    .unwrap();
+                let base_fee_delta = postponement
+                    .fee_stats
+                    .latest_base_fee
+                    .saturating_sub(fee_stats.latest_base_fee);
+                let mut savings = U256::from(gas) * base_fee_delta;
+
+                if self.pubdata_da == PubdataDA::Blobs {
+                    // Blob gas roughly equals the pubdata size in bytes.
+                    let blob_gas: usize = l1_batches
+                        .iter()
+                        .map(|batch| batch.header.pubdata_input.as_ref().map_or(0, Vec::len))
+                        .sum();
+                    let blob_base_fee_delta = postponement
+                        .fee_stats
+                        .latest_blob_base_fee
+                        .saturating_sub(fee_stats.latest_blob_base_fee);
+                    savings += U256::from(blob_gas) * blob_base_fee_delta;
+                }
+                savings
+            }
+            FeePostponementReason::ExpensiveBlobSpace => {
+                // Each additionally packed L1 batch saves the fixed cost of an L1 transaction.
+                let packed_l1_batches = consecutive_l1_batches
+                    .len()
+                    .saturating_sub(postponement.l1_batch_count);
+                U256::from(packed_l1_batches)
+                    * agg_l1_batch_base_cost(self.op)
+                    * fee_stats.latest_base_fee
+            }
+        }
+    }
+
+    async fn finish_postponement(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        fee_stats: &L1FeeHistoryStats,
+        consecutive_l1_batches: &[L1BatchWithMetadata],
+    ) {
+        let Some(postponement) = self.postponement.take() else {
+            return;
+        };
+        let savings = self
+            .estimate_savings(storage, &postponement, fee_stats, consecutive_l1_batches)
+            .await;
+        let elapsed = postponement.started_at.elapsed();
+        tracing::info!(
+            "Finished postponing op {} for {elapsed:?} due to {}; estimated savings: {savings} wei",
+            self.op,
+            postponement.reason.as_str()
+        );
+
+        let labels: AggregationReasonLabels = (self.op, postponement.reason.as_str()).into();
+        METRICS.fee_postponement_duration[&labels].observe(elapsed);
+        let savings_gwei = saturating_u64(savings / U256::exp10(9));
+        METRICS.estimated_fee_savings_gwei[&labels].inc_by(savings_gwei);
+    }
+}
+
+#[async_trait]
+impl L1BatchPublishCriterion for FeeForecastCriterion {
+    fn name(&self) -> &'static str {
+        "fee_forecast"
+    }
+
+    async fn last_l1_batch_to_publish(
+        &mut self,
+        _storage: &mut Connection<'_, Core>,
+        _consecutive_l1_batches: &[L1BatchWithMetadata],
+        _last_sealed_l1_batch: L1BatchNumber,
+    ) -> Option<L1BatchNumber> {
+        None
+    }
+
+    async fn should_postpone(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        consecutive_l1_batches: &[L1BatchWithMetadata],
+    ) -> bool {
+        let Some(first_l1_batch) = consecutive_l1_batches.first() else {
+            return false;
+        };
+        let fee_stats = self.fee_provider.get_fee_history_stats();
+        let oldest_l1_batch_age_seconds =
+            (Utc::now().timestamp() as u64).saturating_sub(first_l1_batch.header.timestamp);
+        let reason = if oldest_l1_batch_age_seconds >= self.max_delay_seconds {
+            None
+        } else {
+            self.postponement_reason(&fee_stats, consecutive_l1_batches.len())
+        };
+
+        let Some(reason) = reason else {
+            self.finish_postponement(storage, &fee_stats, consecutive_l1_batches)
+                .await;
+            return false;
+        };
+        if self.postponement.is_none() {
+            tracing::info!(
+                "Postponing op {} for L1 batches starting from #{} due to {}; L1 fee stats: {fee_stats:?}",
+                self.op,
+                first_l1_batch.header.number,
+                reason.as_str()
+            );
+            METRICS.fee_postponements[&(self.op, reason.as_str()).into()].inc();
+            self.postponement = Some(FeePostponement {
+                reason,
+                started_at: Instant::now(),
+                fee_stats,
+                l1_batch_count: consecutive_l1_batches.len(),
+            });
+        }
+        true
+    }
+}
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use assert_matches::assert_matches;
 use once_cell::sync::Lazy;
@@ -9,19 +9,22 @@ use zksync_config::{
 };
 use zksync_contracts::BaseSystemContractsHashes;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
-use zksync_eth_client::{clients::MockEthereum, BaseFees};
+use zksync_eth_client::{clients::MockEthereum, BaseFees, BoundEthInterface};
+use zksync_health_check::{CheckHealth, HealthStatus};
 use zksync_l1_contract_interface::i_executor::methods::{ExecuteBatches, ProveBatches};
-use zksync_node_fee_model::l1_gas_price::GasAdjuster;
+use zksync_node_fee_model::l1_gas_price::{GasAdjuster, L1FeeHistoryStats, L1TxParamsProvider};
 use zksync_node_test_utils::{create_l1_batch, l1_batch_metadata_to_commitment_artifacts};
 use zksync_object_store::MockObjectStore;
 use zksync_types::{
-    block::L1BatchHeader,
+    aggregated_operations::AggregatedActionType,
+    block::{BlockGasCount, L1BatchHeader},
     commitment::{
         L1BatchCommitmentMode, L1BatchMetaParameters, L1BatchMetadata, L1BatchWithMetadata,
     },
     ethabi::Token,
     helpers::unix_timestamp_ms,
     l2_to_l1_log::{L2ToL1Log, UserL2ToL1Log},
+    protocol_version::L1VerifierConfig,
     pubdata_da::PubdataDA,
     web3::contract::Error,
     Address, L1BatchNumber, ProtocolVersion, ProtocolVersionId, H256,
@@ -30,6 +33,8 @@ use zksync_types::{
 use crate::{
     abstract_l1_interface::{L1BlockNumbers, OperatorType},
     aggregated_operations::AggregatedOperation,
+    metrics::METRICS,
+    publish_criterion::{FeeForecastCriterion, L1BatchPublishCriterion},
     Aggregator, EthSenderError, EthTxAggregator, EthTxManager,
 };
 
@@ -196,6 +201,7 @@ impl EthSenderTester {
             Address::random(),
             Default::default(),
             None,
+            None,
         )
         .await;
 
@@ -205,6 +211,7 @@ impl EthSenderTester {
             gas_adjuster.clone(),
             gateway.clone(),
             None,
+            None,
         );
         Self {
             gateway,
@@ -215,6 +222,46 @@ impl EthSenderTester {
         }
     }
 
+    /// Emulates restarting `eth_sender` with a new operator key; the current operator key becomes the previous one.
+    async fn rotate_operator_key(&mut self, new_operator: Address) {
+        let previous_gateway = self.gateway.clone();
+        self.gateway = Box::new(previous_gateway.with_sender_account(new_operator));
+
+        let eth_sender = EthConfig::for_tests().sender.unwrap();
+        self.aggregator = EthTxAggregator::new(
+            self.conn.clone(),
+            SenderConfig {
+                proof_sending_mode: ProofSendingMode::SkipEveryProof,
+                pubdata_sending_mode: PubdataSendingMode::Calldata,
+                ..eth_sender.clone()
+            },
+            // Aggregator - unused
+            Aggregator::new(
+                eth_sender.clone(),
+                MockObjectStore::arc(),
+                false,
+                L1BatchCommitmentMode::Rollup,
+            ),
+            self.gateway.clone(),
+            // ZKsync contract address
+            Address::random(),
+            ContractsConfig::for_tests().l1_multicall3_addr,
+            Address::random(),
+            Default::default(),
+            None,
+            Some(previous_gateway.sender_account()),
+        )
+        .await;
+        self.manager = EthTxManager::new(
+            self.conn.clone(),
+            eth_sender,
+            self.gas_adjuster.clone(),
+            self.gateway.clone(),
+            None,
+            Some(previous_gateway),
+        );
+    }
+
     async fn storage(&self) -> Connection<'_, Core> {
         self.conn.connection().await.unwrap()
     }
@@ -1142,6 +1189,377 @@ async fn get_multicall_data(commitment_mode: L1BatchCommitmentMode) {
     assert!(multicall_data.is_ok());
 }
 
+// Tests that transactions of the previous operator are confirmed after operator key rotation,
+// while the new operator starts its own nonce sequence.
+#[tokio::test]
+async fn rotating_operator_key() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let mut tester = EthSenderTester::new(
+        connection_pool.clone(),
+        vec![100; 100],
+        false,
+        false,
+        L1BatchCommitmentMode::Rollup,
+    )
+    .await;
+    insert_genesis_protocol_version(&tester).await;
+    let mut l1_batches = vec![];
+    for number in 1..=3 {
+        l1_batches.push(insert_l1_batch(&tester, L1BatchNumber(number)).await);
+    }
+
+    let previous_operator = tester.gateway.sender_account();
+    let previous_hashes = [
+        execute_l1_batches(&mut tester, vec![l1_batches[0].clone()], false).await,
+        execute_l1_batches(&mut tester, vec![l1_batches[1].clone()], false).await,
+    ];
+
+    let new_operator = Address::repeat_byte(0x33);
+    tester.rotate_operator_key(new_operator).await;
+    tester
+        .manager
+        .init_operator_rotation(&mut connection_pool.connection().await.unwrap())
+        .await
+        .unwrap();
+    tester
+        .manager
+        .update_operator_rotation(&mut connection_pool.connection().await.unwrap())
+        .await;
+
+    let health = tester.manager.health_check().check_health().await;
+    assert_matches!(health.status(), HealthStatus::Affected);
+    let rotation_details = &health.details().unwrap()["operator_rotation"];
+    assert_eq!(rotation_details["unconfirmed_txs"], 2);
+
+    // Transactions of the main operator should be attributed to the previous operator.
+    let previous_txs = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .get_inflight_txs(Some(previous_operator))
+        .await
+        .unwrap();
+    assert_eq!(previous_txs.len(), 2);
+    let txs = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .get_inflight_txs(tester.manager.operator_address(OperatorType::NonBlob))
+        .await
+        .unwrap();
+    assert!(txs.is_empty(), "{txs:?}");
+
+    // The new operator should start its own nonce sequence.
+    let new_hash = execute_l1_batches(&mut tester, vec![l1_batches[2].clone()], false).await;
+    let new_tx = tester
+        .manager
+        .l1_interface()
+        .get_tx(new_hash)
+        .await
+        .unwrap()
+        .expect("no transaction");
+    assert_eq!(new_tx.nonce, 0.into());
+    assert_eq!(new_tx.from, Some(new_operator));
+
+    for hash in previous_hashes {
+        tester
+            .gateway
+            .execute_tx(hash, true, EthSenderTester::WAIT_CONFIRMATIONS);
+    }
+    let block_numbers = tester.get_block_numbers().await;
+    tester
+        .manager
+        .loop_iteration(
+            &mut connection_pool.connection().await.unwrap(),
+            block_numbers,
+        )
+        .await;
+
+    let previous_txs = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .get_inflight_txs(Some(previous_operator))
+        .await
+        .unwrap();
+    assert!(previous_txs.is_empty(), "{previous_txs:?}");
+    let rotation = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .get_operator_rotation(previous_operator)
+        .await
+        .unwrap()
+        .expect("no rotation");
+    assert_eq!(rotation.new_operator, new_operator);
+    assert!(rotation.completed_at_timestamp.is_some());
+
+    let health = tester.manager.health_check().check_health().await;
+    assert_matches!(health.status(), HealthStatus::Ready);
+}
+
+async fn create_gas_adjuster(fee_history: Vec<BaseFees>) -> Arc<GasAdjuster> {
+    let samples = fee_history.len();
+    let gateway = MockEthereum::builder()
+        .with_fee_history(fee_history)
+        .build();
+    gateway.advance_block_number(samples as u64);
+    let gas_adjuster = GasAdjuster::new(
+        Box::new(gateway.into_client()),
+        GasAdjusterConfig {
+            max_base_fee_samples: samples,
+            num_samples_for_blob_base_fee_estimate: samples,
+            ..EthConfig::for_tests().gas_adjuster.unwrap()
+        },
+        PubdataSendingMode::Blobs,
+        L1BatchCommitmentMode::Rollup,
+    )
+    .await
+    .unwrap();
+    Arc::new(gas_adjuster)
+}
+
+fn recent_l1_batch(number: u32, age_seconds: u64) -> L1BatchWithMetadata {
+    let mut header = mock_l1_batch_header(number);
+    header.timestamp = unix_timestamp_ms() / 1_000 - age_seconds;
+    l1_batch_with_metadata(header)
+}
+
+#[tokio::test]
+async fn postponing_commit_on_fee_spike() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = connection_pool.connection().await.unwrap();
+    let fee_history = [10, 10, 10, 10, 100].map(|base_fee_per_gas| BaseFees {
+        base_fee_per_gas,
+        base_fee_per_blob_gas: 1.into(),
+    });
+    let gas_adjuster = create_gas_adjuster(fee_history.to_vec()).await;
+    let config = SenderConfig {
+        fee_spike_ratio: Some(2.0),
+        max_fee_postponement_seconds: Some(600),
+        ..EthConfig::for_tests().sender.unwrap()
+    };
+    let mut criterion = FeeForecastCriterion::new(
+        AggregatedActionType::Commit,
+        &config,
+        PubdataDA::Calldata,
+        gas_adjuster,
+    )
+    .unwrap();
+
+    let l1_batches = [recent_l1_batch(1, 10), recent_l1_batch(2, 0)];
+    assert!(criterion.should_postpone(&mut storage, &l1_batches).await);
+    assert_eq!(
+        criterion
+            .last_l1_batch_to_publish(&mut storage, &l1_batches, L1BatchNumber(2))
+            .await,
+        None
+    );
+
+    // The oldest L1 batch has reached the max postponement age.
+    let l1_batches = [recent_l1_batch(1, 600), recent_l1_batch(2, 0)];
+    assert!(!criterion.should_postpone(&mut storage, &l1_batches).await);
+}
+
+#[tokio::test]
+async fn postponing_commit_until_l1_batches_are_packed() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = connection_pool.connection().await.unwrap();
+    let config = SenderConfig {
+        fee_spike_ratio: Some(2.0),
+        cheap_blob_fee_ratio: Some(0.8),
+        max_aggregated_blocks_to_commit: 3,
+        ..EthConfig::for_tests().sender.unwrap()
+    };
+    let fee_history = [10_u32, 10, 10, 10, 15].map(|base_fee_per_blob_gas| BaseFees {
+        base_fee_per_gas: 10,
+        base_fee_per_blob_gas: base_fee_per_blob_gas.into(),
+    });
+    let gas_adjuster = create_gas_adjuster(fee_history.to_vec()).await;
+    let mut criterion = FeeForecastCriterion::new(
+        AggregatedActionType::Commit,
+        &config,
+        PubdataDA::Blobs,
+        gas_adjuster.clone(),
+    )
+    .unwrap();
+
+    let mut l1_batches = vec![recent_l1_batch(1, 10), recent_l1_batch(2, 0)];
+    assert!(criterion.should_postpone(&mut storage, &l1_batches).await);
+    l1_batches.push(recent_l1_batch(3, 0));
+    assert!(!criterion.should_postpone(&mut storage, &l1_batches).await);
+
+    // Expensive blob space shouldn't affect sending pubdata in calldata.
+    let mut criterion = FeeForecastCriterion::new(
+        AggregatedActionType::Commit,
+        &config,
+        PubdataDA::Calldata,
+        gas_adjuster,
+    )
+    .unwrap();
+    assert!(
+        !criterion
+            .should_postpone(&mut storage, &l1_batches[..1])
+            .await
+    );
+
+    // L1 batches shouldn't be postponed while blob space is cheap.
+    let fee_history = [10_u32, 10, 10, 10, 5].map(|base_fee_per_blob_gas| BaseFees {
+        base_fee_per_gas: 10,
+        base_fee_per_blob_gas: base_fee_per_blob_gas.into(),
+    });
+    let gas_adjuster = create_gas_adjuster(fee_history.to_vec()).await;
+    let mut criterion = FeeForecastCriterion::new(
+        AggregatedActionType::Commit,
+        &config,
+        PubdataDA::Blobs,
+        gas_adjuster,
+    )
+    .unwrap();
+    assert!(
+        !criterion
+            .should_postpone(&mut storage, &l1_batches[..1])
+            .await
+    );
+}
+
+/// L1 fee provider with manually controlled fee history stats.
+#[derive(Debug)]
+struct MockFeeProvider(Mutex<L1FeeHistoryStats>);
+
+impl MockFeeProvider {
+    fn new(base_fee: u64) -> Self {
+        Self(Mutex::new(L1FeeHistoryStats {
+            median_base_fee: base_fee,
+            latest_base_fee: base_fee,
+            median_blob_base_fee: 1.into(),
+            latest_blob_base_fee: 1.into(),
+        }))
+    }
+
+    fn set_latest_base_fee(&self, base_fee: u64) {
+        self.0.lock().unwrap().latest_base_fee = base_fee;
+    }
+}
+
+impl L1TxParamsProvider for MockFeeProvider {
+    fn get_base_fee(&self, _time_in_mempool: u32) -> u64 {
+        self.0.lock().unwrap().latest_base_fee
+    }
+
+    fn get_blob_base_fee(&self) -> u64 {
+        1
+    }
+
+    fn get_priority_fee(&self) -> u64 {
+        1
+    }
+
+    fn get_next_block_minimal_base_fee(&self) -> u64 {
+        self.0.lock().unwrap().latest_base_fee
+    }
+
+    fn get_blob_tx_base_fee(&self) -> u64 {
+        self.0.lock().unwrap().latest_base_fee
+    }
+
+    fn get_blob_tx_blob_base_fee(&self) -> u64 {
+        1
+    }
+
+    fn get_blob_tx_priority_fee(&self) -> u64 {
+        1
+    }
+
+    fn get_fee_history_stats(&self) -> L1FeeHistoryStats {
+        *self.0.lock().unwrap()
+    }
+}
+
+async fn get_next_commit_operation(
+    aggregator: &mut Aggregator,
+    storage: &mut Connection<'_, Core>,
+) -> Option<Vec<L1BatchNumber>> {
+    let operation = aggregator
+        .get_next_ready_operation(
+            storage,
+            BaseSystemContractsHashes::default(),
+            ProtocolVersionId::latest(),
+            L1VerifierConfig::default(),
+        )
+        .await?;
+    match operation {
+        AggregatedOperation::Commit(_, l1_batches, _) => {
+            Some(l1_batches.iter().map(|batch| batch.header.number).collect())
+        }
+        other => panic!("unexpected operation: {other:?}"),
+    }
+}
+
+// Tests that fee spike postponement overrides the timestamp deadline and that savings are estimated
+// once fees return to normal.
+#[tokio::test]
+async fn aggregating_commit_after_fee_spike() {
+    const PREDICTED_COMMIT_GAS: u32 = 100_000;
+
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = connection_pool.connection().await.unwrap();
+    storage
+        .protocol_versions_dal()
+        .save_protocol_version_with_tx(&ProtocolVersion::default())
+        .await
+        .unwrap();
+    let now = unix_timestamp_ms() / 1_000;
+    for number in 0..=2 {
+        let mut header = create_l1_batch(number);
+        // L1 batches are old enough for the timestamp deadline to trigger, but not for the max postponement.
+        header.timestamp = now - 60;
+        let predicted_gas = BlockGasCount {
+            commit: PREDICTED_COMMIT_GAS,
+            prove: 0,
+            execute: 0,
+        };
+        save_l1_batch(&mut storage, &header, predicted_gas).await;
+    }
+
+    let config = SenderConfig {
+        fee_spike_ratio: Some(2.0),
+        max_fee_postponement_seconds: Some(600),
+        aggregated_block_commit_deadline: 10,
+        pubdata_sending_mode: PubdataSendingMode::Calldata,
+        ..EthConfig::for_tests().sender.unwrap()
+    };
+    let fee_provider = Arc::new(MockFeeProvider::new(10_000_000_000));
+    let mut aggregator = Aggregator::new(
+        config,
+        MockObjectStore::arc(),
+        false,
+        L1BatchCommitmentMode::Rollup,
+    )
+    .with_fee_forecast(fee_provider.clone());
+
+    // Without a fee spike, the timestamp deadline criterion triggers.
+    let l1_batches = get_next_commit_operation(&mut aggregator, &mut storage).await;
+    assert_eq!(l1_batches, Some(vec![L1BatchNumber(1), L1BatchNumber(2)]));
+
+    fee_provider.set_latest_base_fee(50_000_000_000);
+    let l1_batches = get_next_commit_operation(&mut aggregator, &mut storage).await;
+    assert_eq!(l1_batches, None);
+    let l1_batches = get_next_commit_operation(&mut aggregator, &mut storage).await;
+    assert_eq!(l1_batches, None);
+
+    let savings_metric =
+        &METRICS.estimated_fee_savings_gwei[&(AggregatedActionType::Commit, "fee_spike").into()];
+    let savings_before = savings_metric.get();
+    fee_provider.set_latest_base_fee(20_000_000_000);
+    let l1_batches = get_next_commit_operation(&mut aggregator, &mut storage).await;
+    assert_eq!(l1_batches, Some(vec![L1BatchNumber(1), L1BatchNumber(2)]));
+
+    // Savings are estimated as predicted gas for both L1 batches times the base fee difference (30 gwei).
+    let expected_savings = 2 * u64::from(PREDICTED_COMMIT_GAS) * 30;
+    assert_eq!(savings_metric.get() - savings_before, expected_savings);
+}
+
 async fn insert_genesis_protocol_version(tester: &EthSenderTester) {
     tester
         .storage()
@@ -1154,26 +1572,33 @@ async fn insert_genesis_protocol_version(tester: &EthSenderTester) {
 
 async fn insert_l1_batch(tester: &EthSenderTester, number: L1BatchNumber) -> L1BatchHeader {
     let header = create_l1_batch(number.0);
+    save_l1_batch(
+        &mut tester.storage().await,
+        &header,
+        BlockGasCount::default(),
+    )
+    .await;
+    header
+}
 
-    // Save L1 batch to the database
-    tester
-        .storage()
-        .await
+/// Saves an L1 batch with metadata to the database.
+async fn save_l1_batch(
+    storage: &mut Connection<'_, Core>,
+    header: &L1BatchHeader,
+    predicted_gas: BlockGasCount,
+) {
+    storage
         .blocks_dal()
-        .insert_mock_l1_batch(&header)
+        .insert_l1_batch(header, &[], predicted_gas, &[], &[], Default::default())
         .await
         .unwrap();
     let metadata = default_l1_batch_metadata();
-    tester
-        .storage()
-        .await
+    storage
         .blocks_dal()
         .save_l1_batch_tree_data(header.number, &metadata.tree_data())
         .await
         .unwrap();
-    tester
-        .storage()
-        .await
+    storage
         .blocks_dal()
         .save_l1_batch_commitment_artifacts(
             header.number,
@@ -1181,7 +1606,6 @@ async fn insert_l1_batch(tester: &EthSenderTester, number: L1BatchNumber) -> L1B
         )
         .await
         .unwrap();
-    header
 }
 
 async fn execute_l1_batches(
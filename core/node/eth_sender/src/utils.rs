@@ -1,4 +1,8 @@
-use zksync_types::aggregated_operations::AggregatedActionType;
+use anyhow::Context as _;
+use zksync_dal::{Connection, Core, CoreDal};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType, eth_sender::OperatorRotation, Address,
+};
 
 // TODO(QIT-32): Remove constants(except `L1_OPERATION_EXECUTE_COST`) and logic that use them
 const AGGR_L1_BATCH_COMMIT_BASE_COST: u32 = 242_000;
@@ -12,3 +16,40 @@ pub fn agg_l1_batch_base_cost(op: AggregatedActionType) -> u32 {
         AggregatedActionType::Execute => AGGR_L1_BATCH_EXECUTE_BASE_COST,
     }
 }
+
+/// Starts rotation of the main operator key from `previous_operator` to `new_operator` (unless it is already started)
+/// and returns the current rotation state.
+pub(crate) async fn start_operator_rotation(
+    storage: &mut Connection<'_, Core>,
+    previous_operator: Address,
+    new_operator: Address,
+) -> anyhow::Result<OperatorRotation> {
+    anyhow::ensure!(
+        previous_operator != new_operator,
+        "previous operator {previous_operator:?} is the same as the current one"
+    );
+    let started = storage
+        .eth_sender_dal()
+        .start_operator_rotation(previous_operator, new_operator)
+        .await
+        .context("start_operator_rotation()")?;
+    if started {
+        tracing::info!(
+            "Started rotation of operator key from {previous_operator:?} to {new_operator:?}"
+        );
+    }
+
+    let rotation = storage
+        .eth_sender_dal()
+        .get_operator_rotation(previous_operator)
+        .await
+        .context("get_operator_rotation()")?
+        .context("operator rotation disappeared from storage")?;
+    if rotation.new_operator != new_operator {
+        tracing::warn!(
+            "Operator {previous_operator:?} was rotated to {:?}, but the current operator is {new_operator:?}",
+            rotation.new_operator
+        );
+    }
+    Ok(rotation)
+}
@@ -23,8 +23,22 @@ pub trait EthClient: 'static + fmt::Debug + Send + Sync {
         to: BlockNumber,
         retries_left: usize,
     ) -> EnrichedClientResult<Vec<Log>>;
+    /// Returns events emitted by the specified contracts in a given block range. Only events
+    /// with the first topic contained in `topics` are returned.
+    async fn get_contract_events(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+        contracts: &[Address],
+        topics: &[H256],
+        retries_left: usize,
+    ) -> EnrichedClientResult<Vec<Log>>;
     /// Returns finalized L1 block number.
     async fn finalized_block_number(&self) -> EnrichedClientResult<u64>;
+    /// Returns the latest L1 block number.
+    async fn latest_block_number(&self) -> EnrichedClientResult<u64>;
+    /// Returns the hash of the L1 block with the specified number, or `None` if the block is not present.
+    async fn block_hash(&self, number: u64) -> EnrichedClientResult<Option<H256>>;
     /// Returns scheduler verification key hash by verifier address.
     async fn scheduler_vk_hash(&self, verifier_address: Address)
         -> Result<H256, ContractCallError>;
@@ -87,24 +101,27 @@ impl EthHttpQueryClient {
         }
     }
 
+    fn watched_contracts(&self) -> Vec<Address> {
+        [
+            Some(self.diamond_proxy_addr),
+            Some(self.governance_address),
+            self.state_transition_manager_address,
+            self.chain_admin_address,
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
     async fn get_filter_logs(
         &self,
         from: BlockNumber,
         to: BlockNumber,
+        contracts: Vec<Address>,
         topics: Vec<H256>,
     ) -> EnrichedClientResult<Vec<Log>> {
         let filter = FilterBuilder::default()
-            .address(
-                [
-                    Some(self.diamond_proxy_addr),
-                    Some(self.governance_address),
-                    self.state_transition_manager_address,
-                    self.chain_admin_address,
-                ]
-                .into_iter()
-                .flatten()
-                .collect(),
-            )
+            .address(contracts)
             .from_block(from)
             .to_block(to)
             .topics(Some(topics), None, None, None)
@@ -155,7 +172,22 @@ impl EthClient for EthHttpQueryClient {
         to: BlockNumber,
         retries_left: usize,
     ) -> EnrichedClientResult<Vec<Log>> {
-        let mut result = self.get_filter_logs(from, to, self.topics.clone()).await;
+        let contracts = self.watched_contracts();
+        self.get_contract_events(from, to, &contracts, &self.topics, retries_left)
+            .await
+    }
+
+    async fn get_contract_events(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+        contracts: &[Address],
+        topics: &[H256],
+        retries_left: usize,
+    ) -> EnrichedClientResult<Vec<Log>> {
+        let mut result = self
+            .get_filter_logs(from, to, contracts.to_vec(), topics.to_vec())
+            .await;
 
         // This code is compatible with both Infura and Alchemy API providers.
         // Note: we don't handle rate-limits here - assumption is that we're never going to hit them.
@@ -207,17 +239,31 @@ impl EthClient for EthHttpQueryClient {
 
                 tracing::warn!("Splitting block range in half: {from:?} - {mid:?} - {to:?}");
                 let mut first_half = self
-                    .get_events(from, BlockNumber::Number(mid), RETRY_LIMIT)
+                    .get_contract_events(
+                        from,
+                        BlockNumber::Number(mid),
+                        contracts,
+                        topics,
+                        RETRY_LIMIT,
+                    )
                     .await?;
                 let mut second_half = self
-                    .get_events(BlockNumber::Number(mid + 1u64), to, RETRY_LIMIT)
+                    .get_contract_events(
+                        BlockNumber::Number(mid + 1u64),
+                        to,
+                        contracts,
+                        topics,
+                        RETRY_LIMIT,
+                    )
                     .await?;
 
                 first_half.append(&mut second_half);
                 result = Ok(first_half);
             } else if should_retry(err_code, err_message) && retries_left > 0 {
                 tracing::warn!("Retrying. Retries left: {retries_left}");
-                result = self.get_events(from, to, retries_left - 1).await;
+                result = self
+                    .get_contract_events(from, to, contracts, topics, retries_left - 1)
+                    .await;
             }
         }
 
@@ -245,6 +291,18 @@ impl EthClient for EthHttpQueryClient {
         }
     }
 
+    async fn latest_block_number(&self) -> EnrichedClientResult<u64> {
+        Ok(self.client.block_number().await?.as_u64())
+    }
+
+    async fn block_hash(&self, number: u64) -> EnrichedClientResult<Option<H256>> {
+        let block = self
+            .client
+            .block(BlockId::Number(BlockNumber::Number(number.into())))
+            .await?;
+        Ok(block.and_then(|block| block.hash))
+    }
+
     fn set_topics(&mut self, topics: Vec<H256>) {
         self.topics = topics;
     }
@@ -1,11 +1,14 @@
 //! Ethereum watcher polls the Ethereum node for the relevant events, such as priority operations (aka L1 transactions),
 //! protocol upgrades etc.
 //! New events are accepted to the ZKsync network once they have the sufficient amount of L1 confirmations.
+//! Additionally, the watcher can persist events emitted by arbitrary L1 contracts as configured
+//! via [`EthWatch::with_event_subscriptions()`].
 
-use std::time::Duration;
+use std::{collections::HashSet, time::Duration};
 
 use anyhow::Context as _;
 use tokio::sync::watch;
+use zksync_config::configs::eth_watch::L1EventSubscriptionConfig;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_system_constants::PRIORITY_EXPIRATION;
 use zksync_types::{
@@ -21,12 +24,14 @@ use self::{
         PriorityOpsEventProcessor,
     },
     metrics::{PollStage, METRICS},
+    subscriptions::L1EventSubscription,
 };
 use crate::event_processors::DecentralizedUpgradesEventProcessor;
 
 mod client;
 mod event_processors;
 mod metrics;
+mod subscriptions;
 #[cfg(test)]
 mod tests;
 
@@ -43,6 +48,7 @@ pub struct EthWatch {
     client: Box<dyn EthClient>,
     poll_interval: Duration,
     event_processors: Vec<Box<dyn EventProcessor>>,
+    event_subscriptions: Vec<L1EventSubscription>,
     last_processed_ethereum_block: u64,
     pool: ConnectionPool<Core>,
 }
@@ -88,11 +94,37 @@ impl EthWatch {
             client,
             poll_interval,
             event_processors,
+            event_subscriptions: Vec::new(),
             last_processed_ethereum_block: state.last_processed_ethereum_block,
             pool,
         })
     }
 
+    /// Adds subscriptions to events emitted by L1 contracts. Events for each subscription are persisted
+    /// to the database once they have the configured amount of L1 confirmations, and are rolled back if
+    /// they are affected by an L1 reorg.
+    pub fn with_event_subscriptions(
+        mut self,
+        configs: &[L1EventSubscriptionConfig],
+    ) -> anyhow::Result<Self> {
+        let mut names: HashSet<_> = self
+            .event_subscriptions
+            .iter()
+            .map(|subscription| subscription.name().to_owned())
+            .collect();
+        for config in configs {
+            anyhow::ensure!(
+                names.insert(config.name.clone()),
+                "duplicate event subscription name: `{}`",
+                config.name
+            );
+            let subscription = L1EventSubscription::new(config)
+                .with_context(|| format!("invalid event subscription `{}`", config.name))?;
+            self.event_subscriptions.push(subscription);
+        }
+        Ok(self)
+    }
+
     async fn initialize_state(
         client: &dyn EthClient,
         storage: &mut Connection<'_, Core>,
@@ -160,12 +192,39 @@ impl EthWatch {
                             .last_processed_ethereum_block;
                 }
             }
+            self.process_event_subscriptions(&mut storage).await?;
         }
 
         tracing::info!("Stop signal received, eth_watch is shutting down");
         Ok(())
     }
 
+    async fn process_event_subscriptions(
+        &self,
+        storage: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<()> {
+        for subscription in &self.event_subscriptions {
+            match subscription.process(storage, &*self.client).await {
+                Ok(()) => { /* everything went fine */ }
+                Err(EventProcessorError::Internal(err)) => {
+                    tracing::error!(
+                        "Internal error processing event subscription `{}`: {err:?}",
+                        subscription.name()
+                    );
+                    return Err(err);
+                }
+                Err(err) => {
+                    // Subscription progress is persisted, so the failed block range will be retried on the next iteration.
+                    tracing::warn!(
+                        "Failed to process event subscription `{}`: {err}",
+                        subscription.name()
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
     #[tracing::instrument(skip_all)]
     async fn loop_iteration(
         &mut self,
@@ -2,7 +2,10 @@
 
 use std::time::Duration;
 
-use vise::{Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Histogram, Metrics};
+use vise::{
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, LabeledFamily,
+    Metrics,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
 #[metrics(label = "stage", rename_all = "snake_case")]
@@ -20,6 +23,15 @@ pub(super) struct EthWatcherMetrics {
     /// Latency of polling and processing events split by stage.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub poll_eth_node: Family<PollStage, Histogram<Duration>>,
+    /// Last L1 block processed by an event subscription.
+    #[metrics(labels = ["subscription"])]
+    pub subscription_last_processed_block: LabeledFamily<String, Gauge<u64>>,
+    /// Number of events persisted by an event subscription.
+    #[metrics(labels = ["subscription"])]
+    pub subscription_events: LabeledFamily<String, Counter>,
+    /// Number of L1 reorgs detected by an event subscription.
+    #[metrics(labels = ["subscription"])]
+    pub subscription_reorgs: LabeledFamily<String, Counter>,
 }
 
 #[vise::register]
@@ -0,0 +1,286 @@
+//! Configurable subscriptions to L1 events that are persisted to the database as is, so that they can be consumed
+//! by other components (e.g., custom bridges or oracles).
+
+use anyhow::Context as _;
+use zksync_config::configs::eth_watch::L1EventSubscriptionConfig;
+use zksync_dal::{Connection, Core, CoreDal, DalError};
+use zksync_eth_client::{ClientError, EnrichedClientError};
+use zksync_types::{
+    eth_watch::{L1Event, L1EventCheckpoint},
+    web3::{keccak256, BlockNumber},
+    Address, L1BlockNumber, H256,
+};
+
+use crate::{
+    client::{EthClient, RETRY_LIMIT},
+    event_processors::EventProcessorError,
+    metrics::METRICS,
+};
+
+/// Maximum number of L1 blocks processed by a subscription in a single iteration.
+const MAX_BLOCKS_PER_ITERATION: u64 = 10_000;
+/// Number of latest checkpoints retained for each subscription. Checkpoints are used to find the last processed
+/// L1 block unaffected by an L1 reorg.
+const RETAINED_CHECKPOINTS: usize = 1_000;
+/// Number of checkpoints loaded from the database at once when looking for the L1 reorg fork point.
+const CHECKPOINTS_BATCH_SIZE: usize = 100;
+
+/// Subscription to events emitted by a single L1 contract. Unlike [`EventProcessor`](crate::event_processors::EventProcessor)s,
+/// each subscription tracks its progress separately (recently processed L1 blocks together with their hashes),
+/// so subscriptions can be added or removed without affecting each other or the core watcher logic.
+#[derive(Debug)]
+pub(crate) struct L1EventSubscription {
+    name: String,
+    contract_address: Address,
+    topics: Vec<H256>,
+    confirmations: Option<u64>,
+    start_block: Option<u64>,
+}
+
+impl L1EventSubscription {
+    pub fn new(config: &L1EventSubscriptionConfig) -> anyhow::Result<Self> {
+        anyhow::ensure!(!config.name.is_empty(), "subscription name is empty");
+        anyhow::ensure!(
+            !config.event_signatures.is_empty(),
+            "no event signatures specified for subscription `{}`",
+            config.name
+        );
+        let topics = config
+            .event_signatures
+            .iter()
+            .map(|signature| {
+                let is_valid = signature.ends_with(')')
+                    && signature.find('(').is_some_and(|pos| pos > 0)
+                    && !signature.contains(char::is_whitespace);
+                anyhow::ensure!(
+                    is_valid,
+                    "invalid event signature `{signature}` for subscription `{}`; signatures should have \
+                     the canonical form, e.g. `Transfer(address,address,uint256)`",
+                    config.name
+                );
+                Ok(H256(keccak256(signature.as_bytes())))
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok(Self {
+            name: config.name.clone(),
+            contract_address: config.contract_address,
+            topics,
+            confirmations: config.confirmations,
+            start_block: config.start_block,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn last_confirmed_block(
+        &self,
+        client: &dyn EthClient,
+    ) -> Result<u64, EventProcessorError> {
+        Ok(match self.confirmations {
+            Some(confirmations) => client
+                .latest_block_number()
+                .await?
+                .saturating_sub(confirmations),
+            None => client.finalized_block_number().await?,
+        })
+    }
+
+    async fn block_hash(client: &dyn EthClient, number: u64) -> Result<H256, EventProcessorError> {
+        client.block_hash(number).await?.ok_or_else(|| {
+            let err = ClientError::Custom(format!("L1 block #{number} is not present"));
+            EnrichedClientError::new(err, "block_hash").into()
+        })
+    }
+
+    /// Walks back through the stored checkpoints preceding `mismatched_block` and returns the latest one
+    /// matching L1, i.e. the last processed L1 block unaffected by an L1 reorg.
+    async fn find_retained_checkpoint(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        client: &dyn EthClient,
+        mut mismatched_block: L1BlockNumber,
+    ) -> Result<Option<L1EventCheckpoint>, EventProcessorError> {
+        loop {
+            let checkpoints = storage
+                .eth_watch_dal()
+                .get_subscription_checkpoints(&self.name, mismatched_block, CHECKPOINTS_BATCH_SIZE)
+                .await
+                .map_err(DalError::generalize)?;
+            for checkpoint in &checkpoints {
+                let number = u64::from(checkpoint.l1_block_number.0);
+                if client.block_hash(number).await? == Some(checkpoint.l1_block_hash) {
+                    return Ok(Some(*checkpoint));
+                }
+            }
+            match checkpoints.last() {
+                Some(checkpoint) if checkpoints.len() == CHECKPOINTS_BATCH_SIZE => {
+                    mismatched_block = checkpoint.l1_block_number;
+                }
+                _ => return Ok(None),
+            }
+        }
+    }
+
+    /// Returns the first L1 block to process, rolling back subscription progress if an L1 reorg is detected.
+    async fn first_unprocessed_block(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        client: &dyn EthClient,
+        last_confirmed_block: u64,
+    ) -> Result<u64, EventProcessorError> {
+        let checkpoint = storage
+            .eth_watch_dal()
+            .get_subscription_checkpoint(&self.name)
+            .await
+            .map_err(DalError::generalize)?;
+        let Some(checkpoint) = checkpoint else {
+            return Ok(self.start_block.unwrap_or(last_confirmed_block));
+        };
+
+        let last_processed_block = u64::from(checkpoint.l1_block_number.0);
+        let l1_block_hash = client.block_hash(last_processed_block).await?;
+        if l1_block_hash == Some(checkpoint.l1_block_hash) {
+            return Ok(last_processed_block + 1);
+        }
+
+        let retained_checkpoint = self
+            .find_retained_checkpoint(storage, client, checkpoint.l1_block_number)
+            .await?;
+        let rolled_back_checkpoint = match retained_checkpoint {
+            Some(checkpoint) => checkpoint,
+            None => {
+                // The reorg is deeper than all retained checkpoints, so the subscription is processed
+                // from its start block (or genesis).
+                let first_block = self.start_block.unwrap_or(0).saturating_sub(1);
+                L1EventCheckpoint {
+                    l1_block_number: l1_block_number(first_block)?,
+                    l1_block_hash: Self::block_hash(client, first_block).await?,
+                }
+            }
+        };
+        let last_retained_block = u64::from(rolled_back_checkpoint.l1_block_number.0);
+        tracing::warn!(
+            "Detected L1 reorg for event subscription `{}`: hash of L1 block #{last_processed_block} is {l1_block_hash:?}, \
+             while {:?} was processed; rolling back to L1 block #{last_retained_block}",
+            self.name,
+            checkpoint.l1_block_hash
+        );
+        METRICS.subscription_reorgs[&self.name].inc();
+
+        let mut transaction = storage
+            .start_transaction()
+            .await
+            .map_err(DalError::generalize)?;
+        let removed_count = transaction
+            .eth_watch_dal()
+            .delete_subscription_events_after(&self.name, rolled_back_checkpoint.l1_block_number)
+            .await
+            .map_err(DalError::generalize)?;
+        transaction
+            .eth_watch_dal()
+            .delete_subscription_checkpoints_after(
+                &self.name,
+                rolled_back_checkpoint.l1_block_number,
+            )
+            .await
+            .map_err(DalError::generalize)?;
+        transaction
+            .eth_watch_dal()
+            .set_subscription_checkpoint(&self.name, rolled_back_checkpoint)
+            .await
+            .map_err(DalError::generalize)?;
+        transaction.commit().await.map_err(DalError::generalize)?;
+        tracing::info!(
+            "Removed {removed_count} events for subscription `{}` emitted after L1 block #{last_retained_block}",
+            self.name
+        );
+        Ok(last_retained_block + 1)
+    }
+
+    /// Processes the next range of confirmed L1 blocks.
+    #[tracing::instrument(skip_all, fields(subscription = self.name))]
+    pub async fn process(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        client: &dyn EthClient,
+    ) -> Result<(), EventProcessorError> {
+        let last_confirmed_block = self.last_confirmed_block(client).await?;
+        let from_block = self
+            .first_unprocessed_block(storage, client, last_confirmed_block)
+            .await?;
+        if from_block > last_confirmed_block {
+            return Ok(());
+        }
+        let to_block = last_confirmed_block.min(from_block + MAX_BLOCKS_PER_ITERATION - 1);
+        let to_block_hash = Self::block_hash(client, to_block).await?;
+
+        let logs = client
+            .get_contract_events(
+                BlockNumber::Number(from_block.into()),
+                BlockNumber::Number(to_block.into()),
+                &[self.contract_address],
+                &self.topics,
+                RETRY_LIMIT,
+            )
+            .await?;
+        let events = logs
+            .into_iter()
+            .map(L1Event::try_from)
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(|err| EventProcessorError::log_parse(err, "L1 event"))?;
+
+        // Events and the block hash are fetched with separate requests, so they may correspond to different forks.
+        let checkpoint = L1EventCheckpoint {
+            l1_block_number: l1_block_number(to_block)?,
+            l1_block_hash: to_block_hash,
+        };
+        let is_consistent = events.iter().all(|event| {
+            event.l1_block_number != checkpoint.l1_block_number
+                || event.l1_block_hash == checkpoint.l1_block_hash
+        });
+        if !is_consistent {
+            tracing::info!(
+                "L1 reorg happened while fetching events for blocks #{from_block}..=#{to_block}; will retry"
+            );
+            return Ok(());
+        }
+
+        let mut transaction = storage
+            .start_transaction()
+            .await
+            .map_err(DalError::generalize)?;
+        transaction
+            .eth_watch_dal()
+            .insert_subscription_events(&self.name, &events)
+            .await
+            .map_err(DalError::generalize)?;
+        transaction
+            .eth_watch_dal()
+            .set_subscription_checkpoint(&self.name, checkpoint)
+            .await
+            .map_err(DalError::generalize)?;
+        transaction
+            .eth_watch_dal()
+            .prune_subscription_checkpoints(&self.name, RETAINED_CHECKPOINTS)
+            .await
+            .map_err(DalError::generalize)?;
+        transaction.commit().await.map_err(DalError::generalize)?;
+
+        tracing::debug!(
+            "Processed {} events from L1 blocks #{from_block}..=#{to_block}",
+            events.len()
+        );
+        METRICS.subscription_events[&self.name].inc_by(events.len() as u64);
+        METRICS.subscription_last_processed_block[&self.name].set(to_block);
+        Ok(())
+    }
+}
+
+fn l1_block_number(number: u64) -> anyhow::Result<L1BlockNumber> {
+    let number =
+        u32::try_from(number).with_context(|| format!("L1 block #{number} overflows u32"))?;
+    Ok(L1BlockNumber(number))
+}
@@ -1,6 +1,7 @@
 use std::{collections::HashMap, convert::TryInto, sync::Arc};
 
 use tokio::sync::RwLock;
+use zksync_config::configs::eth_watch::L1EventSubscriptionConfig;
 use zksync_contracts::{chain_admin_contract, governance_contract, hyperchain_contract};
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_eth_client::{ContractCallError, EnrichedClientResult};
@@ -10,19 +11,22 @@ use zksync_types::{
     l1::{L1Tx, OpProcessingType, PriorityQueueType},
     protocol_upgrade::{ProtocolUpgradeTx, ProtocolUpgradeTxCommonData},
     protocol_version::ProtocolSemanticVersion,
-    web3::{BlockNumber, Log},
-    Address, Execute, L1TxCommonData, PriorityOpId, ProtocolUpgrade, ProtocolVersion,
-    ProtocolVersionId, Transaction, H256, U256,
+    web3::{keccak256, BlockNumber, Log},
+    Address, Execute, L1BlockNumber, L1TxCommonData, PriorityOpId, ProtocolUpgrade,
+    ProtocolVersion, ProtocolVersionId, Transaction, H256, U256,
 };
 
-use crate::{client::EthClient, EthWatch};
+use crate::{client::EthClient, subscriptions::L1EventSubscription, EthWatch};
 
 #[derive(Debug)]
 struct FakeEthClientData {
     transactions: HashMap<u64, Vec<Log>>,
     diamond_upgrades: HashMap<u64, Vec<Log>>,
     governance_upgrades: HashMap<u64, Vec<Log>>,
+    contract_events: HashMap<u64, Vec<Log>>,
+    block_hashes: HashMap<u64, H256>,
     last_finalized_block_number: u64,
+    last_block_number: u64,
 }
 
 impl FakeEthClientData {
@@ -31,7 +35,10 @@ impl FakeEthClientData {
             transactions: Default::default(),
             diamond_upgrades: Default::default(),
             governance_upgrades: Default::default(),
+            contract_events: Default::default(),
+            block_hashes: Default::default(),
             last_finalized_block_number: 0,
+            last_block_number: 0,
         }
     }
 
@@ -57,6 +64,39 @@ impl FakeEthClientData {
     fn set_last_finalized_block_number(&mut self, number: u64) {
         self.last_finalized_block_number = number;
     }
+
+    fn block_hash(&self, number: u64) -> H256 {
+        self.block_hashes
+            .get(&number)
+            .copied()
+            .unwrap_or_else(|| H256::from_low_u64_be(number))
+    }
+
+    fn add_contract_events(&mut self, address: Address, topic: H256, eth_blocks: &[u64]) {
+        for &eth_block in eth_blocks {
+            let block_hash = self.block_hash(eth_block);
+            let block_events = self.contract_events.entry(eth_block).or_default();
+            block_events.push(Log {
+                address,
+                topics: vec![topic, H256::random()],
+                data: vec![1, 2, 3].into(),
+                block_hash: Some(block_hash),
+                block_number: Some(eth_block.into()),
+                transaction_hash: Some(H256::random()),
+                log_index: Some(block_events.len().into()),
+                ..Log::default()
+            });
+        }
+    }
+
+    /// Replaces all blocks starting from `first_block` with blocks from another fork. Events from the replaced blocks are removed.
+    fn reorg(&mut self, first_block: u64) {
+        for number in first_block..=self.last_block_number {
+            self.block_hashes.insert(number, H256::random());
+        }
+        self.contract_events
+            .retain(|&eth_block, _| eth_block < first_block);
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -86,6 +126,21 @@ impl MockEthClient {
             .set_last_finalized_block_number(number);
     }
 
+    async fn add_contract_events(&mut self, address: Address, topic: H256, eth_blocks: &[u64]) {
+        self.inner
+            .write()
+            .await
+            .add_contract_events(address, topic, eth_blocks);
+    }
+
+    async fn set_last_block_number(&mut self, number: u64) {
+        self.inner.write().await.last_block_number = number;
+    }
+
+    async fn reorg(&mut self, first_block: u64) {
+        self.inner.write().await.reorg(first_block);
+    }
+
     async fn block_to_number(&self, block: BlockNumber) -> u64 {
         match block {
             BlockNumber::Earliest => 0,
@@ -123,6 +178,32 @@ impl EthClient for MockEthClient {
         Ok(logs)
     }
 
+    async fn get_contract_events(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+        contracts: &[Address],
+        topics: &[H256],
+        _retries_left: usize,
+    ) -> EnrichedClientResult<Vec<Log>> {
+        let from = self.block_to_number(from).await;
+        let to = self.block_to_number(to).await;
+        let inner = self.inner.read().await;
+        let logs = (from..=to)
+            .filter_map(|number| inner.contract_events.get(&number))
+            .flatten()
+            .filter(|log| {
+                contracts.contains(&log.address)
+                    && log
+                        .topics
+                        .first()
+                        .is_some_and(|topic| topics.contains(topic))
+            })
+            .cloned()
+            .collect();
+        Ok(logs)
+    }
+
     fn set_topics(&mut self, _topics: Vec<Hash>) {}
 
     async fn scheduler_vk_hash(
@@ -136,6 +217,15 @@ impl EthClient for MockEthClient {
         Ok(self.inner.read().await.last_finalized_block_number)
     }
 
+    async fn latest_block_number(&self) -> EnrichedClientResult<u64> {
+        Ok(self.inner.read().await.last_block_number)
+    }
+
+    async fn block_hash(&self, number: u64) -> EnrichedClientResult<Option<H256>> {
+        let inner = self.inner.read().await;
+        Ok((number <= inner.last_block_number).then(|| inner.block_hash(number)))
+    }
+
     async fn diamond_cut_by_version(
         &self,
         _packed_version: H256,
@@ -467,6 +557,144 @@ async fn test_overlapping_batches() {
     assert_eq!(tx.common_data.serial_id.0, 4);
 }
 
+#[test]
+fn validating_event_subscriptions() {
+    let mut config = L1EventSubscriptionConfig {
+        name: "test".to_owned(),
+        contract_address: Address::repeat_byte(0x22),
+        event_signatures: vec!["Transfer(address,address,uint256)".to_owned()],
+        confirmations: None,
+        start_block: None,
+    };
+    L1EventSubscription::new(&config).unwrap();
+
+    for invalid_signature in ["Transfer", "(address)", "Transfer(address, uint256)"] {
+        config.event_signatures = vec![invalid_signature.to_owned()];
+        let err = L1EventSubscription::new(&config).unwrap_err().to_string();
+        assert!(err.contains("invalid event signature"), "{err}");
+    }
+    config.event_signatures.clear();
+    L1EventSubscription::new(&config).unwrap_err();
+}
+
+#[tokio::test]
+async fn processing_event_subscription_with_l1_reorg() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = connection_pool.connection().await.unwrap();
+    let contract_address = Address::repeat_byte(0x22);
+    let signature = "Transfer(address,address,uint256)";
+    let topic = H256(keccak256(signature.as_bytes()));
+    let subscription = L1EventSubscription::new(&L1EventSubscriptionConfig {
+        name: "test".to_owned(),
+        contract_address,
+        event_signatures: vec![signature.to_owned()],
+        confirmations: Some(2),
+        start_block: Some(5),
+    })
+    .unwrap();
+
+    let mut client = MockEthClient::new();
+    client.set_last_block_number(20).await;
+    client
+        .add_contract_events(contract_address, topic, &[3, 6, 10, 19])
+        .await;
+    // Events with other topics or from other contracts should be ignored.
+    client
+        .add_contract_events(contract_address, H256::repeat_byte(1), &[7])
+        .await;
+    client
+        .add_contract_events(Address::repeat_byte(0x33), topic, &[8])
+        .await;
+
+    subscription.process(&mut storage, &client).await.unwrap();
+    // The event at block 3 precedes `start_block`, and the one at block 19 doesn't have enough confirmations.
+    assert_subscription_state(&mut storage, &client, &[6, 10], 18).await;
+
+    client.set_last_block_number(25).await;
+    client.reorg(9).await;
+    client
+        .add_contract_events(contract_address, topic, &[12, 19])
+        .await;
+    subscription.process(&mut storage, &client).await.unwrap();
+    assert_subscription_state(&mut storage, &client, &[6, 12, 19], 23).await;
+
+    // Without new blocks, processing should be a no-op.
+    subscription.process(&mut storage, &client).await.unwrap();
+    assert_subscription_state(&mut storage, &client, &[6, 12, 19], 23).await;
+}
+
+#[tokio::test]
+async fn processing_event_subscription_with_deep_l1_reorg() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = connection_pool.connection().await.unwrap();
+    let contract_address = Address::repeat_byte(0x22);
+    let signature = "Transfer(address,address,uint256)";
+    let topic = H256(keccak256(signature.as_bytes()));
+    let subscription = L1EventSubscription::new(&L1EventSubscriptionConfig {
+        name: "test".to_owned(),
+        contract_address,
+        event_signatures: vec![signature.to_owned()],
+        confirmations: Some(2),
+        start_block: Some(5),
+    })
+    .unwrap();
+
+    let mut client = MockEthClient::new();
+    client
+        .add_contract_events(contract_address, topic, &[6, 15, 25, 35])
+        .await;
+    for last_block_number in [20, 30, 40] {
+        client.set_last_block_number(last_block_number).await;
+        subscription.process(&mut storage, &client).await.unwrap();
+    }
+    assert_subscription_state(&mut storage, &client, &[6, 15, 25, 35], 38).await;
+
+    // The reorg affects 2 latest checkpoints (at blocks 28 and 38), so the subscription should be rolled back
+    // to the checkpoint at block 18.
+    client.set_last_block_number(45).await;
+    client.reorg(22).await;
+    client
+        .add_contract_events(contract_address, topic, &[23, 40])
+        .await;
+    subscription.process(&mut storage, &client).await.unwrap();
+    assert_subscription_state(&mut storage, &client, &[6, 15, 23, 40], 43).await;
+}
+
+async fn assert_subscription_state(
+    storage: &mut Connection<'_, Core>,
+    client: &MockEthClient,
+    expected_event_blocks: &[u32],
+    expected_checkpoint: u32,
+) {
+    let events = storage
+        .eth_watch_dal()
+        .get_subscription_events("test", L1BlockNumber(0), 100)
+        .await
+        .unwrap();
+    let event_blocks: Vec<_> = events.iter().map(|event| event.l1_block_number.0).collect();
+    assert_eq!(event_blocks, expected_event_blocks);
+    for event in &events {
+        let expected_hash = client
+            .block_hash(event.l1_block_number.0.into())
+            .await
+            .unwrap();
+        assert_eq!(Some(event.l1_block_hash), expected_hash);
+    }
+
+    let checkpoint = storage
+        .eth_watch_dal()
+        .get_subscription_checkpoint("test")
+        .await
+        .unwrap()
+        .expect("no checkpoint");
+    assert_eq!(
+        checkpoint.l1_block_number,
+        L1BlockNumber(expected_checkpoint)
+    );
+    let expected_hash = client.block_hash(expected_checkpoint.into()).await.unwrap();
+    assert_eq!(Some(checkpoint.l1_block_hash), expected_hash);
+}
+
 async fn get_all_db_txs(storage: &mut Connection<'_, Core>) -> Vec<Transaction> {
     storage.transactions_dal().reset_mempool().await.unwrap();
     storage
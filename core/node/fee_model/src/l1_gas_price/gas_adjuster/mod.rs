@@ -12,7 +12,7 @@ use zksync_types::{commitment::L1BatchCommitmentMode, L1_GAS_PER_PUBDATA_BYTE, U
 use zksync_web3_decl::client::{DynClient, L1};
 
 use self::metrics::METRICS;
-use super::L1TxParamsProvider;
+use super::{L1FeeHistoryStats, L1TxParamsProvider};
 
 mod metrics;
 #[cfg(test)]
@@ -301,6 +301,15 @@ impl L1TxParamsProvider for GasAdjuster {
     fn get_blob_tx_priority_fee(&self) -> u64 {
         self.get_priority_fee() * 2
     }
+
+    fn get_fee_history_stats(&self) -> L1FeeHistoryStats {
+        L1FeeHistoryStats {
+            median_base_fee: self.base_fee_statistics.median(),
+            latest_base_fee: self.base_fee_statistics.last_added_value(),
+            median_blob_base_fee: self.blob_base_fee_statistics.median(),
+            latest_blob_base_fee: self.blob_base_fee_statistics.last_added_value(),
+        }
+    }
 }
 
 /// Helper structure responsible for collecting the data about recent transactions,
@@ -6,6 +6,7 @@ use zksync_eth_client::{clients::MockEthereum, BaseFees};
 use zksync_types::commitment::L1BatchCommitmentMode;
 
 use super::{GasAdjuster, GasStatistics, GasStatisticsInner};
+use crate::l1_gas_price::L1TxParamsProvider;
 
 /// Check that we compute the median correctly
 #[test]
@@ -118,4 +119,14 @@ async fn kept_updated(commitment_mode: L1BatchCommitmentMode) {
         read(&adjuster.blob_base_fee_statistics).median(),
         expected_median_blob_base_fee.into()
     );
+    let stats = adjuster.get_fee_history_stats();
+    assert_eq!(stats.median_base_fee, 7);
+    assert_eq!(
+        stats.latest_base_fee,
+        read(&adjuster.base_fee_statistics).last_added_value()
+    );
+    assert_eq!(
+        stats.median_blob_base_fee,
+        expected_median_blob_base_fee.into()
+    );
 }
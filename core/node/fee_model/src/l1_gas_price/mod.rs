@@ -2,6 +2,8 @@
 
 use std::fmt;
 
+use zksync_types::U256;
+
 pub use self::{
     gas_adjuster::GasAdjuster, main_node_fetcher::MainNodeFeeParamsFetcher,
     singleton::GasAdjusterSingleton,
@@ -11,6 +13,19 @@ mod gas_adjuster;
 mod main_node_fetcher;
 mod singleton;
 
+/// Statistics on L1 fees over recent L1 blocks, which can be used to forecast L1 fees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct L1FeeHistoryStats {
+    /// Median `base_fee_per_gas` over the sampled L1 blocks.
+    pub median_base_fee: u64,
+    /// `base_fee_per_gas` in the latest sampled L1 block.
+    pub latest_base_fee: u64,
+    /// Median `base_fee_per_blob_gas` over the sampled L1 blocks.
+    pub median_blob_base_fee: U256,
+    /// `base_fee_per_blob_gas` in the latest sampled L1 block.
+    pub latest_blob_base_fee: U256,
+}
+
 /// Abstraction that provides parameters to set the fee for an L1 transaction, taking the desired
 /// mining time into account.
 ///
@@ -36,4 +51,7 @@ pub trait L1TxParamsProvider: fmt::Debug + 'static + Send + Sync {
 
     /// Returns the recommended `max_priority_fee_per_gas` value (EIP1559) for blob transaction.
     fn get_blob_tx_priority_fee(&self) -> u64;
+
+    /// Returns statistics on L1 fees over recent L1 blocks.
+    fn get_fee_history_stats(&self) -> L1FeeHistoryStats;
 }
@@ -5,6 +5,16 @@ use zksync_types::protocol_version::ProtocolSemanticVersion;
 pub(crate) struct HouseKeeperMetrics {
     pub prover_job_archived: Counter,
     pub gpu_prover_archived: Counter,
+    /// Number of object store artifacts removed by GC.
+    #[metrics(labels = ["bucket"])]
+    pub object_store_gc_removed_artifacts: LabeledFamily<String, Counter>,
+    /// Number of object store artifacts that GC attempted to remove, but that were missing from the store.
+    #[metrics(labels = ["bucket"])]
+    pub object_store_gc_missing_artifacts: LabeledFamily<String, Counter>,
+    /// Number of L1 batches processed by object store GC.
+    pub object_store_gc_processed_l1_batches: Counter,
+    /// Last L1 batch processed by object store GC.
+    pub object_store_gc_last_l1_batch: Gauge<u64>,
 }
 
 #[vise::register]
@@ -1,10 +1,12 @@
 mod archiver;
 mod metrics;
+mod object_store_gc;
 mod queue_reporter;
 mod retry_manager;
 mod waiting_to_queued_fri_witness_job_mover;
 
 pub use archiver::{FriGpuProverArchiver, FriProverJobsArchiver};
+pub use object_store_gc::ObjectStoreGarbageCollector;
 pub use queue_reporter::{
     FriProofCompressorQueueReporter, FriProverQueueReporter, FriWitnessGeneratorQueueReporter,
 };
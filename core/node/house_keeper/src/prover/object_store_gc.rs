@@ -0,0 +1,395 @@
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_object_store::{Bucket, ObjectStore, ObjectStoreError};
+use zksync_prover_dal::{Prover, ProverDal};
+use zksync_types::{prover_dal::ProverArtifactKind, L1BatchNumber};
+
+use crate::{periodic_job::PeriodicJob, prover::metrics::HOUSE_KEEPER_METRICS};
+
+/// Maximum number of L1 batches processed during a single GC iteration.
+const MAX_L1_BATCHES_PER_ITERATION: usize = 10;
+
+fn artifact_bucket(kind: ProverArtifactKind) -> Bucket {
+    match kind {
+        ProverArtifactKind::WitnessInputs => Bucket::WitnessInput,
+        ProverArtifactKind::Circuit => Bucket::ProverJobsFri,
+        ProverArtifactKind::Proof => Bucket::ProofsFri,
+        ProverArtifactKind::LeafAggregationInputs => Bucket::LeafAggregationWitnessJobsFri,
+        ProverArtifactKind::NodeAggregationInputs => Bucket::NodeAggregationWitnessJobsFri,
+        ProverArtifactKind::SchedulerInputs => Bucket::SchedulerWitnessJobsFri,
+    }
+}
+
+/// Outcome of removing a single artifact from the object store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RemovalOutcome {
+    Removed,
+    /// The artifact was not present in the store.
+    Missing,
+}
+
+/// `ObjectStoreGarbageCollector` is a task that periodically removes object store artifacts (witness inputs,
+/// circuits, intermediate proofs and aggregation inputs) for proven L1 batches.
+/// Artifacts for a batch are removed once the retention period has passed since its proof was generated.
+/// Final L1 batch proofs are never removed.
+#[derive(Debug)]
+pub struct ObjectStoreGarbageCollector {
+    core_pool: ConnectionPool<Core>,
+    prover_pool: ConnectionPool<Prover>,
+    blob_store: Arc<dyn ObjectStore>,
+    gc_interval_ms: u64,
+    retention_period: Duration,
+    dry_run: bool,
+    /// Last processed L1 batch. Necessary for the dry-run mode, in which processed batches aren't marked in the DB.
+    last_processed_l1_batch: L1BatchNumber,
+}
+
+impl ObjectStoreGarbageCollector {
+    pub fn new(
+        core_pool: ConnectionPool<Core>,
+        prover_pool: ConnectionPool<Prover>,
+        blob_store: Arc<dyn ObjectStore>,
+        gc_interval_ms: u64,
+        retention_period_secs: u64,
+        dry_run: bool,
+    ) -> Self {
+        Self {
+            core_pool,
+            prover_pool,
+            blob_store,
+            gc_interval_ms,
+            retention_period: Duration::from_secs(retention_period_secs),
+            dry_run,
+            last_processed_l1_batch: L1BatchNumber(0),
+        }
+    }
+
+    async fn collect_artifacts(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        witness_input_urls: Vec<String>,
+    ) -> anyhow::Result<HashSet<(Bucket, String)>> {
+        let prover_artifacts = self
+            .prover_pool
+            .connection()
+            .await?
+            .fri_prover_jobs_dal()
+            .get_artifact_urls_for_l1_batches(&[l1_batch_number])
+            .await;
+        // Witness inputs are referenced both from the core and prover DBs, hence the deduplication.
+        Ok(witness_input_urls
+            .into_iter()
+            .map(|url| (Bucket::WitnessInput, url))
+            .chain(
+                prover_artifacts
+                    .into_iter()
+                    .map(|(kind, url)| (artifact_bucket(kind), url)),
+            )
+            .collect())
+    }
+
+    /// Removes the specified artifacts. Artifacts missing from the store are not treated as an error, but are reported
+    /// separately, since they may indicate that the GC is pointed to a wrong store.
+    async fn remove_artifacts(
+        &self,
+        artifacts: &HashSet<(Bucket, String)>,
+    ) -> Result<Vec<(Bucket, RemovalOutcome)>, ObjectStoreError> {
+        let mut outcomes = Vec::with_capacity(artifacts.len());
+        for (bucket, key) in artifacts {
+            let outcome = match self.blob_store.remove_raw(*bucket, key).await {
+                Ok(()) => RemovalOutcome::Removed,
+                Err(ObjectStoreError::KeyNotFound(_)) => {
+                    tracing::warn!("Artifact {bucket}/{key} is missing from the object store");
+                    RemovalOutcome::Missing
+                }
+                Err(err) => return Err(err),
+            };
+            outcomes.push((*bucket, outcome));
+        }
+        Ok(outcomes)
+    }
+
+    fn report_removal_outcomes(outcomes: &[(Bucket, RemovalOutcome)]) {
+        for (bucket, outcome) in outcomes {
+            let bucket = bucket.to_string();
+            match outcome {
+                RemovalOutcome::Removed => {
+                    HOUSE_KEEPER_METRICS.object_store_gc_removed_artifacts[&bucket].inc();
+                }
+                RemovalOutcome::Missing => {
+                    HOUSE_KEEPER_METRICS.object_store_gc_missing_artifacts[&bucket].inc();
+                }
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl PeriodicJob for ObjectStoreGarbageCollector {
+    const SERVICE_NAME: &'static str = "ObjectStoreGarbageCollector";
+
+    async fn run_routine_task(&mut self) -> anyhow::Result<()> {
+        let l1_batches = self
+            .core_pool
+            .connection_tagged("house_keeper")
+            .await?
+            .proof_generation_dal()
+            .get_l1_batches_for_blob_gc(
+                self.retention_period,
+                self.last_processed_l1_batch,
+                MAX_L1_BATCHES_PER_ITERATION,
+            )
+            .await?;
+
+        for (l1_batch_number, witness_input_urls) in l1_batches {
+            let artifacts = self
+                .collect_artifacts(l1_batch_number, witness_input_urls)
+                .await?;
+            if self.dry_run {
+                tracing::info!(
+                    "Dry run: would remove {} artifacts for L1 batch #{l1_batch_number}: {artifacts:?}",
+                    artifacts.len()
+                );
+            } else {
+                let outcomes = match self.remove_artifacts(&artifacts).await {
+                    Ok(outcomes) => outcomes,
+                    Err(err) => {
+                        // Removal will be retried during the next iteration.
+                        tracing::warn!(
+                            "Failed removing artifacts for L1 batch #{l1_batch_number}: {:#}",
+                            anyhow::Error::from(err)
+                        );
+                        return Ok(());
+                    }
+                };
+                self.core_pool
+                    .connection_tagged("house_keeper")
+                    .await?
+                    .proof_generation_dal()
+                    .mark_blobs_as_removed(&[l1_batch_number])
+                    .await?;
+                let missing_count = outcomes
+                    .iter()
+                    .filter(|(_, outcome)| *outcome == RemovalOutcome::Missing)
+                    .count();
+                tracing::info!(
+                    "Removed {} artifacts for L1 batch #{l1_batch_number}; {missing_count} artifacts were missing",
+                    outcomes.len() - missing_count
+                );
+                Self::report_removal_outcomes(&outcomes);
+            }
+
+            HOUSE_KEEPER_METRICS
+                .object_store_gc_processed_l1_batches
+                .inc();
+            HOUSE_KEEPER_METRICS
+                .object_store_gc_last_l1_batch
+                .set(l1_batch_number.0.into());
+            self.last_processed_l1_batch = l1_batch_number;
+        }
+        Ok(())
+    }
+
+    fn polling_interval_ms(&self) -> u64 {
+        self.gc_interval_ms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use zksync_object_store::MockObjectStore;
+    use zksync_types::{
+        basic_fri_types::AggregationRound,
+        block::L1BatchHeader,
+        protocol_version::{L1VerifierConfig, ProtocolSemanticVersion, VersionPatch},
+        ProtocolVersion, ProtocolVersionId,
+    };
+
+    use super::*;
+
+    async fn prepare_core_storage(pool: &ConnectionPool<Core>) {
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        for number in [1, 2] {
+            let header = L1BatchHeader::new(
+                L1BatchNumber(number),
+                number.into(),
+                Default::default(),
+                ProtocolVersionId::latest(),
+            );
+            conn.blocks_dal()
+                .insert_mock_l1_batch(&header)
+                .await
+                .unwrap();
+            conn.proof_generation_dal()
+                .insert_proof_generation_details(L1BatchNumber(number))
+                .await
+                .unwrap();
+            conn.proof_generation_dal()
+                .save_merkle_paths_artifacts_metadata(
+                    L1BatchNumber(number),
+                    &format!("merkle_paths_{number}"),
+                )
+                .await
+                .unwrap();
+            conn.proof_generation_dal()
+                .save_vm_runner_artifacts_metadata(
+                    L1BatchNumber(number),
+                    &format!("vm_run_{number}"),
+                )
+                .await
+                .unwrap();
+        }
+        // Only L1 batch #1 is proven.
+        conn.proof_generation_dal()
+            .save_proof_artifacts_metadata(L1BatchNumber(1), "l1_batch_proof_1")
+            .await
+            .unwrap();
+    }
+
+    async fn prepare_prover_storage(pool: &ConnectionPool<Prover>) {
+        let mut conn = pool.connection().await.unwrap();
+        let protocol_version =
+            ProtocolSemanticVersion::new(ProtocolVersionId::latest(), VersionPatch(0));
+        conn.fri_protocol_versions_dal()
+            .save_prover_protocol_version(protocol_version, L1VerifierConfig::default())
+            .await;
+        for number in [1, 2] {
+            conn.fri_witness_generator_dal()
+                .save_witness_inputs(
+                    L1BatchNumber(number),
+                    &format!("witness_inputs_{number}"),
+                    protocol_version,
+                )
+                .await;
+            let circuits = (1..=2)
+                .map(|circuit_id| (circuit_id, format!("circuit_{number}_{circuit_id}")))
+                .collect();
+            conn.fri_prover_jobs_dal()
+                .insert_prover_jobs(
+                    L1BatchNumber(number),
+                    circuits,
+                    AggregationRound::BasicCircuits,
+                    0,
+                    protocol_version,
+                )
+                .await;
+        }
+    }
+
+    async fn put_artifacts(store: &dyn ObjectStore, l1_batch_number: u32) {
+        // `vm_run_*` artifacts are intentionally not put into the store.
+        let artifacts = [
+            (
+                Bucket::WitnessInput,
+                format!("merkle_paths_{l1_batch_number}"),
+            ),
+            (
+                Bucket::WitnessInput,
+                format!("witness_inputs_{l1_batch_number}"),
+            ),
+            (
+                Bucket::ProverJobsFri,
+                format!("circuit_{l1_batch_number}_1"),
+            ),
+            (
+                Bucket::ProverJobsFri,
+                format!("circuit_{l1_batch_number}_2"),
+            ),
+            (
+                Bucket::ProofsFri,
+                format!("l1_batch_proof_{l1_batch_number}"),
+            ),
+        ];
+        for (bucket, key) in artifacts {
+            store.put_raw(bucket, &key, vec![1, 2, 3]).await.unwrap();
+        }
+    }
+
+    async fn artifact_exists(store: &dyn ObjectStore, bucket: Bucket, key: &str) -> bool {
+        match store.get_raw(bucket, key).await {
+            Ok(_) => true,
+            Err(err) => {
+                assert_matches!(err, ObjectStoreError::KeyNotFound(_));
+                false
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn removing_artifacts_for_proven_l1_batches() {
+        let core_pool = ConnectionPool::<Core>::test_pool().await;
+        let prover_pool = ConnectionPool::<Prover>::test_pool().await;
+        prepare_core_storage(&core_pool).await;
+        prepare_prover_storage(&prover_pool).await;
+        let store = MockObjectStore::arc();
+        put_artifacts(&*store, 1).await;
+        put_artifacts(&*store, 2).await;
+
+        let mut gc = ObjectStoreGarbageCollector::new(
+            core_pool.clone(),
+            prover_pool.clone(),
+            store.clone(),
+            100,
+            0,
+            true,
+        );
+        gc.run_routine_task().await.unwrap();
+        assert_eq!(gc.last_processed_l1_batch, L1BatchNumber(1));
+        // No artifacts should be removed in the dry-run mode.
+        assert!(artifact_exists(&*store, Bucket::ProverJobsFri, "circuit_1_1").await);
+        assert!(artifact_exists(&*store, Bucket::WitnessInput, "witness_inputs_1").await);
+
+        let mut gc = ObjectStoreGarbageCollector::new(
+            core_pool.clone(),
+            prover_pool,
+            store.clone(),
+            100,
+            0,
+            false,
+        );
+        gc.run_routine_task().await.unwrap();
+        assert_eq!(gc.last_processed_l1_batch, L1BatchNumber(1));
+
+        for (bucket, key) in [
+            (Bucket::WitnessInput, "merkle_paths_1"),
+            (Bucket::WitnessInput, "witness_inputs_1"),
+            (Bucket::ProverJobsFri, "circuit_1_1"),
+            (Bucket::ProverJobsFri, "circuit_1_2"),
+        ] {
+            assert!(
+                !artifact_exists(&*store, bucket, key).await,
+                "{bucket}/{key}"
+            );
+        }
+        // The final proof and artifacts for the unproven batch must be retained.
+        for (bucket, key) in [
+            (Bucket::ProofsFri, "l1_batch_proof_1"),
+            (Bucket::WitnessInput, "merkle_paths_2"),
+            (Bucket::WitnessInput, "witness_inputs_2"),
+            (Bucket::ProverJobsFri, "circuit_2_1"),
+            (Bucket::ProverJobsFri, "circuit_2_2"),
+        ] {
+            assert!(
+                artifact_exists(&*store, bucket, key).await,
+                "{bucket}/{key}"
+            );
+        }
+
+        // The batch should be marked as processed despite the missing `vm_run_1` artifact.
+        let l1_batches = core_pool
+            .connection()
+            .await
+            .unwrap()
+            .proof_generation_dal()
+            .get_l1_batches_for_blob_gc(Duration::ZERO, L1BatchNumber(0), 10)
+            .await
+            .unwrap();
+        assert!(l1_batches.is_empty(), "{l1_batches:?}");
+    }
+}
@@ -44,6 +44,7 @@ pub struct MerkleTreePruningTask {
     pool: ConnectionPool<Core>,
     health_updater: HealthUpdater,
     poll_interval: Duration,
+    archived_l1_batches: u64,
 }
 
 impl MerkleTreePruningTask {
@@ -57,9 +58,17 @@ impl MerkleTreePruningTask {
             pool,
             health_updater: ReactiveHealthCheck::new("tree_pruner").1,
             poll_interval,
+            archived_l1_batches: 0,
         }
     }
 
+    /// Sets the number of hard-pruned L1 batches for which the tree should retain its versions (aka archive mode).
+    /// By default, the tree is pruned in sync with Postgres.
+    pub fn with_archived_l1_batches(mut self, count: u64) -> Self {
+        self.archived_l1_batches = count;
+        self
+    }
+
     pub fn health_check(&self) -> ReactiveHealthCheck {
         self.health_updater.subscribe()
     }
@@ -101,9 +110,14 @@ impl MerkleTreePruningTask {
             let pruning_info = storage.pruning_dal().get_pruning_info().await?;
             drop(storage);
 
-            if let Some(l1_batch_number) = pruning_info.last_hard_pruned_l1_batch {
-                let target_retained_l1_batch_number = l1_batch_number + 1;
-                let target_retained_version = u64::from(target_retained_l1_batch_number.0);
+            let target_retained_version = pruning_info
+                .last_hard_pruned_l1_batch
+                .map(|number| (u64::from(number.0) + 1).saturating_sub(self.archived_l1_batches))
+                .filter(|&version| version > 0);
+            if let Some(target_retained_version) = target_retained_version {
+                // `unwrap()` is safe: the version does not exceed the next L1 batch number
+                let target_retained_l1_batch_number =
+                    L1BatchNumber(u32::try_from(target_retained_version).unwrap());
                 let Ok(prev_target_version) =
                     pruner_handle.set_target_retained_version(target_retained_version)
                 else {
@@ -209,6 +223,59 @@ mod tests {
             .await;
     }
 
+    #[tokio::test]
+    async fn tree_pruning_with_archived_l1_batches() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+        let config = mock_config(temp_dir.path());
+        let mut storage = pool.connection().await.unwrap();
+        insert_genesis_batch(&mut storage, &GenesisParams::mock())
+            .await
+            .unwrap();
+        reset_db_state(&pool, 5).await;
+
+        let mut calculator = MetadataCalculator::new(config, None, pool.clone())
+            .await
+            .unwrap();
+        let reader = calculator.tree_reader();
+        let pruning_task = calculator
+            .pruning_task(POLL_INTERVAL)
+            .with_archived_l1_batches(2);
+        let mut health_check = pruning_task.health_check();
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let calculator_handle = tokio::spawn(calculator.run(stop_receiver.clone()));
+        let pruning_task_handle = tokio::spawn(pruning_task.run(stop_receiver));
+
+        let reader = reader.wait().await.unwrap();
+        while reader.clone().info().await.next_l1_batch_number < L1BatchNumber(6) {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+
+        storage
+            .pruning_dal()
+            .hard_prune_batches_range(L1BatchNumber(3), L2BlockNumber(3))
+            .await
+            .unwrap();
+
+        health_check
+            .wait_for(|health| {
+                let details = health.details().unwrap();
+                details["target_retained_l1_batch_number"] == 2
+            })
+            .await;
+        while reader.clone().info().await.min_l1_batch_number.unwrap() < L1BatchNumber(2) {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+        // Versions for the archived L1 batches must be retained.
+        let info = reader.clone().info().await;
+        assert_eq!(info.min_l1_batch_number, Some(L1BatchNumber(2)));
+        reader.verify_consistency(L1BatchNumber(2)).await.unwrap();
+
+        stop_sender.send_replace(true);
+        calculator_handle.await.unwrap().unwrap();
+        pruning_task_handle.await.unwrap().unwrap();
+    }
+
     #[derive(Debug)]
     enum PrematureExitScenario {
         CalculatorDrop,
@@ -0,0 +1,133 @@
+use std::{collections::HashSet, sync::Arc};
+
+use zksync_config::configs::ExternalPriceApiClientConfig;
+use zksync_external_price_api::{
+    aggregating_client::AggregatingPriceAPIClient, chainlink::ChainlinkPriceAPIClient,
+    cmc_api::CmcPriceAPIClient, coingecko_api::CoinGeckoPriceAPIClient,
+    forced_price_client::ForcedPriceClient, uniswap_twap::UniswapTwapPriceAPIClient,
+    NoOpPriceAPIClient, PriceAPIClient,
+};
+
+use crate::{
+    implementations::{
+        layers::base_token::{
+            chainlink_client::ChainlinkClientLayer, cmc_client::CmcClientLayer,
+            coingecko_client::CoingeckoClientLayer, forced_price_client::ForcedPriceClientLayer,
+            no_op_external_price_api_client::NoOpExternalPriceApiClientLayer,
+            uniswap_twap_client::UniswapTwapClientLayer,
+        },
+        resources::{
+            eth_interface::EthInterfaceResource, price_api_client::PriceAPIClientResource,
+        },
+    },
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for `AggregatingPriceAPIClient`
+///
+/// Inserts a resource with a client aggregating base token prices from multiple sources to be used
+/// by the `BaseTokenRatioPersister`. Sources are configured in `aggregated_sources` of the client config.
+///
+/// ## Requests resources
+///
+/// - `EthInterfaceResource` (optional; required if any of the sources reads prices from L1)
+#[derive(Debug)]
+pub struct AggregatingPriceClientLayer {
+    config: ExternalPriceApiClientConfig,
+}
+
+impl AggregatingPriceClientLayer {
+    /// Identifier of used client type.
+    /// Can be used to choose the layer for the client based on configuration variables.
+    pub const CLIENT_NAME: &'static str = "aggregated";
+
+    pub fn new(config: ExternalPriceApiClientConfig) -> Self {
+        Self { config }
+    }
+
+    fn create_source(
+        config: &ExternalPriceApiClientConfig,
+        eth_client: Option<&EthInterfaceResource>,
+    ) -> Result<Arc<dyn PriceAPIClient>, WiringError> {
+        let require_eth_client = || {
+            eth_client
+                .map(|EthInterfaceResource(client)| client.clone())
+                .ok_or_else(|| {
+                    WiringError::Configuration(format!(
+                        "Price API client source `{}` requires an L1 client",
+                        config.source
+                    ))
+                })
+        };
+        let map_err = |err: anyhow::Error| WiringError::Configuration(format!("{err:#}"));
+
+        Ok(match config.source.as_str() {
+            CoingeckoClientLayer::CLIENT_NAME => {
+                Arc::new(CoinGeckoPriceAPIClient::new(config.clone()))
+            }
+            CmcClientLayer::CLIENT_NAME => {
+                Arc::new(CmcPriceAPIClient::new(config.clone()).map_err(map_err)?)
+            }
+            UniswapTwapClientLayer::CLIENT_NAME => Arc::new(
+                UniswapTwapPriceAPIClient::new(config.clone(), require_eth_client()?)
+                    .map_err(map_err)?,
+            ),
+            ChainlinkClientLayer::CLIENT_NAME => Arc::new(
+                ChainlinkPriceAPIClient::new(config.clone(), require_eth_client()?)
+                    .map_err(map_err)?,
+            ),
+            ForcedPriceClientLayer::CLIENT_NAME => Arc::new(ForcedPriceClient::new(config.clone())),
+            NoOpExternalPriceApiClientLayer::CLIENT_NAME => Arc::new(NoOpPriceAPIClient),
+            source => {
+                return Err(WiringError::Configuration(format!(
+                    "Unsupported aggregated price API client source: {source}"
+                )));
+            }
+        })
+    }
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub eth_client: Option<EthInterfaceResource>,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    pub price_api_client: PriceAPIClientResource,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for AggregatingPriceClientLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "aggregating_price_client"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let mut source_names = HashSet::new();
+        let mut sources = Vec::with_capacity(self.config.aggregated_sources.len());
+        for source_config in &self.config.aggregated_sources {
+            // Source names are used as metric labels, so they must be unique.
+            if !source_names.insert(source_config.source.as_str()) {
+                return Err(WiringError::Configuration(format!(
+                    "Price API client source `{}` is aggregated more than once",
+                    source_config.source
+                )));
+            }
+            let client = Self::create_source(source_config, input.eth_client.as_ref())?;
+            sources.push((source_config.source.clone(), client));
+        }
+
+        let client = AggregatingPriceAPIClient::new(&self.config, sources)
+            .map_err(|err| WiringError::Configuration(format!("{err:#}")))?;
+        Ok(Output {
+            price_api_client: Arc::new(client).into(),
+        })
+    }
+}
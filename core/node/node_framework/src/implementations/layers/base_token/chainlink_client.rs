@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use zksync_config::configs::ExternalPriceApiClientConfig;
+use zksync_external_price_api::chainlink::ChainlinkPriceAPIClient;
+
+use crate::{
+    implementations::resources::{
+        eth_interface::EthInterfaceResource, price_api_client::PriceAPIClientResource,
+    },
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for `ChainlinkPriceAPIClient`
+///
+/// Responsible for inserting a resource with a client to get base token prices from a Chainlink price feed on L1
+/// to be used by the `BaseTokenRatioPersister`.
+///
+/// ## Requests resources
+///
+/// - `EthInterfaceResource`
+#[derive(Debug)]
+pub struct ChainlinkClientLayer {
+    config: ExternalPriceApiClientConfig,
+}
+
+impl ChainlinkClientLayer {
+    /// Identifier of used client type.
+    /// Can be used to choose the layer for the client based on configuration variables.
+    pub const CLIENT_NAME: &'static str = "chainlink";
+
+    pub fn new(config: ExternalPriceApiClientConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub eth_client: EthInterfaceResource,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    pub price_api_client: PriceAPIClientResource,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for ChainlinkClientLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "chainlink_price_client"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let EthInterfaceResource(eth_client) = input.eth_client;
+        let client = ChainlinkPriceAPIClient::new(self.config, eth_client)
+            .map_err(|err| WiringError::Configuration(format!("{err:#}")))?;
+
+        Ok(Output {
+            price_api_client: Arc::new(client).into(),
+        })
+    }
+}
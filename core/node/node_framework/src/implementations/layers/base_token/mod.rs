@@ -1,5 +1,9 @@
+pub mod aggregating_price_client;
 pub mod base_token_ratio_persister;
 pub mod base_token_ratio_provider;
+pub mod chainlink_client;
+pub mod cmc_client;
 pub mod coingecko_client;
 pub mod forced_price_client;
 pub mod no_op_external_price_api_client;
+pub mod uniswap_twap_client;
@@ -8,7 +8,11 @@ use zksync_types::{commitment::L1BatchCommitmentMode, L2ChainId};
 use crate::{
     implementations::resources::{
         circuit_breakers::CircuitBreakersResource,
-        eth_interface::{BoundEthInterfaceForBlobsResource, BoundEthInterfaceResource},
+        eth_interface::{
+            BoundEthInterfaceForBlobsResource, BoundEthInterfaceForPreviousOperatorResource,
+            BoundEthInterfaceResource,
+        },
+        l1_tx_params::L1TxParamsResource,
         object_store::ObjectStoreResource,
         pools::{MasterPool, PoolResource, ReplicaPool},
     },
@@ -30,7 +34,9 @@ use crate::{
 /// - `PoolResource<ReplicaPool>`
 /// - `BoundEthInterfaceResource`
 /// - `BoundEthInterfaceForBlobsResource` (optional)
+/// - `BoundEthInterfaceForPreviousOperatorResource` (optional; enables operator key rotation)
 /// - `ObjectStoreResource`
+/// - `L1TxParamsResource` (optional; used to postpone commit operations based on L1 fees if configured)
 /// - `CircuitBreakersResource` (adds a circuit breaker)
 ///
 /// ## Adds tasks
@@ -51,7 +57,9 @@ pub struct Input {
     pub replica_pool: PoolResource<ReplicaPool>,
     pub eth_client: BoundEthInterfaceResource,
     pub eth_client_blobs: Option<BoundEthInterfaceForBlobsResource>,
+    pub eth_client_previous_operator: Option<BoundEthInterfaceForPreviousOperatorResource>,
     pub object_store: ObjectStoreResource,
+    pub l1_tx_params: Option<L1TxParamsResource>,
     #[context(default)]
     pub circuit_breakers: CircuitBreakersResource,
 }
@@ -101,14 +109,20 @@ impl WiringLayer for EthTxAggregatorLayer {
         let eth_client_blobs_addr = eth_client_blobs
             .as_deref()
             .map(BoundEthInterface::sender_account);
+        let previous_operator_addr = input
+            .eth_client_previous_operator
+            .map(|client| client.0.sender_account());
 
         let config = self.eth_sender_config.sender.context("sender")?;
-        let aggregator = Aggregator::new(
+        let mut aggregator = Aggregator::new(
             config.clone(),
             object_store,
             eth_client_blobs_addr.is_some(),
             self.l1_batch_commit_data_generator_mode,
         );
+        if let Some(l1_tx_params) = input.l1_tx_params {
+            aggregator = aggregator.with_fee_forecast(l1_tx_params.0);
+        }
 
         let eth_tx_aggregator = EthTxAggregator::new(
             master_pool.clone(),
@@ -120,6 +134,7 @@ impl WiringLayer for EthTxAggregatorLayer {
             self.contracts_config.diamond_proxy_addr,
             self.zksync_network_id,
             eth_client_blobs_addr,
+            previous_operator_addr,
         )
         .await;
 
@@ -6,7 +6,11 @@ use zksync_eth_sender::EthTxManager;
 use crate::{
     implementations::resources::{
         circuit_breakers::CircuitBreakersResource,
-        eth_interface::{BoundEthInterfaceForBlobsResource, BoundEthInterfaceResource},
+        eth_interface::{
+            BoundEthInterfaceForBlobsResource, BoundEthInterfaceForPreviousOperatorResource,
+            BoundEthInterfaceResource,
+        },
+        healthcheck::AppHealthCheckResource,
         l1_tx_params::L1TxParamsResource,
         pools::{MasterPool, PoolResource, ReplicaPool},
     },
@@ -27,8 +31,10 @@ use crate::{
 /// - `PoolResource<ReplicaPool>`
 /// - `BoundEthInterfaceResource`
 /// - `BoundEthInterfaceForBlobsResource` (optional)
+/// - `BoundEthInterfaceForPreviousOperatorResource` (optional; enables operator key rotation)
 /// - `L1TxParamsResource`
 /// - `CircuitBreakersResource` (adds a circuit breaker)
+/// - `AppHealthCheckResource` (adds a health check)
 ///
 /// ## Adds tasks
 ///
@@ -45,9 +51,12 @@ pub struct Input {
     pub replica_pool: PoolResource<ReplicaPool>,
     pub eth_client: BoundEthInterfaceResource,
     pub eth_client_blobs: Option<BoundEthInterfaceForBlobsResource>,
+    pub eth_client_previous_operator: Option<BoundEthInterfaceForPreviousOperatorResource>,
     pub l1_tx_params: L1TxParamsResource,
     #[context(default)]
     pub circuit_breakers: CircuitBreakersResource,
+    #[context(default)]
+    pub app_health: AppHealthCheckResource,
 }
 
 #[derive(Debug, IntoContext)]
@@ -79,6 +88,7 @@ impl WiringLayer for EthTxManagerLayer {
 
         let eth_client = input.eth_client.0;
         let eth_client_blobs = input.eth_client_blobs.map(|c| c.0);
+        let eth_client_previous_operator = input.eth_client_previous_operator.map(|c| c.0);
 
         let config = self.eth_sender_config.sender.context("sender")?;
 
@@ -90,8 +100,15 @@ impl WiringLayer for EthTxManagerLayer {
             gas_adjuster,
             eth_client,
             eth_client_blobs,
+            eth_client_previous_operator,
         );
 
+        input
+            .app_health
+            .0
+            .insert_component(eth_tx_manager.health_check())
+            .map_err(WiringError::internal)?;
+
         // Insert circuit breaker.
         input
             .circuit_breakers
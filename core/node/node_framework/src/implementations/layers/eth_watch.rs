@@ -78,7 +78,8 @@ impl WiringLayer for EthWatchLayer {
             main_pool,
             self.eth_watch_config.poll_interval(),
         )
-        .await?;
+        .await?
+        .with_event_subscriptions(&self.eth_watch_config.event_subscriptions)?;
 
         Ok(Output { eth_watch })
     }
@@ -9,12 +9,13 @@ use zksync_house_keeper::{
         FriGpuProverArchiver, FriProofCompressorJobRetryManager, FriProofCompressorQueueReporter,
         FriProverJobRetryManager, FriProverJobsArchiver, FriProverQueueReporter,
         FriWitnessGeneratorJobRetryManager, FriWitnessGeneratorQueueReporter,
-        WaitingToQueuedFriWitnessJobMover,
+        ObjectStoreGarbageCollector, WaitingToQueuedFriWitnessJobMover,
     },
 };
+use zksync_object_store::ObjectStoreFactory;
 
 use crate::{
-    implementations::resources::pools::{PoolResource, ProverPool, ReplicaPool},
+    implementations::resources::pools::{MasterPool, PoolResource, ProverPool, ReplicaPool},
     service::StopReceiver,
     task::{Task, TaskId},
     wiring_layer::{WiringError, WiringLayer},
@@ -37,6 +38,8 @@ pub struct HouseKeeperLayer {
 pub struct Input {
     pub replica_pool: PoolResource<ReplicaPool>,
     pub prover_pool: PoolResource<ProverPool>,
+    /// Required only if object store GC is enabled.
+    pub master_pool: Option<PoolResource<MasterPool>>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -62,6 +65,8 @@ pub struct Output {
     pub fri_proof_compressor_stats_reporter: FriProofCompressorQueueReporter,
     #[context(task)]
     pub fri_proof_compressor_job_retry_manager: FriProofCompressorJobRetryManager,
+    #[context(task)]
+    pub object_store_gc: Option<ObjectStoreGarbageCollector>,
 }
 
 impl HouseKeeperLayer {
@@ -164,6 +169,35 @@ impl WiringLayer for HouseKeeperLayer {
             prover_pool.clone(),
         );
 
+        let object_store_gc = match self.house_keeper_config.object_store_gc_params() {
+            Some((gc_interval, retention_period)) => {
+                let Some(master_pool) = input.master_pool else {
+                    return Err(WiringError::Configuration(
+                        "Master pool is required for object store GC".into(),
+                    ));
+                };
+                // Artifacts are removed from the prover object store, which may differ from the core one.
+                let Some(object_store_config) = self.fri_prover_config.prover_object_store.clone()
+                else {
+                    return Err(WiringError::Configuration(
+                        "Prover object store config is required for object store GC".into(),
+                    ));
+                };
+                let object_store = ObjectStoreFactory::new(object_store_config)
+                    .create_store()
+                    .await?;
+                Some(ObjectStoreGarbageCollector::new(
+                    master_pool.get().await?,
+                    prover_pool.clone(),
+                    object_store,
+                    gc_interval,
+                    retention_period,
+                    self.house_keeper_config.object_store_gc_dry_run,
+                ))
+            }
+            None => None,
+        };
+
         Ok(Output {
             l1_batch_metrics_reporter,
             fri_prover_job_retry_manager,
@@ -175,6 +209,7 @@ impl WiringLayer for HouseKeeperLayer {
             fri_prover_stats_reporter,
             fri_proof_compressor_stats_reporter,
             fri_proof_compressor_job_retry_manager: fri_proof_compressor_retry_manager,
+            object_store_gc,
         })
     }
 }
@@ -288,3 +323,14 @@ impl Task for FriGpuProverArchiver {
         (*self).run(stop_receiver.0).await
     }
 }
+
+#[async_trait::async_trait]
+impl Task for ObjectStoreGarbageCollector {
+    fn id(&self) -> TaskId {
+        "object_store_gc".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
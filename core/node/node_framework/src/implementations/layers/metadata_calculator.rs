@@ -30,6 +30,7 @@ pub struct MetadataCalculatorLayer {
     config: MetadataCalculatorConfig,
     tree_api_config: Option<MerkleTreeApiConfig>,
     pruning_config: Option<Duration>,
+    archived_l1_batches: u64,
 }
 
 #[derive(Debug, FromContext)]
@@ -64,6 +65,7 @@ impl MetadataCalculatorLayer {
             config,
             tree_api_config: None,
             pruning_config: None,
+            archived_l1_batches: 0,
         }
     }
 
@@ -76,6 +78,13 @@ impl MetadataCalculatorLayer {
         self.pruning_config = Some(pruning_config);
         self
     }
+
+    /// Sets the number of hard-pruned L1 batches for which the tree retains its versions. Only has effect
+    /// if pruning is enabled via [`Self::with_pruning_config()`].
+    pub fn with_archived_l1_batches(mut self, count: u64) -> Self {
+        self.archived_l1_batches = count;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -131,7 +140,9 @@ impl WiringLayer for MetadataCalculatorLayer {
             .pruning_config
             .map(
                 |pruning_removal_delay| -> Result<MerkleTreePruningTask, WiringError> {
-                    let pruning_task = metadata_calculator.pruning_task(pruning_removal_delay);
+                    let pruning_task = metadata_calculator
+                        .pruning_task(pruning_removal_delay)
+                        .with_archived_l1_batches(self.archived_l1_batches);
                     app_health
                         .insert_component(pruning_task.health_check())
                         .map_err(|err| WiringError::Internal(err.into()))?;
@@ -27,7 +27,9 @@ pub mod pruning;
 pub mod query_eth_client;
 pub mod reorg_detector;
 pub mod sigint;
+pub mod signing_eth_client;
 pub mod state_keeper;
+pub mod storage_notifications;
 pub mod sync_state_updater;
 pub mod tee_verifier_input_producer;
 pub mod tree_data_fetcher;
@@ -8,7 +8,8 @@ use zksync_types::L1ChainId;
 
 use crate::{
     implementations::resources::eth_interface::{
-        BoundEthInterfaceForBlobsResource, BoundEthInterfaceResource, EthInterfaceResource,
+        BoundEthInterfaceForBlobsResource, BoundEthInterfaceForPreviousOperatorResource,
+        BoundEthInterfaceResource, EthInterfaceResource,
     },
     wiring_layer::{WiringError, WiringLayer},
     FromContext, IntoContext,
@@ -35,6 +36,8 @@ pub struct Output {
     pub signing_client: BoundEthInterfaceResource,
     /// Only provided if the blob operator key is provided to the layer.
     pub signing_client_for_blobs: Option<BoundEthInterfaceForBlobsResource>,
+    /// Only provided if the previous operator key is provided to the layer.
+    pub signing_client_for_previous_operator: Option<BoundEthInterfaceForPreviousOperatorResource>,
 }
 
 impl PKSigningEthClientLayer {
@@ -87,14 +90,27 @@ impl WiringLayer for PKSigningEthClientLayer {
                 self.contracts_config.diamond_proxy_addr,
                 gas_adjuster_config.default_priority_fee_per_gas,
                 self.l1_chain_id,
-                query_client,
+                query_client.clone(),
             );
             BoundEthInterfaceForBlobsResource(Box::new(signing_client_for_blobs))
         });
 
+        let signing_client_for_previous_operator =
+            self.wallets.previous_operator.map(|previous_operator| {
+                let signing_client = PKSigningClient::new_raw(
+                    previous_operator.private_key().clone(),
+                    self.contracts_config.diamond_proxy_addr,
+                    gas_adjuster_config.default_priority_fee_per_gas,
+                    self.l1_chain_id,
+                    query_client,
+                );
+                BoundEthInterfaceForPreviousOperatorResource(Box::new(signing_client))
+            });
+
         Ok(Output {
             signing_client,
             signing_client_for_blobs,
+            signing_client_for_previous_operator,
         })
     }
 }
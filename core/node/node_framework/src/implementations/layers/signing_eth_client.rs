@@ -0,0 +1,138 @@
+use std::fmt;
+
+use anyhow::Context as _;
+use zksync_config::{configs::ContractsConfig, EthConfig};
+use zksync_contracts::hyperchain_contract;
+use zksync_eth_client::clients::SigningClient;
+use zksync_eth_signer::EthereumSigner;
+use zksync_types::L1ChainId;
+
+use crate::{
+    implementations::resources::eth_interface::{
+        BoundEthInterfaceForBlobsResource, BoundEthInterfaceForPreviousOperatorResource,
+        BoundEthInterfaceResource, EthInterfaceResource,
+    },
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for [`SigningClient`] with an arbitrary [`EthereumSigner`], e.g. a `RemoteSigner`
+/// or an `HsmSigner`. Unlike [`PKSigningEthClientLayer`](super::pk_signing_eth_client::PKSigningEthClientLayer),
+/// doesn't require operator private keys.
+#[derive(Debug)]
+pub struct SigningEthClientLayer<S> {
+    eth_sender_config: EthConfig,
+    contracts_config: ContractsConfig,
+    l1_chain_id: L1ChainId,
+    operator_signer: S,
+    blob_operator_signer: Option<S>,
+    previous_operator_signer: Option<S>,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub eth_client: EthInterfaceResource,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    pub signing_client: BoundEthInterfaceResource,
+    /// Only provided if the blob operator signer is provided to the layer.
+    pub signing_client_for_blobs: Option<BoundEthInterfaceForBlobsResource>,
+    /// Only provided if the previous operator signer is provided to the layer.
+    pub signing_client_for_previous_operator: Option<BoundEthInterfaceForPreviousOperatorResource>,
+}
+
+impl<S: EthereumSigner + fmt::Debug> SigningEthClientLayer<S> {
+    pub fn new(
+        eth_sender_config: EthConfig,
+        contracts_config: ContractsConfig,
+        l1_chain_id: L1ChainId,
+        operator_signer: S,
+        blob_operator_signer: Option<S>,
+    ) -> Self {
+        Self {
+            eth_sender_config,
+            contracts_config,
+            l1_chain_id,
+            operator_signer,
+            blob_operator_signer,
+            previous_operator_signer: None,
+        }
+    }
+
+    /// Sets the signer for the previous main operator, which is used while the operator key is being rotated.
+    pub fn with_previous_operator_signer(mut self, signer: S) -> Self {
+        self.previous_operator_signer = Some(signer);
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl<S: EthereumSigner + fmt::Debug> WiringLayer for SigningEthClientLayer<S> {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "signing_eth_client_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let gas_adjuster_config = self
+            .eth_sender_config
+            .gas_adjuster
+            .as_ref()
+            .context("gas_adjuster config is missing")?;
+        let default_priority_fee_per_gas = gas_adjuster_config.default_priority_fee_per_gas;
+        let diamond_proxy_addr = self.contracts_config.diamond_proxy_addr;
+        let l1_chain_id = self.l1_chain_id;
+        let EthInterfaceResource(query_client) = input.eth_client;
+
+        let create_client = |signer: S, query_client| async move {
+            let operator_address = signer
+                .get_address()
+                .await
+                .context("failed getting operator address from signer")?;
+            tracing::info!("Operator address: {operator_address:?}");
+            anyhow::Ok(SigningClient::new(
+                query_client,
+                hyperchain_contract(),
+                operator_address,
+                signer,
+                diamond_proxy_addr,
+                default_priority_fee_per_gas.into(),
+                l1_chain_id,
+            ))
+        };
+
+        let signing_client = create_client(self.operator_signer, query_client.clone()).await?;
+        let signing_client = BoundEthInterfaceResource(Box::new(signing_client));
+
+        let signing_client_for_blobs = if let Some(signer) = self.blob_operator_signer {
+            let signing_client_for_blobs = create_client(signer, query_client.clone()).await?;
+            Some(BoundEthInterfaceForBlobsResource(Box::new(
+                signing_client_for_blobs,
+            )))
+        } else {
+            None
+        };
+
+        let signing_client_for_previous_operator =
+            if let Some(signer) = self.previous_operator_signer {
+                let signing_client = create_client(signer, query_client).await?;
+                Some(BoundEthInterfaceForPreviousOperatorResource(Box::new(
+                    signing_client,
+                )))
+            } else {
+                None
+            };
+
+        Ok(Output {
+            signing_client,
+            signing_client_for_blobs,
+            signing_client_for_previous_operator,
+        })
+    }
+}
@@ -1,16 +1,31 @@
-use zksync_state_keeper::MainBatchExecutor;
+use zksync_state_keeper::{CustomValidationRules, MainBatchExecutor};
+use zksync_types::Address;
 
 use crate::{
-    implementations::resources::state_keeper::BatchExecutorResource,
+    implementations::resources::{
+        pools::{MasterPool, PoolResource},
+        state_keeper::BatchExecutorResource,
+    },
     wiring_layer::{WiringError, WiringLayer},
-    IntoContext,
+    FromContext, IntoContext,
 };
 
 /// Wiring layer for `MainBatchExecutor`, part of the state keeper responsible for running the VM.
+///
+/// ## Requests resources
+///
+/// - `PoolResource<MasterPool>` (only if account validation is enabled)
 #[derive(Debug)]
 pub struct MainBatchExecutorLayer {
     save_call_traces: bool,
     optional_bytecode_compression: bool,
+    account_validation: Option<(CustomValidationRules, Vec<Address>)>,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub master_pool: Option<PoolResource<MasterPool>>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -24,22 +39,43 @@ impl MainBatchExecutorLayer {
         Self {
             save_call_traces,
             optional_bytecode_compression,
+            account_validation: None,
         }
     }
+
+    /// Enforces account validation rules for executed L2 transactions. Should only be used on the main node.
+    pub fn with_account_validation(
+        mut self,
+        rules: CustomValidationRules,
+        whitelisted_tokens_for_aa: Vec<Address>,
+    ) -> Self {
+        self.account_validation = Some((rules, whitelisted_tokens_for_aa));
+        self
+    }
 }
 
 #[async_trait::async_trait]
 impl WiringLayer for MainBatchExecutorLayer {
-    type Input = ();
+    type Input = Input;
     type Output = Output;
 
     fn layer_name(&self) -> &'static str {
         "main_batch_executor_layer"
     }
 
-    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
-        let builder =
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let mut builder =
             MainBatchExecutor::new(self.save_call_traces, self.optional_bytecode_compression);
+        if let Some((rules, whitelisted_tokens_for_aa)) = self.account_validation {
+            let master_pool = input.master_pool.ok_or_else(|| {
+                WiringError::Configuration(
+                    "Account validation in the state keeper requires a master pool".into(),
+                )
+            })?;
+            // The pool is only used to load trusted tokens, which happens rarely.
+            let pool = master_pool.get_singleton().await?;
+            builder = builder.with_account_validation(rules, whitelisted_tokens_for_aa, pool);
+        }
 
         Ok(Output {
             batch_executor: builder.into(),
@@ -4,7 +4,7 @@ use anyhow::Context;
 use zksync_state::{AsyncCatchupTask, ReadStorageFactory};
 use zksync_state_keeper::{
     seal_criteria::ConditionalSealer, AsyncRocksdbCache, BatchExecutor, OutputHandler,
-    StateKeeperIO, ZkSyncStateKeeper,
+    PendingStatePublisher, StateKeeperIO, ZkSyncStateKeeper,
 };
 use zksync_storage::RocksDB;
 
@@ -21,7 +21,7 @@ use crate::{
         pools::{MasterPool, PoolResource},
         state_keeper::{
             BatchExecutorResource, ConditionalSealerResource, OutputHandlerResource,
-            StateKeeperIOResource,
+            PendingStateResource, StateKeeperIOResource,
         },
     },
     service::{ShutdownHook, StopReceiver},
@@ -31,6 +31,10 @@ use crate::{
 };
 
 /// Wiring layer for the state keeper.
+///
+/// ## Adds resources
+///
+/// - `PendingStateResource`
 #[derive(Debug)]
 pub struct StateKeeperLayer {
     state_keeper_db_path: String,
@@ -55,6 +59,7 @@ pub struct Output {
     #[context(task)]
     pub rocksdb_catchup: AsyncCatchupTask,
     pub rocksdb_termination_hook: ShutdownHook,
+    pub pending_state: PendingStateResource,
 }
 
 impl StateKeeperLayer {
@@ -100,12 +105,15 @@ impl WiringLayer for StateKeeperLayer {
             self.rocksdb_options,
         );
 
+        let pending_state = PendingStatePublisher::new();
+        let pending_state_reader = pending_state.subscribe();
         let state_keeper = StateKeeperTask {
             io,
             batch_executor_base,
             output_handler,
             sealer,
             storage_factory: Arc::new(storage_factory),
+            pending_state,
         };
 
         let rocksdb_termination_hook = ShutdownHook::new("rocksdb_terminaton", async {
@@ -118,6 +126,7 @@ impl WiringLayer for StateKeeperLayer {
             state_keeper,
             rocksdb_catchup,
             rocksdb_termination_hook,
+            pending_state: pending_state_reader.into(),
         })
     }
 }
@@ -129,6 +138,7 @@ pub struct StateKeeperTask {
     output_handler: OutputHandler,
     sealer: Arc<dyn ConditionalSealer>,
     storage_factory: Arc<dyn ReadStorageFactory>,
+    pending_state: PendingStatePublisher,
 }
 
 #[async_trait::async_trait]
@@ -145,7 +155,8 @@ impl Task for StateKeeperTask {
             self.output_handler,
             self.sealer,
             self.storage_factory,
-        );
+        )
+        .with_pending_state(self.pending_state);
         state_keeper.run().await
     }
 }
@@ -10,6 +10,7 @@ use crate::{
     implementations::resources::{
         pools::{MasterPool, PoolResource},
         state_keeper::OutputHandlerResource,
+        storage_notifications::StorageNotificationsResource,
         sync_state::SyncStateResource,
     },
     resource::Unique,
@@ -25,6 +26,7 @@ use crate::{
 ///
 /// - `PoolResource<MasterPool>`
 /// - `SyncStateResource` (optional)
+/// - `StorageNotificationsResource` (optional)
 ///
 /// ## Adds resources
 ///
@@ -52,6 +54,7 @@ pub struct OutputHandlerLayer {
 pub struct Input {
     pub master_pool: PoolResource<MasterPool>,
     pub sync_state: Option<SyncStateResource>,
+    pub storage_notifications: Option<StorageNotificationsResource>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -103,7 +106,7 @@ impl WiringLayer for OutputHandlerLayer {
             .get_custom(L2BlockSealProcess::subtasks_len())
             .await
             .context("Get master pool")?;
-        let (mut persistence, l2_block_sealer) = StateKeeperPersistence::new(
+        let (mut persistence, mut l2_block_sealer) = StateKeeperPersistence::new(
             persistence_pool.clone(),
             self.l2_shared_bridge_addr,
             self.l2_block_seal_queue_capacity,
@@ -117,6 +120,10 @@ impl WiringLayer for OutputHandlerLayer {
             tracing::warn!("Disabling persisting protective reads; this should be safe, but is considered an experimental option at the moment");
             persistence = persistence.without_protective_reads();
         }
+        if let Some(StorageNotificationsResource(notifications)) = input.storage_notifications {
+            persistence = persistence.with_notifications(notifications.clone());
+            l2_block_sealer = l2_block_sealer.with_notifications(notifications);
+        }
 
         let tree_writes_persistence = TreeWritesPersistence::new(persistence_pool);
         let mut output_handler = OutputHandler::new(Box::new(persistence))
@@ -0,0 +1,82 @@
+use zksync_config::configs::api::PubSubNotificationsSource;
+use zksync_dal::notifications::StorageNotifications;
+
+use crate::{
+    implementations::resources::{
+        pools::{MasterPool, PoolResource},
+        storage_notifications::StorageNotificationsResource,
+    },
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Capacity of the in-process notifications channel. Lagging receivers don't lose data; they just re-check
+/// Postgres, so the capacity doesn't need to be large.
+const IN_PROCESS_CHANNEL_CAPACITY: usize = 128;
+
+/// Wiring layer for [`StorageNotifications`] used to notify components (e.g., API server pub/sub) about
+/// changes in Postgres.
+///
+/// Note that in-process notifications only work if all senders (state keeper, mempool sink) and receivers
+/// (API server) are run in the same process.
+///
+/// ## Requests resources
+///
+/// - `PoolResource<MasterPool>` (only for Postgres notifications)
+///
+/// ## Adds resources
+///
+/// - `StorageNotificationsResource`
+#[derive(Debug)]
+pub struct StorageNotificationsLayer {
+    source: PubSubNotificationsSource,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub master_pool: Option<PoolResource<MasterPool>>,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    pub storage_notifications: StorageNotificationsResource,
+}
+
+impl StorageNotificationsLayer {
+    pub fn new(source: PubSubNotificationsSource) -> Self {
+        Self { source }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for StorageNotificationsLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "storage_notifications_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let notifications = match self.source {
+            PubSubNotificationsSource::Postgres => {
+                // Notifications must be sent to and received from the main database instance; `NOTIFY` is not
+                // propagated to replicas.
+                let master_pool = input.master_pool.ok_or_else(|| {
+                    WiringError::Configuration(
+                        "Postgres storage notifications require a master pool".into(),
+                    )
+                })?;
+                StorageNotifications::Postgres(master_pool.get().await?)
+            }
+            PubSubNotificationsSource::InProcess => {
+                StorageNotifications::in_process(IN_PROCESS_CHANNEL_CAPACITY)
+            }
+        };
+        Ok(Output {
+            storage_notifications: notifications.into(),
+        })
+    }
+}
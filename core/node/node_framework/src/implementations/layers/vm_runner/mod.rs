@@ -7,6 +7,7 @@ use crate::{
 
 pub mod bwip;
 pub mod protective_reads;
+pub mod trace_exporter;
 
 #[async_trait::async_trait]
 impl<Io: VmRunnerIo> Task for StorageSyncTask<Io> {
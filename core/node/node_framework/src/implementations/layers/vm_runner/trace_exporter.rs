@@ -0,0 +1,103 @@
+use zksync_config::configs::vm_runner::TraceExporterConfig;
+use zksync_types::L2ChainId;
+use zksync_vm_runner::{
+    ConcurrentOutputHandlerFactoryTask, StorageSyncTask, TraceExporter, TraceExporterIo,
+};
+
+use crate::{
+    implementations::resources::{
+        object_store::ObjectStoreResource,
+        pools::{MasterPool, PoolResource},
+    },
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for the VM runner exporting call traces and state diffs to the object store.
+#[derive(Debug)]
+pub struct TraceExporterLayer {
+    trace_exporter_config: TraceExporterConfig,
+    zksync_network_id: L2ChainId,
+}
+
+impl TraceExporterLayer {
+    pub fn new(trace_exporter_config: TraceExporterConfig, zksync_network_id: L2ChainId) -> Self {
+        Self {
+            trace_exporter_config,
+            zksync_network_id,
+        }
+    }
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub master_pool: PoolResource<MasterPool>,
+    pub object_store: ObjectStoreResource,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    #[context(task)]
+    pub output_handler_factory_task: ConcurrentOutputHandlerFactoryTask<TraceExporterIo>,
+    #[context(task)]
+    pub loader_task: StorageSyncTask<TraceExporterIo>,
+    #[context(task)]
+    pub trace_exporter: TraceExporter,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for TraceExporterLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "vm_runner_trace_exporter"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let Input {
+            master_pool,
+            object_store,
+        } = input;
+
+        let (trace_exporter, tasks) = TraceExporter::new(
+            // One for `StorageSyncTask` which can hold a long-term connection in case it needs to
+            // catch up cache.
+            //
+            // One for `ConcurrentOutputHandlerFactoryTask`/`VmRunner` as they need occasional access
+            // to DB for querying last processed batch and last ready to be loaded batch.
+            //
+            // `window_size` connections for the batches being processed concurrently.
+            master_pool
+                .get_custom(self.trace_exporter_config.window_size + 2)
+                .await?,
+            object_store.0,
+            self.trace_exporter_config.db_path,
+            self.zksync_network_id,
+            self.trace_exporter_config.first_processed_batch,
+            self.trace_exporter_config.window_size,
+        )
+        .await?;
+
+        Ok(Output {
+            output_handler_factory_task: tasks.output_handler_factory_task,
+            loader_task: tasks.loader_task,
+            trace_exporter,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for TraceExporter {
+    fn id(&self) -> TaskId {
+        "vm_runner/trace_exporter".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(&stop_receiver.0).await
+    }
+}
@@ -1,15 +1,19 @@
-use std::{num::NonZeroU32, time::Duration};
+use std::{num::NonZeroU32, sync::Arc, time::Duration};
 
 use tokio::{sync::oneshot, task::JoinHandle};
 use zksync_circuit_breaker::replication_lag::ReplicationLagChecker;
 use zksync_config::configs::api::MaxResponseSize;
-use zksync_node_api_server::web3::{state::InternalApiConfig, ApiBuilder, ApiServer, Namespace};
+use zksync_node_api_server::web3::{
+    backend_jsonrpsee::ComputeUnitLimiter, state::InternalApiConfig, ApiBuilder, ApiServer,
+    Namespace,
+};
 
 use crate::{
     implementations::resources::{
         circuit_breakers::CircuitBreakersResource,
         healthcheck::AppHealthCheckResource,
         pools::{PoolResource, ReplicaPool},
+        storage_notifications::StorageNotificationsResource,
         sync_state::SyncStateResource,
         web3_api::{MempoolCacheResource, TreeApiClientResource, TxSenderResource},
     },
@@ -28,6 +32,8 @@ pub struct Web3ServerOptionalConfig {
     pub batch_request_size_limit: Option<usize>,
     pub response_body_size_limit: Option<MaxResponseSize>,
     pub websocket_requests_per_minute_limit: Option<NonZeroU32>,
+    /// Should be shared among HTTP and WS servers, so that compute units are accounted across transports.
+    pub compute_unit_limiter: Option<Arc<ComputeUnitLimiter>>,
     pub with_extended_tracing: bool,
     // Used by circuit breaker.
     pub replication_lag_limit: Option<Duration>,
@@ -57,6 +63,9 @@ impl Web3ServerOptionalConfig {
             api_builder = api_builder
                 .with_websocket_requests_per_minute_limit(websocket_requests_per_minute_limit);
         }
+        if let Some(compute_unit_limiter) = self.compute_unit_limiter {
+            api_builder = api_builder.with_compute_unit_limiter(compute_unit_limiter);
+        }
         api_builder = api_builder.with_extended_tracing(self.with_extended_tracing);
         api_builder
     }
@@ -77,6 +86,7 @@ enum Transport {
 /// - `TxSenderResource`
 /// - `SyncStateResource` (optional)
 /// - `TreeApiClientResource` (optional)
+/// - `StorageNotificationsResource` (optional; used to drive pub/sub subscriptions)
 /// - `MempoolCacheResource`
 /// - `CircuitBreakersResource` (adds a circuit breaker)
 /// - `AppHealthCheckResource` (adds a health check)
@@ -100,6 +110,7 @@ pub struct Input {
     pub tx_sender: TxSenderResource,
     pub sync_state: Option<SyncStateResource>,
     pub tree_api_client: Option<TreeApiClientResource>,
+    pub storage_notifications: Option<StorageNotificationsResource>,
     pub mempool_cache: MempoolCacheResource,
     #[context(default)]
     pub circuit_breakers: CircuitBreakersResource,
@@ -187,6 +198,9 @@ impl WiringLayer for Web3ServerLayer {
         if let Some(sync_state) = sync_state {
             api_builder = api_builder.with_sync_state(sync_state);
         }
+        if let Some(StorageNotificationsResource(notifications)) = input.storage_notifications {
+            api_builder = api_builder.with_storage_notifications(notifications);
+        }
         if let Some(pruning_info_refresh_interval) =
             self.optional_config.pruning_info_refresh_interval
         {
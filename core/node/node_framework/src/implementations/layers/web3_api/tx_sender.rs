@@ -1,9 +1,12 @@
-use std::{sync::Arc, time::Duration};
+use std::{path::PathBuf, sync::Arc, time::Duration};
 
 use tokio::sync::RwLock;
 use zksync_node_api_server::{
     execution_sandbox::{VmConcurrencyBarrier, VmConcurrencyLimiter},
-    tx_sender::{ApiContracts, TxSenderBuilder, TxSenderConfig},
+    tx_sender::{
+        filter::{AddressDenyListFilter, ContractMethodAllowlistFilter, TxLimitsFilter},
+        ApiContracts, TxSenderBuilder, TxSenderConfig,
+    },
 };
 use zksync_state::{PostgresStorageCaches, PostgresStorageCachesTask};
 use zksync_types::Address;
@@ -18,7 +21,7 @@ use crate::{
         fee_input::FeeInputResource,
         main_node_client::MainNodeClientResource,
         pools::{PoolResource, ReplicaPool},
-        state_keeper::ConditionalSealerResource,
+        state_keeper::{ConditionalSealerResource, PendingStateResource},
         web3_api::{TxSenderResource, TxSinkResource},
     },
     service::StopReceiver,
@@ -34,6 +37,19 @@ pub struct PostgresStorageCachesConfig {
     pub latest_values_cache_size: u64,
 }
 
+/// Configuration of built-in policy filters for submitted transactions.
+#[derive(Debug, Default)]
+pub struct TxFiltersConfig {
+    /// Path to the JSON file with deny-listed addresses.
+    pub deny_list_path: Option<PathBuf>,
+    /// Path to the JSON file with allowed contract methods.
+    pub method_allowlist_path: Option<PathBuf>,
+    /// Calldata size and value limits.
+    pub limits: TxLimitsFilter,
+    /// Interval between checks whether filter lists were modified.
+    pub lists_reload_interval: Duration,
+}
+
 /// Wiring layer for the `TxSender`.
 /// Prepares the `TxSender` itself, as well as the tasks required for its maintenance.
 ///
@@ -42,6 +58,7 @@ pub struct PostgresStorageCachesConfig {
 /// - `TxSinkResource`
 /// - `PoolResource<ReplicaPool>`
 /// - `ConditionalSealerResource` (optional)
+/// - `PendingStateResource` (optional)
 /// - `FeeInputResource`
 ///
 /// ## Adds resources
@@ -60,6 +77,7 @@ pub struct TxSenderLayer {
     max_vm_concurrency: usize,
     api_contracts: ApiContracts,
     whitelisted_tokens_for_aa_cache: bool,
+    tx_filters_config: TxFiltersConfig,
 }
 
 #[derive(Debug, FromContext)]
@@ -70,6 +88,7 @@ pub struct Input {
     pub fee_input: FeeInputResource,
     pub main_node_client: Option<MainNodeClientResource>,
     pub sealer: Option<ConditionalSealerResource>,
+    pub pending_state: Option<PendingStateResource>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -97,9 +116,16 @@ impl TxSenderLayer {
             max_vm_concurrency,
             api_contracts,
             whitelisted_tokens_for_aa_cache: false,
+            tx_filters_config: TxFiltersConfig::default(),
         }
     }
 
+    /// Configures built-in policy filters for submitted transactions. By default, no filters are applied.
+    pub fn with_tx_filters(mut self, config: TxFiltersConfig) -> Self {
+        self.tx_filters_config = config;
+        self
+    }
+
     /// Enables the task for fetching the whitelisted tokens for the AA cache from the main node.
     /// Disabled by default.
     ///
@@ -153,6 +179,27 @@ impl WiringLayer for TxSenderLayer {
         if let Some(sealer) = sealer {
             tx_sender = tx_sender.with_sealer(sealer);
         }
+        if let Some(PendingStateResource(pending_state)) = input.pending_state {
+            tx_sender = tx_sender.with_pending_state(pending_state);
+        }
+
+        // Add policy filters.
+        let filters_config = self.tx_filters_config;
+        if let Some(path) = filters_config.deny_list_path {
+            let filter =
+                AddressDenyListFilter::new(path, filters_config.lists_reload_interval).await?;
+            tx_sender = tx_sender.with_tx_filter(Arc::new(filter));
+        }
+        if let Some(path) = filters_config.method_allowlist_path {
+            let filter =
+                ContractMethodAllowlistFilter::new(path, filters_config.lists_reload_interval)
+                    .await?;
+            tx_sender = tx_sender.with_tx_filter(Arc::new(filter));
+        }
+        let limits = filters_config.limits;
+        if limits.max_calldata_size.is_some() || limits.max_value.is_some() {
+            tx_sender = tx_sender.with_tx_filter(Arc::new(limits));
+        }
 
         // Add the task for updating the whitelisted tokens for the AA cache.
         let whitelisted_tokens_for_aa_update_task = if self.whitelisted_tokens_for_aa_cache {
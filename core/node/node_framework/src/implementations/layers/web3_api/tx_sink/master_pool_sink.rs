@@ -1,8 +1,10 @@
+use zksync_mempool::AccountTxLimits;
 use zksync_node_api_server::tx_sender::master_pool_sink::MasterPoolSink;
 
 use crate::{
     implementations::resources::{
         pools::{MasterPool, PoolResource},
+        storage_notifications::StorageNotificationsResource,
         web3_api::TxSinkResource,
     },
     wiring_layer::{WiringError, WiringLayer},
@@ -10,12 +12,25 @@ use crate::{
 };
 
 /// Wiring layer for [`MasterPoolSink`], [`TxSink`](zksync_node_api_server::tx_sender::tx_sink::TxSink) implementation.
-pub struct MasterPoolSinkLayer;
+#[derive(Debug, Default)]
+pub struct MasterPoolSinkLayer {
+    account_limits: AccountTxLimits,
+}
+
+impl MasterPoolSinkLayer {
+    /// Sets limits for pending transactions of a single account enforced on transaction insertion.
+    #[must_use]
+    pub fn with_account_limits(mut self, limits: AccountTxLimits) -> Self {
+        self.account_limits = limits;
+        self
+    }
+}
 
 #[derive(Debug, FromContext)]
 #[context(crate = crate)]
 pub struct Input {
     pub master_pool: PoolResource<MasterPool>,
+    pub storage_notifications: Option<StorageNotificationsResource>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -35,8 +50,12 @@ impl WiringLayer for MasterPoolSinkLayer {
 
     async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
         let pool = input.master_pool.get().await?;
+        let mut tx_sink = MasterPoolSink::new(pool).with_account_limits(self.account_limits);
+        if let Some(StorageNotificationsResource(notifications)) = input.storage_notifications {
+            tx_sink = tx_sink.with_notifications(notifications);
+        }
         Ok(Output {
-            tx_sink: MasterPoolSink::new(pool).into(),
+            tx_sink: tx_sink.into(),
         })
     }
 }
@@ -32,3 +32,13 @@ impl Resource for BoundEthInterfaceForBlobsResource {
         "common/bound_eth_interface_for_blobs".into()
     }
 }
+
+/// Same as `BoundEthInterfaceResource`, but for the previous main operator while the operator key is being rotated.
+#[derive(Debug, Clone)]
+pub struct BoundEthInterfaceForPreviousOperatorResource(pub Box<dyn BoundEthInterface>);
+
+impl Resource for BoundEthInterfaceForPreviousOperatorResource {
+    fn name() -> String {
+        "common/bound_eth_interface_for_previous_operator".into()
+    }
+}
@@ -12,5 +12,6 @@ pub mod pools;
 pub mod price_api_client;
 pub mod reverter;
 pub mod state_keeper;
+pub mod storage_notifications;
 pub mod sync_state;
 pub mod web3_api;
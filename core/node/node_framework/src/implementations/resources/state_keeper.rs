@@ -1,7 +1,8 @@
 use std::sync::Arc;
 
 use zksync_state_keeper::{
-    seal_criteria::ConditionalSealer, BatchExecutor, OutputHandler, StateKeeperIO,
+    seal_criteria::ConditionalSealer, BatchExecutor, OutputHandler, PendingStateReader,
+    StateKeeperIO,
 };
 
 use crate::resource::{Resource, Unique};
@@ -75,3 +76,19 @@ where
         Self(Arc::new(sealer))
     }
 }
+
+/// A resource that provides read-only access to the state keeper pending state.
+#[derive(Debug, Clone)]
+pub struct PendingStateResource(pub PendingStateReader);
+
+impl Resource for PendingStateResource {
+    fn name() -> String {
+        "state_keeper/pending_state".into()
+    }
+}
+
+impl From<PendingStateReader> for PendingStateResource {
+    fn from(reader: PendingStateReader) -> Self {
+        Self(reader)
+    }
+}
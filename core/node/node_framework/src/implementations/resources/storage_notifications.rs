@@ -0,0 +1,19 @@
+use zksync_dal::notifications::StorageNotifications;
+
+use crate::resource::Resource;
+
+/// A resource that provides [`StorageNotifications`] to the service.
+#[derive(Debug, Clone)]
+pub struct StorageNotificationsResource(pub StorageNotifications);
+
+impl Resource for StorageNotificationsResource {
+    fn name() -> String {
+        "common/storage_notifications".into()
+    }
+}
+
+impl From<StorageNotifications> for StorageNotificationsResource {
+    fn from(notifications: StorageNotifications) -> Self {
+        Self(notifications)
+    }
+}
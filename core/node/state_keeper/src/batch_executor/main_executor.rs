@@ -7,6 +7,7 @@ use tokio::{
     runtime::Handle,
     sync::{mpsc, watch},
 };
+use zksync_dal::{ConnectionPool, Core};
 use zksync_multivm::{
     interface::{
         ExecutionResult, FinishedL1Batch, Halt, L1BatchEnv, L2BlockEnv, SystemEnv,
@@ -14,17 +15,21 @@ use zksync_multivm::{
     },
     tracers::CallTracer,
     vm_latest::HistoryEnabled,
-    MultiVMTracer, VmInstance,
+    MultiVMTracer, MultiVmTracerPointer, VmInstance,
 };
 use zksync_shared_metrics::{InteractionType, TxStage, APP_METRICS};
 use zksync_state::{ReadStorage, ReadStorageFactory, StorageView, WriteStorage};
-use zksync_types::{vm_trace::Call, Transaction};
+use zksync_types::{
+    vm_trace::{Call, ViolatedValidationRule},
+    Address, Transaction,
+};
 use zksync_utils::bytecode::CompressedBytecodeInfo;
 
 use super::{BatchExecutor, BatchExecutorHandle, Command, TxExecutionResult};
 use crate::{
     metrics::{TxExecutionStage, BATCH_TIP_METRICS, EXECUTOR_METRICS, KEEPER_METRICS},
     types::ExecutionMetricsForCriteria,
+    validation::{AccountValidation, BatchAccountValidation, CustomValidationRules},
 };
 
 /// The default implementation of [`BatchExecutor`].
@@ -39,6 +44,9 @@ pub struct MainBatchExecutor {
     /// that in cases where the node is expected to process any transactions processed by the sequencer
     /// regardless of its configuration, this flag should be set to `true`.
     optional_bytecode_compression: bool,
+    /// Account validation rules enforced for executed L2 transactions. Should only be set on the main node;
+    /// the external node must execute all transactions included by the main node.
+    account_validation: Option<Arc<AccountValidation>>,
 }
 
 impl MainBatchExecutor {
@@ -46,8 +54,26 @@ impl MainBatchExecutor {
         Self {
             save_call_traces,
             optional_bytecode_compression,
+            account_validation: None,
         }
     }
+
+    /// Enforces account validation rules for executed L2 transactions, same as the API server does when accepting
+    /// transactions. Transactions violating the rules are rejected. Trusted tokens are loaded from `pool`
+    /// at the start of each L1 batch and are reloaded if a transaction accesses an untrusted contract.
+    pub fn with_account_validation(
+        mut self,
+        rules: CustomValidationRules,
+        whitelisted_tokens_for_aa: Vec<Address>,
+        pool: ConnectionPool<Core>,
+    ) -> Self {
+        self.account_validation = Some(Arc::new(AccountValidation {
+            rules,
+            whitelisted_tokens_for_aa,
+            pool,
+        }));
+        self
+    }
 }
 
 #[async_trait]
@@ -62,14 +88,26 @@ impl BatchExecutor for MainBatchExecutor {
         // Since we process `BatchExecutor` commands one-by-one (the next command is never enqueued
         // until a previous command is processed), capacity 1 is enough for the commands channel.
         let (commands_sender, commands_receiver) = mpsc::channel(1);
-        let executor = CommandReceiver {
+        let mut executor = CommandReceiver {
             save_call_traces: self.save_call_traces,
             optional_bytecode_compression: self.optional_bytecode_compression,
+            account_validation: None,
             commands: commands_receiver,
         };
 
+        let account_validation = self.account_validation.clone();
         let stop_receiver = stop_receiver.clone();
         let handle = tokio::task::spawn_blocking(move || {
+            if let Some(account_validation) = account_validation {
+                let batch_validation = Handle::current()
+                    .block_on(account_validation.for_batch(
+                        system_env.version.into(),
+                        system_env.default_validation_computational_gas_limit,
+                    ))
+                    .context("failed loading account validation params")?;
+                executor.account_validation = Some(batch_validation);
+            }
+
             if let Some(storage) = Handle::current()
                 .block_on(
                     storage_factory.access_storage(&stop_receiver, l1_batch_params.number - 1),
@@ -96,6 +134,7 @@ impl BatchExecutor for MainBatchExecutor {
 struct CommandReceiver {
     save_call_traces: bool,
     optional_bytecode_compression: bool,
+    account_validation: Option<BatchAccountValidation>,
     commands: mpsc::Receiver<Command>,
 }
 
@@ -163,7 +202,7 @@ impl CommandReceiver {
     }
 
     fn execute_tx<S: WriteStorage>(
-        &self,
+        &mut self,
         tx: &Transaction,
         vm: &mut VmInstance<S, HistoryEnabled>,
     ) -> TxExecutionResult {
@@ -172,12 +211,22 @@ impl CommandReceiver {
 
         // Execute the transaction.
         let latency = KEEPER_METRICS.tx_execution_time[&TxExecutionStage::Execution].start();
-        let (tx_result, compressed_bytecodes, call_tracer_result) =
-            if self.optional_bytecode_compression {
-                self.execute_tx_in_vm_with_optional_compression(tx, vm)
-            } else {
-                self.execute_tx_in_vm(tx, vm)
-            };
+        let (tx_result, compressed_bytecodes, call_tracer_result) = loop {
+            let (tx_result, compressed_bytecodes, call_tracer_result, violated_rule) =
+                if self.optional_bytecode_compression {
+                    self.execute_tx_in_vm_with_optional_compression(tx, vm)
+                } else {
+                    self.execute_tx_in_vm(tx, vm)
+                };
+            match violated_rule {
+                Some(rule) if self.refresh_trusted_tokens(&rule) => {
+                    // The transaction has touched a token that became trusted after the start of the batch.
+                    vm.rollback_to_the_latest_snapshot();
+                    vm.make_snapshot();
+                }
+                _ => break (tx_result, compressed_bytecodes, call_tracer_result),
+            }
+        };
         latency.observe();
         APP_METRICS.processed_txs[&TxStage::StateKeeper].inc();
         APP_METRICS.processed_l1_txs[&TxStage::StateKeeper].inc_by(tx.is_l1().into());
@@ -201,6 +250,20 @@ impl CommandReceiver {
         }
     }
 
+    /// Returns `true` if the transaction violating account validation rules should be re-executed
+    /// because of refreshed trusted tokens.
+    fn refresh_trusted_tokens(&mut self, violated_rule: &ViolatedValidationRule) -> bool {
+        let Some(account_validation) = &mut self.account_validation else {
+            return false;
+        };
+        let refresh_result =
+            Handle::current().block_on(account_validation.refresh_trusted_tokens(violated_rule));
+        refresh_result.unwrap_or_else(|err| {
+            tracing::warn!("Failed refreshing trusted tokens for account validation: {err:#}");
+            false
+        })
+    }
+
     fn rollback_last_tx<S: WriteStorage>(&self, vm: &mut VmInstance<S, HistoryEnabled>) {
         let latency = KEEPER_METRICS.tx_execution_time[&TxExecutionStage::TxRollback].start();
         vm.rollback_to_the_latest_snapshot();
@@ -233,6 +296,30 @@ impl CommandReceiver {
         result
     }
 
+    /// Returns tracers for the transaction and the cell receiving the violated account validation rule, if any.
+    fn tracers<S: WriteStorage>(
+        &self,
+        tx: &Transaction,
+        call_tracer_result: &Arc<OnceCell<Vec<Call>>>,
+    ) -> (
+        Vec<MultiVmTracerPointer<S, HistoryEnabled>>,
+        Option<Arc<OnceCell<ViolatedValidationRule>>>,
+    ) {
+        let mut tracers = vec![];
+        if self.save_call_traces {
+            tracers.push(CallTracer::new(call_tracer_result.clone()).into_tracer_pointer());
+        }
+        let mut validation_result = None;
+        if let Some(account_validation) = &self.account_validation {
+            // If the validation rules are violated, the tracer halts the VM, so the transaction gets rejected.
+            if let Some((tracer, result)) = account_validation.tracer(tx) {
+                tracers.push(tracer.into_tracer_pointer());
+                validation_result = Some(result);
+            }
+        }
+        (tracers, validation_result)
+    }
+
     /// Attempts to execute transaction with or without bytecode compression.
     /// If compression fails, the transaction will be re-executed without compression.
     fn execute_tx_in_vm_with_optional_compression<S: WriteStorage>(
@@ -243,6 +330,7 @@ impl CommandReceiver {
         VmExecutionResultAndLogs,
         Vec<CompressedBytecodeInfo>,
         Vec<Call>,
+        Option<ViolatedValidationRule>,
     ) {
         // Note, that the space where we can put the calldata for compressing transactions
         // is limited and the transactions do not pay for taking it.
@@ -257,11 +345,7 @@ impl CommandReceiver {
         vm.make_snapshot();
 
         let call_tracer_result = Arc::new(OnceCell::default());
-        let tracer = if self.save_call_traces {
-            vec![CallTracer::new(call_tracer_result.clone()).into_tracer_pointer()]
-        } else {
-            vec![]
-        };
+        let (tracer, validation_result) = self.tracers(tx, &call_tracer_result);
 
         if let (Ok(()), result) =
             vm.inspect_transaction_with_bytecode_compression(tracer.into(), tx.clone(), true)
@@ -273,16 +357,13 @@ impl CommandReceiver {
                 .unwrap()
                 .take()
                 .unwrap_or_default();
-            return (result, compressed_bytecodes, trace);
+            let violated_rule = validation_result.and_then(|cell| cell.get().cloned());
+            return (result, compressed_bytecodes, trace, violated_rule);
         }
         vm.rollback_to_the_latest_snapshot();
 
         let call_tracer_result = Arc::new(OnceCell::default());
-        let tracer = if self.save_call_traces {
-            vec![CallTracer::new(call_tracer_result.clone()).into_tracer_pointer()]
-        } else {
-            vec![]
-        };
+        let (tracer, validation_result) = self.tracers(tx, &call_tracer_result);
 
         let result =
             vm.inspect_transaction_with_bytecode_compression(tracer.into(), tx.clone(), false);
@@ -297,7 +378,8 @@ impl CommandReceiver {
             .unwrap()
             .take()
             .unwrap_or_default();
-        (result.1, compressed_bytecodes, trace)
+        let violated_rule = validation_result.and_then(|cell| cell.get().cloned());
+        (result.1, compressed_bytecodes, trace, violated_rule)
     }
 
     /// Attempts to execute transaction with mandatory bytecode compression.
@@ -310,13 +392,10 @@ impl CommandReceiver {
         VmExecutionResultAndLogs,
         Vec<CompressedBytecodeInfo>,
         Vec<Call>,
+        Option<ViolatedValidationRule>,
     ) {
         let call_tracer_result = Arc::new(OnceCell::default());
-        let tracer = if self.save_call_traces {
-            vec![CallTracer::new(call_tracer_result.clone()).into_tracer_pointer()]
-        } else {
-            vec![]
-        };
+        let (tracer, validation_result) = self.tracers(tx, &call_tracer_result);
 
         let (published_bytecodes, mut result) =
             vm.inspect_transaction_with_bytecode_compression(tracer.into(), tx.clone(), true);
@@ -327,13 +406,24 @@ impl CommandReceiver {
                 .unwrap()
                 .take()
                 .unwrap_or_default();
-            (result, compressed_bytecodes, trace)
+            let violated_rule = validation_result.and_then(|cell| cell.get().cloned());
+            (result, compressed_bytecodes, trace, violated_rule)
         } else {
             // Transaction failed to publish bytecodes, we reject it so initiator doesn't pay fee.
-            result.result = ExecutionResult::Halt {
-                reason: Halt::FailedToPublishCompressedBytecodes,
-            };
-            (result, Default::default(), Default::default())
+            // If the transaction was halted before publishing (e.g., by the account validation tracer),
+            // the original halt reason is retained.
+            if !matches!(result.result, ExecutionResult::Halt { .. }) {
+                result.result = ExecutionResult::Halt {
+                    reason: Halt::FailedToPublishCompressedBytecodes,
+                };
+            }
+            let violated_rule = validation_result.and_then(|cell| cell.get().cloned());
+            (
+                result,
+                Default::default(),
+                Default::default(),
+                violated_rule,
+            )
         }
     }
 }
@@ -5,9 +5,12 @@ use std::{sync::Arc, time::Instant};
 use anyhow::Context as _;
 use async_trait::async_trait;
 use tokio::sync::{mpsc, oneshot};
-use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_dal::{
+    notifications::{StorageNotification, StorageNotifications},
+    ConnectionPool, Core, CoreDal,
+};
 use zksync_shared_metrics::{BlockStage, APP_METRICS};
-use zksync_types::{writes::TreeWrite, Address};
+use zksync_types::{writes::TreeWrite, Address, L2BlockNumber};
 use zksync_utils::u256_to_h256;
 
 use crate::{
@@ -36,6 +39,7 @@ pub struct StateKeeperPersistence {
     latest_completion_receiver: Option<oneshot::Receiver<()>>,
     // If true, `submit_l2_block()` will wait for the operation to complete.
     is_sync: bool,
+    notifications: Option<StorageNotifications>,
 }
 
 impl StateKeeperPersistence {
@@ -57,6 +61,7 @@ impl StateKeeperPersistence {
             is_sync,
             commands_sender: commands_sender.downgrade(),
             commands_receiver,
+            notifications: None,
         };
         let this = Self {
             pool,
@@ -66,6 +71,7 @@ impl StateKeeperPersistence {
             commands_sender,
             latest_completion_receiver: None,
             is_sync,
+            notifications: None,
         };
         (this, sealer)
     }
@@ -82,6 +88,13 @@ impl StateKeeperPersistence {
         self
     }
 
+    /// Enables sending notifications about fictive L2 blocks sealed together with L1 batches. Notifications
+    /// about other L2 blocks are sent by [`L2BlockSealerTask`] (see [`L2BlockSealerTask::with_notifications()`]).
+    pub fn with_notifications(mut self, notifications: StorageNotifications) -> Self {
+        self.notifications = Some(notifications);
+        self
+    }
+
     /// Submits a new sealing `command` to the sealer that this handle is attached to.
     ///
     /// If there are currently too many unprocessed commands, this method will wait until
@@ -180,10 +193,22 @@ impl StateKeeperOutputHandler for StateKeeperPersistence {
             .await
             .with_context(|| format!("cannot persist L1 batch #{batch_number}"))?;
         APP_METRICS.block_number[&BlockStage::Sealed].set(batch_number.0.into());
+
+        if let Some(notifications) = &self.notifications {
+            notify_l2_block_sealed(notifications, updates_manager.l2_block.number).await;
+        }
         Ok(())
     }
 }
 
+/// Sends a notification about a sealed L2 block. Notifications are best-effort, so errors are logged and ignored.
+async fn notify_l2_block_sealed(notifications: &StorageNotifications, number: L2BlockNumber) {
+    let notification = StorageNotification::L2BlockSealed(number);
+    if let Err(err) = notifications.send(notification).await {
+        tracing::warn!("Failed sending notification about sealed L2 block #{number}: {err:#}");
+    }
+}
+
 /// Component responsible for sealing L2 blocks (i.e., storing their data to Postgres).
 #[derive(Debug)]
 pub struct L2BlockSealerTask {
@@ -192,9 +217,16 @@ pub struct L2BlockSealerTask {
     // Weak sender handle to get queue capacity stats.
     commands_sender: mpsc::WeakSender<Completable<L2BlockSealCommand>>,
     commands_receiver: mpsc::Receiver<Completable<L2BlockSealCommand>>,
+    notifications: Option<StorageNotifications>,
 }
 
 impl L2BlockSealerTask {
+    /// Enables sending notifications about sealed L2 blocks.
+    pub fn with_notifications(mut self, notifications: StorageNotifications) -> Self {
+        self.notifications = Some(notifications);
+        self
+    }
+
     /// Seals L2 blocks as they are received from the [`StateKeeperPersistence`]. This should be run
     /// on a separate Tokio task.
     pub async fn run(mut self) -> anyhow::Result<()> {
@@ -214,6 +246,9 @@ impl L2BlockSealerTask {
         // an earlier one.
         while let Some(completable) = self.next_command().await {
             completable.command.seal(self.pool.clone()).await?;
+            if let Some(notifications) = &self.notifications {
+                notify_l2_block_sealed(notifications, completable.command.l2_block.number).await;
+            }
             if let Some(delta) = l2_block_seal_delta {
                 L2_BLOCK_METRICS.seal_delta.observe(delta.elapsed());
             }
@@ -563,6 +598,41 @@ mod tests {
         assert_eq!(protective_reads, HashSet::new());
     }
 
+    #[tokio::test]
+    async fn l2_block_and_l1_batch_processing_with_notifications() {
+        let pool = ConnectionPool::constrained_test_pool(1).await;
+        let mut storage = pool.connection().await.unwrap();
+        insert_genesis_batch(&mut storage, &GenesisParams::mock())
+            .await
+            .unwrap();
+        storage
+            .blocks_dal()
+            .set_l1_batch_hash(L1BatchNumber(0), H256::zero())
+            .await
+            .unwrap();
+        drop(storage);
+
+        let notifications = StorageNotifications::in_process(16);
+        let mut notifications_receiver = notifications.subscribe().await.unwrap();
+        let (persistence, l2_block_sealer) =
+            StateKeeperPersistence::new(pool.clone(), Address::default(), 1);
+        let persistence = persistence.with_notifications(notifications.clone());
+        let l2_block_sealer = l2_block_sealer.with_notifications(notifications);
+        let mut output_handler = OutputHandler::new(Box::new(persistence));
+        tokio::spawn(l2_block_sealer.run());
+
+        execute_mock_batch(&mut output_handler).await;
+
+        // Notifications must be sent both for the "ordinary" and the fictive L2 block.
+        for number in [1, 2] {
+            let notification = notifications_receiver.recv().await.unwrap();
+            assert_eq!(
+                notification,
+                Some(StorageNotification::L2BlockSealed(L2BlockNumber(number)))
+            );
+        }
+    }
+
     #[tokio::test]
     async fn l2_block_sealer_handle_blocking() {
         let pool = ConnectionPool::constrained_test_pool(1).await;
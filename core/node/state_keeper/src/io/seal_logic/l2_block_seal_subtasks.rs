@@ -444,6 +444,7 @@ mod tests {
                 executed_transactions,
                 events,
                 storage_logs,
+                tx_storage_log_ranges: Default::default(),
                 user_l2_to_l1_logs,
                 system_l2_to_l1_logs: Default::default(),
                 new_factory_deps,
@@ -18,6 +18,7 @@ use super::{
     batch_executor::{BatchExecutor, BatchExecutorHandle, TxExecutionResult},
     io::{IoCursor, L1BatchParams, L2BlockParams, OutputHandler, PendingBatchData, StateKeeperIO},
     metrics::{AGGREGATION_METRICS, KEEPER_METRICS, L1_BATCH_METRICS},
+    pending_state::PendingStatePublisher,
     seal_criteria::{ConditionalSealer, SealData, SealResolution},
     types::ExecutionMetricsForCriteria,
     updates::UpdatesManager,
@@ -64,6 +65,7 @@ pub struct ZkSyncStateKeeper {
     batch_executor_base: Box<dyn BatchExecutor>,
     sealer: Arc<dyn ConditionalSealer>,
     storage_factory: Arc<dyn ReadStorageFactory>,
+    pending_state: Option<PendingStatePublisher>,
 }
 
 impl ZkSyncStateKeeper {
@@ -82,9 +84,16 @@ impl ZkSyncStateKeeper {
             output_handler,
             sealer,
             storage_factory,
+            pending_state: None,
         }
     }
 
+    /// Makes the state keeper publish its pending state using the provided publisher.
+    pub fn with_pending_state(mut self, publisher: PendingStatePublisher) -> Self {
+        self.pending_state = Some(publisher);
+        self
+    }
+
     pub async fn run(mut self) -> anyhow::Result<()> {
         match self.run_inner().await {
             Ok(_) => unreachable!(),
@@ -139,7 +148,7 @@ impl ZkSyncStateKeeper {
         };
 
         let protocol_version = system_env.version;
-        let mut updates_manager = UpdatesManager::new(&l1_batch_env, &system_env);
+        let mut updates_manager = self.new_updates_manager(&l1_batch_env, &system_env);
         let mut protocol_upgrade_tx: Option<ProtocolUpgradeTx> = self
             .load_protocol_upgrade_tx(&pending_l2_blocks, protocol_version, l1_batch_env.number)
             .await?;
@@ -200,7 +209,7 @@ impl ZkSyncStateKeeper {
             // Start the new batch.
             next_cursor.l1_batch += 1;
             (system_env, l1_batch_env) = self.wait_for_new_batch_env(&next_cursor).await?;
-            updates_manager = UpdatesManager::new(&l1_batch_env, &system_env);
+            updates_manager = self.new_updates_manager(&l1_batch_env, &system_env);
             batch_executor = self
                 .batch_executor_base
                 .init_batch(
@@ -271,6 +280,18 @@ impl ZkSyncStateKeeper {
         Ok(protocol_upgrade_tx)
     }
 
+    fn new_updates_manager(
+        &self,
+        l1_batch_env: &L1BatchEnv,
+        system_env: &SystemEnv,
+    ) -> UpdatesManager {
+        let updates_manager = UpdatesManager::new(l1_batch_env, system_env);
+        match &self.pending_state {
+            Some(publisher) => updates_manager.with_pending_state(publisher.clone()),
+            None => updates_manager,
+        }
+    }
+
     fn is_canceled(&self) -> bool {
         *self.stop_receiver.borrow()
     }
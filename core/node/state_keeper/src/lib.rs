@@ -19,10 +19,12 @@ pub use self::{
     },
     keeper::ZkSyncStateKeeper,
     mempool_actor::MempoolFetcher,
+    pending_state::{PendingStatePublisher, PendingStateReader, PendingStateSnapshot},
     seal_criteria::SequencerSealer,
     state_keeper_storage::AsyncRocksdbCache,
     types::{ExecutionMetricsForCriteria, MempoolGuard},
     updates::UpdatesManager,
+    validation::CustomValidationRules,
 };
 
 mod batch_executor;
@@ -30,6 +32,7 @@ pub mod io;
 mod keeper;
 mod mempool_actor;
 pub mod metrics;
+mod pending_state;
 pub mod seal_criteria;
 mod state_keeper_storage;
 pub mod testonly;
@@ -38,6 +41,7 @@ pub(crate) mod tests;
 pub(crate) mod types;
 pub mod updates;
 pub(crate) mod utils;
+mod validation;
 
 #[allow(clippy::too_many_arguments)]
 pub async fn create_state_keeper(
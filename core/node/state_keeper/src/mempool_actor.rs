@@ -4,9 +4,11 @@ use anyhow::Context as _;
 #[cfg(test)]
 use tokio::sync::mpsc;
 use tokio::sync::watch;
-use zksync_config::configs::chain::MempoolConfig;
+use zksync_config::configs::chain::{MempoolConfig, MempoolOrderingPolicyKind};
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
-use zksync_mempool::L2TxFilter;
+use zksync_mempool::{
+    FairShareOrdering, FifoOrdering, HighestFeeFirstOrdering, L2TxFilter, MempoolOrderingPolicy,
+};
 use zksync_multivm::utils::derive_base_fee_and_gas_per_pubdata;
 use zksync_node_fee_model::BatchFeeModelInputProvider;
 #[cfg(test)]
@@ -31,6 +33,15 @@ pub async fn l2_tx_filter(
     })
 }
 
+/// Creates a policy ordering L2 transactions in the mempool.
+pub fn mempool_ordering_policy(kind: MempoolOrderingPolicyKind) -> Box<dyn MempoolOrderingPolicy> {
+    match kind {
+        MempoolOrderingPolicyKind::Fifo => Box::new(FifoOrdering),
+        MempoolOrderingPolicyKind::HighestFeeFirst => Box::new(HighestFeeFirstOrdering),
+        MempoolOrderingPolicyKind::FairShare => Box::new(FairShareOrdering::default()),
+    }
+}
+
 #[derive(Debug)]
 pub struct MempoolFetcher {
     mempool: MempoolGuard,
@@ -176,6 +187,9 @@ mod tests {
         stuck_tx_timeout: 0,
         remove_stuck_txs: false,
         delay_interval: 10,
+        replacement_min_fee_bump_percent: None,
+        max_pending_txs_per_account: None,
+        ordering_policy: MempoolOrderingPolicyKind::Fifo,
     };
 
     #[tokio::test]
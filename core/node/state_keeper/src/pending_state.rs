@@ -0,0 +1,207 @@
+//! Read-only snapshots of the state keeper pending state, i.e. changes made by transactions in the pending L1 batch
+//! that may not be persisted to Postgres yet.
+
+use std::sync::Arc;
+
+use tokio::sync::watch;
+use zksync_types::{L1BatchNumber, L2BlockNumber, StorageKey, StorageLogWithPreviousValue, H256};
+
+/// Snapshot of the state keeper pending state.
+///
+/// The snapshot covers all L2 blocks of the pending L1 batch, including the pending (unsealed) L2 block. Cloning
+/// a snapshot is cheap since the snapshot data is stored in persistent maps; the state keeper only copies the map nodes
+/// it modifies if they are still referenced by other snapshots.
+#[derive(Debug, Clone)]
+pub struct PendingStateSnapshot {
+    l1_batch: L1BatchNumber,
+    first_l2_block: L2BlockNumber,
+    l2_block: L2BlockNumber,
+    /// Storage writes made in the pending L1 batch.
+    writes: im::HashMap<StorageKey, H256>,
+    factory_deps: im::HashMap<H256, Arc<[u8]>>,
+}
+
+impl PendingStateSnapshot {
+    fn new(l1_batch: L1BatchNumber, l2_block: L2BlockNumber) -> Self {
+        Self {
+            l1_batch,
+            first_l2_block: l2_block,
+            l2_block,
+            writes: im::HashMap::new(),
+            factory_deps: im::HashMap::new(),
+        }
+    }
+
+    /// Returns the number of the pending L1 batch.
+    pub fn l1_batch(&self) -> L1BatchNumber {
+        self.l1_batch
+    }
+
+    /// Returns the number of the pending L2 block.
+    pub fn l2_block(&self) -> L2BlockNumber {
+        self.l2_block
+    }
+
+    /// Checks whether this snapshot can be applied on top of the persisted state with the specified pending L2 block
+    /// (i.e., the state after all L2 blocks preceding `pending_l2_block` are applied). If this returns `false`,
+    /// the snapshot is stale or there is a gap between it and the persisted state.
+    pub fn covers(&self, pending_l2_block: L2BlockNumber) -> bool {
+        (self.first_l2_block..=self.l2_block).contains(&pending_l2_block)
+    }
+
+    /// Reads the latest value written to the specified storage slot in the pending L1 batch.
+    pub fn read_value(&self, key: &StorageKey) -> Option<H256> {
+        self.writes.get(key).copied()
+    }
+
+    /// Loads a factory dependency published in the pending L1 batch.
+    pub fn load_factory_dep(&self, hash: H256) -> Option<&[u8]> {
+        self.factory_deps.get(&hash).map(|bytecode| &**bytecode)
+    }
+
+    fn extend(
+        &mut self,
+        storage_logs: &[StorageLogWithPreviousValue],
+        factory_deps: impl Iterator<Item = (H256, Vec<u8>)>,
+    ) {
+        let writes = storage_logs
+            .iter()
+            .filter(|log| log.log.is_write())
+            .map(|log| (log.log.key, log.log.value));
+        self.writes.extend(writes);
+        let deps = factory_deps.map(|(hash, bytecode)| (hash, bytecode.into()));
+        self.factory_deps.extend(deps);
+    }
+
+    fn push_l2_block(&mut self, number: L2BlockNumber) {
+        self.l2_block = number;
+    }
+}
+
+/// Publisher of [`PendingStateSnapshot`]s used by the state keeper.
+#[derive(Debug, Clone)]
+pub struct PendingStatePublisher(Arc<watch::Sender<Option<PendingStateSnapshot>>>);
+
+impl Default for PendingStatePublisher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PendingStatePublisher {
+    pub fn new() -> Self {
+        Self(Arc::new(watch::channel(None).0))
+    }
+
+    /// Creates a reader for the published snapshots.
+    pub fn subscribe(&self) -> PendingStateReader {
+        PendingStateReader(self.0.subscribe())
+    }
+
+    /// Starts a new L1 batch, discarding all changes in the previous batch.
+    pub fn start_l1_batch(&self, l1_batch: L1BatchNumber, first_l2_block: L2BlockNumber) {
+        self.0
+            .send_replace(Some(PendingStateSnapshot::new(l1_batch, first_l2_block)));
+    }
+
+    /// Applies changes made by a transaction in the pending L2 block.
+    pub fn extend(
+        &self,
+        storage_logs: &[StorageLogWithPreviousValue],
+        factory_deps: impl Iterator<Item = (H256, Vec<u8>)>,
+    ) {
+        self.0.send_if_modified(|snapshot| {
+            if let Some(snapshot) = snapshot {
+                snapshot.extend(storage_logs, factory_deps);
+            }
+            snapshot.is_some()
+        });
+    }
+
+    /// Seals the pending L2 block and starts a new one with the specified number.
+    pub fn push_l2_block(&self, number: L2BlockNumber) {
+        self.0.send_if_modified(|snapshot| {
+            if let Some(snapshot) = snapshot {
+                snapshot.push_l2_block(number);
+            }
+            snapshot.is_some()
+        });
+    }
+}
+
+/// Read-only access to the state keeper pending state.
+#[derive(Debug, Clone)]
+pub struct PendingStateReader(watch::Receiver<Option<PendingStateSnapshot>>);
+
+impl PendingStateReader {
+    /// Returns the latest published snapshot, or `None` if the state keeper hasn't started a batch yet.
+    pub fn snapshot(&self) -> Option<PendingStateSnapshot> {
+        self.0.borrow().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{AccountTreeId, Address, StorageLog};
+
+    use super::*;
+
+    fn write_log(key: StorageKey, value: H256) -> StorageLogWithPreviousValue {
+        StorageLogWithPreviousValue {
+            log: StorageLog::new_write_log(key, value),
+            previous_value: H256::zero(),
+        }
+    }
+
+    #[test]
+    fn publishing_snapshots() {
+        let publisher = PendingStatePublisher::new();
+        let reader = publisher.subscribe();
+        assert!(reader.snapshot().is_none());
+        // Changes should be ignored until a batch is started.
+        publisher.push_l2_block(L2BlockNumber(1));
+        assert!(reader.snapshot().is_none());
+
+        publisher.start_l1_batch(L1BatchNumber(1), L2BlockNumber(1));
+        let key = StorageKey::new(AccountTreeId::new(Address::repeat_byte(1)), H256::zero());
+        let other_key = StorageKey::new(AccountTreeId::new(Address::repeat_byte(2)), H256::zero());
+        publisher.extend(
+            &[write_log(key, H256::repeat_byte(1))],
+            [(H256::repeat_byte(0xff), vec![1; 32])].into_iter(),
+        );
+        let first_snapshot = reader.snapshot().unwrap();
+        assert_eq!(first_snapshot.l1_batch(), L1BatchNumber(1));
+        assert_eq!(first_snapshot.read_value(&key), Some(H256::repeat_byte(1)));
+        assert_eq!(first_snapshot.read_value(&other_key), None);
+        assert_eq!(
+            first_snapshot.load_factory_dep(H256::repeat_byte(0xff)),
+            Some([1; 32].as_slice())
+        );
+
+        publisher.push_l2_block(L2BlockNumber(2));
+        publisher.extend(
+            &[
+                write_log(key, H256::repeat_byte(2)),
+                write_log(other_key, H256::repeat_byte(3)),
+            ],
+            std::iter::empty(),
+        );
+        let snapshot = reader.snapshot().unwrap();
+        assert_eq!(snapshot.l2_block(), L2BlockNumber(2));
+        assert_eq!(snapshot.read_value(&key), Some(H256::repeat_byte(2)));
+        assert_eq!(snapshot.read_value(&other_key), Some(H256::repeat_byte(3)));
+        // The earlier snapshot must not be affected.
+        assert_eq!(first_snapshot.read_value(&key), Some(H256::repeat_byte(1)));
+        assert_eq!(first_snapshot.read_value(&other_key), None);
+
+        assert!(!snapshot.covers(L2BlockNumber(0)));
+        assert!(snapshot.covers(L2BlockNumber(1)));
+        assert!(snapshot.covers(L2BlockNumber(2)));
+        assert!(!snapshot.covers(L2BlockNumber(3)));
+
+        publisher.start_l1_batch(L1BatchNumber(2), L2BlockNumber(3));
+        let snapshot = reader.snapshot().unwrap();
+        assert_eq!(snapshot.read_value(&key), None);
+        assert!(snapshot.covers(L2BlockNumber(3)));
+    }
+}
@@ -3,6 +3,7 @@ use std::{
     sync::{Arc, Mutex},
 };
 
+use zksync_config::configs::chain::MempoolConfig;
 use zksync_dal::{Connection, Core, CoreDal};
 use zksync_mempool::{L2TxFilter, MempoolInfo, MempoolStore};
 use zksync_multivm::interface::VmExecutionResultAndLogs;
@@ -11,6 +12,7 @@ use zksync_types::{
 };
 
 use super::{
+    mempool_actor::mempool_ordering_policy,
     metrics::StateKeeperGauges,
     utils::{gas_count_from_metrics, gas_count_from_tx_and_metrics},
 };
@@ -19,12 +21,17 @@ use super::{
 pub struct MempoolGuard(Arc<Mutex<MempoolStore>>);
 
 impl MempoolGuard {
-    pub async fn from_storage(storage_processor: &mut Connection<'_, Core>, capacity: u64) -> Self {
+    pub async fn from_storage(
+        storage_processor: &mut Connection<'_, Core>,
+        config: &MempoolConfig,
+    ) -> Self {
         let next_priority_id = storage_processor
             .transactions_dal()
             .next_priority_id()
             .await;
-        Self::new(next_priority_id, capacity)
+        let store = MempoolStore::new(next_priority_id, config.capacity)
+            .with_ordering_policy(mempool_ordering_policy(config.ordering_policy));
+        Self(Arc::new(Mutex::new(store)))
     }
 
     pub(super) fn new(next_priority_id: PriorityOpId, capacity: u64) -> Self {
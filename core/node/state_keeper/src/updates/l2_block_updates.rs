@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, ops::Range};
 
 use zksync_multivm::{
     interface::{ExecutionResult, L2BlockEnv, VmExecutionResultAndLogs},
@@ -21,6 +21,8 @@ pub struct L2BlockUpdates {
     pub executed_transactions: Vec<TransactionExecutionResult>,
     pub events: Vec<VmEvent>,
     pub storage_logs: Vec<StorageLogWithPreviousValue>,
+    /// Ranges of `storage_logs` produced by each of `executed_transactions`.
+    pub(crate) tx_storage_log_ranges: Vec<Range<usize>>,
     pub user_l2_to_l1_logs: Vec<UserL2ToL1Log>,
     pub system_l2_to_l1_logs: Vec<SystemL2ToL1Log>,
     pub new_factory_deps: HashMap<H256, Vec<u8>>,
@@ -48,6 +50,7 @@ impl L2BlockUpdates {
             executed_transactions: vec![],
             events: vec![],
             storage_logs: vec![],
+            tx_storage_log_ranges: vec![],
             user_l2_to_l1_logs: vec![],
             system_l2_to_l1_logs: vec![],
             new_factory_deps: HashMap::new(),
@@ -144,8 +147,11 @@ impl L2BlockUpdates {
         self.txs_encoding_size += tx.bootloader_encoding_size();
         self.payload_encoding_size +=
             zksync_protobuf::repr::encode::<zksync_dal::consensus::proto::Transaction>(&tx).len();
+        let storage_logs_start = self.storage_logs.len();
         self.storage_logs
             .extend(tx_execution_result.logs.storage_logs);
+        self.tx_storage_log_ranges
+            .push(storage_logs_start..self.storage_logs.len());
 
         self.executed_transactions.push(TransactionExecutionResult {
             hash: tx.hash(),
@@ -160,6 +166,13 @@ impl L2BlockUpdates {
         });
     }
 
+    /// Returns storage logs produced by the executed transaction with the specified index in this L2 block.
+    /// Returns `None` if there is no such transaction.
+    pub fn tx_storage_logs(&self, tx_index: usize) -> Option<&[StorageLogWithPreviousValue]> {
+        let range = self.tx_storage_log_ranges.get(tx_index)?;
+        Some(&self.storage_logs[range.clone()])
+    }
+
     /// Calculates L2 block hash based on the protocol version.
     pub(crate) fn get_l2_block_hash(&self) -> H256 {
         let mut digest = L2BlockHasher::new(self.number, self.timestamp, self.prev_block_hash);
@@ -212,6 +225,7 @@ mod tests {
         assert_eq!(accumulator.executed_transactions.len(), 1);
         assert_eq!(accumulator.events.len(), 0);
         assert_eq!(accumulator.storage_logs.len(), 0);
+        assert_eq!(accumulator.tx_storage_logs(0), Some(&[][..]));
         assert_eq!(accumulator.user_l2_to_l1_logs.len(), 0);
         assert_eq!(accumulator.system_l2_to_l1_logs.len(), 0);
         assert_eq!(accumulator.l1_gas_count, Default::default());
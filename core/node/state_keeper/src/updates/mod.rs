@@ -5,17 +5,18 @@ use zksync_multivm::{
 };
 use zksync_state::StorageViewCache;
 use zksync_types::{
-    block::BlockGasCount, fee_model::BatchFeeInput,
+    block::BlockGasCount, event::extract_bytecodes_marked_as_known, fee_model::BatchFeeInput,
     storage_writes_deduplicator::StorageWritesDeduplicator,
     tx::tx_execution_info::ExecutionMetrics, vm_trace::Call, Address, L1BatchNumber, L2BlockNumber,
     ProtocolVersionId, Transaction,
 };
-use zksync_utils::bytecode::CompressedBytecodeInfo;
+use zksync_utils::bytecode::{hash_bytecode, CompressedBytecodeInfo};
 
 pub(crate) use self::{l1_batch_updates::L1BatchUpdates, l2_block_updates::L2BlockUpdates};
 use super::{
     io::{IoCursor, L2BlockParams},
     metrics::{BATCH_TIP_METRICS, UPDATES_MANAGER_METRICS},
+    pending_state::PendingStatePublisher,
 };
 use crate::types::ExecutionMetricsForCriteria;
 
@@ -37,6 +38,7 @@ pub struct UpdatesManager {
     base_system_contract_hashes: BaseSystemContractsHashes,
     protocol_version: ProtocolVersionId,
     storage_view_cache: Option<StorageViewCache>,
+    pending_state: Option<PendingStatePublisher>,
     pub l1_batch: L1BatchUpdates,
     pub l2_block: L2BlockUpdates,
     pub storage_writes_deduplicator: StorageWritesDeduplicator,
@@ -62,9 +64,17 @@ impl UpdatesManager {
             ),
             storage_writes_deduplicator: StorageWritesDeduplicator::new(),
             storage_view_cache: None,
+            pending_state: None,
         }
     }
 
+    /// Publishes pending state changes accumulated by this manager using the provided publisher.
+    pub(crate) fn with_pending_state(mut self, publisher: PendingStatePublisher) -> Self {
+        publisher.start_l1_batch(self.l1_batch.number, self.l2_block.number);
+        self.pending_state = Some(publisher);
+        self
+    }
+
     pub(crate) fn batch_timestamp(&self) -> u64 {
         self.batch_timestamp
     }
@@ -119,6 +129,19 @@ impl UpdatesManager {
             .start();
         self.storage_writes_deduplicator
             .apply(&tx_execution_result.logs.storage_logs);
+        if let Some(pending_state) = &self.pending_state {
+            let factory_deps = extract_bytecodes_marked_as_known(&tx_execution_result.logs.events)
+                .into_iter()
+                .filter_map(|hash| {
+                    let bytecode = tx
+                        .execute
+                        .factory_deps
+                        .iter()
+                        .find(|bytecode| hash_bytecode(bytecode) == hash)?;
+                    Some((hash, bytecode.clone()))
+                });
+            pending_state.extend(&tx_execution_result.logs.storage_logs, factory_deps);
+        }
         self.l2_block.extend_from_executed_transaction(
             tx,
             tx_execution_result,
@@ -174,6 +197,9 @@ impl UpdatesManager {
             l2_block_params.virtual_blocks,
             self.protocol_version,
         );
+        if let Some(pending_state) = &self.pending_state {
+            pending_state.push_l2_block(new_l2_block_updates.number);
+        }
         let old_l2_block_updates = std::mem::replace(&mut self.l2_block, new_l2_block_updates);
         self.l1_batch
             .extend_from_sealed_l2_block(old_l2_block_updates);
@@ -0,0 +1,198 @@
+//! Account validation rules shared by the API server (when accepting transactions) and the state keeper
+//! (when executing them).
+
+use std::{collections::HashSet, sync::Arc};
+
+use anyhow::Context as _;
+use once_cell::sync::OnceCell;
+use zksync_config::configs::chain::{StateKeeperConfig, ValidationOpcode, ValidationSlotPattern};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_multivm::{
+    tracers::validator::{ValidationTracer, ValidationTracerParams},
+    vm_latest::HistoryEnabled,
+    VmVersion,
+};
+use zksync_types::{
+    vm_trace::ViolatedValidationRule, Address, ExecuteTransactionCommon, Transaction,
+    TRUSTED_ADDRESS_SLOTS, TRUSTED_TOKEN_SLOTS, U256,
+};
+
+/// Operator-defined account validation rules applied on top of the default ones.
+#[derive(Debug, Clone, Default)]
+pub struct CustomValidationRules {
+    trusted_addresses: HashSet<Address>,
+    trusted_slots: HashSet<(Address, U256)>,
+    trusted_slots_in_all_contracts: HashSet<U256>,
+    allow_meta_opcode: bool,
+}
+
+impl CustomValidationRules {
+    pub fn new(
+        trusted_addresses: &[Address],
+        trusted_slots: &[ValidationSlotPattern],
+        allowed_opcodes: &[ValidationOpcode],
+    ) -> Self {
+        let mut this = Self {
+            trusted_addresses: trusted_addresses.iter().copied().collect(),
+            allow_meta_opcode: allowed_opcodes.contains(&ValidationOpcode::Meta),
+            ..Self::default()
+        };
+        for pattern in trusted_slots {
+            if let Some(address) = pattern.address {
+                this.trusted_slots.insert((address, pattern.slot));
+            } else {
+                this.trusted_slots_in_all_contracts.insert(pattern.slot);
+            }
+        }
+        this
+    }
+
+    /// Creates rules from the corresponding fields of the state keeper config.
+    pub fn from_config(config: &StateKeeperConfig) -> Self {
+        Self::new(
+            &config.validation_trusted_addresses,
+            &config.validation_trusted_slots,
+            &config.validation_allowed_opcodes,
+        )
+    }
+
+    /// Checks whether these rules are the same as the default ones.
+    pub fn is_empty(&self) -> bool {
+        self.trusted_addresses.is_empty()
+            && self.trusted_slots.is_empty()
+            && self.trusted_slots_in_all_contracts.is_empty()
+            && !self.allow_meta_opcode
+    }
+
+    /// Returns validation tracer params for a transaction initiated by `user_address`.
+    ///
+    /// Some slots of `trusted_tokens` are marked as "trusted". That is needed for slots which can not be
+    /// trusted to change between validation and execution in general case, but sometimes we can safely rely on them
+    /// to not change often. The returned params stop VM execution after the validation step.
+    pub fn tracer_params(
+        &self,
+        user_address: Address,
+        paymaster_address: Address,
+        trusted_tokens: &[Address],
+        computational_gas_limit: u32,
+    ) -> ValidationTracerParams {
+        let trusted_slots = trusted_tokens
+            .iter()
+            .flat_map(|&token| TRUSTED_TOKEN_SLOTS.iter().map(move |&slot| (token, slot)))
+            .chain(self.trusted_slots.iter().copied())
+            .collect();
+
+        // The slots the value of which will be added as allowed address on the fly.
+        // Required for working with transparent proxies.
+        let trusted_address_slots = trusted_tokens
+            .iter()
+            .flat_map(|&token| TRUSTED_ADDRESS_SLOTS.iter().map(move |&slot| (token, slot)))
+            .collect();
+
+        ValidationTracerParams {
+            user_address,
+            paymaster_address,
+            trusted_slots,
+            // By default, there are no specific trusted addresses; the operator may add them.
+            trusted_addresses: self.trusted_addresses.clone(),
+            trusted_address_slots,
+            trusted_slots_in_all_contracts: self.trusted_slots_in_all_contracts.clone(),
+            allow_meta_opcode: self.allow_meta_opcode,
+            stop_after_validation: true,
+            computational_gas_limit,
+        }
+    }
+}
+
+/// Account validation enforced by the state keeper for executed L2 transactions.
+#[derive(Debug)]
+pub(crate) struct AccountValidation {
+    pub rules: CustomValidationRules,
+    pub whitelisted_tokens_for_aa: Vec<Address>,
+    pub pool: ConnectionPool<Core>,
+}
+
+impl AccountValidation {
+    /// Loads the trusted tokens for an L1 batch. Bridged tokens are loaded at the start of the batch and are reloaded
+    /// if a transaction accesses an untrusted contract (e.g., a token bridged in the middle of the batch).
+    pub async fn for_batch(
+        self: Arc<Self>,
+        vm_version: VmVersion,
+        computational_gas_limit: u32,
+    ) -> anyhow::Result<BatchAccountValidation> {
+        let trusted_tokens = self.load_trusted_tokens().await?;
+        Ok(BatchAccountValidation {
+            params: self,
+            trusted_tokens,
+            vm_version,
+            computational_gas_limit,
+        })
+    }
+
+    async fn load_trusted_tokens(&self) -> anyhow::Result<Vec<Address>> {
+        let mut connection = self.pool.connection_tagged("state_keeper").await?;
+        let bridged_tokens = connection
+            .tokens_dal()
+            .get_all_l2_token_addresses()
+            .await
+            .context("failed loading bridged tokens")?;
+        Ok(bridged_tokens
+            .into_iter()
+            .chain(self.whitelisted_tokens_for_aa.iter().copied())
+            .collect())
+    }
+}
+
+/// [`AccountValidation`] resolved for a specific L1 batch.
+#[derive(Debug)]
+pub(crate) struct BatchAccountValidation {
+    params: Arc<AccountValidation>,
+    trusted_tokens: Vec<Address>,
+    vm_version: VmVersion,
+    computational_gas_limit: u32,
+}
+
+impl BatchAccountValidation {
+    /// Creates a validation tracer for the transaction together with the cell receiving the violated rule, if any.
+    /// Returns `None` for non-L2 transactions, which are not subject to account validation.
+    pub fn tracer(
+        &self,
+        tx: &Transaction,
+    ) -> Option<(
+        ValidationTracer<HistoryEnabled>,
+        Arc<OnceCell<ViolatedValidationRule>>,
+    )> {
+        let ExecuteTransactionCommon::L2(common_data) = &tx.common_data else {
+            return None;
+        };
+        let mut params = self.params.rules.tracer_params(
+            common_data.initiator_address,
+            common_data.paymaster_params.paymaster,
+            &self.trusted_tokens,
+            self.computational_gas_limit,
+        );
+        // Unlike in the API server sandbox, the transaction must be executed in full.
+        params.stop_after_validation = false;
+        Some(ValidationTracer::new(params, self.vm_version))
+    }
+
+    /// Reloads trusted tokens if `violated_rule` was caused by accessing storage of an untrusted contract.
+    /// The API server loads trusted tokens for each transaction, so without reloading, transactions relying on
+    /// a token bridged after the start of the batch would be accepted by the API server, but rejected here.
+    ///
+    /// Returns `true` if the contract has become trusted, i.e., the transaction should be re-executed.
+    pub async fn refresh_trusted_tokens(
+        &mut self,
+        violated_rule: &ViolatedValidationRule,
+    ) -> anyhow::Result<bool> {
+        let ViolatedValidationRule::TouchedUnallowedStorageSlots(contract, _) = violated_rule
+        else {
+            return Ok(false);
+        };
+        if self.trusted_tokens.contains(contract) {
+            return Ok(false);
+        }
+        self.trusted_tokens = self.params.load_trusted_tokens().await?;
+        Ok(self.trusted_tokens.contains(contract))
+    }
+}
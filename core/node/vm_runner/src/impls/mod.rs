@@ -1,7 +1,12 @@
 mod bwip;
 mod protective_reads;
+mod trace_exporter;
 
 pub use bwip::{
     BasicWitnessInputProducer, BasicWitnessInputProducerIo, BasicWitnessInputProducerTasks,
 };
 pub use protective_reads::{ProtectiveReadsIo, ProtectiveReadsWriter, ProtectiveReadsWriterTasks};
+pub use trace_exporter::{
+    L1BatchTraces, StateDiffEntry, TraceExporter, TraceExporterIo, TraceExporterTasks,
+    TransactionTraceRecord,
+};
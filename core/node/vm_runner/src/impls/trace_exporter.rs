@@ -0,0 +1,365 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_object_store::{Bucket, ObjectStore, StoredObject, _reexports::BoxedError};
+use zksync_state_keeper::{
+    updates::L2BlockUpdates, MainBatchExecutor, StateKeeperOutputHandler, UpdatesManager,
+};
+use zksync_types::{
+    api, Address, L1BatchNumber, L2BlockNumber, L2ChainId, StorageLogWithPreviousValue, H256,
+};
+
+use crate::{
+    storage::StorageSyncTask, ConcurrentOutputHandlerFactory, ConcurrentOutputHandlerFactoryTask,
+    OutputHandlerFactory, VmRunner, VmRunnerIo, VmRunnerStorage,
+};
+
+/// A standalone component that re-executes L1 batches with call tracing enabled and exports
+/// per-transaction call traces and state diffs to the object store.
+#[derive(Debug)]
+pub struct TraceExporter {
+    vm_runner: VmRunner,
+}
+
+impl TraceExporter {
+    /// Create a new trace exporter from the provided DB parameters and window size which
+    /// regulates how many batches this component can handle at the same time.
+    pub async fn new(
+        pool: ConnectionPool<Core>,
+        object_store: Arc<dyn ObjectStore>,
+        rocksdb_path: String,
+        chain_id: L2ChainId,
+        first_processed_batch: L1BatchNumber,
+        window_size: u32,
+    ) -> anyhow::Result<(Self, TraceExporterTasks)> {
+        let io = TraceExporterIo {
+            first_processed_batch,
+            window_size,
+        };
+        let (loader, loader_task) =
+            VmRunnerStorage::new(pool.clone(), rocksdb_path, io.clone(), chain_id).await?;
+        let output_handler_factory = TraceExporterOutputHandlerFactory { object_store };
+        let (output_handler_factory, output_handler_factory_task) =
+            ConcurrentOutputHandlerFactory::new(pool.clone(), io.clone(), output_handler_factory);
+        let batch_processor = MainBatchExecutor::new(true, false);
+        let vm_runner = VmRunner::new(
+            pool,
+            Box::new(io),
+            Arc::new(loader),
+            Box::new(output_handler_factory),
+            Box::new(batch_processor),
+        );
+        Ok((
+            Self { vm_runner },
+            TraceExporterTasks {
+                loader_task,
+                output_handler_factory_task,
+            },
+        ))
+    }
+
+    /// Continuously loads new available batches and exports traces for them.
+    ///
+    /// # Errors
+    ///
+    /// Propagates RocksDB, Postgres and object store errors.
+    pub async fn run(self, stop_receiver: &watch::Receiver<bool>) -> anyhow::Result<()> {
+        self.vm_runner.run(stop_receiver).await
+    }
+}
+
+/// A collections of tasks that need to be run in order for trace exporter to work as
+/// intended.
+#[derive(Debug)]
+pub struct TraceExporterTasks {
+    /// Task that synchronizes storage with new available batches.
+    pub loader_task: StorageSyncTask<TraceExporterIo>,
+    /// Task that handles output from processed batches.
+    pub output_handler_factory_task: ConcurrentOutputHandlerFactoryTask<TraceExporterIo>,
+}
+
+/// IO implementation for the trace exporter.
+#[derive(Debug, Clone)]
+pub struct TraceExporterIo {
+    first_processed_batch: L1BatchNumber,
+    window_size: u32,
+}
+
+#[async_trait]
+impl VmRunnerIo for TraceExporterIo {
+    fn name(&self) -> &'static str {
+        "trace_exporter"
+    }
+
+    async fn latest_processed_batch(
+        &self,
+        conn: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<L1BatchNumber> {
+        Ok(conn
+            .vm_runner_dal()
+            .get_trace_exporter_latest_processed_batch()
+            .await?
+            .unwrap_or(self.first_processed_batch))
+    }
+
+    async fn last_ready_to_be_loaded_batch(
+        &self,
+        conn: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<L1BatchNumber> {
+        Ok(conn
+            .vm_runner_dal()
+            .get_trace_exporter_last_ready_batch(self.first_processed_batch, self.window_size)
+            .await?)
+    }
+
+    async fn mark_l1_batch_as_processing(
+        &self,
+        conn: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        Ok(conn
+            .vm_runner_dal()
+            .mark_trace_exporter_batch_as_processing(l1_batch_number)
+            .await?)
+    }
+
+    async fn mark_l1_batch_as_completed(
+        &self,
+        conn: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        conn.vm_runner_dal()
+            .mark_trace_exporter_batch_as_completed(l1_batch_number)
+            .await
+    }
+}
+
+/// Storage slot changed by a transaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateDiffEntry {
+    pub address: Address,
+    pub key: H256,
+    pub previous_value: H256,
+    pub value: H256,
+}
+
+/// Exported data for a single transaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionTraceRecord {
+    pub l1_batch_number: L1BatchNumber,
+    pub l2_block_number: L2BlockNumber,
+    pub index_in_block: usize,
+    pub tx_hash: H256,
+    /// Call trace in the same format as returned by `debug_traceBlockByNumber`. `None` if the VM
+    /// didn't produce any call traces for the transaction.
+    pub call_trace: Option<api::DebugCall>,
+    /// Storage slots changed by the transaction, ordered by address and key. Slots overwritten
+    /// with their original value are omitted.
+    pub state_diff: Vec<StateDiffEntry>,
+}
+
+impl TransactionTraceRecord {
+    fn new(
+        l1_batch_number: L1BatchNumber,
+        l2_block: &L2BlockUpdates,
+        index_in_block: usize,
+    ) -> anyhow::Result<Self> {
+        let tx = &l2_block.executed_transactions[index_in_block];
+        let storage_logs = l2_block
+            .tx_storage_logs(index_in_block)
+            .with_context(|| format!("no storage logs for transaction {:?}", tx.hash))?;
+        Ok(Self {
+            l1_batch_number,
+            l2_block_number: l2_block.number,
+            index_in_block,
+            tx_hash: tx.hash,
+            call_trace: tx.call_trace().map(api::DebugCall::from),
+            state_diff: state_diff(storage_logs),
+        })
+    }
+}
+
+fn state_diff(storage_logs: &[StorageLogWithPreviousValue]) -> Vec<StateDiffEntry> {
+    let mut diff = BTreeMap::new();
+    for log in storage_logs.iter().filter(|log| log.log.is_write()) {
+        let key = (*log.log.key.address(), *log.log.key.key());
+        diff.entry(key)
+            .and_modify(|(_, value)| *value = log.log.value)
+            .or_insert((log.previous_value, log.log.value));
+    }
+    diff.into_iter()
+        .filter(|(_, (previous_value, value))| previous_value != value)
+        .map(|((address, key), (previous_value, value))| StateDiffEntry {
+            address,
+            key,
+            previous_value,
+            value,
+        })
+        .collect()
+}
+
+/// Traces for all transactions in an L1 batch. Stored in the object store as JSON Lines, one
+/// [`TransactionTraceRecord`] per line.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct L1BatchTraces {
+    pub records: Vec<TransactionTraceRecord>,
+}
+
+impl StoredObject for L1BatchTraces {
+    const BUCKET: Bucket = Bucket::VmTraces;
+    type Key<'a> = L1BatchNumber;
+
+    fn encode_key(key: Self::Key<'_>) -> String {
+        format!("l1_batch_{key}_traces.jsonl")
+    }
+
+    fn serialize(&self) -> Result<Vec<u8>, BoxedError> {
+        let mut bytes = vec![];
+        for record in &self.records {
+            serde_json::to_writer(&mut bytes, record)?;
+            bytes.push(b'\n');
+        }
+        Ok(bytes)
+    }
+
+    fn deserialize(bytes: Vec<u8>) -> Result<Self, BoxedError> {
+        let records = serde_json::Deserializer::from_slice(&bytes)
+            .into_iter::<TransactionTraceRecord>()
+            .collect::<Result<_, _>>()?;
+        Ok(Self { records })
+    }
+}
+
+#[derive(Debug)]
+struct TraceExporterOutputHandler {
+    object_store: Arc<dyn ObjectStore>,
+    traces: L1BatchTraces,
+}
+
+#[async_trait]
+impl StateKeeperOutputHandler for TraceExporterOutputHandler {
+    async fn handle_l2_block(&mut self, updates_manager: &UpdatesManager) -> anyhow::Result<()> {
+        // Storage logs are only split by transaction in L2 block updates, so records are collected here.
+        let l1_batch_number = updates_manager.l1_batch.number;
+        let l2_block = &updates_manager.l2_block;
+        for index_in_block in 0..l2_block.executed_transactions.len() {
+            let record = TransactionTraceRecord::new(l1_batch_number, l2_block, index_in_block)?;
+            self.traces.records.push(record);
+        }
+        Ok(())
+    }
+
+    async fn handle_l1_batch(
+        &mut self,
+        updates_manager: Arc<UpdatesManager>,
+    ) -> anyhow::Result<()> {
+        let l1_batch_number = updates_manager.l1_batch.number;
+        let traces = std::mem::take(&mut self.traces);
+        let tx_count = traces.records.len();
+        let blob_url = self
+            .object_store
+            .put(l1_batch_number, &traces)
+            .await
+            .with_context(|| format!("failed saving traces for L1 batch #{l1_batch_number}"))?;
+        tracing::info!(%l1_batch_number, tx_count, blob_url, "Exported VM traces");
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct TraceExporterOutputHandlerFactory {
+    object_store: Arc<dyn ObjectStore>,
+}
+
+#[async_trait]
+impl OutputHandlerFactory for TraceExporterOutputHandlerFactory {
+    async fn create_handler(
+        &mut self,
+        _l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Box<dyn StateKeeperOutputHandler>> {
+        Ok(Box::new(TraceExporterOutputHandler {
+            object_store: self.object_store.clone(),
+            traces: L1BatchTraces::default(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{
+        storage::{StorageLog, StorageLogKind},
+        AccountTreeId, StorageKey,
+    };
+
+    use super::*;
+
+    fn write_log(key: StorageKey, previous_value: u64, value: u64) -> StorageLogWithPreviousValue {
+        StorageLogWithPreviousValue {
+            log: StorageLog {
+                kind: StorageLogKind::RepeatedWrite,
+                key,
+                value: H256::from_low_u64_be(value),
+            },
+            previous_value: H256::from_low_u64_be(previous_value),
+        }
+    }
+
+    #[test]
+    fn state_diff_is_aggregated_per_slot() {
+        let address = Address::repeat_byte(1);
+        let key = StorageKey::new(AccountTreeId::new(address), H256::zero());
+        let reverted_key = StorageKey::new(AccountTreeId::new(address), H256::repeat_byte(1));
+        let read_key = StorageKey::new(AccountTreeId::new(address), H256::repeat_byte(2));
+        let logs = [
+            write_log(key, 1, 2),
+            write_log(reverted_key, 5, 6),
+            StorageLogWithPreviousValue {
+                log: StorageLog::new_read_log(read_key, H256::repeat_byte(3)),
+                previous_value: H256::repeat_byte(3),
+            },
+            write_log(key, 2, 3),
+            write_log(reverted_key, 6, 5),
+        ];
+
+        let diff = state_diff(&logs);
+        assert_eq!(
+            diff,
+            [StateDiffEntry {
+                address,
+                key: H256::zero(),
+                previous_value: H256::from_low_u64_be(1),
+                value: H256::from_low_u64_be(3),
+            }]
+        );
+    }
+
+    #[test]
+    fn traces_roundtrip() {
+        let record = TransactionTraceRecord {
+            l1_batch_number: L1BatchNumber(1),
+            l2_block_number: L2BlockNumber(2),
+            index_in_block: 0,
+            tx_hash: H256::repeat_byte(1),
+            call_trace: None,
+            state_diff: vec![StateDiffEntry {
+                address: Address::repeat_byte(2),
+                key: H256::repeat_byte(3),
+                previous_value: H256::zero(),
+                value: H256::repeat_byte(4),
+            }],
+        };
+        let traces = L1BatchTraces {
+            records: vec![record.clone(), record],
+        };
+        let bytes = traces.serialize().unwrap();
+        assert_eq!(bytes.iter().filter(|&&b| b == b'\n').count(), 2);
+        let restored = L1BatchTraces::deserialize(bytes).unwrap();
+        assert_eq!(restored, traces);
+    }
+}
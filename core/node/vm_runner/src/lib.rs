@@ -15,7 +15,8 @@ mod tests;
 
 pub use impls::{
     BasicWitnessInputProducer, BasicWitnessInputProducerIo, BasicWitnessInputProducerTasks,
-    ProtectiveReadsIo, ProtectiveReadsWriter, ProtectiveReadsWriterTasks,
+    L1BatchTraces, ProtectiveReadsIo, ProtectiveReadsWriter, ProtectiveReadsWriterTasks,
+    StateDiffEntry, TraceExporter, TraceExporterIo, TraceExporterTasks, TransactionTraceRecord,
 };
 pub use io::VmRunnerIo;
 pub use output_handler::{
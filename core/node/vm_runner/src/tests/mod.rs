@@ -27,6 +27,7 @@ use super::{OutputHandlerFactory, VmRunnerIo};
 mod output_handler;
 mod process;
 mod storage;
+mod trace_exporter;
 
 #[derive(Debug, Default)]
 struct IoMock {
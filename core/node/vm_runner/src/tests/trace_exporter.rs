@@ -0,0 +1,111 @@
+use std::time::Duration;
+
+use tempfile::TempDir;
+use tokio::sync::watch;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
+use zksync_object_store::{Bucket, MockObjectStore};
+use zksync_test_account::Account;
+use zksync_types::{L1BatchNumber, L2BlockNumber, L2ChainId};
+
+use crate::{
+    tests::{fund, store_l1_batches},
+    L1BatchTraces, TraceExporter,
+};
+
+async fn wait_for_processed_batch(
+    pool: &ConnectionPool<Core>,
+    l1_batch_number: L1BatchNumber,
+    timeout: Duration,
+) -> anyhow::Result<()> {
+    const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+    let started_at = tokio::time::Instant::now();
+    loop {
+        let latest_processed_batch = pool
+            .connection()
+            .await?
+            .vm_runner_dal()
+            .get_trace_exporter_latest_processed_batch()
+            .await?;
+        if latest_processed_batch >= Some(l1_batch_number) {
+            return Ok(());
+        }
+        anyhow::ensure!(
+            started_at.elapsed() < timeout,
+            "Batch #{l1_batch_number} has not been processed yet (latest: {latest_processed_batch:?})"
+        );
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+#[tokio::test]
+async fn exporting_traces_for_batch() -> anyhow::Result<()> {
+    let rocksdb_dir = TempDir::new()?;
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = connection_pool.connection().await.unwrap();
+    let genesis_params = GenesisParams::mock();
+    insert_genesis_batch(&mut conn, &genesis_params)
+        .await
+        .unwrap();
+    let alice = Account::random();
+    let bob = Account::random();
+    let mut accounts = vec![alice, bob];
+    fund(&connection_pool, &accounts).await;
+
+    store_l1_batches(
+        &mut conn,
+        1..=1,
+        genesis_params.base_system_contracts().hashes(),
+        &mut accounts,
+    )
+    .await?;
+    let batch_txs = conn
+        .transactions_web3_dal()
+        .get_raw_l2_block_transactions(L2BlockNumber(1))
+        .await?;
+    drop(conn);
+
+    let object_store = MockObjectStore::arc();
+    let (trace_exporter, tasks) = TraceExporter::new(
+        connection_pool.clone(),
+        object_store.clone(),
+        rocksdb_dir.path().to_str().unwrap().to_owned(),
+        L2ChainId::default(),
+        L1BatchNumber(0),
+        1,
+    )
+    .await?;
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let loader_task = tokio::spawn(tasks.loader_task.run(stop_receiver.clone()));
+    let output_handler_task =
+        tokio::spawn(tasks.output_handler_factory_task.run(stop_receiver.clone()));
+    let exporter_task = tokio::spawn(async move { trace_exporter.run(&stop_receiver).await });
+
+    wait_for_processed_batch(&connection_pool, L1BatchNumber(1), Duration::from_secs(10)).await?;
+
+    let traces: L1BatchTraces = object_store.get(L1BatchNumber(1)).await?;
+    assert_eq!(traces.records.len(), batch_txs.len());
+    for (record, tx) in traces.records.iter().zip(&batch_txs) {
+        assert_eq!(record.l1_batch_number, L1BatchNumber(1));
+        assert_eq!(record.l2_block_number, L2BlockNumber(1));
+        assert_eq!(record.tx_hash, tx.hash());
+        assert!(record.call_trace.is_some(), "{record:?}");
+        // At least the fee payment must be recorded in the state diff.
+        assert!(!record.state_diff.is_empty(), "{record:?}");
+    }
+    // Traces must be stored as JSON Lines in the dedicated bucket.
+    let raw_traces = object_store
+        .get_raw(Bucket::VmTraces, "l1_batch_1_traces.jsonl")
+        .await?;
+    assert_eq!(
+        raw_traces.iter().filter(|&&b| b == b'\n').count(),
+        batch_txs.len()
+    );
+
+    stop_sender.send_replace(true);
+    exporter_task.await??;
+    loader_task.await??;
+    output_handler_task.await??;
+    Ok(())
+}
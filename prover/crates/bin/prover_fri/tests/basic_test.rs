@@ -2,7 +2,10 @@ use std::sync::Arc;
 
 use anyhow::Context as _;
 use serde::Serialize;
-use zksync_config::configs::{object_store::ObjectStoreMode, FriProverConfig, ObjectStoreConfig};
+use zksync_config::configs::{
+    object_store::{BucketCompressionCodecs, ObjectStoreMode},
+    FriProverConfig, ObjectStoreConfig,
+};
 use zksync_env_config::FromEnv;
 use zksync_object_store::{bincode, ObjectStoreFactory};
 use zksync_prover_fri::prover_job_processor::Prover;
@@ -32,6 +35,7 @@ async fn prover_and_assert_base_layer(
         },
         max_retries: 5,
         local_mirror_path: None,
+        compression: BucketCompressionCodecs::empty(),
     };
     let object_store = ObjectStoreFactory::new(object_store_config)
         .create_store()
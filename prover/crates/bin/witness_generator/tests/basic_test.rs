@@ -1,7 +1,10 @@
 use std::time::Instant;
 
 use serde::Serialize;
-use zksync_config::{configs::object_store::ObjectStoreMode, ObjectStoreConfig};
+use zksync_config::{
+    configs::object_store::{BucketCompressionCodecs, ObjectStoreMode},
+    ObjectStoreConfig,
+};
 use zksync_object_store::ObjectStoreFactory;
 use zksync_prover_fri_types::keys::AggregationsKey;
 use zksync_prover_fri_utils::get_recursive_layer_circuit_id_for_base_layer;
@@ -31,6 +34,7 @@ async fn test_leaf_witness_gen() {
         },
         max_retries: 5,
         local_mirror_path: None,
+        compression: BucketCompressionCodecs::empty(),
     };
     let object_store = ObjectStoreFactory::new(object_store_config)
         .create_store()
@@ -73,6 +77,7 @@ async fn test_node_witness_gen() {
         },
         max_retries: 5,
         local_mirror_path: None,
+        compression: BucketCompressionCodecs::empty(),
     };
     let object_store = ObjectStoreFactory::new(object_store_config)
         .create_store()
@@ -5,7 +5,8 @@ use zksync_basic_types::{
     basic_fri_types::{AggregationRound, CircuitIdRoundTuple, JobIdentifiers},
     protocol_version::{ProtocolSemanticVersion, ProtocolVersionId},
     prover_dal::{
-        FriProverJobMetadata, JobCountStatistics, ProverJobFriInfo, ProverJobStatus, StuckJobs,
+        FriProverJobMetadata, JobCountStatistics, ProverArtifactKind, ProverJobFriInfo,
+        ProverJobStatus, StuckJobs,
     },
     L1BatchNumber,
 };
@@ -605,6 +606,100 @@ impl FriProverDal<'_, '_> {
         .unwrap_or(0) as usize
     }
 
+    /// Returns URLs of all object store artifacts produced by witness generators and provers for the specified
+    /// L1 batches, including artifacts of archived prover jobs.
+    pub async fn get_artifact_urls_for_l1_batches(
+        &mut self,
+        l1_batch_numbers: &[L1BatchNumber],
+    ) -> Vec<(ProverArtifactKind, String)> {
+        let l1_batch_numbers: Vec<_> = l1_batch_numbers
+            .iter()
+            .map(|number| i64::from(number.0))
+            .collect();
+        sqlx::query!(
+            r#"
+            SELECT
+                'witness_inputs' AS "kind!",
+                witness_inputs_blob_url AS "url!"
+            FROM
+                witness_inputs_fri
+            WHERE
+                l1_batch_number = ANY ($1)
+                AND witness_inputs_blob_url IS NOT NULL
+            UNION ALL
+            SELECT
+                'circuit',
+                circuit_blob_url
+            FROM
+                prover_jobs_fri
+            WHERE
+                l1_batch_number = ANY ($1)
+            UNION ALL
+            SELECT
+                'circuit',
+                circuit_blob_url
+            FROM
+                prover_jobs_fri_archive
+            WHERE
+                l1_batch_number = ANY ($1)
+            UNION ALL
+            SELECT
+                'proof',
+                proof_blob_url
+            FROM
+                prover_jobs_fri
+            WHERE
+                l1_batch_number = ANY ($1)
+                AND proof_blob_url IS NOT NULL
+            UNION ALL
+            SELECT
+                'proof',
+                proof_blob_url
+            FROM
+                prover_jobs_fri_archive
+            WHERE
+                l1_batch_number = ANY ($1)
+                AND proof_blob_url IS NOT NULL
+            UNION ALL
+            SELECT
+                'leaf_aggregation_inputs',
+                closed_form_inputs_blob_url
+            FROM
+                leaf_aggregation_witness_jobs_fri
+            WHERE
+                l1_batch_number = ANY ($1)
+                AND closed_form_inputs_blob_url IS NOT NULL
+            UNION ALL
+            SELECT
+                'node_aggregation_inputs',
+                aggregations_url
+            FROM
+                node_aggregation_witness_jobs_fri
+            WHERE
+                l1_batch_number = ANY ($1)
+                AND aggregations_url IS NOT NULL
+            UNION ALL
+            SELECT
+                'scheduler_inputs',
+                scheduler_partial_input_blob_url
+            FROM
+                scheduler_witness_jobs_fri
+            WHERE
+                l1_batch_number = ANY ($1)
+            "#,
+            &l1_batch_numbers
+        )
+        .fetch_all(self.storage.conn())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|row| {
+            let kind = ProverArtifactKind::from_str(&row.kind).unwrap();
+            (kind, row.url)
+        })
+        .collect()
+    }
+
     pub async fn get_final_node_proof_job_ids_for(
         &mut self,
         l1_batch_number: L1BatchNumber,
@@ -44,7 +44,9 @@ where
 pub struct Prover;
 
 // Implement the marker trait for the Prover to be able to use it in Connection.
-impl DbMarker for Prover {}
+impl DbMarker for Prover {
+    const TEST_DATABASE_URL_VAR: &'static str = "TEST_DATABASE_PROVER_URL";
+}
 // Implement the sealed trait for the Connection.
 impl private::Sealed for Connection<'_, Prover> {}
 
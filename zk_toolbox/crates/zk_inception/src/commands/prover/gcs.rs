@@ -1,6 +1,9 @@
 use common::{cmd::Cmd, logger, spinner::Spinner};
 use xshell::{cmd, Shell};
-use zksync_config::{configs::object_store::ObjectStoreMode, ObjectStoreConfig};
+use zksync_config::{
+    configs::object_store::{BucketCompressionCodecs, ObjectStoreMode},
+    ObjectStoreConfig,
+};
 
 use super::args::init::ProofStorageGCSCreateBucket;
 use crate::{
@@ -34,6 +37,7 @@ pub(crate) fn create_gcs_bucket(
         },
         max_retries: PROVER_STORE_MAX_RETRIES,
         local_mirror_path: None,
+        compression: BucketCompressionCodecs::empty(),
     })
 }
 
@@ -3,7 +3,10 @@ use common::{check_prover_prequisites, cmd::Cmd, logger, spinner::Spinner};
 use config::EcosystemConfig;
 use xshell::{cmd, Shell};
 use zksync_config::{
-    configs::{object_store::ObjectStoreMode, GeneralConfig},
+    configs::{
+        object_store::{BucketCompressionCodecs, ObjectStoreMode},
+        GeneralConfig,
+    },
     ObjectStoreConfig,
 };
 
@@ -120,6 +123,7 @@ fn get_object_store_config(
             },
             max_retries: PROVER_STORE_MAX_RETRIES,
             local_mirror_path: None,
+            compression: BucketCompressionCodecs::empty(),
         }),
         Some(ProofStorageConfig::GCS(config)) => Some(ObjectStoreConfig {
             mode: ObjectStoreMode::GCSWithCredentialFile {
@@ -128,6 +132,7 @@ fn get_object_store_config(
             },
             max_retries: PROVER_STORE_MAX_RETRIES,
             local_mirror_path: None,
+            compression: BucketCompressionCodecs::empty(),
         }),
         Some(ProofStorageConfig::GCSCreateBucket(config)) => {
             Some(create_gcs_bucket(shell, config)?)